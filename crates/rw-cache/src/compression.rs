@@ -0,0 +1,77 @@
+//! Compression codecs for cache entries.
+
+use std::io::{Read, Write};
+
+/// Codec used to compress [`FileCache`](crate::FileCache) entries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    /// gzip at the default level: widely supported, moderate speed.
+    Gzip,
+    /// Zstandard at the default level: faster, and usually smaller than gzip.
+    Zstd,
+}
+
+impl Compression {
+    /// Byte identifying the codec in an entry header.
+    pub(crate) fn id(self) -> u8 {
+        match self {
+            Self::Gzip => 1,
+            Self::Zstd => 2,
+        }
+    }
+
+    /// Codec for a header byte, or `None` for an unknown one.
+    pub(crate) fn from_id(id: u8) -> Option<Self> {
+        match id {
+            1 => Some(Self::Gzip),
+            2 => Some(Self::Zstd),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn compress(self, data: &[u8]) -> std::io::Result<Vec<u8>> {
+        match self {
+            Self::Gzip => {
+                let mut encoder =
+                    flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder.write_all(data)?;
+                encoder.finish()
+            }
+            Self::Zstd => zstd::encode_all(data, 0),
+        }
+    }
+
+    pub(crate) fn decompress(self, data: &[u8]) -> std::io::Result<Vec<u8>> {
+        match self {
+            Self::Gzip => {
+                let mut out = Vec::new();
+                flate2::read::GzDecoder::new(data).read_to_end(&mut out)?;
+                Ok(out)
+            }
+            Self::Zstd => zstd::decode_all(data),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_is_byte_identical() {
+        let html = "<h1>Title</h1>\n<svg><text>ü</text></svg>\n".repeat(50);
+
+        for codec in [Compression::Gzip, Compression::Zstd] {
+            let compressed = codec.compress(html.as_bytes()).unwrap();
+            assert!(compressed.len() < html.len(), "{codec:?} did not shrink");
+            assert_eq!(codec.decompress(&compressed).unwrap(), html.as_bytes());
+            assert_eq!(Compression::from_id(codec.id()), Some(codec));
+        }
+    }
+
+    #[test]
+    fn test_decompress_rejects_garbage() {
+        assert!(Compression::Gzip.decompress(b"not gzip").is_err());
+        assert!(Compression::Zstd.decompress(b"not zstd").is_err());
+    }
+}
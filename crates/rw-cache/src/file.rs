@@ -8,18 +8,47 @@
 //! [etag_len: u32 LE][etag bytes][data bytes]
 //! ```
 //!
+//! With compression ([`FileCache::with_compression`]), the header gains a
+//! 4-byte prefix naming the codec and the data is compressed:
+//!
+//! ```text
+//! ["RWZ"][codec id: u8][etag_len: u32 LE][etag bytes][compressed data]
+//! ```
+//!
+//! The prefix read as an etag length is far above any valid one, so the two
+//! layouts can't be confused: entries of either kind read back whatever the
+//! cache's own setting, and turning compression on or off keeps existing
+//! entries usable.
+//!
 //! On read, only the header is read first to validate the etag. The full data
 //! is read only on cache hit, avoiding unnecessary I/O on mismatch.
 //!
+//! With a TTL ([`FileCache::with_ttl`]), an entry whose file was last written
+//! longer ago than the TTL is a miss whatever its etag. The file's mtime serves
+//! as the write timestamp, so the entry format is the same with or without a
+//! TTL.
+//!
 //! On construction, [`FileCache`] validates a `VERSION` file in the cache root.
 //! If the version mismatches or is missing, the entire cache directory is wiped
 //! and recreated. This ensures stale caches from previous builds are never used.
 
+use std::borrow::Cow;
+use std::collections::BTreeMap;
 use std::fs::{self, File};
 use std::io::Read;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::stats::{CounterRegistry, Counters};
+use crate::{Cache, CacheBucket, CacheStats, Compression};
 
-use crate::{Cache, CacheBucket};
+/// First bytes of a compressed entry, followed by the codec id.
+const COMPRESSED_MAGIC: &[u8; 3] = b"RWZ";
+
+/// Largest etag length accepted on read. Guards against corrupted files that
+/// could cause huge allocations.
+const MAX_ETAG_LEN: usize = 8192;
 
 /// File-based [`Cache`] rooted at a directory on disk.
 ///
@@ -34,6 +63,9 @@ use crate::{Cache, CacheBucket};
 /// ```
 pub struct FileCache {
     root: PathBuf,
+    ttl: Option<Duration>,
+    compression: Option<Compression>,
+    counters: CounterRegistry,
 }
 
 impl FileCache {
@@ -45,7 +77,32 @@ impl FileCache {
     #[must_use]
     pub fn new(root: PathBuf, version: &str) -> Self {
         validate_version(&root, version);
-        Self { root }
+        Self {
+            root,
+            ttl: None,
+            compression: None,
+            counters: CounterRegistry::default(),
+        }
+    }
+
+    /// Expire entries `ttl` after they were written.
+    ///
+    /// An expired entry is a miss even when its etag matches, so the caller
+    /// recomputes and overwrites it. Without a TTL entries never expire.
+    #[must_use]
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Compress entries written from now on with `codec`.
+    ///
+    /// Reads decompress transparently, and entries written without
+    /// compression (or with another codec) still read back.
+    #[must_use]
+    pub fn with_compression(mut self, codec: Compression) -> Self {
+        self.compression = Some(codec);
+        self
     }
 }
 
@@ -53,13 +110,23 @@ impl Cache for FileCache {
     fn bucket(&self, name: &str) -> Box<dyn CacheBucket> {
         Box::new(FileCacheBucket {
             dir: self.root.join(name),
+            ttl: self.ttl,
+            compression: self.compression,
+            counters: self.counters.get(name),
         })
     }
+
+    fn stats(&self) -> BTreeMap<String, CacheStats> {
+        self.counters.snapshot()
+    }
 }
 
 /// A single bucket backed by a directory on disk.
 struct FileCacheBucket {
     dir: PathBuf,
+    ttl: Option<Duration>,
+    compression: Option<Compression>,
+    counters: Arc<Counters>,
 }
 
 impl FileCacheBucket {
@@ -74,20 +141,33 @@ impl FileCacheBucket {
         filename.push_str(".cache");
         self.dir.join(filename)
     }
-}
 
-impl CacheBucket for FileCacheBucket {
-    fn get(&self, key: &str, etag: &str) -> Option<Vec<u8>> {
+    /// Read an entry, validating its etag (skipped when `etag` is empty).
+    fn read(&self, key: &str, etag: &str) -> Option<Vec<u8>> {
         let path = self.key_path(key);
         let mut file = File::open(&path).ok()?;
 
-        // Read etag length (u32 LE)
+        if let Some(ttl) = self.ttl {
+            let written = file.metadata().and_then(|m| m.modified()).ok()?;
+            // An mtime in the future (clock skew) counts as fresh
+            if written.elapsed().is_ok_and(|age| age > ttl) {
+                return None;
+            }
+        }
+
+        // Read the codec prefix, if any, then the etag length (u32 LE)
         let mut len_buf = [0u8; 4];
         file.read_exact(&mut len_buf).ok()?;
+        let codec = if len_buf.starts_with(COMPRESSED_MAGIC) {
+            let codec = Compression::from_id(len_buf[3])?;
+            file.read_exact(&mut len_buf).ok()?;
+            Some(codec)
+        } else {
+            None
+        };
         let etag_len = u32::from_le_bytes(len_buf) as usize;
 
-        // Guard against corrupted files that could cause huge allocations
-        if etag_len > 8192 {
+        if etag_len > MAX_ETAG_LEN {
             return None;
         }
 
@@ -104,10 +184,14 @@ impl CacheBucket for FileCacheBucket {
         let mut data = Vec::new();
         #[allow(clippy::verbose_file_reads)]
         file.read_to_end(&mut data).ok()?;
-        Some(data)
+        match codec {
+            Some(codec) => codec.decompress(&data).ok(),
+            None => Some(data),
+        }
     }
 
-    fn set(&self, key: &str, etag: &str, value: &[u8]) {
+    /// Write an entry, silently ignoring errors.
+    fn write(&self, key: &str, etag: &str, value: &[u8]) {
         let path = self.key_path(key);
 
         // Silently ignore errors — cache is optional
@@ -123,15 +207,41 @@ impl CacheBucket for FileCacheBucket {
             Ok(len) => len,
             Err(_) => return,
         };
-        let mut buf = Vec::with_capacity(4 + etag_bytes.len() + value.len());
+        let data = match self.compression {
+            Some(codec) => match codec.compress(value) {
+                Ok(compressed) => Cow::Owned(compressed),
+                Err(_) => return,
+            },
+            None => Cow::Borrowed(value),
+        };
+        let mut buf = Vec::with_capacity(8 + etag_bytes.len() + data.len());
+        if let Some(codec) = self.compression {
+            buf.extend_from_slice(COMPRESSED_MAGIC);
+            buf.push(codec.id());
+        }
         buf.extend_from_slice(&etag_len.to_le_bytes());
         buf.extend_from_slice(etag_bytes);
-        buf.extend_from_slice(value);
+        buf.extend_from_slice(&data);
 
         let _ = fs::write(&path, &buf);
     }
 }
 
+impl CacheBucket for FileCacheBucket {
+    fn get(&self, key: &str, etag: &str) -> Option<Vec<u8>> {
+        self.counters.record_get(self.read(key, etag))
+    }
+
+    fn set(&self, key: &str, etag: &str, value: &[u8]) {
+        self.counters.record_set();
+        self.write(key, etag, value);
+    }
+
+    fn stats(&self) -> CacheStats {
+        self.counters.snapshot()
+    }
+}
+
 /// Validate the cache version, wiping the directory on mismatch.
 fn validate_version(root: &Path, version: &str) {
     let version_file = root.join("VERSION");
@@ -170,6 +280,8 @@ fn validate_version(root: &Path, version: &str) {
 
 #[cfg(test)]
 mod tests {
+    use std::time::SystemTime;
+
     use super::*;
     use tempfile::TempDir;
 
@@ -292,6 +404,75 @@ mod tests {
         assert_eq!(bucket.get("binary", "etag1"), Some(binary_data));
     }
 
+    #[test]
+    fn test_file_cache_stats() {
+        let tmp = TempDir::new().unwrap();
+        let cache = FileCache::new(tmp.path().join("cache"), "v1");
+        let bucket = cache.bucket("pages");
+
+        bucket.set("key", "etag1", b"hello");
+        bucket.get("key", "etag1");
+        bucket.get("key", "etag2");
+        cache.bucket("pages").get("missing", "");
+        cache.bucket("diagrams");
+
+        let expected = CacheStats {
+            hits: 1,
+            misses: 2,
+            sets: 1,
+        };
+        assert_eq!(bucket.stats(), expected);
+        assert_eq!(
+            cache.stats(),
+            BTreeMap::from([
+                ("diagrams".to_owned(), CacheStats::default()),
+                ("pages".to_owned(), expected),
+            ])
+        );
+    }
+
+    /// Backdate the file of `key` in `bucket` by `age`.
+    fn age_entry(root: &Path, bucket: &str, key: &str, age: Duration) {
+        let file = File::options()
+            .write(true)
+            .open(root.join(bucket).join(format!("{key}.cache")))
+            .unwrap();
+        file.set_modified(SystemTime::now() - age).unwrap();
+    }
+
+    #[test]
+    fn test_file_bucket_ttl_expires_old_entries() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path().join("cache");
+        let cache = FileCache::new(root.clone(), "v1").with_ttl(Duration::from_mins(1));
+        let bucket = cache.bucket("diagrams");
+
+        bucket.set("fresh", "etag1", b"new");
+        bucket.set("stale", "etag1", b"old");
+        age_entry(&root, "diagrams", "stale", Duration::from_mins(2));
+
+        assert_eq!(bucket.get("fresh", "etag1"), Some(b"new".to_vec()));
+        assert_eq!(bucket.get("stale", "etag1"), None);
+        assert_eq!(bucket.get("stale", ""), None);
+
+        // Rewriting the entry makes it fresh again
+        bucket.set("stale", "etag1", b"retried");
+        assert_eq!(bucket.get("stale", "etag1"), Some(b"retried".to_vec()));
+    }
+
+    #[test]
+    fn test_file_bucket_without_ttl_never_expires() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path().join("cache");
+        let cache = FileCache::new(root.clone(), "v1");
+        let bucket = cache.bucket("diagrams");
+
+        bucket.set("old", "etag1", b"data");
+        age_entry(&root, "diagrams", "old", Duration::from_hours(365 * 24));
+
+        assert_eq!(bucket.get("old", "etag1"), Some(b"data".to_vec()));
+    }
+
     #[test]
     fn test_version_match_keeps_cache() {
         let tmp = TempDir::new().unwrap();
@@ -366,6 +547,66 @@ mod tests {
         assert_eq!(bucket.get("key", "etag1"), None);
     }
 
+    #[test]
+    fn test_compressed_entries_round_trip() {
+        let html = "<p>page</p><svg><text>diagram</text></svg>\n".repeat(100);
+
+        for codec in [Compression::Gzip, Compression::Zstd] {
+            let tmp = TempDir::new().unwrap();
+            let cache = FileCache::new(tmp.path().join("cache"), "v1").with_compression(codec);
+            let bucket = cache.bucket("pages");
+
+            bucket.set("page", "etag1", html.as_bytes());
+
+            let raw = fs::read(tmp.path().join("cache/pages/page.cache")).unwrap();
+            assert!(raw.starts_with(COMPRESSED_MAGIC), "{codec:?}");
+            assert!(raw.len() < html.len(), "{codec:?} did not shrink the entry");
+            assert_eq!(bucket.get("page", "etag1"), Some(html.clone().into_bytes()));
+            assert_eq!(bucket.get("page", "etag2"), None);
+        }
+    }
+
+    #[test]
+    fn test_compression_setting_change_keeps_entries_readable() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path().join("cache");
+
+        let plain = FileCache::new(root.clone(), "v1");
+        plain.bucket("pages").set("old", "etag1", b"uncompressed");
+
+        let zstd = FileCache::new(root.clone(), "v1").with_compression(Compression::Zstd);
+        zstd.bucket("pages").set("new", "etag1", b"compressed");
+        assert_eq!(
+            zstd.bucket("pages").get("old", "etag1"),
+            Some(b"uncompressed".to_vec())
+        );
+
+        let gzip = FileCache::new(root, "v1").with_compression(Compression::Gzip);
+        assert_eq!(
+            gzip.bucket("pages").get("new", "etag1"),
+            Some(b"compressed".to_vec())
+        );
+        assert_eq!(
+            plain.bucket("pages").get("new", "etag1"),
+            Some(b"compressed".to_vec())
+        );
+    }
+
+    #[test]
+    fn test_unknown_codec_returns_none() {
+        let tmp = TempDir::new().unwrap();
+        let cache = FileCache::new(tmp.path().join("cache"), "v1");
+        let bucket = cache.bucket("pages");
+        bucket.set("key", "etag1", b"data");
+
+        let path = tmp.path().join("cache/pages/key.cache");
+        let mut corrupt = b"RWZ\xff".to_vec();
+        corrupt.extend_from_slice(&fs::read(&path).unwrap());
+        fs::write(&path, &corrupt).unwrap();
+
+        assert_eq!(bucket.get("key", "etag1"), None);
+    }
+
     #[test]
     fn test_nonexistent_root_creates_version() {
         let tmp = TempDir::new().unwrap();
@@ -6,10 +6,14 @@
 //! - [`Cache`]: Factory for named cache buckets
 //! - [`CacheBucket`]: Key-value store with etag-based invalidation
 //!
+//! Implementations may count lookups per bucket; [`Cache::stats`] reports them
+//! as [`CacheStats`].
+//!
 //! # Implementations
 //!
 //! - [`NullCache`]: No-op implementation (always miss)
 //! - [`FileCache`]: File-based implementation with version validation
+//! - [`MemoryCache`]: In-process implementation with a bounded LRU per bucket
 //!
 //! # Example
 //!
@@ -22,11 +26,19 @@
 //! assert_eq!(bucket.get("my-page", "v1"), None); // NullCache always misses
 //! ```
 
+use std::collections::BTreeMap;
+
+mod compression;
 mod ext;
 mod file;
+mod memory;
+mod stats;
 
+pub use compression::Compression;
 pub use ext::CacheBucketExt;
 pub use file::FileCache;
+pub use memory::MemoryCache;
+pub use stats::CacheStats;
 
 /// A named partition within a [`Cache`].
 ///
@@ -60,6 +72,14 @@ pub trait CacheBucket: Send + Sync {
     /// * `etag` - Etag to associate with this entry
     /// * `value` - Raw bytes to cache
     fn set(&self, key: &str, etag: &str, value: &[u8]);
+
+    /// Hits, misses and sets counted for this bucket.
+    ///
+    /// Handles sharing storage share counters. The default reports zeros, for
+    /// implementations that don't count.
+    fn stats(&self) -> CacheStats {
+        CacheStats::default()
+    }
 }
 
 /// Factory for named cache [`CacheBucket`]s.
@@ -77,6 +97,13 @@ pub trait Cache: Send + Sync {
     ///
     /// * `name` - Bucket name (e.g., "pages", "diagrams", "site")
     fn bucket(&self, name: &str) -> Box<dyn CacheBucket>;
+
+    /// [`CacheBucket::stats`] of every bucket opened so far, by name.
+    ///
+    /// The default reports no buckets, for implementations that don't count.
+    fn stats(&self) -> BTreeMap<String, CacheStats> {
+        BTreeMap::new()
+    }
 }
 
 /// No-op [`CacheBucket`] that never stores or retrieves data.
@@ -132,4 +159,15 @@ mod tests {
             assert_eq!(bucket.get("k", "v"), None, "bucket {name} should miss");
         }
     }
+
+    #[test]
+    fn test_null_cache_reports_no_stats() {
+        let cache = NullCache;
+        let bucket = cache.bucket("pages");
+        bucket.set("k", "v", b"data");
+        bucket.get("k", "v");
+
+        assert_eq!(bucket.stats(), CacheStats::default());
+        assert!(cache.stats().is_empty());
+    }
 }
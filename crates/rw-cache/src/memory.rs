@@ -0,0 +1,388 @@
+//! In-memory cache implementation.
+//!
+//! [`MemoryCache`] keeps entries in process memory, one bounded LRU per bucket.
+//! It suits short-lived processes (tests, bindings, serverless handlers) where
+//! a cache directory is overkill but re-rendering everything is wasteful.
+//!
+//! Limits apply to each bucket on its own, so a busy bucket never evicts
+//! another bucket's entries. An entry's size is its key, etag and value bytes
+//! together.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+
+use crate::stats::Counters;
+use crate::{Cache, CacheBucket, CacheStats};
+
+/// In-memory [`Cache`] with a least-recently-used bucket per name.
+///
+/// Buckets opened with the same name share storage. Without limits a bucket
+/// grows unbounded; [`with_max_entries`](Self::with_max_entries) and
+/// [`with_max_bytes`](Self::with_max_bytes) cap it, and both may be combined.
+///
+/// ```
+/// use rw_cache::{Cache, MemoryCache};
+///
+/// let cache = MemoryCache::new().with_max_entries(2);
+/// let bucket = cache.bucket("pages");
+/// bucket.set("a", "v1", b"A");
+/// bucket.set("b", "v1", b"B");
+/// bucket.set("c", "v1", b"C"); // evicts "a"
+/// assert_eq!(bucket.get("a", "v1"), None);
+/// assert_eq!(bucket.get("c", "v1"), Some(b"C".to_vec()));
+/// ```
+#[derive(Default)]
+pub struct MemoryCache {
+    limits: Limits,
+    buckets: Mutex<HashMap<String, Arc<Shared>>>,
+}
+
+impl MemoryCache {
+    /// Create an empty cache with unbounded buckets.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cap each bucket at `max_entries` entries.
+    #[must_use]
+    pub fn with_max_entries(mut self, max_entries: usize) -> Self {
+        self.limits.max_entries = Some(max_entries);
+        self
+    }
+
+    /// Cap each bucket at `max_bytes` bytes of keys, etags and values.
+    ///
+    /// An entry larger than the whole budget is not stored.
+    #[must_use]
+    pub fn with_max_bytes(mut self, max_bytes: usize) -> Self {
+        self.limits.max_bytes = Some(max_bytes);
+        self
+    }
+}
+
+impl Cache for MemoryCache {
+    fn bucket(&self, name: &str) -> Box<dyn CacheBucket> {
+        let mut buckets = self.buckets.lock();
+        let shared = buckets.entry(name.to_owned()).or_insert_with(|| {
+            Arc::new(Shared {
+                lru: Mutex::new(Lru::new(self.limits)),
+                counters: Counters::default(),
+            })
+        });
+        Box::new(MemoryCacheBucket {
+            shared: Arc::clone(shared),
+        })
+    }
+
+    fn stats(&self) -> BTreeMap<String, CacheStats> {
+        self.buckets
+            .lock()
+            .iter()
+            .map(|(name, shared)| (name.clone(), shared.counters.snapshot()))
+            .collect()
+    }
+}
+
+/// Storage and counters of one bucket, shared by its handles.
+struct Shared {
+    lru: Mutex<Lru>,
+    counters: Counters,
+}
+
+/// A handle to one bucket of a [`MemoryCache`].
+struct MemoryCacheBucket {
+    shared: Arc<Shared>,
+}
+
+impl CacheBucket for MemoryCacheBucket {
+    fn get(&self, key: &str, etag: &str) -> Option<Vec<u8>> {
+        let value = self.shared.lru.lock().get(key, etag);
+        self.shared.counters.record_get(value)
+    }
+
+    fn set(&self, key: &str, etag: &str, value: &[u8]) {
+        self.shared.counters.record_set();
+        self.shared.lru.lock().set(key, etag, value);
+    }
+
+    fn stats(&self) -> CacheStats {
+        self.shared.counters.snapshot()
+    }
+}
+
+/// Per-bucket size limits; `None` means unbounded.
+#[derive(Debug, Default, Clone, Copy)]
+struct Limits {
+    max_entries: Option<usize>,
+    max_bytes: Option<usize>,
+}
+
+struct Entry {
+    etag: String,
+    value: Vec<u8>,
+    /// Position in [`Lru::recency`].
+    tick: u64,
+}
+
+impl Entry {
+    fn size(&self, key: &str) -> usize {
+        key.len() + self.etag.len() + self.value.len()
+    }
+}
+
+/// Least-recently-used map bounded by [`Limits`].
+struct Lru {
+    limits: Limits,
+    entries: HashMap<String, Entry>,
+    /// Keys by last use, oldest first.
+    recency: BTreeMap<u64, String>,
+    next_tick: u64,
+    bytes: usize,
+}
+
+impl Lru {
+    fn new(limits: Limits) -> Self {
+        Self {
+            limits,
+            entries: HashMap::new(),
+            recency: BTreeMap::new(),
+            next_tick: 0,
+            bytes: 0,
+        }
+    }
+
+    fn tick(&mut self) -> u64 {
+        let tick = self.next_tick;
+        self.next_tick += 1;
+        tick
+    }
+
+    fn get(&mut self, key: &str, etag: &str) -> Option<Vec<u8>> {
+        let tick = self.tick();
+        let entry = self.entries.get_mut(key)?;
+
+        // Validate etag (skip if caller passes empty etag)
+        if !etag.is_empty() && entry.etag != etag {
+            return None;
+        }
+
+        let key = self.recency.remove(&entry.tick)?;
+        entry.tick = tick;
+        self.recency.insert(tick, key);
+        Some(entry.value.clone())
+    }
+
+    fn set(&mut self, key: &str, etag: &str, value: &[u8]) {
+        self.remove(key);
+
+        let tick = self.tick();
+        let entry = Entry {
+            etag: etag.to_owned(),
+            value: value.to_vec(),
+            tick,
+        };
+        let size = entry.size(key);
+        if self.limits.max_bytes.is_some_and(|max| size > max) {
+            return;
+        }
+
+        self.bytes += size;
+        self.entries.insert(key.to_owned(), entry);
+        self.recency.insert(tick, key.to_owned());
+        self.evict();
+    }
+
+    fn remove(&mut self, key: &str) {
+        if let Some((key, entry)) = self.entries.remove_entry(key) {
+            self.bytes -= entry.size(&key);
+            self.recency.remove(&entry.tick);
+        }
+    }
+
+    /// Drop the least recently used entries until both limits hold.
+    fn evict(&mut self) {
+        while self
+            .limits
+            .max_entries
+            .is_some_and(|max| self.entries.len() > max)
+            || self.limits.max_bytes.is_some_and(|max| self.bytes > max)
+        {
+            let Some((_, key)) = self.recency.pop_first() else {
+                break;
+            };
+            if let Some(entry) = self.entries.remove(&key) {
+                self.bytes -= entry.size(&key);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use super::*;
+
+    #[test]
+    fn test_memory_bucket_set_and_get() {
+        let cache = MemoryCache::new();
+        let bucket = cache.bucket("pages");
+
+        bucket.set("key", "v1", b"hello");
+        assert_eq!(bucket.get("key", "v1"), Some(b"hello".to_vec()));
+        assert_eq!(bucket.get("key", "v2"), None);
+        assert_eq!(bucket.get("missing", "v1"), None);
+    }
+
+    #[test]
+    fn test_memory_bucket_empty_etag_skips_validation() {
+        let cache = MemoryCache::new();
+        let bucket = cache.bucket("pages");
+
+        bucket.set("key", "v1", b"hello");
+        assert_eq!(bucket.get("key", ""), Some(b"hello".to_vec()));
+    }
+
+    #[test]
+    fn test_memory_bucket_overwrite() {
+        let cache = MemoryCache::new().with_max_bytes(32);
+        let bucket = cache.bucket("pages");
+
+        bucket.set("key", "v1", b"first");
+        bucket.set("key", "v2", b"second");
+        assert_eq!(bucket.get("key", "v1"), None);
+        assert_eq!(bucket.get("key", "v2"), Some(b"second".to_vec()));
+    }
+
+    #[test]
+    fn test_memory_cache_same_name_shares_storage() {
+        let cache = MemoryCache::new();
+
+        cache.bucket("pages").set("key", "v1", b"hello");
+        assert_eq!(
+            cache.bucket("pages").get("key", "v1"),
+            Some(b"hello".to_vec())
+        );
+        assert_eq!(cache.bucket("diagrams").get("key", "v1"), None);
+    }
+
+    #[test]
+    fn test_memory_bucket_evicts_least_recently_used() {
+        let cache = MemoryCache::new().with_max_entries(2);
+        let bucket = cache.bucket("pages");
+
+        bucket.set("a", "v1", b"A");
+        bucket.set("b", "v1", b"B");
+        // Reading "a" makes "b" the least recently used
+        assert!(bucket.get("a", "v1").is_some());
+        bucket.set("c", "v1", b"C");
+
+        assert_eq!(bucket.get("a", "v1"), Some(b"A".to_vec()));
+        assert_eq!(bucket.get("b", "v1"), None);
+        assert_eq!(bucket.get("c", "v1"), Some(b"C".to_vec()));
+    }
+
+    #[test]
+    fn test_memory_bucket_etag_mismatch_does_not_refresh() {
+        let cache = MemoryCache::new().with_max_entries(2);
+        let bucket = cache.bucket("pages");
+
+        bucket.set("a", "v1", b"A");
+        bucket.set("b", "v1", b"B");
+        assert_eq!(bucket.get("a", "stale"), None);
+        bucket.set("c", "v1", b"C");
+
+        assert_eq!(bucket.get("a", "v1"), None);
+        assert_eq!(bucket.get("b", "v1"), Some(b"B".to_vec()));
+    }
+
+    #[test]
+    fn test_memory_bucket_byte_budget() {
+        // Each entry is 1 (key) + 2 (etag) + 4 (value) = 7 bytes
+        let cache = MemoryCache::new().with_max_bytes(14);
+        let bucket = cache.bucket("pages");
+
+        bucket.set("a", "v1", b"AAAA");
+        bucket.set("b", "v1", b"BBBB");
+        bucket.set("c", "v1", b"CCCC");
+
+        assert_eq!(bucket.get("a", "v1"), None);
+        assert!(bucket.get("b", "v1").is_some());
+        assert!(bucket.get("c", "v1").is_some());
+    }
+
+    #[test]
+    fn test_memory_bucket_skips_entry_over_byte_budget() {
+        let cache = MemoryCache::new().with_max_bytes(8);
+        let bucket = cache.bucket("pages");
+
+        bucket.set("a", "v1", b"A");
+        bucket.set("a", "v2", b"far too large");
+
+        // The oversized value is dropped along with the entry it replaced
+        assert_eq!(bucket.get("a", ""), None);
+    }
+
+    #[test]
+    fn test_memory_cache_evicts_per_bucket() {
+        let cache = MemoryCache::new().with_max_entries(1);
+        let pages = cache.bucket("pages");
+        let diagrams = cache.bucket("diagrams");
+
+        pages.set("a", "v1", b"A");
+        diagrams.set("a", "v1", b"D1");
+        diagrams.set("b", "v1", b"D2");
+
+        assert_eq!(pages.get("a", "v1"), Some(b"A".to_vec()));
+        assert_eq!(diagrams.get("a", "v1"), None);
+    }
+
+    #[test]
+    fn test_memory_cache_stats() {
+        let cache = MemoryCache::new();
+        let bucket = cache.bucket("pages");
+
+        bucket.set("key", "v1", b"hello");
+        bucket.get("key", "v1");
+        bucket.get("key", "v2");
+        cache.bucket("pages").get("missing", "");
+
+        let expected = CacheStats {
+            hits: 1,
+            misses: 2,
+            sets: 1,
+        };
+        assert_eq!(bucket.stats(), expected);
+        assert_eq!(
+            cache.stats(),
+            BTreeMap::from([("pages".to_owned(), expected)])
+        );
+    }
+
+    #[test]
+    fn test_memory_cache_shared_across_threads() {
+        let cache = Arc::new(MemoryCache::new());
+
+        let handles: Vec<_> = (0..4)
+            .map(|i| {
+                let cache = Arc::clone(&cache);
+                thread::spawn(move || {
+                    cache
+                        .bucket("pages")
+                        .set(&format!("key-{i}"), "v1", b"data");
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let bucket = cache.bucket("pages");
+        for i in 0..4 {
+            assert!(bucket.get(&format!("key-{i}"), "v1").is_some());
+        }
+    }
+}
@@ -0,0 +1,135 @@
+//! Hit/miss counters for cache buckets.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use parking_lot::Mutex;
+use serde::Serialize;
+
+/// Counters of a [`CacheBucket`](crate::CacheBucket) since its cache was
+/// created.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct CacheStats {
+    /// `get` calls that returned a value.
+    pub hits: u64,
+    /// `get` calls that returned `None`, etag mismatches included.
+    pub misses: u64,
+    /// `set` calls.
+    pub sets: u64,
+}
+
+impl CacheStats {
+    /// Fraction of lookups that hit, or `None` before the first lookup.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)] // counts stay well within f64 range
+    pub fn hit_rate(&self) -> Option<f64> {
+        let lookups = self.hits + self.misses;
+        (lookups > 0).then(|| self.hits as f64 / lookups as f64)
+    }
+}
+
+/// Atomic counters behind [`CacheStats`], shared by every handle to a bucket.
+#[derive(Debug, Default)]
+pub(crate) struct Counters {
+    hits: AtomicU64,
+    misses: AtomicU64,
+    sets: AtomicU64,
+}
+
+impl Counters {
+    /// Count a lookup and pass its result through.
+    pub(crate) fn record_get(&self, value: Option<Vec<u8>>) -> Option<Vec<u8>> {
+        let counter = if value.is_some() {
+            &self.hits
+        } else {
+            &self.misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        value
+    }
+
+    pub(crate) fn record_set(&self) {
+        self.sets.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn snapshot(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            sets: self.sets.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// [`Counters`] per bucket name.
+#[derive(Debug, Default)]
+pub(crate) struct CounterRegistry {
+    buckets: Mutex<BTreeMap<String, Arc<Counters>>>,
+}
+
+impl CounterRegistry {
+    /// Counters for bucket `name`, created on first use.
+    pub(crate) fn get(&self, name: &str) -> Arc<Counters> {
+        Arc::clone(self.buckets.lock().entry(name.to_owned()).or_default())
+    }
+
+    pub(crate) fn snapshot(&self) -> BTreeMap<String, CacheStats> {
+        self.buckets
+            .lock()
+            .iter()
+            .map(|(name, counters)| (name.clone(), counters.snapshot()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counters_record_hits_misses_and_sets() {
+        let counters = Counters::default();
+
+        assert_eq!(
+            counters.record_get(Some(b"x".to_vec())),
+            Some(b"x".to_vec())
+        );
+        assert_eq!(counters.record_get(None), None);
+        assert_eq!(counters.record_get(None), None);
+        counters.record_set();
+
+        assert_eq!(
+            counters.snapshot(),
+            CacheStats {
+                hits: 1,
+                misses: 2,
+                sets: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn test_registry_shares_counters_by_name() {
+        let registry = CounterRegistry::default();
+
+        registry.get("pages").record_set();
+        registry.get("pages").record_set();
+        registry.get("diagrams").record_get(None);
+
+        let stats = registry.snapshot();
+        assert_eq!(stats["pages"].sets, 2);
+        assert_eq!(stats["diagrams"].misses, 1);
+    }
+
+    #[test]
+    fn test_hit_rate() {
+        assert_eq!(CacheStats::default().hit_rate(), None);
+        let stats = CacheStats {
+            hits: 3,
+            misses: 1,
+            sets: 0,
+        };
+        assert_eq!(stats.hit_rate(), Some(0.75));
+    }
+}
@@ -3,6 +3,7 @@
 //! Supports:
 //! - `${VAR}` - expands to the value of VAR, errors if unset
 //! - `${VAR:-default}` - expands to VAR if set, otherwise uses default
+//! - `$$` - a literal `$`, so `$${VAR}` yields the text `${VAR}`
 
 use crate::ConfigError;
 
@@ -11,33 +12,59 @@ use crate::ConfigError;
 /// Supports:
 /// - `${VAR}` - expands to the value of VAR, errors if unset
 /// - `${VAR:-default}` - expands to VAR if set, otherwise uses default
+/// - `$$` - escape for a literal `$`
 ///
-/// Returns the original string unchanged if no `${}` patterns are present.
-/// Bare `$VAR` syntax is not expanded (only `${VAR}` with braces).
+/// Any other `$` is copied through unchanged: bare `$VAR` syntax is not
+/// expanded (only `${VAR}` with braces), and `$5` stays `$5`. The escape is
+/// `$$` rather than `\$` because TOML basic strings reject `\$`.
 pub(crate) fn expand_env(value: &str, field: &str) -> Result<String, ConfigError> {
     // Fast path: no expansion needed
-    if !value.contains("${") {
+    if !value.contains('$') {
         return Ok(value.to_owned());
     }
 
-    shellexpand::env_with_context(value, |var| -> Result<Option<String>, LookupError> {
-        match std::env::var(var) {
-            Ok(val) => Ok(Some(val)),
-            Err(_) => Err(LookupError {
-                var_name: var.to_owned(),
-            }),
-        }
-    })
-    .map(std::borrow::Cow::into_owned)
-    .map_err(|e| ConfigError::EnvVar {
+    let error = |message: String| ConfigError::EnvVar {
         field: field.to_owned(),
-        message: format!("${{{0}}} not set", e.cause.var_name),
-    })
+        message,
+    };
+
+    let mut out = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(dollar) = rest.find('$') {
+        out.push_str(&rest[..dollar]);
+        let after = &rest[dollar + 1..];
+        if let Some(tail) = after.strip_prefix('$') {
+            out.push('$');
+            rest = tail;
+        } else if let Some(tail) = after.strip_prefix('{') {
+            let close = tail
+                .find('}')
+                .ok_or_else(|| error(format!("unterminated `${{` in {value:?}")))?;
+            out.push_str(&lookup(&tail[..close]).map_err(error)?);
+            rest = &tail[close + 1..];
+        } else {
+            out.push('$');
+            rest = after;
+        }
+    }
+    out.push_str(rest);
+    Ok(out)
 }
 
-/// Error returned when environment variable lookup fails.
-struct LookupError {
-    var_name: String,
+/// Resolve the inside of one `${...}` reference.
+fn lookup(reference: &str) -> Result<String, String> {
+    let (name, default) = match reference.split_once(":-") {
+        Some((name, default)) => (name, Some(default)),
+        None => (reference, None),
+    };
+    if name.is_empty() {
+        return Err("empty variable name in `${}`".to_owned());
+    }
+    match (std::env::var(name), default) {
+        (Ok(val), _) => Ok(val),
+        (Err(_), Some(default)) => Ok(default.to_owned()),
+        (Err(_), None) => Err(format!("${{{name}}} not set")),
+    }
 }
 
 #[cfg(test)]
@@ -136,6 +163,79 @@ mod tests {
         assert_eq!(result, "$VAR");
     }
 
+    #[test]
+    fn test_double_dollar_escapes_reference() {
+        // SAFETY: test runs single-threaded per test function
+        unsafe {
+            std::env::remove_var("NOT_A_VAR");
+        }
+        let result = expand_env("$${NOT_A_VAR}", "test.field").unwrap();
+        assert_eq!(result, "${NOT_A_VAR}");
+    }
+
+    #[test]
+    fn test_double_dollar_is_literal_dollar() {
+        let result = expand_env("a$$b", "test.field").unwrap();
+        assert_eq!(result, "a$b");
+    }
+
+    #[test]
+    fn test_price_string_unchanged() {
+        let result = expand_env("costs $5", "test.field").unwrap();
+        assert_eq!(result, "costs $5");
+    }
+
+    #[test]
+    fn test_bare_dollar_not_expanded_next_to_reference() {
+        // SAFETY: test runs single-threaded per test function
+        unsafe {
+            std::env::set_var("HOST_TEST_MIXED", "example.com");
+            std::env::set_var("HOME_TEST_MIXED", "/home/x");
+        }
+        let result = expand_env("https://${HOST_TEST_MIXED}/$HOME_TEST_MIXED", "test.url").unwrap();
+        assert_eq!(result, "https://example.com/$HOME_TEST_MIXED");
+        unsafe {
+            std::env::remove_var("HOST_TEST_MIXED");
+            std::env::remove_var("HOME_TEST_MIXED");
+        }
+    }
+
+    #[test]
+    fn test_escape_and_expansion_together() {
+        // SAFETY: test runs single-threaded per test function
+        unsafe {
+            std::env::set_var("TEST_VAR_ESCAPE_MIX", "v");
+        }
+        let result = expand_env(
+            "$${TEST_VAR_ESCAPE_MIX}=${TEST_VAR_ESCAPE_MIX}",
+            "test.field",
+        )
+        .unwrap();
+        assert_eq!(result, "${TEST_VAR_ESCAPE_MIX}=v");
+        unsafe {
+            std::env::remove_var("TEST_VAR_ESCAPE_MIX");
+        }
+    }
+
+    #[test]
+    fn test_trailing_dollar_unchanged() {
+        let result = expand_env("cost$", "test.field").unwrap();
+        assert_eq!(result, "cost$");
+    }
+
+    #[test]
+    fn test_unterminated_reference_error() {
+        let err = expand_env("${UNCLOSED", "test.field").unwrap_err();
+        assert_matches!(err, ConfigError::EnvVar { .. });
+        assert!(err.to_string().contains("unterminated"), "{err}");
+    }
+
+    #[test]
+    fn test_empty_reference_error() {
+        let err = expand_env("${}", "test.field").unwrap_err();
+        assert!(err.to_string().contains("empty variable name"), "{err}");
+    }
+
     #[test]
     fn test_url_with_dollar_not_expanded() {
         // URLs with dollar signs should work unchanged
@@ -0,0 +1,254 @@
+//! `extends`: layering an `rw.toml` on top of a base config file.
+//!
+//! A config with `extends = "../base/rw.toml"` is the base deep-merged with
+//! the file itself: scalars override, tables merge key by key, and arrays
+//! replace. A base may extend another base in turn.
+//!
+//! Relative paths resolve against the directory of the file that sets them, so
+//! path fields taken from a base are reported in [`Layered::bases`] for the
+//! caller to rebase once environment variables are expanded.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use toml::{Table, Value};
+
+use crate::ConfigError;
+
+/// Top-level key naming the base config file.
+pub(crate) const EXTENDS_KEY: &str = "extends";
+
+/// Config fields holding paths relative to the file that sets them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum PathField {
+    /// `docs.source_dir`
+    SourceDir,
+    /// `docs.homepage`
+    Homepage,
+    /// `diagrams.include_dirs`
+    IncludeDirs,
+}
+
+impl PathField {
+    const ALL: [Self; 3] = [Self::SourceDir, Self::Homepage, Self::IncludeDirs];
+
+    /// `(table, key)` of the field in the TOML document.
+    fn location(self) -> (&'static str, &'static str) {
+        match self {
+            Self::SourceDir => ("docs", "source_dir"),
+            Self::Homepage => ("docs", "homepage"),
+            Self::IncludeDirs => ("diagrams", "include_dirs"),
+        }
+    }
+
+    /// Whether `table` sets this field.
+    fn is_set_in(self, table: &Table) -> bool {
+        let (section, key) = self.location();
+        table
+            .get(section)
+            .and_then(Value::as_table)
+            .is_some_and(|t| t.contains_key(key))
+    }
+}
+
+/// A config document with its `extends` chain merged in.
+#[derive(Debug)]
+pub(crate) struct Layered {
+    /// The merged document, without any `extends` key.
+    pub(crate) table: Table,
+    /// Path fields whose value came from a base, and that base's directory.
+    /// Fields the top-level file sets itself are absent.
+    pub(crate) bases: BTreeMap<PathField, PathBuf>,
+}
+
+/// Merge the `extends` chain of `table`, the parsed contents of `path`.
+///
+/// # Errors
+///
+/// Returns [`ConfigError::NotFound`] for a missing base,
+/// [`ConfigError::Validation`] for a non-string `extends` or a cycle, and the
+/// usual I/O and parse errors for a base that cannot be read.
+pub(crate) fn resolve(path: &Path, table: Table) -> Result<Layered, ConfigError> {
+    let mut chain = vec![path.canonicalize()?];
+    let mut bases = BTreeMap::new();
+    let table = resolve_layer(path, table, &mut chain, &mut bases)?;
+    Ok(Layered { table, bases })
+}
+
+/// Merge `table` (from `path`) over its base, recursively. `chain` holds the
+/// canonical paths of the files above this one.
+fn resolve_layer(
+    path: &Path,
+    mut table: Table,
+    chain: &mut Vec<PathBuf>,
+    bases: &mut BTreeMap<PathField, PathBuf>,
+) -> Result<Table, ConfigError> {
+    let Some(extends) = table.remove(EXTENDS_KEY) else {
+        return Ok(table);
+    };
+    let Value::String(extends) = extends else {
+        return Err(ConfigError::Validation(format!(
+            "{EXTENDS_KEY} in {} must be a path to a config file",
+            path.display()
+        )));
+    };
+
+    let base_path = path.parent().unwrap_or(Path::new(".")).join(&extends);
+    let base_path = base_path
+        .canonicalize()
+        .map_err(|_| ConfigError::NotFound(base_path.clone()))?;
+    if let Some(start) = chain.iter().position(|p| *p == base_path) {
+        let cycle: Vec<String> = chain[start..]
+            .iter()
+            .chain([&base_path])
+            .map(|p| p.display().to_string())
+            .collect();
+        return Err(ConfigError::Validation(format!(
+            "{EXTENDS_KEY} cycle: {}",
+            cycle.join(" -> ")
+        )));
+    }
+    chain.push(base_path.clone());
+
+    let content = std::fs::read_to_string(&base_path)?;
+    let base = resolve_layer(&base_path, toml::from_str(&content)?, chain, bases)?;
+
+    let base_dir = base_path.parent().unwrap_or(Path::new(".")).to_path_buf();
+    for field in PathField::ALL {
+        if field.is_set_in(&table) {
+            bases.remove(&field);
+        } else if field.is_set_in(&base) {
+            // Already present when a base further up set it.
+            bases.entry(field).or_insert_with(|| base_dir.clone());
+        }
+    }
+
+    let mut merged = base;
+    merge(&mut merged, table);
+    Ok(merged)
+}
+
+/// Deep-merge `overlay` onto `base`: tables merge, anything else replaces.
+fn merge(base: &mut Table, overlay: Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(Value::Table(base_table)), Value::Table(overlay_table)) => {
+                merge(base_table, overlay_table);
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::assert_matches;
+    use std::fs;
+
+    use super::*;
+
+    fn table(toml: &str) -> Table {
+        toml::from_str(toml).unwrap()
+    }
+
+    #[test]
+    fn merge_overrides_scalars_merges_tables_replaces_arrays() {
+        let mut base = table(
+            "[server]\nhost = \"0.0.0.0\"\nport = 8000\n\
+             [diagrams]\ninclude_dirs = [\"a\", \"b\"]\n\
+             [diagrams.kroki_headers]\nX-One = \"1\"\n",
+        );
+        merge(
+            &mut base,
+            table(
+                "[server]\nport = 9000\n\
+                 [diagrams]\ninclude_dirs = [\"c\"]\n\
+                 [diagrams.kroki_headers]\nX-Two = \"2\"\n",
+            ),
+        );
+
+        assert_eq!(
+            base,
+            table(
+                "[server]\nhost = \"0.0.0.0\"\nport = 9000\n\
+                 [diagrams]\ninclude_dirs = [\"c\"]\n\
+                 [diagrams.kroki_headers]\nX-One = \"1\"\nX-Two = \"2\"\n",
+            )
+        );
+    }
+
+    #[test]
+    fn resolve_reports_base_directories_of_inherited_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("shared/base")).unwrap();
+        fs::create_dir(root.join("project")).unwrap();
+        fs::write(
+            root.join("shared/base/rw.toml"),
+            "[docs]\nsource_dir = \"docs\"\n[diagrams]\ninclude_dirs = [\"c4\"]\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("shared/rw.toml"),
+            "extends = \"base/rw.toml\"\n[diagrams]\ninclude_dirs = [\"puml\"]\n",
+        )
+        .unwrap();
+        let child = "extends = \"../shared/rw.toml\"\n[docs]\nhomepage = []\n";
+        let path = root.join("project/rw.toml");
+        fs::write(&path, child).unwrap();
+
+        let layered = resolve(&path, table(child)).unwrap();
+
+        assert!(!layered.table.contains_key(EXTENDS_KEY));
+        assert_eq!(
+            layered.bases,
+            BTreeMap::from([
+                (
+                    PathField::SourceDir,
+                    root.join("shared/base").canonicalize().unwrap()
+                ),
+                (
+                    PathField::IncludeDirs,
+                    root.join("shared").canonicalize().unwrap()
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn resolve_rejects_a_cycle() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("a.toml");
+        fs::write(&a, "extends = \"b.toml\"\n").unwrap();
+        fs::write(dir.path().join("b.toml"), "extends = \"a.toml\"\n").unwrap();
+
+        let err = resolve(&a, table("extends = \"b.toml\"\n")).unwrap_err();
+
+        assert_matches!(err, ConfigError::Validation(ref msg) if msg.contains("cycle"));
+        assert!(err.to_string().contains("b.toml -> "), "{err}");
+    }
+
+    #[test]
+    fn resolve_reports_a_missing_base() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("rw.toml");
+        fs::write(&path, "").unwrap();
+
+        let err = resolve(&path, table("extends = \"missing.toml\"\n")).unwrap_err();
+
+        assert_matches!(err, ConfigError::NotFound(ref p) if p.ends_with("missing.toml"));
+    }
+
+    #[test]
+    fn resolve_rejects_a_non_string_extends() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("rw.toml");
+        fs::write(&path, "").unwrap();
+
+        let err = resolve(&path, table("extends = 1\n")).unwrap_err();
+
+        assert_matches!(err, ConfigError::Validation(_));
+    }
+}
@@ -11,10 +11,22 @@
 //!
 //! - `${VAR}` - expands to the value of VAR, errors if unset
 //! - `${VAR:-default}` - expands to VAR if set, otherwise uses default
+//! - `$$` - a literal `$` (so `$${VAR}` is the text `${VAR}`)
+//!
+//! Any other `$` is kept as written.
 //!
 //! Expanded fields:
 //! - `server.host`
+//! - `docs.source_dir` (each entry of a list)
 //! - `diagrams.kroki_url`
+//! - `diagrams.include_dirs` (each entry)
+//! - `diagrams.kroki_headers` (each value)
+//!
+//! ## Base Config
+//!
+//! `extends = "<path>"` at the top of `rw.toml` layers the file over a base
+//! config: scalars override, tables merge, arrays replace. Relative paths set
+//! in a base resolve against the base's own directory. See `extends.rs`.
 //!
 //! ## Environment Variable Fallback
 //!
@@ -27,19 +39,23 @@
 //! CLI flag always wins. Empty env-var values are treated as unset.
 
 mod expand;
+mod extends;
 
 use serde::Deserialize;
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 
 /// CLI settings that override configuration file values.
 ///
 /// All fields are optional. Only non-None values override the loaded config.
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct CliSettings {
     /// Override server host.
     pub host: Option<String>,
     /// Override server port.
     pub port: Option<u16>,
+    /// Override the port auto-increment flag.
+    pub port_auto: Option<bool>,
     /// Override cache enabled flag.
     pub cache_enabled: Option<bool>,
     /// Override Kroki URL for diagram rendering.
@@ -56,6 +72,9 @@ const CONFIG_FILENAME: &str = "rw.toml";
 /// the resolved `data_dir` is the single source for all of those paths.
 pub const DATA_DIR_NAME: &str = ".rw";
 
+/// Accepted values of `diagrams.default_format`.
+const DIAGRAM_FORMATS: &[&str] = &["svg", "png"];
+
 /// Application configuration.
 #[derive(Debug, Deserialize)]
 #[serde(default)]
@@ -85,8 +104,9 @@ pub struct Config {
     /// configuration at.
     ///
     /// This is the base [`Self::resolve_paths`] resolves against, so
-    /// [`DocsConfig::source_dir`], [`DocsConfig::data_dir`], and
-    /// [`DiagramsConfig::include_dirs`] are all derived from it. A CLI override
+    /// [`DocsConfig::source_dir`], [`DocsConfig::data_dir`],
+    /// [`DocsConfig::homepage`], and [`DiagramsConfig::include_dirs`] are all
+    /// derived from it. A CLI override
     /// breaks that relationship in one direction: `source_dir` can be replaced
     /// outright without moving the project root, so it is not safe to assume
     /// `source_dir` sits inside `project_dir`.
@@ -98,6 +118,11 @@ pub struct Config {
     // deserialize straight into this field.
     #[serde(skip)]
     pub project_dir: PathBuf,
+
+    /// The `rw.toml` this configuration was loaded from, whether given
+    /// explicitly or discovered. `None` when only defaults were used.
+    #[serde(skip)]
+    pub config_file: Option<PathBuf>,
 }
 
 impl Default for Config {
@@ -120,6 +145,22 @@ pub struct ServerConfig {
     /// `rw serve` falls back to the next free port when the *default* port is
     /// busy, but treats an explicit port as a hard requirement (fail if busy).
     pub port_explicit: bool,
+    /// Fall back to the next free port even when the port is explicit — via
+    /// `[server].port_auto` in `rw.toml` or the `--port-auto` CLI flag.
+    pub port_auto: bool,
+    /// Compress responses for clients that accept gzip or brotli (default:
+    /// on).
+    pub compression: bool,
+    /// URL path of the page `rw serve` shows for missing pages, with a 404
+    /// status (default: `"404"`). Empty turns it off.
+    pub not_found_page: String,
+    /// Token `rw serve` requires as `Authorization: Bearer <token>` on every
+    /// route but the health probes (default: none).
+    pub auth_token: Option<String>,
+    /// Cross-origin access to the API routes, from `[server.cors]`.
+    pub cors: CorsConfig,
+    /// `Cache-Control` values for pages and assets, from `[server.cache]`.
+    pub cache: CacheConfig,
 }
 
 impl Default for ServerConfig {
@@ -128,6 +169,79 @@ impl Default for ServerConfig {
             host: "127.0.0.1".to_owned(),
             port: 7979,
             port_explicit: false,
+            port_auto: false,
+            compression: true,
+            not_found_page: "404".to_owned(),
+            auth_token: None,
+            cors: CorsConfig::default(),
+            cache: CacheConfig::default(),
+        }
+    }
+}
+
+/// CORS settings for the server's API routes (`[server.cors]`).
+///
+/// With no allowed origins (the default) responses carry no CORS headers.
+/// `"*"` in any of the lists allows every value.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct CorsConfig {
+    /// Origins allowed to call the API, such as `"https://backstage.example.com"`,
+    /// or `["*"]` for any origin.
+    pub allowed_origins: Vec<String>,
+    /// Methods allowed in cross-origin requests (default: the methods the
+    /// API serves).
+    pub allowed_methods: Vec<String>,
+    /// Request headers allowed in cross-origin requests (default:
+    /// `content-type`).
+    pub allowed_headers: Vec<String>,
+}
+
+impl CorsConfig {
+    /// Whether CORS is off: no origin is allowed.
+    #[must_use]
+    pub fn is_disabled(&self) -> bool {
+        self.allowed_origins.is_empty()
+    }
+}
+
+impl Default for CorsConfig {
+    fn default() -> Self {
+        Self {
+            allowed_origins: Vec::new(),
+            allowed_methods: ["GET", "POST", "PATCH", "DELETE"]
+                .map(str::to_owned)
+                .to_vec(),
+            allowed_headers: vec!["content-type".to_owned()],
+        }
+    }
+}
+
+/// `Cache-Control` values `rw serve` sends (`[server.cache]`).
+///
+/// Everything not covered here — `index.html`, unhashed files, the other
+/// API routes — is always `no-cache`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct CacheConfig {
+    /// For page responses from `/_api/pages/…` (default: `"no-cache"`, so
+    /// browsers revalidate against the page `ETag` on every load).
+    pub pages: String,
+    /// For the viewer's content-hashed build assets under `/assets/`
+    /// (default: `"public, max-age=31536000, immutable"`).
+    pub assets: String,
+    /// For page responses while live reload is on, replacing `pages` so an
+    /// edit shows on the next load (default: `"no-cache"`; `"no-store"`
+    /// keeps pages out of the browser cache entirely).
+    pub live_reload: String,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            pages: "no-cache".to_owned(),
+            assets: "public, max-age=31536000, immutable".to_owned(),
+            live_reload: "no-cache".to_owned(),
         }
     }
 }
@@ -145,6 +259,12 @@ impl<'de> Deserialize<'de> for ServerConfig {
         struct Raw {
             host: Option<String>,
             port: Option<u16>,
+            port_auto: bool,
+            compression: Option<bool>,
+            not_found_page: Option<String>,
+            auth_token: Option<String>,
+            cors: CorsConfig,
+            cache: CacheConfig,
         }
 
         let raw = Raw::deserialize(deserializer)?;
@@ -153,6 +273,12 @@ impl<'de> Deserialize<'de> for ServerConfig {
             host: raw.host.unwrap_or(defaults.host),
             port_explicit: raw.port.is_some(),
             port: raw.port.unwrap_or(defaults.port),
+            port_auto: raw.port_auto,
+            compression: raw.compression.unwrap_or(defaults.compression),
+            not_found_page: raw.not_found_page.unwrap_or(defaults.not_found_page),
+            auth_token: raw.auth_token,
+            cors: raw.cors,
+            cache: raw.cache,
         })
     }
 }
@@ -161,19 +287,59 @@ impl<'de> Deserialize<'de> for ServerConfig {
 #[derive(Debug, Deserialize, Default)]
 #[serde(default)]
 struct DocsConfigRaw {
-    source_dir: Option<String>,
+    source_dir: Option<SourceDirsRaw>,
     cache_enabled: Option<bool>,
+    extensions: Option<Vec<String>>,
+    case_insensitive: Option<bool>,
+    homepage: Option<Vec<String>>,
+    toc_max_depth: Option<u8>,
+    math: Option<bool>,
+}
+
+/// `docs.source_dir` as written: one directory or a list of them.
+#[derive(Debug, Deserialize)]
+#[serde(untagged, expecting = "a directory or a list of directories")]
+enum SourceDirsRaw {
+    One(String),
+    Many(Vec<String>),
 }
 
 /// Resolved documentation configuration with absolute paths.
 #[derive(Debug, Default)]
 pub struct DocsConfig {
-    /// Source directory for markdown files.
+    /// Source directory for markdown files. When `docs.source_dir` lists
+    /// several directories, this is the first of them.
     pub source_dir: PathBuf,
+    /// Every source directory, in the order listed. Earlier directories win
+    /// when two provide the same page. Always contains [`Self::source_dir`]
+    /// first.
+    pub source_dirs: Vec<PathBuf>,
     /// Absolute path to the project's data directory — see [`DATA_DIR_NAME`].
     pub data_dir: PathBuf,
     /// Whether caching is enabled.
     pub cache_enabled: bool,
+    /// Markdown file extensions, without the leading dot, in precedence order
+    /// (default `["md"]`). When one page exists in several extensions, the
+    /// earliest listed wins.
+    pub extensions: Vec<String>,
+    /// Whether a URL path that matches no file exactly falls back to a
+    /// case-insensitive match (default `false`).
+    pub case_insensitive: bool,
+    /// Homepage fallback files, tried in order when `source_dir` has no
+    /// `index.md` (default `[<project_dir>/README.md]`). An empty list
+    /// disables the fallback.
+    pub homepage: Vec<PathBuf>,
+    /// Deepest heading level listed in a page's table of contents, 1 to 6
+    /// (default `None`: every level). Deeper headings keep their anchors.
+    pub toc_max_depth: Option<u8>,
+    /// Whether `$tex$` and `$$tex$$` render as math for a client-side `KaTeX`
+    /// or `MathJax` pass (default `false`).
+    pub math: bool,
+}
+
+/// Markdown file extensions recognized when `docs.extensions` is unset.
+fn default_extensions() -> Vec<String> {
+    vec!["md".to_owned()]
 }
 
 impl DocsConfig {
@@ -190,6 +356,8 @@ impl DocsConfig {
 struct DiagramsConfigRaw {
     kroki_url: Option<String>,
     include_dirs: Option<Vec<String>>,
+    default_format: Option<String>,
+    kroki_headers: Option<BTreeMap<String, String>>,
 }
 
 /// Resolved diagram rendering configuration with absolute paths.
@@ -199,6 +367,12 @@ pub struct DiagramsConfig {
     pub kroki_url: Option<String>,
     /// Directories to search for `PlantUML` `!include` directives.
     pub include_dirs: Vec<PathBuf>,
+    /// Output format (`"svg"` or `"png"`) for diagrams whose fence has no
+    /// `format` attribute. `None` means SVG.
+    pub default_format: Option<String>,
+    /// Extra HTTP headers sent with every Kroki request (e.g., an API key for
+    /// an auth proxy), by header name. Values may hold secrets.
+    pub kroki_headers: BTreeMap<String, String>,
 }
 
 /// Live reload configuration.
@@ -278,6 +452,14 @@ fn require_http_url(url: &str, field: &str) -> Result<(), ConfigError> {
     Ok(())
 }
 
+/// Whether `name` is a valid HTTP header name (an RFC 9110 token).
+fn is_header_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b))
+}
+
 impl Config {
     /// Load configuration from file with optional CLI settings.
     ///
@@ -357,6 +539,9 @@ impl Config {
             // An explicit `-p`/`--port` is a hard requirement — no port fallback.
             self.server.port_explicit = true;
         }
+        if let Some(port_auto) = settings.port_auto {
+            self.server.port_auto = port_auto;
+        }
         if let Some(cache_enabled) = settings.cache_enabled {
             self.docs_resolved.cache_enabled = cache_enabled;
         }
@@ -414,23 +599,43 @@ impl Config {
             metadata: MetadataConfig::default(),
             docs_resolved: DocsConfig {
                 source_dir: base.join("docs"),
+                source_dirs: vec![base.join("docs")],
                 data_dir: base.join(DATA_DIR_NAME),
                 cache_enabled: true,
+                extensions: default_extensions(),
+                case_insensitive: false,
+                homepage: vec![base.join("README.md")],
+                toc_max_depth: None,
+                math: false,
             },
             diagrams_resolved: DiagramsConfig::default(),
             project_dir: base.to_path_buf(),
+            config_file: None,
         }
     }
 
     /// Load configuration from a specific file.
     fn load_from_file(path: &Path) -> Result<Self, ConfigError> {
         let content = std::fs::read_to_string(path)?;
-        let mut config: Self = toml::from_str(&content)?;
-
-        // Expand environment variables before path resolution
-        config.expand_env_vars()?;
+        let table: toml::Table = toml::from_str(&content)?;
+
+        let mut config: Self = if table.contains_key(extends::EXTENDS_KEY) {
+            let layered = extends::resolve(path, table)?;
+            let mut config: Self = toml::Value::Table(layered.table).try_into()?;
+            // Expand before rebasing: a variable may hold an absolute path
+            config.expand_env_vars()?;
+            config.rebase_inherited_paths(&layered.bases);
+            config
+        } else {
+            // Parse the text itself so type errors keep their line and column
+            let mut config: Self = toml::from_str(&content)?;
+            // Expand environment variables before path resolution
+            config.expand_env_vars()?;
+            config
+        };
 
         config.project_dir = path.parent().unwrap_or(Path::new(".")).to_path_buf();
+        config.config_file = Some(path.to_path_buf());
         config.resolve_paths();
 
         Ok(config)
@@ -446,10 +651,87 @@ impl Config {
     /// Returns `ConfigError::Validation` if any validation fails.
     pub fn validate(&self) -> Result<(), ConfigError> {
         self.validate_server()?;
+        self.validate_metadata()?;
+        self.validate_docs()?;
         self.validate_diagrams()?;
         Ok(())
     }
 
+    /// Validate docs configuration.
+    fn validate_docs(&self) -> Result<(), ConfigError> {
+        let extensions = &self.docs_resolved.extensions;
+        if extensions.is_empty() {
+            return Err(ConfigError::Validation(
+                "docs.extensions cannot be empty".to_owned(),
+            ));
+        }
+        // A single directory may be missing (a README-only project); a list
+        // must name at least one that exists.
+        if let Some(SourceDirsRaw::Many(dirs)) = &self.docs.source_dir {
+            if dirs.is_empty() {
+                return Err(ConfigError::Validation(
+                    "docs.source_dir cannot be an empty list".to_owned(),
+                ));
+            }
+            for dir in dirs {
+                require_non_empty(dir, "docs.source_dir entry")?;
+            }
+            if !self.docs_resolved.source_dirs.iter().any(|d| d.is_dir()) {
+                return Err(ConfigError::Validation(
+                    "none of the directories in docs.source_dir exist".to_owned(),
+                ));
+            }
+        }
+        if self
+            .docs_resolved
+            .toc_max_depth
+            .is_some_and(|depth| !(1..=6).contains(&depth))
+        {
+            return Err(ConfigError::Validation(
+                "docs.toc_max_depth must be between 1 and 6".to_owned(),
+            ));
+        }
+        for candidate in &self.docs_resolved.homepage {
+            // `project_dir.join("")` is the project directory itself.
+            if candidate == &self.project_dir {
+                return Err(ConfigError::Validation(
+                    "docs.homepage entry cannot be empty".to_owned(),
+                ));
+            }
+        }
+        for ext in extensions {
+            require_non_empty(ext, "docs.extensions entry")?;
+            if ext.contains(['.', '/', '\\', '*', '?', '[', '{']) {
+                return Err(ConfigError::Validation(format!(
+                    "docs.extensions entry `{ext}` must be a bare extension such as `markdown`"
+                )));
+            }
+            // Content is classified before metadata, so an extension the
+            // metadata file ends in would turn every sidecar into a page.
+            if self.metadata.name.ends_with(&format!(".{ext}")) {
+                return Err(ConfigError::Validation(format!(
+                    "docs.extensions entry `{ext}` matches metadata file `{}`",
+                    self.metadata.name
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Validate metadata configuration.
+    fn validate_metadata(&self) -> Result<(), ConfigError> {
+        let name = &self.metadata.name;
+        require_non_empty(name, "metadata.name")?;
+        // Sidecars are matched by file name in every directory.
+        if name.contains(['/', '\\']) {
+            return Err(ConfigError::Validation(format!(
+                "metadata.name `{name}` must be a file name, not a path"
+            )));
+        }
+        Ok(())
+    }
+
     /// Validate server configuration.
     fn validate_server(&self) -> Result<(), ConfigError> {
         require_non_empty(&self.server.host, "server.host")?;
@@ -462,6 +744,43 @@ impl Config {
             ));
         }
 
+        let cors = &self.server.cors;
+        for origin in cors.allowed_origins.iter().filter(|o| *o != "*") {
+            require_http_url(origin, "server.cors.allowed_origins")?;
+            // Browsers send `Origin` as scheme://host[:port], so anything
+            // after the authority could never match.
+            let authority = origin.split_once("://").map_or("", |(_, rest)| rest);
+            if authority.is_empty() || authority.contains('/') {
+                return Err(ConfigError::Validation(format!(
+                    "server.cors.allowed_origins entry \"{origin}\" must be scheme://host[:port] with no path"
+                )));
+            }
+        }
+        for (field, values) in [
+            ("server.cors.allowed_methods", &cors.allowed_methods),
+            ("server.cors.allowed_headers", &cors.allowed_headers),
+        ] {
+            if let Some(value) = values.iter().find(|v| *v != "*" && !is_header_name(v)) {
+                return Err(ConfigError::Validation(format!(
+                    "{field} has an invalid entry \"{value}\""
+                )));
+            }
+        }
+
+        let cache = &self.server.cache;
+        for (field, value) in [
+            ("server.cache.pages", &cache.pages),
+            ("server.cache.assets", &cache.assets),
+            ("server.cache.live_reload", &cache.live_reload),
+        ] {
+            require_non_empty(value, field)?;
+            if value.chars().any(|c| c.is_control() && c != '\t') {
+                return Err(ConfigError::Validation(format!(
+                    "{field} contains a control character"
+                )));
+            }
+        }
+
         Ok(())
     }
 
@@ -473,6 +792,27 @@ impl Config {
             require_non_empty(kroki_url, "diagrams.kroki_url")?;
             require_http_url(kroki_url, "diagrams.kroki_url")?;
         }
+        if let Some(ref format) = self.diagrams_resolved.default_format
+            && !DIAGRAM_FORMATS.contains(&format.as_str())
+        {
+            return Err(ConfigError::Validation(format!(
+                "diagrams.default_format must be one of {}, got \"{format}\"",
+                DIAGRAM_FORMATS.join(", ")
+            )));
+        }
+        for (name, value) in &self.diagrams_resolved.kroki_headers {
+            if !is_header_name(name) {
+                return Err(ConfigError::Validation(format!(
+                    "diagrams.kroki_headers has an invalid header name \"{name}\""
+                )));
+            }
+            // The value may be a secret, so it is never echoed.
+            if value.chars().any(|c| c.is_control() && c != '\t') {
+                return Err(ConfigError::Validation(format!(
+                    "diagrams.kroki_headers.{name} contains a control character"
+                )));
+            }
+        }
 
         Ok(())
     }
@@ -481,6 +821,23 @@ impl Config {
     fn expand_env_vars(&mut self) -> Result<(), ConfigError> {
         // Server config
         self.server.host = expand::expand_env(&self.server.host, "server.host")?;
+        if let Some(ref mut token) = self.server.auth_token {
+            *token = expand::expand_env(token, "server.auth_token")?;
+        }
+
+        // Docs config: paths are expanded here, before `resolve_paths` joins
+        // them to the project directory
+        match &mut self.docs.source_dir {
+            Some(SourceDirsRaw::One(dir)) => {
+                *dir = expand::expand_env(dir, "docs.source_dir")?;
+            }
+            Some(SourceDirsRaw::Many(dirs)) => {
+                for (i, dir) in dirs.iter_mut().enumerate() {
+                    *dir = expand::expand_env(dir, &format!("docs.source_dir[{i}]"))?;
+                }
+            }
+            None => {}
+        }
 
         // Diagrams config (if present)
         if let Some(ref mut diagrams) = self.diagrams
@@ -488,10 +845,48 @@ impl Config {
         {
             diagrams.kroki_url = Some(expand::expand_env(url, "diagrams.kroki_url")?);
         }
+        if let Some(ref mut diagrams) = self.diagrams
+            && let Some(ref mut include_dirs) = diagrams.include_dirs
+        {
+            for (i, dir) in include_dirs.iter_mut().enumerate() {
+                *dir = expand::expand_env(dir, &format!("diagrams.include_dirs[{i}]"))?;
+            }
+        }
+        if let Some(ref mut diagrams) = self.diagrams
+            && let Some(ref mut headers) = diagrams.kroki_headers
+        {
+            for (name, value) in headers.iter_mut() {
+                *value = expand::expand_env(value, &format!("diagrams.kroki_headers.{name}"))?;
+            }
+        }
 
         Ok(())
     }
 
+    /// Make path fields inherited through `extends` absolute against the
+    /// directory of the base that set them, so [`Self::resolve_paths`] leaves
+    /// them alone. Empty entries stay empty for validation to reject.
+    fn rebase_inherited_paths(&mut self, bases: &BTreeMap<extends::PathField, PathBuf>) {
+        for (field, base_dir) in bases {
+            let dirs: Vec<&mut String> = match field {
+                extends::PathField::SourceDir => match &mut self.docs.source_dir {
+                    Some(SourceDirsRaw::One(dir)) => vec![dir],
+                    Some(SourceDirsRaw::Many(dirs)) => dirs.iter_mut().collect(),
+                    None => Vec::new(),
+                },
+                extends::PathField::Homepage => self.docs.homepage.iter_mut().flatten().collect(),
+                extends::PathField::IncludeDirs => self
+                    .diagrams
+                    .iter_mut()
+                    .flat_map(|d| d.include_dirs.iter_mut().flatten())
+                    .collect(),
+            };
+            for dir in dirs.into_iter().filter(|d| !d.is_empty()) {
+                *dir = base_dir.join(&*dir).to_string_lossy().into_owned();
+            }
+        }
+    }
+
     /// Resolve relative paths in `docs` and `diagrams` against
     /// [`Config::project_dir`], which must already be set.
     ///
@@ -504,12 +899,40 @@ impl Config {
     /// [`Self::validate`] afterwards.
     fn resolve_paths(&mut self) {
         let project_dir = self.project_dir.clone();
-        let resolve = |path: Option<&str>, default: &str| project_dir.join(path.unwrap_or(default));
+        let source_dirs: Vec<PathBuf> = match &self.docs.source_dir {
+            Some(SourceDirsRaw::One(dir)) => vec![project_dir.join(dir)],
+            Some(SourceDirsRaw::Many(dirs)) => dirs.iter().map(|d| project_dir.join(d)).collect(),
+            None => vec![project_dir.join("docs")],
+        };
 
         self.docs_resolved = DocsConfig {
-            source_dir: resolve(self.docs.source_dir.as_deref(), "docs"),
+            // An empty list is rejected by `validate_docs`; the fallback only
+            // keeps the field meaningful until then.
+            source_dir: source_dirs
+                .first()
+                .cloned()
+                .unwrap_or_else(|| project_dir.join("docs")),
+            source_dirs,
             data_dir: project_dir.join(DATA_DIR_NAME),
             cache_enabled: self.docs.cache_enabled.unwrap_or(true),
+            // A leading dot is accepted and dropped: `.markdown` and
+            // `markdown` mean the same thing.
+            extensions: self
+                .docs
+                .extensions
+                .as_ref()
+                .map_or_else(default_extensions, |exts| {
+                    exts.iter()
+                        .map(|ext| ext.strip_prefix('.').unwrap_or(ext).to_owned())
+                        .collect()
+                }),
+            case_insensitive: self.docs.case_insensitive.unwrap_or(false),
+            homepage: match &self.docs.homepage {
+                Some(candidates) => candidates.iter().map(|c| project_dir.join(c)).collect(),
+                None => vec![project_dir.join("README.md")],
+            },
+            toc_max_depth: self.docs.toc_max_depth,
+            math: self.docs.math.unwrap_or(false),
         };
 
         self.diagrams_resolved = match &self.diagrams {
@@ -523,6 +946,8 @@ impl Config {
                 DiagramsConfig {
                     kroki_url: diagrams.kroki_url.clone(),
                     include_dirs,
+                    default_format: diagrams.default_format.clone(),
+                    kroki_headers: diagrams.kroki_headers.clone().unwrap_or_default(),
                 }
             }
             None => DiagramsConfig::default(),
@@ -658,6 +1083,82 @@ port = 9000
         assert!(config.server.port_explicit);
     }
 
+    #[test]
+    fn test_port_auto() {
+        let default = Config::default_with_base(Path::new("/test"));
+        assert!(!default.server.port_auto);
+
+        let mut config: Config =
+            toml::from_str("[server]\nport = 9000\nport_auto = true\n").unwrap();
+        assert!(config.server.port_explicit);
+        assert!(config.server.port_auto);
+
+        config.apply_cli_settings(&CliSettings {
+            port_auto: Some(false),
+            ..Default::default()
+        });
+        assert!(!config.server.port_auto);
+    }
+
+    #[test]
+    fn test_parse_server_compression() {
+        assert!(
+            Config::default_with_base(Path::new("/test"))
+                .server
+                .compression
+        );
+        let config: Config = toml::from_str("[server]\ncompression = false\n").unwrap();
+        assert!(!config.server.compression);
+    }
+
+    #[test]
+    fn test_parse_server_not_found_page() {
+        let default = Config::default_with_base(Path::new("/test"));
+        assert_eq!(default.server.not_found_page, "404");
+        let config: Config =
+            toml::from_str("[server]\nnot_found_page = \"errors/missing\"\n").unwrap();
+        assert_eq!(config.server.not_found_page, "errors/missing");
+    }
+
+    #[test]
+    fn test_parse_server_cors() {
+        let default = Config::default_with_base(Path::new("/test"));
+        assert!(default.server.cors.is_disabled());
+
+        let config: Config = toml::from_str(
+            "[server.cors]\nallowed_origins = [\"https://backstage.example.com\"]\n\
+             allowed_headers = [\"content-type\", \"x-request-id\"]\n",
+        )
+        .unwrap();
+        let cors = &config.server.cors;
+        assert!(!cors.is_disabled());
+        assert_eq!(cors.allowed_origins, ["https://backstage.example.com"]);
+        assert_eq!(cors.allowed_headers, ["content-type", "x-request-id"]);
+        // Unset lists keep their defaults.
+        assert_eq!(cors.allowed_methods, CorsConfig::default().allowed_methods);
+    }
+
+    #[test]
+    fn test_parse_server_cache() {
+        let default = Config::default_with_base(Path::new("/test"));
+        assert_eq!(default.server.cache.pages, "no-cache");
+        assert_eq!(
+            default.server.cache.assets,
+            "public, max-age=31536000, immutable"
+        );
+        assert_eq!(default.server.cache.live_reload, "no-cache");
+
+        let config: Config = toml::from_str(
+            "[server.cache]\npages = \"max-age=60, must-revalidate\"\nlive_reload = \"no-store\"\n",
+        )
+        .unwrap();
+        let cache = &config.server.cache;
+        assert_eq!(cache.pages, "max-age=60, must-revalidate");
+        assert_eq!(cache.live_reload, "no-store");
+        // Unset values keep their defaults.
+        assert_eq!(cache.assets, CacheConfig::default().assets);
+    }
+
     #[test]
     fn test_parse_live_reload_config() {
         let toml = r"
@@ -700,6 +1201,184 @@ include_dirs = ["diagrams", "shared/diagrams"]
         );
     }
 
+    #[test]
+    fn test_docs_extensions_default_to_md() {
+        let mut config: Config = toml::from_str("").unwrap();
+        config.project_dir = PathBuf::from("/project");
+        config.resolve_paths();
+
+        assert_eq!(config.docs_resolved.extensions, vec!["md".to_owned()]);
+    }
+
+    #[test]
+    fn test_docs_extensions_drop_leading_dot() {
+        let toml = r#"
+[docs]
+extensions = ["md", ".markdown"]
+"#;
+        let mut config: Config = toml::from_str(toml).unwrap();
+        config.project_dir = PathBuf::from("/project");
+        config.resolve_paths();
+
+        assert_eq!(
+            config.docs_resolved.extensions,
+            vec!["md".to_owned(), "markdown".to_owned()]
+        );
+    }
+
+    #[test]
+    fn test_docs_case_insensitive() {
+        let mut config: Config = toml::from_str("").unwrap();
+        config.project_dir = PathBuf::from("/project");
+        config.resolve_paths();
+        assert!(!config.docs_resolved.case_insensitive);
+
+        let mut config: Config = toml::from_str("[docs]\ncase_insensitive = true\n").unwrap();
+        config.project_dir = PathBuf::from("/project");
+        config.resolve_paths();
+        assert!(config.docs_resolved.case_insensitive);
+    }
+
+    #[test]
+    fn test_docs_math() {
+        let mut config: Config = toml::from_str("").unwrap();
+        config.project_dir = PathBuf::from("/project");
+        config.resolve_paths();
+        assert!(!config.docs_resolved.math);
+
+        let mut config: Config = toml::from_str("[docs]\nmath = true\n").unwrap();
+        config.project_dir = PathBuf::from("/project");
+        config.resolve_paths();
+        assert!(config.docs_resolved.math);
+    }
+
+    #[test]
+    fn test_docs_toc_max_depth() {
+        let mut config: Config = toml::from_str("").unwrap();
+        config.project_dir = PathBuf::from("/project");
+        config.resolve_paths();
+        assert_eq!(config.docs_resolved.toc_max_depth, None);
+
+        let mut config: Config = toml::from_str("[docs]\ntoc_max_depth = 3\n").unwrap();
+        config.project_dir = PathBuf::from("/project");
+        config.resolve_paths();
+        assert_eq!(config.docs_resolved.toc_max_depth, Some(3));
+        config.validate().unwrap();
+
+        let mut config: Config = toml::from_str("[docs]\ntoc_max_depth = 0\n").unwrap();
+        config.project_dir = PathBuf::from("/project");
+        config.resolve_paths();
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("docs.toc_max_depth"), "{err}");
+    }
+
+    #[test]
+    fn test_docs_homepage_defaults_to_readme() {
+        let mut config: Config = toml::from_str("").unwrap();
+        config.project_dir = PathBuf::from("/project");
+        config.resolve_paths();
+
+        assert_eq!(
+            config.docs_resolved.homepage,
+            vec![PathBuf::from("/project/README.md")]
+        );
+    }
+
+    #[test]
+    fn test_docs_homepage_resolves_against_project_dir() {
+        let toml = r#"
+[docs]
+homepage = ["HOME.md", "docs/overview.md", "README.md"]
+"#;
+        let mut config: Config = toml::from_str(toml).unwrap();
+        config.project_dir = PathBuf::from("/project");
+        config.resolve_paths();
+
+        assert_eq!(
+            config.docs_resolved.homepage,
+            vec![
+                PathBuf::from("/project/HOME.md"),
+                PathBuf::from("/project/docs/overview.md"),
+                PathBuf::from("/project/README.md"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_docs_homepage_rejects_empty_entry() {
+        let mut config = Config::default_with_base(Path::new("/test"));
+        config.docs_resolved.homepage = vec![PathBuf::from("/test")];
+        assert_validation_error(&config, &["docs.homepage", "empty"]);
+    }
+
+    #[test]
+    fn test_docs_source_dir_accepts_a_list() {
+        let toml = r#"
+[docs]
+source_dir = ["docs", "../shared/docs"]
+"#;
+        let mut config: Config = toml::from_str(toml).unwrap();
+        config.project_dir = PathBuf::from("/project");
+        config.resolve_paths();
+
+        assert_eq!(
+            config.docs_resolved.source_dirs,
+            vec![
+                PathBuf::from("/project/docs"),
+                PathBuf::from("/project/../shared/docs"),
+            ]
+        );
+        assert_eq!(
+            config.docs_resolved.source_dir,
+            PathBuf::from("/project/docs")
+        );
+    }
+
+    #[test]
+    fn test_docs_source_dir_string_is_a_one_entry_list() {
+        let mut config: Config = toml::from_str("[docs]\nsource_dir = \"content\"\n").unwrap();
+        config.project_dir = PathBuf::from("/project");
+        config.resolve_paths();
+
+        assert_eq!(
+            config.docs_resolved.source_dirs,
+            vec![PathBuf::from("/project/content")]
+        );
+    }
+
+    #[test]
+    fn test_docs_source_dir_rejects_other_types() {
+        let err = toml::from_str::<Config>("[docs]\nsource_dir = 42\n").unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("a directory or a list of directories"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_validate_docs_source_dir_rejects_empty_list() {
+        let mut config: Config = toml::from_str("[docs]\nsource_dir = []\n").unwrap();
+        config.project_dir = PathBuf::from("/test");
+        config.resolve_paths();
+        assert_validation_error(&config, &["docs.source_dir", "empty list"]);
+    }
+
+    #[test]
+    fn test_validate_docs_source_dir_list_needs_an_existing_dir() {
+        let (dir, _) = rw_toml_tempdir(
+            "source-dir-list",
+            "[docs]\nsource_dir = [\"docs\", \"extra\"]\n",
+        );
+
+        let err = Config::load_from_dir(dir.path(), None).unwrap_err();
+        assert!(err.to_string().contains("docs.source_dir"), "{err}");
+
+        std::fs::create_dir(dir.path().join("extra")).unwrap();
+        let config = Config::load_from_dir(dir.path(), None).expect("one dir exists");
+        assert_eq!(config.docs_resolved.source_dirs.len(), 2);
+    }
+
     #[test]
     fn test_diagrams_section_without_kroki_url_is_valid() {
         let toml = r#"
@@ -884,6 +1563,27 @@ host = "${TEST_HOST}"
         }
     }
 
+    #[test]
+    fn test_expand_env_vars_server_auth_token() {
+        // SAFETY: test runs single-threaded per test function
+        unsafe {
+            std::env::set_var("TEST_RW_AUTH_TOKEN", "s3cret");
+        }
+
+        let toml = r#"
+[server]
+auth_token = "${TEST_RW_AUTH_TOKEN}"
+"#;
+        let mut config: Config = toml::from_str(toml).unwrap();
+        config.expand_env_vars().unwrap();
+
+        assert_eq!(config.server.auth_token.as_deref(), Some("s3cret"));
+
+        unsafe {
+            std::env::remove_var("TEST_RW_AUTH_TOKEN");
+        }
+    }
+
     #[test]
     fn test_expand_env_vars_diagrams_kroki_url() {
         // SAFETY: test runs single-threaded per test function
@@ -929,6 +1629,89 @@ kroki_url = "${MISSING_VAR_CONFIG_TEST}"
         assert!(err.to_string().contains("diagrams.kroki_url"));
     }
 
+    #[test]
+    fn test_expand_env_vars_in_source_dir() {
+        // SAFETY: test runs single-threaded per test function
+        unsafe {
+            std::env::set_var("TEST_DOCS_ROOT_CONFIG", "/srv/docs");
+        }
+
+        let toml = r#"
+[docs]
+source_dir = "${TEST_DOCS_ROOT_CONFIG}/content"
+"#;
+        let mut config: Config = toml::from_str(toml).unwrap();
+        config.expand_env_vars().unwrap();
+        config.project_dir = PathBuf::from("/project");
+        config.resolve_paths();
+
+        assert_eq!(
+            config.docs_resolved.source_dir,
+            PathBuf::from("/srv/docs/content")
+        );
+
+        // SAFETY: test cleanup
+        unsafe {
+            std::env::remove_var("TEST_DOCS_ROOT_CONFIG");
+        }
+    }
+
+    #[test]
+    fn test_expand_env_vars_in_include_dirs_with_default() {
+        // SAFETY: test runs single-threaded per test function
+        unsafe {
+            std::env::remove_var("TEST_SHARED_INCLUDES_CONFIG");
+        }
+
+        let toml = r#"
+[diagrams]
+include_dirs = ["local", "${TEST_SHARED_INCLUDES_CONFIG:-shared}/c4"]
+"#;
+        let mut config: Config = toml::from_str(toml).unwrap();
+        config.expand_env_vars().unwrap();
+        config.project_dir = PathBuf::from("/project");
+        config.resolve_paths();
+
+        assert_eq!(
+            config.diagrams_resolved.include_dirs,
+            vec![
+                PathBuf::from("/project/local"),
+                PathBuf::from("/project/shared/c4"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_expand_env_vars_error_names_list_entry() {
+        // SAFETY: test runs single-threaded per test function
+        unsafe {
+            std::env::remove_var("MISSING_INCLUDE_CONFIG_TEST");
+            std::env::remove_var("MISSING_SOURCE_CONFIG_TEST");
+        }
+
+        let toml = r#"
+[diagrams]
+include_dirs = ["local", "${MISSING_INCLUDE_CONFIG_TEST}"]
+"#;
+        let mut config: Config = toml::from_str(toml).unwrap();
+        let err = config.expand_env_vars().unwrap_err();
+        assert_matches!(
+            err,
+            ConfigError::EnvVar { ref field, .. } if field == "diagrams.include_dirs[1]"
+        );
+
+        let toml = r#"
+[docs]
+source_dir = ["docs", "${MISSING_SOURCE_CONFIG_TEST}"]
+"#;
+        let mut config: Config = toml::from_str(toml).unwrap();
+        let err = config.expand_env_vars().unwrap_err();
+        assert_matches!(
+            err,
+            ConfigError::EnvVar { ref field, .. } if field == "docs.source_dir[1]"
+        );
+    }
+
     #[test]
     fn test_expand_env_vars_literal_unchanged() {
         let toml = r#"
@@ -981,6 +1764,74 @@ host = "127.0.0.1"
         assert_validation_error(&config, &["server.port"]);
     }
 
+    #[test]
+    fn test_validate_server_cors() {
+        let mut config = Config::default_with_base(Path::new("/test"));
+        config.server.cors.allowed_origins =
+            vec!["*".to_owned(), "http://localhost:3000".to_owned()];
+        assert!(config.validate().is_ok());
+
+        config.server.cors.allowed_origins = vec!["localhost:3000".to_owned()];
+        assert_validation_error(&config, &["server.cors.allowed_origins", "http://"]);
+        config.server.cors.allowed_origins = vec!["https://example.com/docs".to_owned()];
+        assert_validation_error(&config, &["server.cors.allowed_origins", "no path"]);
+
+        config.server.cors.allowed_origins = vec!["*".to_owned()];
+        config.server.cors.allowed_headers = vec!["x request".to_owned()];
+        assert_validation_error(&config, &["server.cors.allowed_headers", "x request"]);
+    }
+
+    #[test]
+    fn test_validate_server_cache() {
+        let mut config = Config::default_with_base(Path::new("/test"));
+        config.server.cache.pages = "max-age=60, must-revalidate".to_owned();
+        assert!(config.validate().is_ok());
+
+        config.server.cache.assets = String::new();
+        assert_validation_error(&config, &["server.cache.assets", "empty"]);
+        config.server.cache.assets = "no-cache\r\nX-Evil: 1".to_owned();
+        assert_validation_error(&config, &["server.cache.assets", "control character"]);
+    }
+
+    #[test]
+    fn test_validate_docs_extensions_empty() {
+        let mut config = Config::default_with_base(Path::new("/test"));
+        config.docs_resolved.extensions = Vec::new();
+        assert_validation_error(&config, &["docs.extensions", "empty"]);
+    }
+
+    #[test]
+    fn test_validate_docs_extensions_rejects_patterns() {
+        for ext in ["", "tar.md", "*.md", "docs/md"] {
+            let mut config = Config::default_with_base(Path::new("/test"));
+            config.docs_resolved.extensions = vec![ext.to_owned()];
+            assert_validation_error(&config, &["docs.extensions"]);
+        }
+    }
+
+    #[test]
+    fn test_validate_docs_extensions_rejects_metadata_suffix() {
+        let mut config = Config::default_with_base(Path::new("/test"));
+        config.docs_resolved.extensions = vec!["md".to_owned(), "yaml".to_owned()];
+        assert_validation_error(&config, &["yaml", "meta.yaml"]);
+    }
+
+    #[test]
+    fn test_validate_metadata_name_rejects_paths() {
+        for name in ["", "meta/yaml", "..\\meta.yaml"] {
+            let mut config = Config::default_with_base(Path::new("/test"));
+            config.metadata.name = name.to_owned();
+            assert_validation_error(&config, &["metadata.name"]);
+        }
+    }
+
+    #[test]
+    fn test_validate_metadata_name_accepts_glob_characters() {
+        let mut config = Config::default_with_base(Path::new("/test"));
+        config.metadata.name = "[unclosed".to_owned();
+        assert!(config.validate().is_ok());
+    }
+
     #[test]
     fn test_validate_diagrams_kroki_url_empty() {
         let mut config = Config::default_with_base(Path::new("/test"));
@@ -1002,6 +1853,88 @@ host = "127.0.0.1"
         assert!(config.validate().is_ok());
     }
 
+    #[test]
+    fn test_diagrams_default_format_is_resolved() {
+        let toml = r#"
+[diagrams]
+default_format = "png"
+"#;
+        let mut config: Config = toml::from_str(toml).unwrap();
+        config.project_dir = PathBuf::from("/test");
+        config.resolve_paths();
+        assert_eq!(
+            config.diagrams_resolved.default_format.as_deref(),
+            Some("png")
+        );
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_diagrams_kroki_headers_are_resolved_and_expanded() {
+        // SAFETY: test runs single-threaded per test function
+        unsafe {
+            std::env::set_var("TEST_KROKI_API_KEY", "s3cret");
+        }
+
+        let toml = r#"
+[diagrams]
+kroki_url = "https://kroki.internal"
+kroki_headers = { "X-Api-Key" = "${TEST_KROKI_API_KEY}", "X-Team" = "docs" }
+"#;
+        let mut config: Config = toml::from_str(toml).unwrap();
+        config.project_dir = PathBuf::from("/test");
+        config.expand_env_vars().unwrap();
+        config.resolve_paths();
+
+        let headers: Vec<_> = config
+            .diagrams_resolved
+            .kroki_headers
+            .iter()
+            .map(|(k, v)| (k.as_str(), v.as_str()))
+            .collect();
+        assert_eq!(headers, [("X-Api-Key", "s3cret"), ("X-Team", "docs")]);
+        assert!(config.validate().is_ok());
+
+        unsafe {
+            std::env::remove_var("TEST_KROKI_API_KEY");
+        }
+    }
+
+    #[test]
+    fn test_diagrams_kroki_headers_default_empty() {
+        let config = Config::default_with_base(Path::new("/test"));
+        assert!(config.diagrams_resolved.kroki_headers.is_empty());
+    }
+
+    #[test]
+    fn test_validate_diagrams_kroki_headers_invalid_name() {
+        let mut config = Config::default_with_base(Path::new("/test"));
+        config
+            .diagrams_resolved
+            .kroki_headers
+            .insert("X Api Key".to_owned(), "secret".to_owned());
+        assert_validation_error(&config, &["diagrams.kroki_headers", "X Api Key"]);
+    }
+
+    #[test]
+    fn test_validate_diagrams_kroki_headers_value_not_echoed() {
+        let mut config = Config::default_with_base(Path::new("/test"));
+        config
+            .diagrams_resolved
+            .kroki_headers
+            .insert("X-Api-Key".to_owned(), "sec\nret".to_owned());
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("diagrams.kroki_headers.X-Api-Key"), "{err}");
+        assert!(!err.contains("sec"), "{err}");
+    }
+
+    #[test]
+    fn test_validate_diagrams_default_format_unknown() {
+        let mut config = Config::default_with_base(Path::new("/test"));
+        config.diagrams_resolved.default_format = Some("jpeg".to_owned());
+        assert_validation_error(&config, &["diagrams.default_format", "svg, png", "jpeg"]);
+    }
+
     #[test]
     fn test_validate_diagrams_kroki_url_valid_https() {
         let mut config = Config::default_with_base(Path::new("/test"));
@@ -1150,6 +2083,51 @@ host = "127.0.0.1"
         );
     }
 
+    #[test]
+    fn extends_merges_base_and_resolves_its_paths_against_its_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path().join("project");
+        let base = dir.path().join("base");
+        std::fs::create_dir(&project).unwrap();
+        std::fs::create_dir(&base).unwrap();
+        std::fs::write(
+            base.join("rw.toml"),
+            "[server]\nhost = \"0.0.0.0\"\nport = 8000\n\
+             [docs]\nsource_dir = \"base-docs\"\n\
+             [diagrams]\ninclude_dirs = [\"c4\"]\n",
+        )
+        .unwrap();
+        let toml_path = project.join("rw.toml");
+        std::fs::write(
+            &toml_path,
+            "extends = \"../base/rw.toml\"\n\
+             [server]\nport = 9000\n\
+             [docs]\nsource_dir = \"docs\"\n",
+        )
+        .unwrap();
+
+        let config = Config::load_from_file(&toml_path).expect("load config");
+
+        assert_eq!(config.server.host, "0.0.0.0");
+        assert_eq!(config.server.port, 9000);
+        assert_eq!(config.project_dir, project);
+        assert_eq!(config.docs_resolved.source_dir, project.join("docs"));
+        assert_eq!(
+            config.diagrams_resolved.include_dirs,
+            vec![base.canonicalize().unwrap().join("c4")]
+        );
+    }
+
+    #[test]
+    fn extends_cycle_is_a_validation_error() {
+        let (dir, toml_path) = rw_toml_tempdir("extends-cycle", "extends = \"base.toml\"\n");
+        std::fs::write(dir.path().join("base.toml"), "extends = \"rw.toml\"\n").unwrap();
+
+        let err = Config::load_from_file(&toml_path).unwrap_err();
+
+        assert_matches!(err, ConfigError::Validation(ref msg) if msg.contains("extends cycle"));
+    }
+
     #[test]
     fn project_dir_is_the_base_for_a_nested_source_dir() {
         let (dir, toml_path) =
@@ -1185,6 +2163,7 @@ host = "127.0.0.1"
         let config = Config::load_from_dir(dir.path(), None).expect("load from dir");
 
         assert_eq!(config.project_dir, dir.path());
+        assert_eq!(config.config_file, Some(dir.path().join("rw.toml")));
         assert_eq!(config.docs_resolved.source_dir, dir.path().join("content"));
     }
 
@@ -1199,6 +2178,7 @@ host = "127.0.0.1"
         let config = Config::load_from_dir(dir.path(), None).expect("load from dir");
 
         assert_eq!(config.project_dir, dir.path());
+        assert_eq!(config.config_file, None);
         assert_eq!(config.docs_resolved.source_dir, dir.path().join("docs"));
         assert_eq!(
             config.docs_resolved.data_dir,
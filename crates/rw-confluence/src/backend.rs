@@ -1,7 +1,8 @@
 //! Confluence render backend.
 //!
 //! This module provides [`ConfluenceBackend`] for rendering markdown to
-//! Confluence XHTML storage format.
+//! Confluence XHTML storage format, and [`PlainQuoteBackend`], its variant
+//! that leaves blockquotes as `<blockquote>`.
 
 use std::fmt::Write;
 
@@ -11,15 +12,41 @@ use rw_renderer::{AlertKind, RenderBackend, StatusColor, escape_html};
 ///
 /// Produces Confluence XHTML storage format with:
 /// - `ac:structured-macro` for code blocks
+/// - Verbatim passthrough for ```` ```confluence ```` fences
 /// - Info panel macro for blockquotes
 /// - `ac:image` with `ri:url` or `ri:attachment` for images
 /// - Title extraction from first H1 with level shifting
 pub(crate) struct ConfluenceBackend;
 
+/// Fence language whose body passes through to the storage format verbatim.
+const PASSTHROUGH_LANGUAGE: &str = "confluence";
+
+/// The parameters every `code` macro is written with, after `language`.
+/// [`PageRenderer`](crate::renderer::PageRenderer) swaps them for the
+/// configured ones when they differ.
+pub(crate) const CODE_MACRO_PARAMS: &str =
+    r#"<ac:parameter ac:name="linenumbers">true</ac:parameter>"#;
+
+/// Opens a `code` macro's body; follows [`CODE_MACRO_PARAMS`] directly.
+pub(crate) const CODE_MACRO_BODY: &str = "<ac:plain-text-body><![CDATA[";
+
 impl RenderBackend for ConfluenceBackend {
     const TITLE_AS_METADATA: bool = true;
 
+    /// Writes a `code` macro, except for a ```` ```confluence ```` fence,
+    /// whose body is already storage format (a JIRA macro, page properties,
+    /// anything markdown has no syntax for) and is written out unescaped.
+    ///
+    /// The passthrough is not sanitized: whoever authors the markdown can put
+    /// any markup into the page. That is the same trust the publisher already
+    /// extends to the author, but never feed it markdown from an untrusted
+    /// source. The HTML backend has no such branch, so local preview shows the
+    /// raw macro as an ordinary code block.
     fn code_block(lang: Option<&str>, content: &str, out: &mut String) {
+        if lang == Some(PASSTHROUGH_LANGUAGE) {
+            out.push_str(content.trim_end_matches('\n'));
+            return;
+        }
         out.push_str(r#"<ac:structured-macro ac:name="code" ac:schema-version="1">"#);
         if let Some(lang) = lang {
             write!(
@@ -29,13 +56,9 @@ impl RenderBackend for ConfluenceBackend {
             )
             .unwrap();
         }
-        out.push_str(r#"<ac:parameter ac:name="linenumbers">true</ac:parameter>"#);
+        out.push_str(CODE_MACRO_PARAMS);
         // CDATA content is not escaped
-        write!(
-            out,
-            r"<ac:plain-text-body><![CDATA[{content}]]></ac:plain-text-body>"
-        )
-        .unwrap();
+        write!(out, r"{CODE_MACRO_BODY}{content}]]></ac:plain-text-body>").unwrap();
         out.push_str("</ac:structured-macro>");
     }
 
@@ -125,8 +148,65 @@ impl RenderBackend for ConfluenceBackend {
     }
 }
 
+/// [`ConfluenceBackend`] with plain `<blockquote>`s.
+///
+/// For pages whose blockquotes are quotations rather than callouts: only GFM
+/// alerts (`> [!NOTE]`) become panel macros. Everything else delegates to
+/// `ConfluenceBackend`.
+pub(crate) struct PlainQuoteBackend;
+
+impl RenderBackend for PlainQuoteBackend {
+    const TITLE_AS_METADATA: bool = ConfluenceBackend::TITLE_AS_METADATA;
+
+    fn code_block(lang: Option<&str>, content: &str, out: &mut String) {
+        ConfluenceBackend::code_block(lang, content, out);
+    }
+
+    fn blockquote_start(out: &mut String) {
+        out.push_str("<blockquote>");
+    }
+
+    fn blockquote_end(out: &mut String) {
+        out.push_str("</blockquote>");
+    }
+
+    fn alert_start(kind: AlertKind, out: &mut String) {
+        ConfluenceBackend::alert_start(kind, out);
+    }
+
+    fn alert_end(kind: AlertKind, out: &mut String) {
+        ConfluenceBackend::alert_end(kind, out);
+    }
+
+    fn image(src: &str, alt: &str, title: &str, out: &mut String) {
+        ConfluenceBackend::image(src, alt, title, out);
+    }
+
+    fn hard_break(out: &mut String) {
+        ConfluenceBackend::hard_break(out);
+    }
+
+    fn horizontal_rule(out: &mut String) {
+        ConfluenceBackend::horizontal_rule(out);
+    }
+
+    fn task_list_marker(checked: bool, out: &mut String) {
+        ConfluenceBackend::task_list_marker(checked, out);
+    }
+
+    fn status_open(color: StatusColor, out: &mut String) {
+        ConfluenceBackend::status_open(color, out);
+    }
+
+    fn status_close(out: &mut String) {
+        ConfluenceBackend::status_close(out);
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use rw_renderer::{MarkdownRenderer, Pipeline};
+
     use super::*;
 
     #[test]
@@ -148,6 +228,14 @@ mod tests {
         assert!(out.contains("plain code"));
     }
 
+    #[test]
+    fn test_confluence_fence_passes_through_unescaped() {
+        let mut out = String::new();
+        let macro_xml = r#"<ac:structured-macro ac:name="jira"><ac:parameter ac:name="key">RW-1</ac:parameter></ac:structured-macro>"#;
+        ConfluenceBackend::code_block(Some("confluence"), &format!("{macro_xml}\n"), &mut out);
+        assert_eq!(out, macro_xml);
+    }
+
     #[test]
     fn test_blockquote() {
         let mut out = String::new();
@@ -158,6 +246,22 @@ mod tests {
         assert!(out.contains("<ac:rich-text-body>content</ac:rich-text-body>"));
     }
 
+    #[test]
+    fn test_plain_quote_blockquote() {
+        let mut out = String::new();
+        PlainQuoteBackend::blockquote_start(&mut out);
+        out.push_str("content");
+        PlainQuoteBackend::blockquote_end(&mut out);
+        assert_eq!(out, "<blockquote>content</blockquote>");
+    }
+
+    #[test]
+    fn test_plain_quote_keeps_alert_panels() {
+        let mut out = String::new();
+        PlainQuoteBackend::alert_start(AlertKind::Note, &mut out);
+        assert!(out.contains(r#"ac:name="info""#));
+    }
+
     #[test]
     fn test_external_image() {
         let mut out = String::new();
@@ -274,4 +378,24 @@ mod tests {
         ConfluenceBackend::status_close(&mut out);
         assert_eq!(out, "</ac:parameter></ac:structured-macro>");
     }
+
+    #[test]
+    fn smart_punctuation_renders_characters_not_named_entities() {
+        let markdown = "\"Quotes\" -- it's... `a--b`\n\n```\n\"x\" -- ...\n```";
+        let xhtml = MarkdownRenderer::<ConfluenceBackend>::new()
+            .with_smart_punctuation(true)
+            .render(markdown, Pipeline::new())
+            .html;
+
+        assert!(
+            xhtml.contains("<p>“Quotes” – it’s… <code>a--b</code></p>"),
+            "{xhtml}"
+        );
+        assert!(xhtml.contains("<![CDATA[\"x\" -- ...\n]]>"), "{xhtml}");
+        // Storage format is XML: HTML named entities such as `&rsquo;` are
+        // rejected on publish.
+        for entity in ["&ldquo;", "&rdquo;", "&rsquo;", "&ndash;", "&hellip;"] {
+            assert!(!xhtml.contains(entity), "{xhtml}");
+        }
+    }
 }
@@ -0,0 +1,141 @@
+//! Reviewable diff between a page's current storage XHTML and a fresh render.
+//!
+//! Storage format is one long line as far as a line differ is concerned, and
+//! comment markers carry server-assigned `ac:ref` ids that churn between
+//! fetches. [`StorageDiff`] normalizes both sides before diffing so the result
+//! shows content changes only:
+//!
+//! - `<ac:inline-comment-marker>` open/close tags are dropped, keeping the
+//!   text they wrap.
+//! - Every tag boundary (`><`) starts a new line, so a changed paragraph is a
+//!   changed line rather than a changed page.
+
+use std::sync::LazyLock;
+use std::time::Duration;
+
+use regex::Regex;
+use similar::TextDiff;
+
+/// Unchanged lines shown around each change. Keeps a one-word edit on a
+/// thousand-line page to a handful of output lines.
+const CONTEXT_LINES: usize = 3;
+
+/// Upper bound on diff computation. Past it the differ settles for a coarser
+/// (still correct) result instead of going quadratic on a very large page.
+const DIFF_DEADLINE: Duration = Duration::from_secs(2);
+
+static COMMENT_MARKER_TAG: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"</?ac:inline-comment-marker(?:\s[^>]*)?>").expect("valid regex"));
+
+/// Line-level unified diff between two storage-format XHTML bodies.
+///
+/// # Examples
+///
+/// ```
+/// use rw_confluence::StorageDiff;
+///
+/// let diff = StorageDiff::new("<p>old</p><p>same</p>", "<p>new</p><p>same</p>");
+/// assert!(diff.has_changes());
+/// assert!(diff.unified().contains("-<p>old</p>"));
+/// assert!(diff.unified().contains("+<p>new</p>"));
+///
+/// let marked = r#"<p><ac:inline-comment-marker ac:ref="1">same</ac:inline-comment-marker></p>"#;
+/// assert!(!StorageDiff::new(marked, "<p>same</p>").has_changes());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StorageDiff {
+    unified: String,
+}
+
+impl StorageDiff {
+    /// Diff `current` (the page as it is in Confluence) against `new` (what a
+    /// render would publish).
+    #[must_use]
+    pub fn new(current: &str, new: &str) -> Self {
+        let current = normalize(current);
+        let new = normalize(new);
+        let diff = TextDiff::configure()
+            .deadline(std::time::Instant::now() + DIFF_DEADLINE)
+            .diff_lines(&current, &new);
+        let unified = diff
+            .unified_diff()
+            .context_radius(CONTEXT_LINES)
+            .header("current", "rendered")
+            .to_string();
+        Self { unified }
+    }
+
+    /// Whether the two bodies differ after normalization.
+    #[must_use]
+    pub fn has_changes(&self) -> bool {
+        !self.unified.is_empty()
+    }
+
+    /// The diff in unified format (`---`/`+++` header, `@@` hunks, lines
+    /// prefixed with ` `, `-` or `+`). Empty when nothing changed.
+    #[must_use]
+    pub fn unified(&self) -> &str {
+        &self.unified
+    }
+}
+
+/// Drop comment markers and put each tag on its own line.
+fn normalize(xhtml: &str) -> String {
+    let stripped = COMMENT_MARKER_TAG.replace_all(xhtml, "");
+    let mut out = stripped.replace("><", ">\n<");
+    if !out.ends_with('\n') {
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fmt::Write;
+
+    use super::*;
+
+    #[test]
+    fn identical_bodies_have_no_changes() {
+        let diff = StorageDiff::new("<p>a</p><p>b</p>", "<p>a</p><p>b</p>");
+        assert!(!diff.has_changes());
+        assert_eq!(diff.unified(), "");
+    }
+
+    #[test]
+    fn changed_paragraph_is_one_changed_line() {
+        let diff = StorageDiff::new("<p>a</p><p>b</p><p>c</p>", "<p>a</p><p>B</p><p>c</p>");
+        assert!(diff.has_changes());
+        let unified = diff.unified();
+        assert!(
+            unified.starts_with("--- current\n+++ rendered\n"),
+            "{unified}"
+        );
+        assert!(unified.contains("\n-<p>b</p>\n"), "{unified}");
+        assert!(unified.contains("\n+<p>B</p>\n"), "{unified}");
+        assert!(unified.contains("\n <p>a</p>\n"), "{unified}");
+    }
+
+    #[test]
+    fn comment_markers_are_ignored() {
+        let current = r#"<p>Hello <ac:inline-comment-marker ac:ref="abc-123">marked</ac:inline-comment-marker> text</p>"#;
+        let new = r#"<p>Hello <ac:inline-comment-marker ac:ref="def-456">marked</ac:inline-comment-marker> text</p>"#;
+        assert!(!StorageDiff::new(current, new).has_changes());
+        assert!(!StorageDiff::new(current, "<p>Hello marked text</p>").has_changes());
+    }
+
+    #[test]
+    fn context_is_capped_on_large_pages() {
+        let mut body = String::new();
+        for i in 0..1000 {
+            write!(body, "<p>{i}</p>").unwrap();
+        }
+        let changed = body.replace("<p>500</p>", "<p>five hundred</p>");
+        let unified = StorageDiff::new(&body, &changed).unified().to_owned();
+        assert!(unified.contains("-<p>500</p>"), "{unified}");
+        assert!(unified.contains("+<p>five hundred</p>"), "{unified}");
+        assert!(!unified.contains("<p>490</p>"), "{unified}");
+        // Header + hunk header + 2 changed lines + 3 context lines each side.
+        assert_eq!(unified.lines().count(), 2 + 1 + 2 + 2 * CONTEXT_LINES);
+    }
+}
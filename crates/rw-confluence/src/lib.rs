@@ -9,7 +9,8 @@
 //! This crate does **not** talk to the Confluence REST API. Publishing
 //! is the caller's responsibility — point `rw confluence render` (or this
 //! library) at your markdown, then upload `<out>/page.xhtml` and the
-//! PNGs in `<out>/` with the publisher of your choice.
+//! PNGs in `<out>/` with the publisher of your choice. [`StorageDiff`]
+//! shows what such an upload would change on the current page.
 //!
 //! # Example
 //!
@@ -38,7 +39,10 @@ mod comment_preservation;
 pub use comment_preservation::{PreserveResult, UnmatchedComment, preserve_comments};
 
 mod render;
-pub use render::{RenderOptions, RenderOutput, render};
+pub use render::{CodeMacroOptions, RenderOptions, RenderOutput, render};
+
+mod diff;
+pub use diff::StorageDiff;
 
 mod error;
 pub use error::{CommentPreservationError, ConfluenceError};
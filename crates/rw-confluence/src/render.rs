@@ -12,24 +12,70 @@ use crate::renderer::PageRenderer;
 
 /// Options for [`render`].
 #[derive(Debug, Clone, Default)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct RenderOptions {
     /// Kroki server URL. When `None`, diagram code fences fall through to
     /// syntax-highlighted code (the same default as `rw serve`).
     pub kroki_url: Option<String>,
     /// Directories to search for `PlantUML` `!include` resolution.
     pub include_dirs: Vec<PathBuf>,
-    /// DPI for diagram rendering. `None` means "use the renderer's
+    /// Extra `(name, value)` HTTP headers sent with every Kroki request.
+    pub kroki_headers: Vec<(String, String)>,
     /// Pull title from the first H1 heading. Default `false`.
     pub extract_title: bool,
     /// Prepend a Confluence TOC macro to the rendered XHTML. Default
     /// `false`.
     pub prepend_toc: bool,
+    /// Shallowest heading level the TOC macro lists (its `minLevel`
+    /// parameter). `None` leaves it to Confluence. Ignored unless
+    /// `prepend_toc` is set.
+    pub toc_min_level: Option<u8>,
+    /// Deepest heading level the TOC macro lists (its `maxLevel`
+    /// parameter). `None` leaves it to Confluence. Ignored unless
+    /// `prepend_toc` is set.
+    pub toc_max_level: Option<u8>,
+    /// Emit a Confluence `anchor` macro before every heading, named after
+    /// the same slug id `rw serve` gives the heading, so `page#section`
+    /// links target the same section in both outputs. Default `false`.
+    pub heading_anchors: bool,
+    /// Parameters for the `code` macro every code block becomes. The default
+    /// turns line numbers on and leaves the rest to Confluence.
+    pub code_macro: CodeMacroOptions,
+    /// Render blockquotes as plain `<blockquote>`s rather than `info` panel
+    /// macros. GFM alerts (`> [!NOTE]`) stay panels. Default `false`.
+    pub plain_blockquotes: bool,
     /// Current page's storage XHTML body. When provided, inline-comment
     /// markers are carried over from this XHTML into the freshly rendered
     /// XHTML. When `None`, no preservation is attempted.
     pub current_xhtml: Option<String>,
 }
 
+/// Parameters of the Confluence `code` macro, applied to every code block on
+/// the page. ```` ```confluence ```` fences are written verbatim and keep
+/// whatever parameters they spell out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeMacroOptions {
+    /// Number the lines (the `linenumbers` parameter). Default `true`; when
+    /// `false` the parameter is left out, which Confluence reads as off.
+    pub line_numbers: bool,
+    /// Color theme (the `theme` parameter), e.g. `"Midnight"` or
+    /// `"Eclipse"`. `None` leaves it to Confluence.
+    pub theme: Option<String>,
+    /// Render the block collapsed (the `collapse` parameter). Default
+    /// `false`.
+    pub collapse: bool,
+}
+
+impl Default for CodeMacroOptions {
+    fn default() -> Self {
+        Self {
+            line_numbers: true,
+            theme: None,
+            collapse: false,
+        }
+    }
+}
+
 /// Output produced by [`render`].
 #[derive(Debug, Clone)]
 pub struct RenderOutput {
@@ -87,8 +133,13 @@ pub fn render(
 
     let page_renderer = PageRenderer::new()
         .prepend_toc(opts.prepend_toc)
+        .toc_levels(opts.toc_min_level, opts.toc_max_level)
+        .heading_anchors(opts.heading_anchors)
+        .code_macro(opts.code_macro)
+        .plain_blockquotes(opts.plain_blockquotes)
         .extract_title(opts.extract_title)
-        .include_dirs(opts.include_dirs);
+        .include_dirs(opts.include_dirs)
+        .kroki_headers(opts.kroki_headers);
 
     let render_result = page_renderer.render(markdown, opts.kroki_url.as_deref(), Some(out_dir));
 
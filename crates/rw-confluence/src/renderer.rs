@@ -7,7 +7,10 @@
 //!
 //! - GitHub Flavored Markdown support (tables, strikethrough, task lists)
 //! - Title extraction from first H1 heading
-//! - Table of contents macro prepending
+//! - Table of contents macro prepending (with optional heading-level bounds)
+//! - Optional `anchor` macros before headings, named like the HTML heading ids
+//! - Configurable `code` macro parameters (line numbers, theme, collapse)
+//! - Blockquotes as `info` panels (default) or plain `<blockquote>`s
 //! - Diagram rendering via Kroki service
 //! - Configurable DPI for diagram output
 //!
@@ -16,26 +19,34 @@
 //! Create a `PageRenderer` with builder methods (`prepend_toc`, `extract_title`),
 //! then call `render(markdown, kroki_url, diagram_dir)` to produce Confluence XHTML.
 
+use std::fmt::Write;
+
 use rw_kroki::{DiagramOutput, DiagramProcessor};
 use rw_renderer::directive::DirectiveProcessor;
-use rw_renderer::{MarkdownRenderer, Pipeline, RenderResult, TocEntry};
+use rw_renderer::{MarkdownRenderer, Pipeline, RenderBackend, RenderResult, TocEntry, escape_html};
 use std::path::{Path, PathBuf};
 
-use crate::backend::ConfluenceBackend;
+use crate::backend::{CODE_MACRO_BODY, CODE_MACRO_PARAMS, ConfluenceBackend, PlainQuoteBackend};
+use crate::render::CodeMacroOptions;
 use crate::tags::confluence_tag_generator;
 
-const TOC_MACRO: &str = r#"<ac:structured-macro ac:name="toc" ac:schema-version="1" />"#;
-
 /// Renders markdown to Confluence XHTML storage format.
 ///
 /// Note: This is distinct from `rw_site::PageRenderer` which renders
 /// markdown to HTML for the web server. Both are "page renderers" but for
 /// different output formats.
 #[derive(Debug)]
+#[allow(clippy::struct_excessive_bools)]
 pub(crate) struct PageRenderer {
     prepend_toc: bool,
+    toc_min_level: Option<u8>,
+    toc_max_level: Option<u8>,
+    heading_anchors: bool,
     extract_title: bool,
     include_dirs: Vec<PathBuf>,
+    kroki_headers: Vec<(String, String)>,
+    code_macro: CodeMacroOptions,
+    plain_blockquotes: bool,
 }
 
 impl Default for PageRenderer {
@@ -50,8 +61,14 @@ impl PageRenderer {
     pub(crate) fn new() -> Self {
         Self {
             prepend_toc: false,
+            toc_min_level: None,
+            toc_max_level: None,
+            heading_anchors: false,
             extract_title: false,
             include_dirs: Vec::new(),
+            kroki_headers: Vec::new(),
+            code_macro: CodeMacroOptions::default(),
+            plain_blockquotes: false,
         }
     }
 
@@ -62,6 +79,28 @@ impl PageRenderer {
         self
     }
 
+    /// Bound the heading levels the prepended TOC macro lists.
+    ///
+    /// Emitted as the macro's `minLevel`/`maxLevel` parameters; `None` leaves
+    /// the parameter out, so Confluence applies its own default (1 and 7).
+    #[must_use]
+    pub(crate) fn toc_levels(mut self, min: Option<u8>, max: Option<u8>) -> Self {
+        self.toc_min_level = min;
+        self.toc_max_level = max;
+        self
+    }
+
+    /// Enable or disable an `anchor` macro before every heading.
+    ///
+    /// The anchor is named after the heading's slug id, the same id the HTML
+    /// backend puts on `<hN id="...">`, so `#section` fragments carry over
+    /// from the web preview to Confluence.
+    #[must_use]
+    pub(crate) fn heading_anchors(mut self, enabled: bool) -> Self {
+        self.heading_anchors = enabled;
+        self
+    }
+
     /// Enable or disable extracting the first H1 as page title.
     #[must_use]
     pub(crate) fn extract_title(mut self, enabled: bool) -> Self {
@@ -79,15 +118,139 @@ impl PageRenderer {
         self
     }
 
+    /// Set extra HTTP headers sent with every Kroki request.
+    #[must_use]
+    pub(crate) fn kroki_headers(mut self, headers: Vec<(String, String)>) -> Self {
+        self.kroki_headers = headers;
+        self
+    }
+
+    /// Set the parameters of the `code` macro code blocks become.
+    #[must_use]
+    pub(crate) fn code_macro(mut self, options: CodeMacroOptions) -> Self {
+        self.code_macro = options;
+        self
+    }
+
+    /// Render blockquotes as plain `<blockquote>`s instead of `info` panels.
+    ///
+    /// GFM alerts (`> [!NOTE]`) stay panel macros either way.
+    #[must_use]
+    pub(crate) fn plain_blockquotes(mut self, enabled: bool) -> Self {
+        self.plain_blockquotes = enabled;
+        self
+    }
+
+    /// Rewrite every `code` macro's parameters if they differ from the
+    /// backend's defaults.
+    ///
+    /// The backend is stateless, so it always writes [`CODE_MACRO_PARAMS`];
+    /// the match includes the body opener that follows them so a
+    /// ```` ```confluence ```` passthrough is only touched if it spells out
+    /// the exact same markup.
+    fn apply_code_macro(&self, html: String) -> String {
+        let params = self.code_macro_params();
+        if params == CODE_MACRO_PARAMS {
+            return html;
+        }
+        html.replace(
+            &format!("{CODE_MACRO_PARAMS}{CODE_MACRO_BODY}"),
+            &format!("{params}{CODE_MACRO_BODY}"),
+        )
+    }
+
+    /// The configured `code` macro parameters, in the backend's markup.
+    fn code_macro_params(&self) -> String {
+        let options = &self.code_macro;
+        let mut out = String::new();
+        if options.line_numbers {
+            out.push_str(CODE_MACRO_PARAMS);
+        }
+        if let Some(theme) = &options.theme {
+            write!(
+                out,
+                r#"<ac:parameter ac:name="theme">{}</ac:parameter>"#,
+                escape_html(theme)
+            )
+            .unwrap();
+        }
+        if options.collapse {
+            out.push_str(r#"<ac:parameter ac:name="collapse">true</ac:parameter>"#);
+        }
+        out
+    }
+
     /// Prepend TOC macro if enabled and there are headings.
     fn maybe_prepend_toc(&self, html: String, toc: &[TocEntry]) -> String {
         if self.prepend_toc && !toc.is_empty() {
-            format!("{TOC_MACRO}{html}")
+            let mut out = self.toc_macro();
+            out.push_str(&html);
+            out
         } else {
             html
         }
     }
 
+    /// Insert an `anchor` macro before each heading if enabled.
+    ///
+    /// Confluence drops the `id` attribute on headings, so the id the backend
+    /// already wrote is turned into a macro Confluence keeps. `toc` lists every
+    /// emitted heading in document order with its level and unique id, which
+    /// pins each opening tag down exactly.
+    fn maybe_insert_anchors(&self, html: String, toc: &[TocEntry]) -> String {
+        if !self.heading_anchors || toc.is_empty() {
+            return html;
+        }
+        let mut out = String::with_capacity(html.len() + toc.len() * 128);
+        let mut rest = html.as_str();
+        for entry in toc {
+            let open_tag = format!(r#"<h{} id="{}">"#, entry.level, entry.id);
+            let Some(pos) = rest.find(&open_tag) else {
+                continue;
+            };
+            out.push_str(&rest[..pos]);
+            write!(
+                out,
+                concat!(
+                    r#"<ac:structured-macro ac:name="anchor" ac:schema-version="1">"#,
+                    r#"<ac:parameter ac:name="">{}</ac:parameter>"#,
+                    "</ac:structured-macro>",
+                ),
+                entry.id
+            )
+            .unwrap();
+            out.push_str(&open_tag);
+            rest = &rest[pos + open_tag.len()..];
+        }
+        out.push_str(rest);
+        out
+    }
+
+    /// The native Confluence `toc` macro. Confluence builds the list itself
+    /// from the page's headings, so it stays interactive and picks up the
+    /// space's styling; only the level bounds are ours to set.
+    fn toc_macro(&self) -> String {
+        let params = [
+            ("minLevel", self.toc_min_level),
+            ("maxLevel", self.toc_max_level),
+        ];
+        if params.iter().all(|(_, level)| level.is_none()) {
+            return r#"<ac:structured-macro ac:name="toc" ac:schema-version="1" />"#.to_owned();
+        }
+        let mut out = String::from(r#"<ac:structured-macro ac:name="toc" ac:schema-version="1">"#);
+        for (name, level) in params {
+            if let Some(level) = level {
+                write!(
+                    out,
+                    r#"<ac:parameter ac:name="{name}">{level}</ac:parameter>"#
+                )
+                .unwrap();
+            }
+        }
+        out.push_str("</ac:structured-macro>");
+        out
+    }
+
     /// Render markdown to Confluence storage format with optional diagram rendering via Kroki.
     ///
     /// When `kroki_url` and `output_dir` are provided, diagrams are rendered via the Kroki
@@ -100,29 +263,39 @@ impl PageRenderer {
         kroki_url: Option<&str>,
         output_dir: Option<&Path>,
     ) -> RenderResult {
-        let renderer = self.create_renderer();
         let pipeline = self.create_pipeline(kroki_url, output_dir);
 
-        let result = renderer.render(markdown_text, pipeline);
+        let result = if self.plain_blockquotes {
+            self.create_renderer::<PlainQuoteBackend>()
+                .render(markdown_text, pipeline)
+        } else {
+            self.create_renderer::<ConfluenceBackend>()
+                .render(markdown_text, pipeline)
+        };
 
+        let html = self.apply_code_macro(result.html);
+        let html = self.maybe_insert_anchors(html, &result.toc);
         RenderResult {
-            html: self.maybe_prepend_toc(result.html, &result.toc),
+            html: self.maybe_prepend_toc(html, &result.toc),
             title: result.title,
             toc: result.toc,
             warnings: result.warnings,
             has_transient_error: result.has_transient_error,
             section_refs: result.section_refs,
+            code_block_stats: result.code_block_stats,
         }
     }
 
     /// Create a diagram processor with common configuration.
     fn create_diagram_processor(&self, kroki_url: &str) -> DiagramProcessor {
-        DiagramProcessor::new(kroki_url).include_dirs(&self.include_dirs)
+        DiagramProcessor::new(kroki_url)
+            .include_dirs(&self.include_dirs)
+            .kroki_headers(self.kroki_headers.clone())
     }
 
     /// Build the settings-only renderer.
-    fn create_renderer(&self) -> MarkdownRenderer<ConfluenceBackend> {
-        let mut renderer = MarkdownRenderer::<ConfluenceBackend>::new();
+    fn create_renderer<B: RenderBackend>(&self) -> MarkdownRenderer<B> {
+        let mut renderer = MarkdownRenderer::<B>::new();
         if self.extract_title {
             renderer = renderer.with_title_extraction();
         }
@@ -182,6 +355,237 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_confluence_fence_is_passthrough_here_and_code_in_html() {
+        use rw_renderer::HtmlBackend;
+
+        let markdown = "```confluence\n<ac:structured-macro ac:name=\"jira\" />\n```\n";
+
+        let confluence = PageRenderer::new().render(markdown, None, None);
+        assert_eq!(confluence.html, r#"<ac:structured-macro ac:name="jira" />"#);
+
+        let html = MarkdownRenderer::<HtmlBackend>::new().render(markdown, Pipeline::new());
+        assert_eq!(
+            html.html,
+            r#"<pre><code class="language-confluence">&lt;ac:structured-macro ac:name=&quot;jira&quot; /&gt;
+</code></pre>"#
+        );
+    }
+
+    #[test]
+    fn test_heading_anchors_are_off_by_default() {
+        let result = PageRenderer::new().render("## Getting Started", None, None);
+        assert!(
+            !result.html.contains(r#"ac:name="anchor""#),
+            "got: {}",
+            result.html
+        );
+    }
+
+    #[test]
+    fn test_heading_anchors_match_html_heading_ids() {
+        use rw_renderer::HtmlBackend;
+
+        let markdown = "# Title\n\n## Getting Started\n\n### API & CLI\n\n## Getting Started\n";
+
+        let confluence = PageRenderer::new()
+            .extract_title(true)
+            .heading_anchors(true)
+            .render(markdown, None, None);
+        let html = MarkdownRenderer::<HtmlBackend>::new()
+            .with_title_extraction()
+            .render(markdown, Pipeline::new());
+
+        let ids: Vec<&str> = html.toc.iter().map(|entry| entry.id.as_str()).collect();
+        assert_eq!(ids, ["getting-started", "api-cli", "getting-started-1"]);
+        for (entry, id) in confluence.toc.iter().zip(&ids) {
+            let expected = format!(
+                r#"<ac:structured-macro ac:name="anchor" ac:schema-version="1"><ac:parameter ac:name="">{id}</ac:parameter></ac:structured-macro><h{} id="{id}">"#,
+                entry.level
+            );
+            assert!(
+                confluence.html.contains(&expected),
+                "got: {}",
+                confluence.html
+            );
+        }
+    }
+
+    #[test]
+    fn test_heading_anchors_follow_toc_macro() {
+        let result = PageRenderer::new()
+            .prepend_toc(true)
+            .heading_anchors(true)
+            .render("## One", None, None);
+        assert!(
+            result.html.starts_with(concat!(
+                r#"<ac:structured-macro ac:name="toc" ac:schema-version="1" />"#,
+                r#"<ac:structured-macro ac:name="anchor" ac:schema-version="1">"#,
+            )),
+            "got: {}",
+            result.html
+        );
+    }
+
+    #[test]
+    fn test_toc_macro_without_levels_is_self_closing() {
+        let renderer = PageRenderer::new().prepend_toc(true);
+        let result = renderer.render("## One\n\n## Two", None, None);
+        assert!(
+            result
+                .html
+                .starts_with(r#"<ac:structured-macro ac:name="toc" ac:schema-version="1" /><h2"#),
+            "got: {}",
+            result.html
+        );
+    }
+
+    #[test]
+    fn test_toc_macro_with_levels_emits_parameters() {
+        let renderer = PageRenderer::new()
+            .prepend_toc(true)
+            .toc_levels(Some(2), Some(3));
+        let result = renderer.render("## One\n\n### Two", None, None);
+        assert!(
+            result.html.starts_with(concat!(
+                r#"<ac:structured-macro ac:name="toc" ac:schema-version="1">"#,
+                r#"<ac:parameter ac:name="minLevel">2</ac:parameter>"#,
+                r#"<ac:parameter ac:name="maxLevel">3</ac:parameter>"#,
+                "</ac:structured-macro><h2",
+            )),
+            "got: {}",
+            result.html
+        );
+    }
+
+    #[test]
+    fn test_toc_macro_with_only_max_level() {
+        let renderer = PageRenderer::new()
+            .prepend_toc(true)
+            .toc_levels(None, Some(2));
+        let result = renderer.render("## One", None, None);
+        assert!(
+            result
+                .html
+                .contains(r#"<ac:parameter ac:name="maxLevel">2</ac:parameter>"#),
+            "got: {}",
+            result.html
+        );
+        assert!(!result.html.contains("minLevel"), "got: {}", result.html);
+    }
+
+    #[test]
+    fn test_toc_levels_ignored_without_prepend_toc() {
+        let renderer = PageRenderer::new().toc_levels(Some(2), Some(3));
+        let result = renderer.render("## One", None, None);
+        assert!(
+            !result.html.contains(r#"ac:name="toc""#),
+            "got: {}",
+            result.html
+        );
+    }
+
+    #[test]
+    fn test_code_macro_defaults_to_line_numbers() {
+        let result = PageRenderer::new().render("```rust\nfn main() {}\n```", None, None);
+        assert!(
+            result.html.contains(concat!(
+                r#"<ac:parameter ac:name="language">rust</ac:parameter>"#,
+                r#"<ac:parameter ac:name="linenumbers">true</ac:parameter>"#,
+                "<ac:plain-text-body>",
+            )),
+            "got: {}",
+            result.html
+        );
+    }
+
+    #[test]
+    fn test_code_macro_without_line_numbers_omits_parameter() {
+        let renderer = PageRenderer::new().code_macro(CodeMacroOptions {
+            line_numbers: false,
+            ..CodeMacroOptions::default()
+        });
+        let result = renderer.render("```\nplain\n```", None, None);
+        assert!(!result.html.contains("linenumbers"), "got: {}", result.html);
+        assert!(
+            result.html.contains(concat!(
+                r#"<ac:structured-macro ac:name="code" ac:schema-version="1">"#,
+                "<ac:plain-text-body>",
+            )),
+            "got: {}",
+            result.html
+        );
+    }
+
+    #[test]
+    fn test_code_macro_emits_theme_and_collapse() {
+        let renderer = PageRenderer::new().code_macro(CodeMacroOptions {
+            theme: Some("Midnight".to_owned()),
+            collapse: true,
+            ..CodeMacroOptions::default()
+        });
+        let result = renderer.render("```sh\nls\n```\n\n```\nmore\n```", None, None);
+        let params = concat!(
+            r#"<ac:parameter ac:name="linenumbers">true</ac:parameter>"#,
+            r#"<ac:parameter ac:name="theme">Midnight</ac:parameter>"#,
+            r#"<ac:parameter ac:name="collapse">true</ac:parameter>"#,
+            "<ac:plain-text-body>",
+        );
+        assert_eq!(
+            result.html.matches(params).count(),
+            2,
+            "got: {}",
+            result.html
+        );
+    }
+
+    #[test]
+    fn test_code_macro_options_leave_passthrough_alone() {
+        let renderer = PageRenderer::new().code_macro(CodeMacroOptions {
+            line_numbers: false,
+            ..CodeMacroOptions::default()
+        });
+        let markdown = concat!(
+            "```confluence\n",
+            r#"<ac:structured-macro ac:name="code">"#,
+            r#"<ac:parameter ac:name="linenumbers">true</ac:parameter>"#,
+            "\n<ac:plain-text-body><![CDATA[x]]></ac:plain-text-body></ac:structured-macro>\n",
+            "```\n",
+        );
+        let result = renderer.render(markdown, None, None);
+        assert!(result.html.contains("linenumbers"), "got: {}", result.html);
+    }
+
+    #[test]
+    fn test_blockquote_is_info_panel_by_default() {
+        let result = PageRenderer::new().render("> A quotation.", None, None);
+        assert_eq!(
+            result.html,
+            concat!(
+                r#"<ac:structured-macro ac:name="info" ac:schema-version="1"><ac:rich-text-body>"#,
+                "<p>A quotation.</p></ac:rich-text-body></ac:structured-macro>",
+            )
+        );
+    }
+
+    #[test]
+    fn test_plain_blockquotes_keep_alerts_as_panels() {
+        let renderer = PageRenderer::new().plain_blockquotes(true);
+        let result = renderer.render("> A quotation.\n\n> [!TIP]\n> A tip.", None, None);
+        assert!(
+            result
+                .html
+                .starts_with("<blockquote><p>A quotation.</p></blockquote>"),
+            "got: {}",
+            result.html
+        );
+        assert!(
+            result.html.contains(r#"ac:name="tip""#),
+            "got: {}",
+            result.html
+        );
+    }
+
     #[test]
     fn test_status_directive_unknown_color_is_grey() {
         let renderer = PageRenderer::new();
@@ -1,6 +1,6 @@
 //! Integration tests for `rw_confluence::render`.
 
-use rw_confluence::{RenderOptions, render};
+use rw_confluence::{CodeMacroOptions, RenderOptions, render};
 
 #[test]
 fn render_writes_page_xhtml_for_plain_markdown() {
@@ -221,3 +221,83 @@ fn render_status_directive_escapes_label_and_defaults_color() {
         output.xhtml
     );
 }
+
+#[test]
+fn render_drops_leading_front_matter() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+
+    let markdown = "---\ntitle: Install\ntags: [setup]\n---\n\n# Install\n\nRun it.\n";
+    let output = render(markdown, tmp.path(), RenderOptions::default()).expect("render succeeded");
+
+    assert!(
+        output.xhtml.starts_with("<h1"),
+        "front matter leaked: {}",
+        output.xhtml
+    );
+    assert!(!output.xhtml.contains("title:"), "got: {}", output.xhtml);
+    assert!(!output.xhtml.contains("<hr"), "got: {}", output.xhtml);
+}
+
+#[test]
+fn render_keeps_leading_thematic_break() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+
+    // No closing delimiter, so the `---` is a rule, not front matter.
+    let markdown = "---\n\nText after a rule.\n";
+    let output = render(markdown, tmp.path(), RenderOptions::default()).expect("render succeeded");
+
+    assert_eq!(output.xhtml, "<hr /><p>Text after a rule.</p>");
+}
+
+#[test]
+fn render_applies_code_macro_options() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+
+    let opts = RenderOptions {
+        code_macro: CodeMacroOptions {
+            line_numbers: false,
+            theme: Some("Eclipse".to_owned()),
+            collapse: false,
+        },
+        ..RenderOptions::default()
+    };
+    let output = render("```toml\nkey = 1\n```\n", tmp.path(), opts).expect("render succeeded");
+
+    assert!(
+        !output.xhtml.contains("linenumbers"),
+        "got: {}",
+        output.xhtml
+    );
+    assert!(
+        output
+            .xhtml
+            .contains(r#"<ac:parameter ac:name="theme">Eclipse</ac:parameter>"#),
+        "got: {}",
+        output.xhtml
+    );
+}
+
+#[test]
+fn render_plain_blockquotes_option() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let markdown = "> To be, or not to be.\n";
+
+    let panel = render(markdown, tmp.path(), RenderOptions::default()).expect("render succeeded");
+    assert!(
+        panel
+            .xhtml
+            .starts_with(r#"<ac:structured-macro ac:name="info""#),
+        "got: {}",
+        panel.xhtml
+    );
+
+    let opts = RenderOptions {
+        plain_blockquotes: true,
+        ..RenderOptions::default()
+    };
+    let plain = render(markdown, tmp.path(), opts).expect("render succeeded");
+    assert_eq!(
+        plain.xhtml,
+        "<blockquote><p>To be, or not to be.</p></blockquote>"
+    );
+}
@@ -15,7 +15,7 @@ use base64::Engine;
 use base64::prelude::BASE64_STANDARD;
 use rayon::prelude::*;
 use std::path::Path;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use ureq::Agent;
 
 use crate::cache::DiagramKey;
@@ -30,6 +30,10 @@ pub struct RenderedDiagram {
     /// Language this was rendered from. Only PlantUML-family output is
     /// oversized, so the caller needs it to decide whether to scale.
     pub language: DiagramLanguage,
+    /// Size of the written PNG file in bytes.
+    pub bytes: usize,
+    /// Wall-clock time for the Kroki request and file write.
+    pub duration: Duration,
 }
 
 /// Result of rendering a single diagram to SVG.
@@ -41,6 +45,8 @@ pub struct RenderedSvg {
     pub svg: String,
     /// Language this was rendered from — see [`RenderedDiagram::language`].
     pub language: DiagramLanguage,
+    /// Wall-clock time for the Kroki request.
+    pub duration: Duration,
 }
 
 /// Result of rendering a single diagram to PNG (as base64 data URI).
@@ -52,6 +58,8 @@ pub struct RenderedPngDataUri {
     pub data_uri: String,
     /// Language this was rendered from — see [`RenderedDiagram::language`].
     pub language: DiagramLanguage,
+    /// Wall-clock time for the Kroki request and encoding.
+    pub duration: Duration,
 }
 
 /// Diagram info for rendering.
@@ -94,9 +102,12 @@ pub struct DiagramError {
 #[derive(Debug, thiserror::Error)]
 #[non_exhaustive]
 pub enum DiagramErrorKind {
-    /// HTTP request failed (network error, timeout, etc).
-    #[error("HTTP request failed")]
+    /// HTTP request failed (connection refused, DNS failure, etc).
+    #[error("HTTP request failed: {0}")]
     HttpRequest(#[source] ureq::Error),
+    /// Kroki did not answer within the client's timeout.
+    #[error("timed out after {0:?}")]
+    Timeout(Duration),
     /// HTTP response error (server returned error status).
     #[error("HTTP {status}: {body}")]
     HttpResponse {
@@ -120,7 +131,7 @@ impl DiagramErrorKind {
     /// Whether this error is transient — i.e. a retry with the same diagram
     /// source could succeed once the underlying condition clears.
     ///
-    /// Network failures and Kroki 5xx server errors are transient (Kroki was
+    /// Network failures, timeouts and Kroki 5xx server errors are transient (Kroki was
     /// unreachable or briefly failing), as are the retryable 4xx statuses a
     /// server or reverse proxy raises under transient conditions — 408 Request
     /// Timeout, 425 Too Early, and 429 Too Many Requests. Every other 4xx
@@ -131,7 +142,7 @@ impl DiagramErrorKind {
     #[must_use]
     pub fn is_transient(&self) -> bool {
         match self {
-            Self::HttpRequest(_) => true,
+            Self::HttpRequest(_) | Self::Timeout(_) => true,
             Self::HttpResponse { status, .. } => {
                 *status >= 500 || matches!(status, 408 | 425 | 429)
             }
@@ -140,16 +151,71 @@ impl DiagramErrorKind {
     }
 }
 
-/// Create HTTP agent with the specified timeout.
+/// Delay before the first retry of a transient failure; each further retry
+/// waits twice as long as the one before.
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(200);
+
+/// Longest wait between two attempts, however many retries are allowed.
+const RETRY_MAX_DELAY: Duration = Duration::from_secs(5);
+
+/// HTTP client for Kroki: a pooled agent plus extra headers sent with every
+/// request (e.g., an API key for an auth proxy in front of a private Kroki).
 ///
-/// Use this to create a reusable agent for connection pooling when making
-/// multiple render calls.
-pub fn create_agent(timeout: Duration) -> Agent {
-    Agent::config_builder()
-        .timeout_global(Some(timeout))
-        .http_status_as_error(false)
-        .build()
-        .into()
+/// Deliberately not `Debug`, so header values never end up in logs.
+pub struct KrokiClient {
+    agent: Agent,
+    /// Per-request timeout the agent was built with.
+    timeout: Duration,
+    /// Extra `(name, value)` request headers.
+    pub(crate) headers: Vec<(String, String)>,
+    /// How many times a transient failure is retried before it is reported.
+    pub(crate) retries: u32,
+}
+
+impl KrokiClient {
+    /// Create a client with the specified timeout, no extra headers and no
+    /// retries.
+    ///
+    /// Reuse one client across render calls for connection pooling.
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            agent: Self::agent(timeout),
+            timeout,
+            headers: Vec::new(),
+            retries: 0,
+        }
+    }
+
+    /// Replace the per-request timeout, keeping headers and retries.
+    pub(crate) fn set_timeout(&mut self, timeout: Duration) {
+        self.agent = Self::agent(timeout);
+        self.timeout = timeout;
+    }
+
+    fn agent(timeout: Duration) -> Agent {
+        Agent::config_builder()
+            .timeout_global(Some(timeout))
+            .http_status_as_error(false)
+            .build()
+            .into()
+    }
+
+    /// Classify a failed request, telling a timeout apart from other
+    /// transport errors.
+    fn request_error(&self, error: ureq::Error) -> DiagramErrorKind {
+        match error {
+            ureq::Error::Timeout(_) => DiagramErrorKind::Timeout(self.timeout),
+            error => DiagramErrorKind::HttpRequest(error),
+        }
+    }
+}
+
+/// Wait before retry number `retry` (zero-based): exponential backoff from
+/// [`RETRY_BASE_DELAY`], capped at [`RETRY_MAX_DELAY`].
+fn retry_delay(retry: u32) -> Duration {
+    RETRY_BASE_DELAY
+        .saturating_mul(2u32.saturating_pow(retry))
+        .min(RETRY_MAX_DELAY)
 }
 
 /// Extract width and height from PNG image data.
@@ -189,9 +255,33 @@ pub(crate) fn png_data_uri_dimensions(data_uri: &str) -> Option<(u32, u32)> {
 
 /// Send a diagram to Kroki and return the response body as bytes.
 ///
-/// Handles HTTP errors by reading the response body for error details.
+/// A transient failure (see [`DiagramErrorKind::is_transient`]) is retried
+/// up to the client's `retries` times with exponential backoff; a
+/// deterministic one, such as Kroki's 400 for malformed source, is returned
+/// at once. The error returned is the last attempt's.
 fn send_diagram_request(
-    agent: &Agent,
+    client: &KrokiClient,
+    diagram: &DiagramRequest,
+    server_url: &str,
+    format: &str,
+) -> Result<Vec<u8>, DiagramError> {
+    let mut retry = 0;
+    loop {
+        match send_diagram_request_once(client, diagram, server_url, format) {
+            Err(e) if e.kind.is_transient() && retry < client.retries => {
+                std::thread::sleep(retry_delay(retry));
+                retry += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Send one request for a diagram, without retrying.
+///
+/// Handles HTTP errors by reading the response body for error details.
+fn send_diagram_request_once(
+    client: &KrokiClient,
     diagram: &DiagramRequest,
     server_url: &str,
     format: &str,
@@ -199,11 +289,13 @@ fn send_diagram_request(
     let endpoint = diagram.language.kroki_endpoint();
     let url = format!("{server_url}/{endpoint}/{format}");
 
-    let response = agent
-        .post(&url)
-        .header("Content-Type", "text/plain")
+    let mut request = client.agent.post(&url).header("Content-Type", "text/plain");
+    for (name, value) in &client.headers {
+        request = request.header(name, value);
+    }
+    let response = request
         .send(diagram.source.as_bytes())
-        .map_err(|e| diagram.error(DiagramErrorKind::HttpRequest(e)))?;
+        .map_err(|e| diagram.error(client.request_error(e)))?;
 
     let status = response.status().as_u16();
     let mut body = response.into_body();
@@ -219,17 +311,18 @@ fn send_diagram_request(
     }
 
     body.read_to_vec()
-        .map_err(|e| diagram.error(DiagramErrorKind::HttpRequest(e)))
+        .map_err(|e| diagram.error(client.request_error(e)))
 }
 
 /// Render a single diagram to PNG via Kroki.
 fn render_one_png(
-    agent: &Agent,
+    client: &KrokiClient,
     diagram: &DiagramRequest,
     server_url: &str,
     output_dir: &Path,
 ) -> Result<RenderedDiagram, DiagramError> {
-    let data = send_diagram_request(agent, diagram, server_url, "png")?;
+    let start = Instant::now();
+    let data = send_diagram_request(client, diagram, server_url, "png")?;
 
     // Height is unused: consumers size diagrams by width and let aspect ratio
     // follow, but a malformed PNG header must still fail the render here.
@@ -259,6 +352,8 @@ fn render_one_png(
         filename,
         width,
         language: diagram.language,
+        bytes: data.len(),
+        duration: start.elapsed(),
     })
 }
 
@@ -272,7 +367,7 @@ fn render_one_png(
 /// * `server_url` - Kroki server URL (e.g., `<https://kroki.io>`)
 /// * `output_dir` - Directory to save rendered PNG files
 /// * `dpi` - DPI used for rendering (affects filename hash)
-/// * `agent` - HTTP agent for connection pooling
+/// * `client` - Kroki HTTP client (connection pool and extra headers)
 ///
 /// # Returns
 /// Partial result containing both successful renders and errors.
@@ -281,7 +376,7 @@ pub fn render_all(
     diagrams: &[DiagramRequest],
     server_url: &str,
     output_dir: &Path,
-    agent: &Agent,
+    client: &KrokiClient,
 ) -> PartialRenderResult<RenderedDiagram> {
     if diagrams.is_empty() {
         return PartialRenderResult {
@@ -294,19 +389,20 @@ pub fn render_all(
 
     let results: Vec<Result<RenderedDiagram, DiagramError>> = diagrams
         .par_iter()
-        .map(|d| render_one_png(agent, d, server_url, output_dir))
+        .map(|d| render_one_png(client, d, server_url, output_dir))
         .collect();
 
     partition_results(results)
 }
 
 /// Render a single diagram to SVG via Kroki.
-fn render_one_svg(
-    agent: &Agent,
+pub(crate) fn render_one_svg(
+    client: &KrokiClient,
     diagram: &DiagramRequest,
     server_url: &str,
 ) -> Result<RenderedSvg, DiagramError> {
-    let data = send_diagram_request(agent, diagram, server_url, "svg")?;
+    let start = Instant::now();
+    let data = send_diagram_request(client, diagram, server_url, "svg")?;
     let svg =
         String::from_utf8(data).map_err(|e| diagram.error(DiagramErrorKind::InvalidUtf8(e)))?;
 
@@ -314,16 +410,18 @@ fn render_one_svg(
         index: diagram.index,
         svg,
         language: diagram.language,
+        duration: start.elapsed(),
     })
 }
 
 /// Render a single diagram to PNG as base64 data URI via Kroki.
-fn render_one_png_data_uri(
-    agent: &Agent,
+pub(crate) fn render_one_png_data_uri(
+    client: &KrokiClient,
     diagram: &DiagramRequest,
     server_url: &str,
 ) -> Result<RenderedPngDataUri, DiagramError> {
-    let data = send_diagram_request(agent, diagram, server_url, "png")?;
+    let start = Instant::now();
+    let data = send_diagram_request(client, diagram, server_url, "png")?;
 
     if get_png_dimensions(&data).is_none() {
         return Err(diagram.error(DiagramErrorKind::InvalidPng));
@@ -336,15 +434,22 @@ fn render_one_png_data_uri(
         index: diagram.index,
         data_uri,
         language: diagram.language,
+        duration: start.elapsed(),
     })
 }
 
 /// Result of rendering diagrams with partial failures.
+///
+/// Every rendered item and every error carries the `index` of the
+/// [`DiagramRequest`] it came from, so a failed diagram leaves a gap rather
+/// than shifting later results into its slot. Match results to diagrams by
+/// that `index`, never by position. Both vectors are also in request order
+/// regardless of which render finishes first.
 #[derive(Debug)]
 pub struct PartialRenderResult<T> {
-    /// Successfully rendered diagrams.
+    /// Successfully rendered diagrams, in request order.
     pub rendered: Vec<T>,
-    /// Errors for diagrams that failed to render.
+    /// Errors for diagrams that failed to render, in request order.
     pub errors: Vec<DiagramError>,
 }
 
@@ -355,8 +460,8 @@ pub struct PartialRenderResult<T> {
 fn render_all_partial<T: Send + std::fmt::Debug>(
     diagrams: &[DiagramRequest],
     server_url: &str,
-    agent: &Agent,
-    render_fn: fn(&Agent, &DiagramRequest, &str) -> Result<T, DiagramError>,
+    client: &KrokiClient,
+    render_fn: fn(&KrokiClient, &DiagramRequest, &str) -> Result<T, DiagramError>,
 ) -> PartialRenderResult<T> {
     if diagrams.is_empty() {
         return PartialRenderResult {
@@ -367,9 +472,11 @@ fn render_all_partial<T: Send + std::fmt::Debug>(
 
     let server_url = server_url.trim_end_matches('/');
 
+    // `collect` on an indexed parallel iterator keeps input order, however the
+    // renders interleave.
     let results: Vec<Result<T, DiagramError>> = diagrams
         .par_iter()
-        .map(|d| render_fn(agent, d, server_url))
+        .map(|d| render_fn(client, d, server_url))
         .collect();
 
     partition_results(results)
@@ -401,7 +508,7 @@ fn partition_results<T: std::fmt::Debug>(
 /// # Arguments
 /// * `diagrams` - List of diagrams to render
 /// * `server_url` - Kroki server URL (e.g., `<https://kroki.io>`)
-/// * `agent` - HTTP agent for connection pooling
+/// * `client` - Kroki HTTP client (connection pool and extra headers)
 ///
 /// # Returns
 /// Partial result containing both successful renders and errors.
@@ -409,9 +516,9 @@ fn partition_results<T: std::fmt::Debug>(
 pub fn render_all_svg_partial(
     diagrams: &[DiagramRequest],
     server_url: &str,
-    agent: &Agent,
+    client: &KrokiClient,
 ) -> PartialRenderResult<RenderedSvg> {
-    render_all_partial(diagrams, server_url, agent, render_one_svg)
+    render_all_partial(diagrams, server_url, client, render_one_svg)
 }
 
 /// Render all diagrams to PNG as base64 data URIs, returning partial results on failure.
@@ -422,7 +529,7 @@ pub fn render_all_svg_partial(
 /// # Arguments
 /// * `diagrams` - List of diagrams to render
 /// * `server_url` - Kroki server URL
-/// * `agent` - HTTP agent for connection pooling
+/// * `client` - Kroki HTTP client (connection pool and extra headers)
 ///
 /// # Returns
 /// Partial result containing both successful renders and errors.
@@ -430,9 +537,9 @@ pub fn render_all_svg_partial(
 pub fn render_all_png_data_uri_partial(
     diagrams: &[DiagramRequest],
     server_url: &str,
-    agent: &Agent,
+    client: &KrokiClient,
 ) -> PartialRenderResult<RenderedPngDataUri> {
-    render_all_partial(diagrams, server_url, agent, render_one_png_data_uri)
+    render_all_partial(diagrams, server_url, client, render_one_png_data_uri)
 }
 
 #[cfg(test)]
@@ -491,6 +598,198 @@ mod tests {
         assert!(!DiagramErrorKind::InvalidPng.is_transient());
     }
 
+    /// Fails the second diagram and finishes the first one last, so results
+    /// complete out of request order.
+    fn render_fails_second(
+        _client: &KrokiClient,
+        diagram: &DiagramRequest,
+        _server_url: &str,
+    ) -> Result<RenderedSvg, DiagramError> {
+        if diagram.index == 0 {
+            std::thread::sleep(Duration::from_millis(50));
+        }
+        if diagram.index == 1 {
+            return Err(diagram.error(DiagramErrorKind::InvalidPng));
+        }
+        Ok(RenderedSvg {
+            index: diagram.index,
+            svg: diagram.source.clone(),
+            language: diagram.language,
+            duration: Duration::ZERO,
+        })
+    }
+
+    #[test]
+    fn partial_results_keep_request_indexes_when_one_fails() {
+        let requests: Vec<_> = ["first", "second", "third"]
+            .into_iter()
+            .enumerate()
+            .map(|(i, source)| DiagramRequest::new(i, source.to_owned(), DiagramLanguage::Mermaid))
+            .collect();
+        let client = KrokiClient::new(Duration::from_secs(1));
+
+        let result = render_all_partial(&requests, "http://unused", &client, render_fails_second);
+
+        let rendered: Vec<_> = result
+            .rendered
+            .iter()
+            .map(|r| (r.index, r.svg.as_str()))
+            .collect();
+        assert_eq!(rendered, [(0, "first"), (2, "third")]);
+        let failed: Vec<_> = result.errors.iter().map(|e| e.index).collect();
+        assert_eq!(failed, [1]);
+    }
+
+    /// Serve one request on a loopback port with a fixed SVG body, returning
+    /// the server URL and a handle that yields the raw request head.
+    fn serve_one_svg() -> (String, std::thread::JoinHandle<String>) {
+        use std::io::{Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0; 1024];
+            while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                let n = stream.read(&mut buf).unwrap();
+                request.extend_from_slice(&buf[..n]);
+            }
+            let body = "<svg></svg>";
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+            String::from_utf8_lossy(&request).to_lowercase()
+        });
+        (url, handle)
+    }
+
+    #[test]
+    fn client_sends_extra_headers() {
+        let (url, server) = serve_one_svg();
+        let mut client = KrokiClient::new(Duration::from_secs(5));
+        client.headers = vec![
+            ("X-Api-Key".to_owned(), "secret".to_owned()),
+            ("X-Team".to_owned(), "docs".to_owned()),
+        ];
+        let request = DiagramRequest::new(0, "A -> B".to_owned(), DiagramLanguage::PlantUml);
+
+        let rendered = render_one_svg(&client, &request, &url).unwrap();
+
+        assert_eq!(rendered.svg, "<svg></svg>");
+        let head = server.join().unwrap();
+        assert!(head.contains("x-api-key: secret"), "request: {head}");
+        assert!(head.contains("x-team: docs"), "request: {head}");
+        assert!(head.contains("content-type: text/plain"), "request: {head}");
+    }
+
+    /// Answer one request per entry of `statuses`, in order, on a loopback
+    /// port; a 200 carries an SVG body. The handle yields the number of
+    /// requests served.
+    fn serve_statuses(statuses: &'static [u16]) -> (String, std::thread::JoinHandle<usize>) {
+        use std::io::{Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = std::thread::spawn(move || {
+            for &status in statuses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut request = Vec::new();
+                let mut buf = [0; 1024];
+                while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                    let n = stream.read(&mut buf).unwrap();
+                    request.extend_from_slice(&buf[..n]);
+                }
+                let body = if status == 200 { "<svg></svg>" } else { "nope" };
+                write!(
+                    stream,
+                    "HTTP/1.1 {status} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+            statuses.len()
+        });
+        (url, handle)
+    }
+
+    #[test]
+    fn transient_failures_are_retried() {
+        let (url, server) = serve_statuses(&[503, 502, 200]);
+        let mut client = KrokiClient::new(Duration::from_secs(5));
+        client.retries = 2;
+        let request = DiagramRequest::new(0, "A -> B".to_owned(), DiagramLanguage::PlantUml);
+
+        let rendered = render_one_svg(&client, &request, &url).unwrap();
+
+        assert_eq!(rendered.svg, "<svg></svg>");
+        assert_eq!(server.join().unwrap(), 3);
+    }
+
+    #[test]
+    fn retries_are_bounded_and_report_the_last_failure() {
+        let (url, server) = serve_statuses(&[503, 500]);
+        let mut client = KrokiClient::new(Duration::from_secs(5));
+        client.retries = 1;
+        let request = DiagramRequest::new(0, "A -> B".to_owned(), DiagramLanguage::PlantUml);
+
+        let err = render_one_svg(&client, &request, &url).unwrap_err();
+
+        assert!(
+            matches!(err.kind, DiagramErrorKind::HttpResponse { status: 500, .. }),
+            "{err}"
+        );
+        assert_eq!(server.join().unwrap(), 2);
+    }
+
+    #[test]
+    fn bad_diagram_is_not_retried() {
+        // The server answers once; a retry would find the port closed and
+        // report a connection error instead of the 400.
+        let (url, server) = serve_statuses(&[400]);
+        let mut client = KrokiClient::new(Duration::from_secs(5));
+        client.retries = 3;
+        let request = DiagramRequest::new(0, "A -> ".to_owned(), DiagramLanguage::PlantUml);
+
+        let err = render_one_svg(&client, &request, &url).unwrap_err();
+
+        assert_eq!(err.to_string(), "diagram 0: HTTP 400: nope");
+        assert_eq!(server.join().unwrap(), 1);
+    }
+
+    #[test]
+    fn timeout_is_reported_as_such() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        // Accept and hold the connection without ever answering.
+        let server = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            std::thread::sleep(Duration::from_secs(1));
+            drop(stream);
+        });
+        let client = KrokiClient::new(Duration::from_millis(100));
+        let request = DiagramRequest::new(0, "A -> B".to_owned(), DiagramLanguage::PlantUml);
+
+        let err = render_one_svg(&client, &request, &url).unwrap_err();
+
+        assert!(matches!(err.kind, DiagramErrorKind::Timeout(_)), "{err}");
+        assert_eq!(err.to_string(), "diagram 0: timed out after 100ms");
+        assert!(err.kind.is_transient());
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn retry_delay_doubles_up_to_the_cap() {
+        assert_eq!(retry_delay(0), RETRY_BASE_DELAY);
+        assert_eq!(retry_delay(1), RETRY_BASE_DELAY * 2);
+        assert_eq!(retry_delay(2), RETRY_BASE_DELAY * 4);
+        assert_eq!(retry_delay(10), RETRY_MAX_DELAY);
+        assert_eq!(retry_delay(u32::MAX), RETRY_MAX_DELAY);
+    }
+
     #[test]
     fn test_get_png_dimensions() {
         // Minimal valid PNG with 100x50 dimensions
@@ -5,6 +5,8 @@
 //! - Parallel rendering via Kroki service (`PlantUML`, Mermaid, `GraphViz`, etc.)
 //! - `PlantUML` preprocessing with `!include` resolution and DPI configuration
 //! - HTML embedding with SVG scaling and link annotation
+//! - [`DiagramProcessor::render_diagram`] for one diagram outside the markdown pipeline
+//! - [`ClientMermaidProcessor`] for Mermaid rendered in the browser, without Kroki
 //!
 //! # Architecture
 //!
@@ -35,6 +37,7 @@ mod consts;
 mod html_embed;
 mod kroki;
 mod language;
+mod mermaid;
 mod meta_includes;
 mod output;
 mod plantuml;
@@ -42,7 +45,10 @@ mod processor;
 mod scale;
 mod search;
 
+pub use kroki::{DiagramError, DiagramErrorKind};
+pub use language::{DiagramFormat, DiagramLanguage};
+pub use mermaid::ClientMermaidProcessor;
 pub use meta_includes::{EntityInfo, MetaIncludeSource};
 pub use output::{DiagramOutput, RenderedDiagramInfo, TagGenerator};
-pub use processor::DiagramProcessor;
+pub use processor::{DiagramProcessor, StandaloneDiagram};
 pub use search::SearchDiagramProcessor;
@@ -0,0 +1,81 @@
+//! Client-side Mermaid rendering.
+//!
+//! [`ClientMermaidProcessor`] needs no Kroki server: it hands ```` ```mermaid ````
+//! fences to the browser as `<pre class="mermaid">`, the container mermaid.js
+//! renders in place. Every other fence, `kroki-mermaid` included, passes
+//! through to the next processor.
+
+use rw_renderer::{CodeBlockProcessor, FenceAttrs, ProcessResult, escape_html};
+
+/// Code block processor that emits Mermaid source for mermaid.js.
+///
+/// Register it ahead of a [`DiagramProcessor`](crate::DiagramProcessor):
+/// processors run in order, so Mermaid fences never reach Kroki while the
+/// other diagram languages still do.
+///
+/// # Example
+///
+/// ```
+/// use rw_kroki::ClientMermaidProcessor;
+/// use rw_renderer::{HtmlBackend, MarkdownRenderer, Pipeline};
+///
+/// let result = MarkdownRenderer::<HtmlBackend>::new().render(
+///     "```mermaid\ngraph TD; A-->B\n```",
+///     Pipeline::new().with_processor(ClientMermaidProcessor),
+/// );
+/// assert_eq!(result.html, "<pre class=\"mermaid\">graph TD; A--&gt;B\n</pre>");
+/// ```
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ClientMermaidProcessor;
+
+impl CodeBlockProcessor for ClientMermaidProcessor {
+    fn process(
+        &mut self,
+        language: &str,
+        _attrs: &FenceAttrs,
+        source: &str,
+        _index: usize,
+    ) -> ProcessResult {
+        if language != "mermaid" {
+            return ProcessResult::PassThrough;
+        }
+        // mermaid.js reads the element's text, so escaped markup round-trips.
+        ProcessResult::Inline(format!(
+            r#"<pre class="mermaid">{}</pre>"#,
+            escape_html(source)
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rw_renderer::{HtmlBackend, MarkdownRenderer, Pipeline};
+
+    use super::*;
+    use crate::DiagramProcessor;
+
+    #[test]
+    fn mermaid_takes_precedence_over_kroki_and_other_diagrams_do_not() {
+        let markdown =
+            "```mermaid\nA-->B\n```\n\n```kroki-mermaid\nC-->D\n```\n\n```rust\nfn a() {}\n```";
+        // An unreachable Kroki: it must only ever be asked for `kroki-mermaid`.
+        let pipeline = Pipeline::new()
+            .with_processor(ClientMermaidProcessor)
+            .with_processor(DiagramProcessor::new("http://127.0.0.1:1"));
+        let result = MarkdownRenderer::<HtmlBackend>::new().render(markdown, pipeline);
+
+        assert_eq!(result.html.matches(r#"<pre class="mermaid">"#).count(), 1);
+        assert!(
+            result
+                .html
+                .contains("<pre class=\"mermaid\">A--&gt;B\n</pre>"),
+            "{}",
+            result.html
+        );
+        assert!(
+            result.html.contains(r#"<code class="language-rust">"#),
+            "{}",
+            result.html
+        );
+    }
+}
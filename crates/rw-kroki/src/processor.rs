@@ -6,16 +6,19 @@
 use std::collections::{BTreeSet, HashMap};
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use rw_renderer::{CodeBlockProcessor, ExtractedCodeBlock, FenceAttrs, Fills, ProcessResult};
-use ureq::Agent;
+use rw_renderer::{
+    CodeBlockProcessor, CodeBlockStats, ExtractedCodeBlock, FenceAttrs, Fills, ProcessResult,
+};
 
 use crate::cache::DiagramKey;
 use crate::consts::DEFAULT_TIMEOUT;
 use crate::html_embed::{annotate_svg_links, scale_svg_dimensions, strip_google_fonts_import};
 use crate::kroki::{
-    DiagramError, DiagramRequest, create_agent, png_data_uri_dimensions, render_all,
-    render_all_png_data_uri_partial, render_all_svg_partial,
+    DiagramError, DiagramRequest, KrokiClient, png_data_uri_dimensions, render_all,
+    render_all_png_data_uri_partial, render_all_svg_partial, render_one_png_data_uri,
+    render_one_svg,
 };
 use crate::language::{DiagramFormat, DiagramLanguage, ExtractedDiagram};
 use crate::meta_includes::MetaIncludeSource;
@@ -34,12 +37,15 @@ struct ProcessorConfig {
     kroki_url: String,
     /// Directories to search for `PlantUML` `!include` files.
     include_dirs: Vec<PathBuf>,
+    /// Format for diagrams whose fence has no `format` attribute.
+    default_format: DiagramFormat,
     /// Cache for diagram rendering (defaults to no-op cache).
     cache: Box<dyn CacheBucket>,
     /// Output mode for diagram rendering.
     output: DiagramOutput,
-    /// HTTP agent for connection pooling (reused across render calls).
-    agent: Agent,
+    /// HTTP client for connection pooling and extra request headers (reused
+    /// across render calls).
+    client: KrokiClient,
     /// Optional metadata source for resolving virtual `PlantUML` includes.
     meta_include_source: Option<Arc<dyn MetaIncludeSource>>,
     /// Sections for annotating SVG links with section ref data attributes.
@@ -56,6 +62,10 @@ struct ProcessorConfig {
 ///
 /// Create the processor with a required Kroki URL, then configure using builder methods:
 /// - [`include_dirs`](Self::include_dirs): Set directories for `PlantUML` `!include` resolution
+/// - [`default_format`](Self::default_format): Set the format for fences without `{format=...}`
+/// - [`kroki_headers`](Self::kroki_headers): Send extra headers (e.g., auth) to Kroki
+/// - [`timeout`](Self::timeout) and [`retries`](Self::retries): Bound each
+///   Kroki request and retry transient failures
 ///
 /// Diagram sizing is not configurable: `PlantUML` output is rendered oversized
 /// and scaled back down for retina displays, which
@@ -94,6 +104,24 @@ pub struct DiagramProcessor {
     /// [`fills`](CodeBlockProcessor::fills). Consumed via
     /// [`section_refs`](CodeBlockProcessor::section_refs).
     section_refs: BTreeSet<String>,
+    /// Cache hit/miss, timing and size per rendered diagram, recorded during
+    /// [`fills`](CodeBlockProcessor::fills). Consumed via
+    /// [`stats`](CodeBlockProcessor::stats).
+    stats: Vec<CodeBlockStats>,
+}
+
+/// A diagram rendered by [`DiagramProcessor::render_diagram`].
+#[derive(Debug)]
+pub struct StandaloneDiagram {
+    /// SVG markup (scaled for the language's DPI) or a PNG data URI — the
+    /// same content the processor caches for a diagram inside a page.
+    pub content: String,
+    /// Format of [`content`](Self::content).
+    pub format: DiagramFormat,
+    /// Whether the content came from the cache instead of Kroki.
+    pub from_cache: bool,
+    /// Warnings from preparing the source (e.g., an unresolved `!include`).
+    pub warnings: Vec<String>,
 }
 
 impl DiagramProcessor {
@@ -115,9 +143,10 @@ impl DiagramProcessor {
             config: ProcessorConfig {
                 kroki_url: kroki_url.into(),
                 include_dirs: Vec::new(),
+                default_format: DiagramFormat::default(),
                 cache: rw_cache::NullCache.bucket("diagrams"),
                 output: DiagramOutput::default(),
-                agent: create_agent(DEFAULT_TIMEOUT),
+                client: KrokiClient::new(DEFAULT_TIMEOUT),
                 meta_include_source: None,
                 sections: None,
             },
@@ -125,6 +154,7 @@ impl DiagramProcessor {
             warnings: Vec::new(),
             has_transient_error: false,
             section_refs: BTreeSet::new(),
+            stats: Vec::new(),
         }
     }
 
@@ -144,6 +174,83 @@ impl DiagramProcessor {
         self
     }
 
+    /// Set the output format for diagrams whose fence has no `format`
+    /// attribute (or an invalid one). Default is [`DiagramFormat::Svg`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rw_kroki::{DiagramFormat, DiagramProcessor};
+    ///
+    /// let processor = DiagramProcessor::new("https://kroki.io")
+    ///     .default_format(DiagramFormat::Png);
+    /// ```
+    #[must_use]
+    pub fn default_format(mut self, format: DiagramFormat) -> Self {
+        self.config.default_format = format;
+        self
+    }
+
+    /// Set extra HTTP headers sent with every Kroki request, as `(name,
+    /// value)` pairs — e.g., an API key for an auth proxy in front of a
+    /// private Kroki instance. Headers do not affect the rendered output, so
+    /// they are not part of the cache key.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rw_kroki::DiagramProcessor;
+    ///
+    /// let processor = DiagramProcessor::new("https://kroki.internal")
+    ///     .kroki_headers(vec![("X-Api-Key".to_owned(), "secret".to_owned())]);
+    /// ```
+    #[must_use]
+    pub fn kroki_headers(mut self, headers: Vec<(String, String)>) -> Self {
+        self.config.client.headers = headers;
+        self
+    }
+
+    /// Set how long a single Kroki request may take before it fails as a
+    /// timeout. Default is 30 seconds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use rw_kroki::DiagramProcessor;
+    ///
+    /// let processor = DiagramProcessor::new("https://kroki.io")
+    ///     .timeout(Duration::from_secs(10));
+    /// ```
+    #[must_use]
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.config.client.set_timeout(timeout);
+        self
+    }
+
+    /// Set how many times a diagram is re-requested after a transient
+    /// failure — a timeout, a connection error, or a Kroki 5xx (see
+    /// [`DiagramErrorKind::is_transient`](crate::kroki::DiagramErrorKind::is_transient)).
+    /// Retries back off exponentially from 200 ms, waiting at most 5 seconds
+    /// between attempts. A rejected diagram (Kroki's 400 for malformed
+    /// source) is never retried. Default is 0.
+    ///
+    /// A diagram that still fails renders as an error figure naming the last
+    /// attempt's failure, e.g. `timed out after 10s` or `HTTP 400: …`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rw_kroki::DiagramProcessor;
+    ///
+    /// let processor = DiagramProcessor::new("https://kroki.io").retries(2);
+    /// ```
+    #[must_use]
+    pub fn retries(mut self, retries: u32) -> Self {
+        self.config.client.retries = retries;
+        self
+    }
+
     /// Set the diagram cache for content-based caching.
     ///
     /// When a cache is provided, [`fills`](CodeBlockProcessor::fills) will:
@@ -206,6 +313,90 @@ impl DiagramProcessor {
         self
     }
 
+    /// Render one diagram outside the markdown pipeline.
+    ///
+    /// Goes through the same steps as a diagram fence on a page: the source is
+    /// prepared the same way (`!include` resolution against
+    /// [`include_dirs`](Self::include_dirs) and metadata includes, plus DPI
+    /// config for `PlantUML`), looked up in and stored to the
+    /// [cache](Self::with_cache) under the same key, and rendered by the same
+    /// Kroki request. The result is not wrapped in a `<figure>` and its links
+    /// are not annotated with section refs.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`DiagramError`] (with `index` 0) if Kroki is unreachable or
+    /// rejects the diagram, or returns a malformed response.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rw_kroki::{DiagramFormat, DiagramLanguage, DiagramProcessor};
+    ///
+    /// let processor = DiagramProcessor::new("https://kroki.io");
+    /// let diagram = processor.render_diagram(
+    ///     "@startuml\nA -> B\n@enduml",
+    ///     DiagramLanguage::PlantUml,
+    ///     DiagramFormat::Svg,
+    /// )?;
+    /// assert!(diagram.content.contains("<svg"));
+    /// # Ok::<(), rw_kroki::DiagramError>(())
+    /// ```
+    pub fn render_diagram(
+        &self,
+        source: &str,
+        language: DiagramLanguage,
+        format: DiagramFormat,
+    ) -> Result<StandaloneDiagram, DiagramError> {
+        let config = &self.config;
+        let diagram = ExtractedDiagram {
+            source: source.to_owned(),
+            index: 0,
+            language,
+            format,
+            id: None,
+        };
+        let PrepareResult { source, warnings } = Self::prepare_source(config, &diagram);
+
+        let dpi = language.render_dpi();
+        let hash = DiagramKey {
+            source: &source,
+            endpoint: language.kroki_endpoint(),
+            format: format.as_str(),
+            dpi,
+        }
+        .compute_hash();
+
+        if let Some(content) = config.cache.get_string(&hash, "") {
+            return Ok(StandaloneDiagram {
+                content,
+                format,
+                from_cache: true,
+                warnings,
+            });
+        }
+
+        let request = DiagramRequest::new(0, source, language);
+        let server_url = config.kroki_url.trim_end_matches('/');
+        let content = match format {
+            DiagramFormat::Svg => {
+                let rendered = render_one_svg(&config.client, &request, server_url)?;
+                scale_svg_dimensions(&strip_google_fonts_import(rendered.svg.trim()), dpi)
+            }
+            DiagramFormat::Png => {
+                render_one_png_data_uri(&config.client, &request, server_url)?.data_uri
+            }
+        };
+        config.cache.set_string(&hash, "", &content);
+
+        Ok(StandaloneDiagram {
+            content,
+            format,
+            from_cache: false,
+            warnings,
+        })
+    }
+
     /// Annotate SVG links if sections are configured, recording each resolved
     /// ref in `refs`.
     fn annotate_links(config: &ProcessorConfig, svg: &str, refs: &mut BTreeSet<String>) -> String {
@@ -288,12 +479,14 @@ impl CodeBlockProcessor for DiagramProcessor {
         };
 
         // Parse format attribute with validation
-        let format = attrs.get("format").map_or(DiagramFormat::default(), |value| {
+        let default_format = self.config.default_format;
+        let format = attrs.get("format").map_or(default_format, |value| {
             DiagramFormat::parse(value).unwrap_or_else(|| {
                 self.warnings.push(format!(
-                    "diagram {index}: unknown format value '{value}', using default 'svg' (valid: svg, png)"
+                    "diagram {index}: unknown format value '{value}', using default '{}' (valid: svg, png)",
+                    default_format.as_str()
                 ));
-                DiagramFormat::default()
+                default_format
             })
         });
 
@@ -333,7 +526,7 @@ impl CodeBlockProcessor for DiagramProcessor {
     }
 
     fn fills(&mut self, fills: &mut Fills) {
-        // Reset up front so these two fields are fully determined by this
+        // Reset up front so these three fields are fully determined by this
         // render rather than carried over from a previous one. This runs before
         // the early return deliberately, and the renderer calls `fills` on every
         // processor regardless of whether any hole was reserved.
@@ -343,6 +536,7 @@ impl CodeBlockProcessor for DiagramProcessor {
         // the next render's fills/warnings will be mixed with this one's.
         self.has_transient_error = false;
         self.section_refs.clear();
+        self.stats.clear();
 
         let diagrams = to_extracted_diagrams(&self.extracted);
         if diagrams.is_empty() {
@@ -354,6 +548,7 @@ impl CodeBlockProcessor for DiagramProcessor {
                 &self.config,
                 &mut self.warnings,
                 &mut self.section_refs,
+                &mut self.stats,
                 fills,
                 &diagrams,
             ),
@@ -363,6 +558,7 @@ impl CodeBlockProcessor for DiagramProcessor {
             } => Self::resolve_files(
                 &self.config,
                 &mut self.warnings,
+                &mut self.stats,
                 fills,
                 &diagrams,
                 output_dir,
@@ -387,6 +583,10 @@ impl CodeBlockProcessor for DiagramProcessor {
         &self.section_refs
     }
 
+    fn stats(&self) -> &[CodeBlockStats] {
+        &self.stats
+    }
+
     fn bundle(&mut self, language: &str, source: &str) -> Option<String> {
         let lang = DiagramLanguage::parse(language)?;
         if !lang.needs_plantuml_preprocessing() {
@@ -440,12 +640,14 @@ impl CacheInfo {
 impl DiagramProcessor {
     /// Resolve diagrams in inline output mode.
     ///
-    /// Checks cache first, renders only cache misses via Kroki, and collects
-    /// the section refs resolved from diagram links into `refs`.
+    /// Checks cache first, renders only cache misses via Kroki, collects the
+    /// section refs resolved from diagram links into `refs`, and records
+    /// per-diagram `stats`.
     fn resolve_inline(
         config: &ProcessorConfig,
         warnings: &mut Vec<String>,
         refs: &mut BTreeSet<String>,
+        stats: &mut Vec<CodeBlockStats>,
         fills: &mut Fills,
         diagrams: &[ExtractedDiagram],
     ) -> bool {
@@ -490,7 +692,7 @@ impl DiagramProcessor {
             .iter()
             .map(|diagram| {
                 let prepare_result = Self::prepare_source(config, diagram);
-                warnings.extend(prepare_result.warnings);
+                warnings.extend(tag_warnings(diagram.index, prepare_result.warnings));
                 (diagram, prepare_result.source)
             })
             .collect();
@@ -514,8 +716,16 @@ impl DiagramProcessor {
             // Etag is empty: diagrams use content-addressed hashing (the key
             // IS the hash), so etag validation is unnecessary. Version-level
             // invalidation is handled by FileCache's VERSION file.
+            let lookup = Instant::now();
             if let Some(cached_content) = config.cache.get_string(&hash, "") {
                 // Cache hit: add figure directly
+                stats.push(CodeBlockStats {
+                    index: diagram.index,
+                    language: endpoint.to_owned(),
+                    from_cache: true,
+                    duration: lookup.elapsed(),
+                    bytes: cached_content.len(),
+                });
                 let id_attr = figures.id_attr(diagram.index);
                 let figure = match diagram.format {
                     DiagramFormat::Svg => {
@@ -544,8 +754,9 @@ impl DiagramProcessor {
         }
 
         // Render cache misses and collect figures
-        let svg_transient = Self::render_and_cache_svg(config, &mut figures, refs, svg_to_render);
-        let png_transient = Self::render_and_cache_png(config, &mut figures, png_to_render);
+        let svg_transient =
+            Self::render_and_cache_svg(config, &mut figures, refs, stats, svg_to_render);
+        let png_transient = Self::render_and_cache_png(config, &mut figures, stats, png_to_render);
 
         figures.into_fills(fills);
 
@@ -558,6 +769,7 @@ impl DiagramProcessor {
         config: &ProcessorConfig,
         figures: &mut Figures,
         refs: &mut BTreeSet<String>,
+        stats: &mut Vec<CodeBlockStats>,
         to_render: Vec<(DiagramRequest, CacheInfo)>,
     ) -> bool {
         if to_render.is_empty() {
@@ -566,11 +778,18 @@ impl DiagramProcessor {
 
         let (requests, cache_map) = extract_requests_and_cache_info(to_render);
 
-        let result = render_all_svg_partial(&requests, &config.kroki_url, &config.agent);
+        let result = render_all_svg_partial(&requests, &config.kroki_url, &config.client);
         for r in result.rendered {
             let dpi = r.language.render_dpi();
             let clean_svg = strip_google_fonts_import(r.svg.trim());
             let scaled_svg = scale_svg_dimensions(&clean_svg, dpi);
+            stats.push(CodeBlockStats {
+                index: r.index,
+                language: r.language.kroki_endpoint().to_owned(),
+                from_cache: false,
+                duration: r.duration,
+                bytes: scaled_svg.len(),
+            });
 
             if let Some(info) = cache_map.get(&r.index) {
                 let hash = info.key().compute_hash();
@@ -589,6 +808,7 @@ impl DiagramProcessor {
     fn render_and_cache_png(
         config: &ProcessorConfig,
         figures: &mut Figures,
+        stats: &mut Vec<CodeBlockStats>,
         to_render: Vec<(DiagramRequest, CacheInfo)>,
     ) -> bool {
         if to_render.is_empty() {
@@ -597,12 +817,19 @@ impl DiagramProcessor {
 
         let (requests, cache_map) = extract_requests_and_cache_info(to_render);
 
-        let result = render_all_png_data_uri_partial(&requests, &config.kroki_url, &config.agent);
+        let result = render_all_png_data_uri_partial(&requests, &config.kroki_url, &config.client);
         for r in result.rendered {
             if let Some(info) = cache_map.get(&r.index) {
                 let hash = info.key().compute_hash();
                 config.cache.set_string(&hash, "", &r.data_uri);
             }
+            stats.push(CodeBlockStats {
+                index: r.index,
+                language: r.language.kroki_endpoint().to_owned(),
+                from_cache: false,
+                duration: r.duration,
+                bytes: r.data_uri.len(),
+            });
 
             let id_attr = figures.id_attr(r.index);
             let figure = Self::png_figure(&id_attr, &r.data_uri, r.language.render_dpi());
@@ -617,6 +844,7 @@ impl DiagramProcessor {
     fn resolve_files(
         config: &ProcessorConfig,
         warnings: &mut Vec<String>,
+        stats: &mut Vec<CodeBlockStats>,
         fills: &mut Fills,
         diagrams: &[ExtractedDiagram],
         output_dir: &std::path::Path,
@@ -630,18 +858,25 @@ impl DiagramProcessor {
             .iter()
             .map(|d| {
                 let prepare_result = Self::prepare_source(config, d);
-                warnings.extend(prepare_result.warnings);
+                warnings.extend(tag_warnings(d.index, prepare_result.warnings));
                 DiagramRequest::new(d.index, prepare_result.source, d.language)
             })
             .collect();
 
         let server_url = config.kroki_url.trim_end_matches('/');
 
-        let result = render_all(&diagram_requests, server_url, output_dir, &config.agent);
+        let result = render_all(&diagram_requests, server_url, output_dir, &config.client);
         for r in result.rendered {
             // Only PlantUML-family output is oversized, so each diagram is
             // scaled by its own DPI rather than the configured one. The tag
             // generator receives the result, never the DPI.
+            stats.push(CodeBlockStats {
+                index: r.index,
+                language: r.language.kroki_endpoint().to_owned(),
+                from_cache: false,
+                duration: r.duration,
+                bytes: r.bytes,
+            });
             let display_width = to_display_px(r.width, r.language.render_dpi());
             let info = RenderedDiagramInfo::new(r.filename, display_width);
             let tag = tag_generator(&info);
@@ -667,6 +902,14 @@ fn extract_requests_and_cache_info(
     (requests, cache_map)
 }
 
+/// Source-preparation `warnings` for diagram `index`, prefixed like the
+/// processor's own so a missing `!include` can be traced to its fence.
+fn tag_warnings(index: usize, warnings: Vec<String>) -> impl Iterator<Item = String> {
+    warnings
+        .into_iter()
+        .map(move |warning| format!("diagram {index}: {warning}"))
+}
+
 /// Render the optional `data-diagram-id` attribute (leading space included), or
 /// an empty string when there is no id. The value is HTML-attribute-escaped.
 fn diagram_id_attr(id: Option<&str>) -> String {
@@ -1270,6 +1513,139 @@ mod tests {
         assert!(!result.html.contains("rw-diagram"));
     }
 
+    #[test]
+    fn include_warnings_name_their_diagram_once() {
+        let fence = "```plantuml\n@startuml\n!include missing.iuml\nA -> B\n@enduml\n```\n\n";
+        let markdown = format!("```mermaid\nA-->B\n```\n\n{fence}{fence}");
+
+        let warnings = render_diagrams_warnings(&markdown);
+
+        let missing: Vec<_> = warnings
+            .iter()
+            .filter(|w| w.contains("missing.iuml"))
+            .collect();
+        assert_eq!(missing.len(), 2, "{warnings:?}");
+        assert!(
+            missing[0].starts_with("diagram 1: Include file not found: 'missing.iuml'"),
+            "{warnings:?}"
+        );
+        assert!(missing[1].starts_with("diagram 2: "), "{warnings:?}");
+    }
+
+    #[test]
+    fn render_diagram_uses_cache_and_reports_prepare_warnings() {
+        struct AlwaysHit(Vec<u8>);
+        impl CacheBucket for AlwaysHit {
+            fn get(&self, _key: &str, _etag: &str) -> Option<Vec<u8>> {
+                Some(self.0.clone())
+            }
+            fn set(&self, _key: &str, _etag: &str, _value: &[u8]) {}
+        }
+
+        let cached = r#"<svg width="10" height="10"></svg>"#;
+        let processor = DiagramProcessor::new("http://127.0.0.1:1")
+            .with_cache(Box::new(AlwaysHit(cached.as_bytes().to_vec())));
+        let diagram = processor
+            .render_diagram(
+                "@startuml\n!include missing.iuml\nA -> B\n@enduml",
+                DiagramLanguage::PlantUml,
+                DiagramFormat::Svg,
+            )
+            .unwrap();
+
+        assert_eq!(diagram.content, cached);
+        assert_eq!(diagram.format, DiagramFormat::Svg);
+        assert!(diagram.from_cache);
+        assert_eq!(diagram.warnings.len(), 1, "{:?}", diagram.warnings);
+        assert!(diagram.warnings[0].contains("missing.iuml"));
+    }
+
+    #[test]
+    fn render_diagram_shares_cache_key_with_pages() {
+        use rw_renderer::{HtmlBackend, MarkdownRenderer, Pipeline};
+        use std::sync::mpsc;
+
+        struct Lookups(mpsc::Sender<String>);
+        impl CacheBucket for Lookups {
+            fn get(&self, key: &str, _etag: &str) -> Option<Vec<u8>> {
+                self.0.send(key.to_owned()).unwrap();
+                None
+            }
+            fn set(&self, _key: &str, _etag: &str, _value: &[u8]) {}
+        }
+
+        // A fence's source keeps its trailing newline.
+        let source = "@startuml\nA -> B\n@enduml\n";
+        let (tx, page_lookups) = mpsc::channel();
+        let processor =
+            DiagramProcessor::new("http://127.0.0.1:1").with_cache(Box::new(Lookups(tx)));
+        MarkdownRenderer::<HtmlBackend>::new().render(
+            &format!("```plantuml {{format=png}}\n{source}```"),
+            Pipeline::new().with_processor(processor),
+        );
+
+        let (tx, single_lookups) = mpsc::channel();
+        let processor =
+            DiagramProcessor::new("http://127.0.0.1:1").with_cache(Box::new(Lookups(tx)));
+        let err = processor
+            .render_diagram(source, DiagramLanguage::PlantUml, DiagramFormat::Png)
+            .unwrap_err();
+
+        assert!(err.kind.is_transient());
+        let page: Vec<String> = page_lookups.try_iter().collect();
+        let single: Vec<String> = single_lookups.try_iter().collect();
+        assert_eq!(page.len(), 1);
+        assert_eq!(single, page);
+    }
+
+    #[test]
+    fn stats_report_cache_hits_per_diagram() {
+        use rw_renderer::{HtmlBackend, MarkdownRenderer, Pipeline};
+
+        struct AlwaysHit(Vec<u8>);
+        impl CacheBucket for AlwaysHit {
+            fn get(&self, _key: &str, _etag: &str) -> Option<Vec<u8>> {
+                Some(self.0.clone())
+            }
+            fn set(&self, _key: &str, _etag: &str, _value: &[u8]) {}
+        }
+
+        let cached = r#"<svg width="10" height="10"></svg>"#;
+        let processor = DiagramProcessor::new("http://127.0.0.1:1")
+            .with_cache(Box::new(AlwaysHit(cached.as_bytes().to_vec())));
+        let result = MarkdownRenderer::<HtmlBackend>::new().render(
+            "```plantuml\nA -> B\n```\n\n```rust\nfn main() {}\n```\n\n```mermaid\nA-->B\n```\n",
+            Pipeline::new().with_processor(processor),
+        );
+
+        let stats: Vec<_> = result
+            .code_block_stats
+            .iter()
+            .map(|s| (s.index, s.language.as_str(), s.from_cache, s.bytes))
+            .collect();
+        assert_eq!(
+            stats,
+            [
+                (0, "plantuml", true, cached.len()),
+                (2, "mermaid", true, cached.len()),
+            ]
+        );
+    }
+
+    #[test]
+    fn stats_omit_failed_diagrams() {
+        use rw_renderer::{HtmlBackend, MarkdownRenderer, Pipeline};
+
+        let processor = DiagramProcessor::new("http://127.0.0.1:1");
+        let result = MarkdownRenderer::<HtmlBackend>::new().render(
+            "```plantuml\n@startuml\nA -> B\n@enduml\n```\n",
+            Pipeline::new().with_processor(processor),
+        );
+
+        assert!(result.html.contains("diagram-error"));
+        assert!(result.code_block_stats.is_empty());
+    }
+
     #[test]
     fn add_errors_reports_transient_only_for_transient_kinds() {
         use crate::kroki::DiagramErrorKind;
@@ -1376,6 +1752,28 @@ mod tests {
         assert!(processor.warnings()[0].contains("unknown format value 'jpeg'"));
     }
 
+    #[test]
+    fn test_process_uses_configured_default_format() {
+        let mut processor =
+            DiagramProcessor::new("https://kroki.io").default_format(DiagramFormat::Png);
+        let mut svg = FenceAttrs::default();
+        svg.insert("format".to_owned(), "svg".to_owned());
+        let mut invalid = FenceAttrs::default();
+        invalid.insert("format".to_owned(), "jpeg".to_owned());
+
+        processor.process("plantuml", &FenceAttrs::default(), "source", 0);
+        processor.process("plantuml", &svg, "source", 1);
+        processor.process("plantuml", &invalid, "source", 2);
+
+        let formats: Vec<_> = processor
+            .extracted()
+            .iter()
+            .map(|block| block.attr("format"))
+            .collect();
+        assert_eq!(formats, [Some("png"), Some("svg"), Some("png")]);
+        assert!(processor.warnings()[0].contains("using default 'png'"));
+    }
+
     #[test]
     fn test_process_with_unknown_attribute() {
         let mut processor = DiagramProcessor::new("https://kroki.io");
@@ -8,6 +8,9 @@ pub(crate) struct MetaFields {
     pub title: Option<String>,
     pub description: Option<String>,
     pub pages: Option<Vec<String>>,
+    #[serde(alias = "weight")]
+    pub order: Option<i64>,
+    pub redirects: Option<Vec<String>>,
 }
 
 impl MetaFields {
@@ -23,6 +26,8 @@ impl MetaFields {
         self.title = other.title.or(self.title);
         self.description = other.description.or(self.description);
         self.pages = other.pages.or(self.pages);
+        self.order = other.order.or(self.order);
+        self.redirects = other.redirects.or(self.redirects);
         self
     }
 }
@@ -165,6 +170,21 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_order_and_weight_alias() {
+        assert_eq!(MetaFields::from_yaml("order: 2").order, Some(2));
+        assert_eq!(MetaFields::from_yaml("weight: -1").order, Some(-1));
+    }
+
+    #[test]
+    fn parse_redirects() {
+        let fields = MetaFields::from_yaml("redirects:\n  - old/path\n  - legacy");
+        assert_eq!(
+            fields.redirects,
+            Some(vec!["old/path".to_owned(), "legacy".to_owned()])
+        );
+    }
+
     #[test]
     fn parse_namespace() {
         let fields = MetaFields::from_yaml("namespace: payments");
@@ -16,13 +16,18 @@ impl Head {
         let mut in_h1 = false;
         let mut title_buf = String::new();
 
-        for event in parser {
+        for (event, range) in parser.into_offset_iter() {
             match event {
-                Event::Start(Tag::MetadataBlock(_)) => {
+                // pulldown-cmark accepts a metadata block at any block start, so
+                // a `---` rule followed by `key: value` lines after an H2 would
+                // parse as one. Only a block opening the file is frontmatter.
+                Event::Start(Tag::MetadataBlock(_)) if range.start == 0 => {
                     in_metadata = true;
                 }
+                // A block with CRLF line endings arrives as several text
+                // events, one per line break.
                 Event::Text(ref text) if in_metadata => {
-                    frontmatter = Some(text.to_string());
+                    frontmatter.get_or_insert_with(String::new).push_str(text);
                 }
                 Event::End(TagEnd::MetadataBlock(_)) => {
                     in_metadata = false;
@@ -36,6 +41,10 @@ impl Head {
                 Event::Text(ref text) | Event::Code(ref text) if in_h1 => {
                     title_buf.push_str(text);
                 }
+                // A setext heading's text may span several lines.
+                Event::SoftBreak if in_h1 => {
+                    title_buf.push(' ');
+                }
                 Event::End(TagEnd::Heading(HeadingLevel::H1)) => {
                     title = Some(title_buf);
                     break;
@@ -100,6 +109,44 @@ mod tests {
         assert_eq!(head.title.as_deref(), Some("See docs"));
     }
 
+    #[test]
+    fn setext_h1() {
+        let head = Head::parse("My Title\n========\n\nContent.");
+        assert_eq!(head.title.as_deref(), Some("My Title"));
+    }
+
+    #[test]
+    fn setext_h1_is_trimmed() {
+        let head = Head::parse("  Padded Title   \n===\n");
+        assert_eq!(head.title.as_deref(), Some("Padded Title"));
+    }
+
+    #[test]
+    fn setext_h1_spanning_lines() {
+        let head = Head::parse("A Title Split\nAcross Lines\n===\n");
+        assert_eq!(head.title.as_deref(), Some("A Title Split Across Lines"));
+    }
+
+    #[test]
+    fn setext_underline_after_blank_line_not_h1() {
+        let head = Head::parse("My Title\n\n========\n");
+        assert!(head.title.is_none());
+    }
+
+    #[test]
+    fn setext_h1_after_frontmatter() {
+        let md = "---\ndescription: Intro\n---\n\nMy Title\n========\n";
+        let head = Head::parse(md);
+        assert!(head.frontmatter.is_some());
+        assert_eq!(head.title.as_deref(), Some("My Title"));
+    }
+
+    #[test]
+    fn setext_h2_not_h1() {
+        let head = Head::parse("Subtitle\n--------\n");
+        assert!(head.title.is_none());
+    }
+
     #[test]
     fn code_block_comment_not_h1() {
         let md = "```\n# comment\n```\n";
@@ -150,6 +197,21 @@ mod tests {
         assert_eq!(head.title.as_deref(), Some("Heading"));
     }
 
+    #[test]
+    fn frontmatter_with_crlf_line_endings() {
+        let md = "---\r\ntitle: Windows\r\n---\r\n\r\n# Heading\r\n";
+        let head = Head::parse(md);
+        assert!(head.frontmatter.unwrap().contains("title: Windows"));
+        assert_eq!(head.title.as_deref(), Some("Heading"));
+    }
+
+    #[test]
+    fn metadata_block_after_heading_not_frontmatter() {
+        let md = "## Changes\n\n---\nauthor: someone\n---\n\n# Late\n";
+        let head = Head::parse(md);
+        assert!(head.frontmatter.is_none());
+    }
+
     #[test]
     fn h1_after_paragraph_not_extracted() {
         let md = "Some introductory paragraph.\n\n# Late Heading\n";
@@ -3,9 +3,13 @@ mod head;
 
 use fields::MetaFields;
 use head::Head;
+use serde::{Deserialize, Serialize};
 
 /// Resolved page metadata from all sources.
-#[derive(Debug, Clone, PartialEq)]
+///
+/// Serializable so storage backends can persist resolved metadata between
+/// runs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Meta {
     /// Page kind (e.g., "domain", "guide").
     pub kind: Option<String>,
@@ -17,6 +21,11 @@ pub struct Meta {
     pub description: Option<String>,
     /// Ordered list of child page slugs for navigation ordering.
     pub pages: Option<Vec<String>>,
+    /// Position among siblings in navigation (`order`, or its alias
+    /// `weight`); lower sorts first.
+    pub order: Option<i64>,
+    /// Former URL paths of this page that should redirect to it.
+    pub redirects: Option<Vec<String>>,
 }
 
 impl Meta {
@@ -25,11 +34,19 @@ impl Meta {
     /// Internally:
     /// 1. Parses meta.yaml into base fields
     /// 2. Extracts frontmatter and first H1 from markdown via pulldown-cmark
-    /// 3. Merges frontmatter over meta.yaml (frontmatter wins per field)
-    /// 4. Resolves title: frontmatter.title > meta.title > H1 > titlecase(filename)
+    /// 3. Merges frontmatter over meta.yaml (frontmatter wins per field,
+    ///    except the title)
+    /// 4. Resolves title: meta.title > frontmatter.title > H1 > titlecase(filename)
     #[must_use]
     pub fn resolve(markdown: Option<&str>, meta_yaml: Option<&str>, filename: &str) -> Self {
+        // A byte order mark would hide both frontmatter and the H1.
+        let markdown = markdown.map(strip_bom);
+        let meta_yaml = meta_yaml.map(strip_bom);
+
         let base = meta_yaml.map(MetaFields::from_yaml).unwrap_or_default();
+        // A sidecar title lets a page be renamed in navigation without
+        // touching its source, so it outranks the page's own frontmatter.
+        let sidecar_title = base.title.clone();
 
         let (frontmatter, h1_title) = markdown
             .map(Head::parse)
@@ -41,8 +58,8 @@ impl Meta {
             .unwrap_or_default();
         let merged = base.merge(overlay);
 
-        let title = merged
-            .title
+        let title = sidecar_title
+            .or(merged.title)
             .or(h1_title)
             .filter(|t| !t.is_empty())
             .unwrap_or_else(|| {
@@ -56,10 +73,17 @@ impl Meta {
             title,
             description: merged.description,
             pages: merged.pages,
+            order: merged.order,
+            redirects: merged.redirects,
         }
     }
 }
 
+/// `text` without a leading byte order mark.
+fn strip_bom(text: &str) -> &str {
+    text.strip_prefix('\u{FEFF}').unwrap_or(text)
+}
+
 /// Convert a slug to title case.
 ///
 /// Replaces `-` and `_` with spaces, capitalizes each word.
@@ -108,10 +132,17 @@ mod tests {
     // --- resolve: title priority ---
 
     #[test]
-    fn resolve_frontmatter_title_wins_over_meta_yaml() {
+    fn resolve_meta_yaml_title_wins_over_frontmatter() {
         let md = "---\ntitle: Frontmatter Title\n---\n\n# H1 Title\n";
         let meta_yaml = "title: Meta YAML Title";
         let meta = Meta::resolve(Some(md), Some(meta_yaml), "page.md");
+        assert_eq!(meta.title, "Meta YAML Title");
+    }
+
+    #[test]
+    fn resolve_frontmatter_title_wins_over_h1() {
+        let md = "---\ntitle: Frontmatter Title\n---\n\n# H1 Title\n";
+        let meta = Meta::resolve(Some(md), None, "page.md");
         assert_eq!(meta.title, "Frontmatter Title");
     }
 
@@ -130,6 +161,29 @@ mod tests {
         assert_eq!(meta.title, "H1 Title");
     }
 
+    #[test]
+    fn resolve_ignores_byte_order_mark() {
+        let md = "\u{FEFF}---\ndescription: From frontmatter\n---\n\n# Title\n";
+        let meta = Meta::resolve(Some(md), Some("\u{FEFF}kind: guide"), "page.md");
+        assert_eq!(meta.title, "Title");
+        assert_eq!(meta.description.as_deref(), Some("From frontmatter"));
+        assert_eq!(meta.kind.as_deref(), Some("guide"));
+    }
+
+    #[test]
+    fn resolve_frontmatter_title_without_h1() {
+        let md = "---\r\ntitle: 'Billing: \"Overview\"'\r\n---\r\n\r\nSome content.\r\n";
+        let meta = Meta::resolve(Some(md), None, "page.md");
+        assert_eq!(meta.title, r#"Billing: "Overview""#);
+    }
+
+    #[test]
+    fn resolve_horizontal_rule_in_content_not_frontmatter() {
+        let md = "Intro.\n\n---\ntitle: Not A Title\n---\n";
+        let meta = Meta::resolve(Some(md), None, "setup-guide.md");
+        assert_eq!(meta.title, "Setup Guide");
+    }
+
     #[test]
     fn resolve_filename_fallback() {
         let meta = Meta::resolve(None, None, "setup-guide.md");
@@ -249,6 +303,13 @@ mod tests {
         assert!(meta.pages.is_none());
     }
 
+    #[test]
+    fn resolve_order_frontmatter_overrides_meta_yaml() {
+        let md = "---\norder: 1\n---\n# Title\n";
+        let meta = Meta::resolve(Some(md), Some("weight: 5"), "page.md");
+        assert_eq!(meta.order, Some(1));
+    }
+
     #[test]
     fn resolve_namespace_from_meta_yaml() {
         let meta = Meta::resolve(None, Some("namespace: payments"), "page.md");
@@ -10,8 +10,8 @@ use rw_cache::{Cache, NullCache};
 use rw_cache_s3::S3Cache;
 use rw_config::Config;
 use rw_site::{
-    NavItem, PageEntry, PageRendererConfig, ScopeInfo, SectionAnchor, SectionEntry, Site,
-    to_url_path,
+    DiagramFormat, NavItem, PageEntry, PageRendererConfig, ScopeInfo, SectionAnchor, SectionEntry,
+    Site, to_url_path,
 };
 use rw_storage::{Storage, mtime_to_datetime};
 use rw_storage_fs::{FsStorage, MtimeSource};
@@ -56,6 +56,31 @@ fn apply_diagrams_config(
     if let Some(url) = diagrams.and_then(|d| d.kroki_url.as_ref()) {
         renderer_config.kroki_url = Some(url.clone());
     }
+    if let Some(headers) = diagrams.and_then(|d| d.kroki_headers.as_ref()) {
+        // Sorted, so requests carry headers in a stable order.
+        let mut headers: Vec<_> = headers
+            .iter()
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect();
+        headers.sort();
+        renderer_config.kroki_headers = headers;
+    }
+    if let Some(client_side_mermaid) = diagrams.and_then(|d| d.client_side_mermaid) {
+        renderer_config.client_side_mermaid = client_side_mermaid;
+    }
+}
+
+/// Check a `tocMaxDepth` option: a heading level from 1 to 6.
+fn parse_toc_max_depth(depth: u32) -> Result<u8> {
+    u8::try_from(depth)
+        .ok()
+        .filter(|depth| (1..=6).contains(depth))
+        .ok_or_else(|| {
+            napi::Error::new(
+                napi::Status::InvalidArg,
+                format!("invalid tocMaxDepth {depth} (expected 1 to 6)"),
+            )
+        })
 }
 
 fn convert_nav_item(item: NavItem) -> NavItemResponse {
@@ -112,6 +137,7 @@ pub fn create_site(config: SiteConfig) -> Result<RwSite> {
             "Cannot specify both projectDir and s3",
         ));
     }
+    let toc_max_depth = config.toc_max_depth.map(parse_toc_max_depth).transpose()?;
 
     let (storage, renderer_config, cache): (Arc<dyn Storage>, PageRendererConfig, Arc<dyn Cache>) =
         if let Some(s3) = config.s3 {
@@ -176,18 +202,40 @@ pub fn create_site(config: SiteConfig) -> Result<RwSite> {
                     ));
                 }
             };
-            let storage = Arc::new(
-                FsStorage::with_meta_filename(
-                    rw_config.project_dir.clone(),
-                    rw_config.docs_resolved.source_dir.clone(),
-                    &rw_config.metadata.name,
-                )
-                .with_mtime_source(mtime_source),
+            let storage = rw_storage_fs::source_dirs_storage(
+                &rw_config.docs_resolved.source_dirs,
+                |_, source_dir| {
+                    FsStorage::with_meta_filename(
+                        rw_config.project_dir.clone(),
+                        source_dir.to_path_buf(),
+                        &rw_config.metadata.name,
+                    )
+                    .with_extensions(&rw_config.docs_resolved.extensions)
+                    .with_case_insensitive(rw_config.docs_resolved.case_insensitive)
+                    .with_homepage(rw_config.docs_resolved.homepage.clone())
+                    .with_mtime_source(mtime_source)
+                },
             );
             let mut renderer_config = PageRendererConfig {
                 extract_title: true,
                 kroki_url: rw_config.diagrams_resolved.kroki_url,
                 include_dirs: rw_config.diagrams_resolved.include_dirs,
+                diagram_format: rw_config
+                    .diagrams_resolved
+                    .default_format
+                    .as_deref()
+                    .and_then(DiagramFormat::parse)
+                    .unwrap_or_default(),
+                kroki_headers: rw_config
+                    .diagrams_resolved
+                    .kroki_headers
+                    .into_iter()
+                    .collect(),
+                toc_max_depth: rw_config.docs_resolved.toc_max_depth,
+                math: rw_config.docs_resolved.math,
+                // `rw.toml` has no setting for it: only a host that loads
+                // mermaid.js can opt in, through `diagrams.clientSideMermaid`.
+                client_side_mermaid: false,
             };
             apply_diagrams_config(&mut renderer_config, config.diagrams.as_ref());
             (storage, renderer_config, Arc::new(NullCache))
@@ -198,6 +246,12 @@ pub fn create_site(config: SiteConfig) -> Result<RwSite> {
             ));
         };
 
+    let renderer_config = PageRendererConfig {
+        toc_max_depth: toc_max_depth.or(renderer_config.toc_max_depth),
+        math: config.math.unwrap_or(renderer_config.math),
+        ..renderer_config
+    };
+
     let site = Arc::new(Site::new(storage, cache, renderer_config));
     Ok(RwSite { site })
 }
@@ -514,6 +568,8 @@ mod tests {
     fn apply_diagrams_config_sets_kroki_url() {
         let diagrams = Some(DiagramsConfig {
             kroki_url: Some("https://kroki.io".to_owned()),
+            kroki_headers: None,
+            client_side_mermaid: None,
         });
         let mut renderer_config = PageRendererConfig::default();
         apply_diagrams_config(&mut renderer_config, diagrams.as_ref());
@@ -523,6 +579,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn apply_diagrams_config_sets_kroki_headers() {
+        let diagrams = Some(DiagramsConfig {
+            kroki_url: None,
+            kroki_headers: Some(HashMap::from([
+                ("X-Team".to_owned(), "docs".to_owned()),
+                ("X-Api-Key".to_owned(), "secret".to_owned()),
+            ])),
+            client_side_mermaid: None,
+        });
+        let mut renderer_config = PageRendererConfig::default();
+        apply_diagrams_config(&mut renderer_config, diagrams.as_ref());
+        assert_eq!(
+            renderer_config.kroki_headers,
+            [
+                ("X-Api-Key".to_owned(), "secret".to_owned()),
+                ("X-Team".to_owned(), "docs".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn apply_diagrams_config_sets_client_side_mermaid() {
+        let diagrams = Some(DiagramsConfig {
+            kroki_url: None,
+            kroki_headers: None,
+            client_side_mermaid: Some(true),
+        });
+        let mut renderer_config = PageRendererConfig::default();
+        apply_diagrams_config(&mut renderer_config, diagrams.as_ref());
+        assert!(renderer_config.client_side_mermaid);
+    }
+
     #[test]
     fn apply_diagrams_config_none_is_noop() {
         let mut renderer_config = PageRendererConfig::default();
@@ -531,6 +620,15 @@ mod tests {
         assert_eq!(renderer_config.kroki_url, before_kroki);
     }
 
+    #[test]
+    fn parse_toc_max_depth_accepts_heading_levels() {
+        assert_eq!(parse_toc_max_depth(1).unwrap(), 1);
+        assert_eq!(parse_toc_max_depth(6).unwrap(), 6);
+        assert!(parse_toc_max_depth(0).is_err());
+        assert!(parse_toc_max_depth(7).is_err());
+        assert!(parse_toc_max_depth(256).is_err());
+    }
+
     #[test]
     fn convert_scope_info_preserves_fields() {
         let info = ScopeInfo {
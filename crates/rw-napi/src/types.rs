@@ -7,6 +7,14 @@ use rw_site::{Section, SectionAnchor};
 pub struct DiagramsConfig {
     #[napi(js_name = "krokiUrl")]
     pub kroki_url: Option<String>,
+    /// Extra HTTP headers sent with every Kroki request, by header name.
+    #[napi(js_name = "krokiHeaders")]
+    pub kroki_headers: Option<HashMap<String, String>>,
+    /// Render ```` ```mermaid ```` fences as `<pre class="mermaid">` for
+    /// mermaid.js in the page, instead of through Kroki. The host must load
+    /// mermaid.js itself; `rw serve` has no such mode.
+    #[napi(js_name = "clientSideMermaid")]
+    pub client_side_mermaid: Option<bool>,
 }
 
 #[napi(object)]
@@ -23,6 +31,15 @@ pub struct SiteConfig {
     /// rejected.
     #[napi(js_name = "mtimeSource")]
     pub mtime_source: Option<String>,
+    /// Deepest heading level, 1 to 6, listed in a page's `toc`; deeper
+    /// headings keep their anchors. Overrides `[docs] toc_max_depth` of a
+    /// `projectDir` site. Every level when neither is set.
+    #[napi(js_name = "tocMaxDepth")]
+    pub toc_max_depth: Option<u32>,
+    /// Render `$tex$` and `$$tex$$` as math, for the host's `KaTeX` or
+    /// `MathJax` to typeset. Overrides `[docs] math` of a `projectDir` site.
+    /// Off when neither is set.
+    pub math: Option<bool>,
 }
 
 #[napi(object)]
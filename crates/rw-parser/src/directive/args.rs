@@ -188,9 +188,15 @@ impl DirectiveArgs {
     }
 }
 
+/// Quote pairs a value may be wrapped in. The typographic pairs are what
+/// smart punctuation turns `"…"` and `'…'` into before the text reaches the
+/// directive scanner.
+const QUOTES: [(char, char); 4] = [('"', '"'), ('\'', '\''), ('“', '”'), ('‘', '’')];
+
 /// Parse a key-value pair from the attributes string.
 ///
-/// Supports: `key="value"`, `key='value'`, `key=value`
+/// Supports: `key="value"`, `key='value'`, `key=value`, and `“…”` / `‘…’`
+/// quoting.
 fn parse_key_value(s: &str) -> Option<(&str, &str, &str)> {
     let eq_pos = s.find('=')?;
     let key = s[..eq_pos].trim();
@@ -201,25 +207,20 @@ fn parse_key_value(s: &str) -> Option<(&str, &str, &str)> {
 
     let after_eq = &s[eq_pos + 1..];
 
-    if let Some(stripped) = after_eq.strip_prefix('"') {
-        // Quoted with double quotes
-        let end_quote = stripped.find('"')?;
-        let value = &stripped[..end_quote];
-        let rest = &stripped[end_quote + 1..];
-        Some((key, value, rest))
-    } else if let Some(stripped) = after_eq.strip_prefix('\'') {
-        // Quoted with single quotes
-        let end_quote = stripped.find('\'')?;
-        let value = &stripped[..end_quote];
-        let rest = &stripped[end_quote + 1..];
-        Some((key, value, rest))
-    } else {
-        // Unquoted value (until whitespace)
-        let end = after_eq.find(char::is_whitespace).unwrap_or(after_eq.len());
-        let value = &after_eq[..end];
-        let rest = &after_eq[end..];
-        Some((key, value, rest))
+    for (open, close) in QUOTES {
+        if let Some(stripped) = after_eq.strip_prefix(open) {
+            let end_quote = stripped.find(close)?;
+            let value = &stripped[..end_quote];
+            let rest = &stripped[end_quote + close.len_utf8()..];
+            return Some((key, value, rest));
+        }
     }
+
+    // Unquoted value (until whitespace)
+    let end = after_eq.find(char::is_whitespace).unwrap_or(after_eq.len());
+    let value = &after_eq[..end];
+    let rest = &after_eq[end..];
+    Some((key, value, rest))
 }
 
 #[cfg(test)]
@@ -280,6 +281,13 @@ mod tests {
         assert_eq!(args.get("title"), Some("Hello World"));
     }
 
+    #[test]
+    fn test_typographic_quoted_value() {
+        let args = DirectiveArgs::parse("", "title=“Hello World” alt=‘Hi there’");
+        assert_eq!(args.get("title"), Some("Hello World"));
+        assert_eq!(args.get("alt"), Some("Hi there"));
+    }
+
     #[test]
     fn test_unquoted_value() {
         let args = DirectiveArgs::parse("", "width=560");
@@ -11,10 +11,9 @@
 //!
 //! # Why cmark variants are missing
 //!
-//! * `FootnoteDefinition`, `FootnoteReference`, `InlineMath`, `DisplayMath` —
-//!   rw's parser options (`parser::cmark_options`) enable neither footnotes
-//!   nor math, so cmark never emits them. Verified against a document
-//!   containing all four syntaxes.
+//! * `FootnoteDefinition`, `FootnoteReference` — rw's parser options
+//!   (`parser::cmark_options`) do not enable footnotes, so cmark never emits
+//!   them. Verified against a document containing both syntaxes.
 //! * `HtmlBlock` — emitted, but dropped here. Its raw contents still arrive,
 //!   as [`Event::RawHtml`].
 //! * `MetadataBlock` — the whole block is swallowed, its text included, so the
@@ -42,6 +41,14 @@ pub enum Event<'a> {
     End(TagEnd),
     Text(CowStr<'a>),
     Code(CowStr<'a>),
+    /// `$tex$`: the TeX between the delimiters, unescaped.
+    InlineMath(CowStr<'a>),
+    /// `$$tex$$`: the TeX between the delimiters, unescaped. A block: the
+    /// Parser closes the enclosing paragraph before it and reopens one for
+    /// any prose after it, so it never arrives between a paragraph's
+    /// `Start`/`End`. Inside emphasis, a link, a heading, a table cell or a
+    /// tight list item it stays where cmark reports it.
+    DisplayMath(CowStr<'a>),
     /// Raw HTML, block or inline. One variant, because rw renders both
     /// identically; a consumer needing the distinction cannot recover it here.
     RawHtml(CowStr<'a>),
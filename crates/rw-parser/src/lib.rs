@@ -63,4 +63,4 @@ pub use event::{
 pub use pulldown_cmark::{Alignment, CowStr};
 
 pub use fence::{FenceAttrs, parse_fence_info};
-pub use parser::Parser;
+pub use parser::{ParseOptions, Parser};
@@ -64,11 +64,18 @@ enum Deferred<'a> {
     None,
     /// `Start(Paragraph)` seen and held; the run is still coalescing.
     Paragraph,
+    /// A `Start(Paragraph)` held for the prose after display math that split
+    /// its paragraph. Leading whitespace and breaks are dropped, and if
+    /// nothing else follows no paragraph is emitted at all.
+    ReopenedParagraph,
     /// Owe the held `Start(Paragraph)`, then the run, then this event.
     ReleasingParagraph(Event<'a>),
     /// Owe the run, then this event. The non-paragraph case — a run inside a
     /// heading or a tight list item interrupted by markup.
     Draining(Event<'a>),
+    /// Owe this display math, which has just closed its paragraph, then hold
+    /// a [`ReopenedParagraph`](Self::ReopenedParagraph).
+    SplittingParagraph(Event<'a>),
 }
 
 /// One slice cut out of the coalesced run, named by **byte range** rather than
@@ -152,6 +159,10 @@ pub struct Parser<'a> {
     /// the reset belongs on the very `Text` event this flag makes the Parser
     /// swallow.
     skip_wikilink_text: bool,
+    /// Open inline tags inside the current paragraph: `Some(0)` directly in
+    /// it, `None` outside any paragraph. Display math splits the paragraph
+    /// only at `Some(0)`, where no inline tag would be cut in two.
+    paragraph_depth: Option<usize>,
 }
 
 /// rw's markdown dialect: the cmark features every render enables, plus
@@ -172,6 +183,24 @@ fn cmark_options(wikilinks: bool) -> Options {
     opts
 }
 
+/// Dialect switches for [`Parser::new_ext`], all off by default.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// `[[target]]` syntax; with it off, cmark leaves the brackets as literal
+    /// text.
+    pub wikilinks: bool,
+    /// rw's `:name` / `::name` / `:::name` syntax; with it off, those stay
+    /// prose.
+    pub directives: bool,
+    /// cmark's smart punctuation: straight quotes curl, `--` and `---` become
+    /// en and em dashes, `...` an ellipsis. Code spans and code blocks keep
+    /// their text, and directive attributes accept the curled quotes.
+    pub smart_punctuation: bool,
+    /// cmark's `$…$` / `$$…$$` math; with it off, dollar signs stay prose.
+    pub math: bool,
+}
+
 impl<'a> Parser<'a> {
     /// Tokenize `markdown`.
     ///
@@ -190,9 +219,43 @@ impl<'a> Parser<'a> {
     /// ```
     #[must_use]
     pub fn new(markdown: &'a str, wikilinks: bool, directives: bool) -> Self {
+        Self::new_ext(
+            markdown,
+            ParseOptions {
+                wikilinks,
+                directives,
+                ..ParseOptions::default()
+            },
+        )
+    }
+
+    /// Tokenize `markdown` with the dialect switches in `options`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rw_parser::{Event, ParseOptions, Parser, Tag};
+    ///
+    /// let options = ParseOptions {
+    ///     smart_punctuation: true,
+    ///     ..ParseOptions::default()
+    /// };
+    /// let mut parser = Parser::new_ext("\"hi\"", options);
+    /// assert_eq!(parser.next(), Some(Event::Start(Tag::Paragraph)));
+    /// assert_eq!(parser.next(), Some(Event::Text("“hi”".into())));
+    /// ```
+    #[must_use]
+    pub fn new_ext(markdown: &'a str, options: ParseOptions) -> Self {
+        let mut opts = cmark_options(options.wikilinks);
+        if options.smart_punctuation {
+            opts |= Options::ENABLE_SMART_PUNCTUATION;
+        }
+        if options.math {
+            opts |= Options::ENABLE_MATH;
+        }
         Self {
-            inner: cmark::Parser::new_ext(markdown, cmark_options(wikilinks)),
-            directives,
+            inner: cmark::Parser::new_ext(markdown, opts),
+            directives: options.directives,
             run: String::new(),
             run_cursor: 0,
             pending: None,
@@ -200,6 +263,7 @@ impl<'a> Parser<'a> {
             code_block: None,
             in_metadata: false,
             skip_wikilink_text: false,
+            paragraph_depth: None,
         }
     }
 
@@ -237,6 +301,10 @@ impl<'a> Parser<'a> {
                     self.deferred = Deferred::Draining(event);
                     return Some(Event::Start(Tag::Paragraph));
                 }
+                Deferred::SplittingParagraph(event) => {
+                    self.deferred = Deferred::ReopenedParagraph;
+                    return Some(event);
+                }
                 other => self.deferred = other,
             }
 
@@ -383,6 +451,28 @@ impl<'a> Parser<'a> {
             return None;
         }
 
+        if matches!(event, Event::DisplayMath(_)) && self.paragraph_depth == Some(0) {
+            return self.split_paragraph(event);
+        }
+
+        if matches!(self.deferred, Deferred::ReopenedParagraph) {
+            self.trim_reopened_run();
+            if self.run.is_empty() {
+                match event {
+                    Event::SoftBreak | Event::HardBreak => return None,
+                    Event::End(TagEnd::Paragraph) => {
+                        self.deferred = Deferred::None;
+                        return None;
+                    }
+                    _ => {}
+                }
+            }
+            // Released as it stands, even at `End(Paragraph)`: the rest of a
+            // split paragraph is prose, never a block directive.
+            self.deferred = Deferred::ReleasingParagraph(event);
+            return None;
+        }
+
         if matches!(self.deferred, Deferred::Paragraph) {
             if matches!(event, Event::End(TagEnd::Paragraph)) {
                 return Some(self.decide_block());
@@ -406,6 +496,45 @@ impl<'a> Parser<'a> {
         }
     }
 
+    /// Route display math that sits directly in a paragraph: close the
+    /// paragraph before it, and hold a new `<p>` for the prose after it.
+    ///
+    /// Display math renders as a block, which a `<p>` cannot contain. Nothing
+    /// empty is emitted: math that opens a paragraph is preceded by no
+    /// `Start(Paragraph)`, and math that ends one is followed by no
+    /// `End(Paragraph)`.
+    fn split_paragraph(&mut self, math: Event<'a>) -> Option<Event<'a>> {
+        if matches!(
+            self.deferred,
+            Deferred::Paragraph | Deferred::ReopenedParagraph
+        ) {
+            if self.run.trim().is_empty() {
+                // Nothing before the math but whitespace: no `<p>` is owed,
+                // and the held one now waits for what follows.
+                self.run.clear();
+                self.deferred = Deferred::ReopenedParagraph;
+                return Some(math);
+            }
+            // Release the `<p>` and its run; once drained, the math comes
+            // back here with the paragraph open.
+            self.deferred = Deferred::ReleasingParagraph(math);
+            return None;
+        }
+        if !self.run.is_empty() {
+            self.deferred = Deferred::Draining(math);
+            return None;
+        }
+        self.deferred = Deferred::SplittingParagraph(math);
+        Some(Event::End(TagEnd::Paragraph))
+    }
+
+    /// Drop the whitespace a reopened paragraph starts with: it separated the
+    /// prose from the display math before it.
+    fn trim_reopened_run(&mut self) {
+        let leading = self.run.len() - self.run.trim_start().len();
+        self.run.drain(..leading);
+    }
+
     /// Decide, at `End(Paragraph)`, whether the coalesced run is a block
     /// directive or an ordinary paragraph.
     ///
@@ -487,6 +616,14 @@ impl<'a> Parser<'a> {
             return self.accumulate_code_block(event);
         }
 
+        match (&event, &mut self.paragraph_depth) {
+            (cmark::Event::Start(cmark::Tag::Paragraph), depth) => *depth = Some(0),
+            (cmark::Event::End(cmark::TagEnd::Paragraph), depth) => *depth = None,
+            (cmark::Event::Start(_), Some(depth)) => *depth += 1,
+            (cmark::Event::End(_), Some(depth)) => *depth = depth.saturating_sub(1),
+            _ => {}
+        }
+
         match event {
             cmark::Event::Start(tag) => self.start_tag(tag),
             cmark::Event::End(tag) => Self::end_tag(tag).map(Event::End),
@@ -505,15 +642,15 @@ impl<'a> Parser<'a> {
             cmark::Event::HardBreak => Some(Event::HardBreak),
             cmark::Event::Rule => Some(Event::Rule),
             cmark::Event::TaskListMarker(checked) => Some(Event::TaskListMarker(checked)),
-            cmark::Event::FootnoteReference(_)
-            | cmark::Event::InlineMath(_)
-            | cmark::Event::DisplayMath(_) => {
-                // `cmark_options` enables neither `ENABLE_FOOTNOTES` nor
-                // `ENABLE_MATH`, so cmark cannot emit these. Verified against
-                // a document containing all three syntaxes.
+            cmark::Event::InlineMath(tex) => Some(Event::InlineMath(tex)),
+            cmark::Event::DisplayMath(tex) => Some(Event::DisplayMath(tex)),
+            cmark::Event::FootnoteReference(_) => {
+                // `cmark_options` does not enable `ENABLE_FOOTNOTES`, so cmark
+                // cannot emit this. Verified against a document using the
+                // syntax.
                 debug_assert!(
                     false,
-                    "cmark emitted a footnote/math event, which rw's parser options never enable"
+                    "cmark emitted a footnote event, which rw's parser options never enable"
                 );
                 None
             }
@@ -745,6 +882,7 @@ mod tests {
         assert!(opts.contains(Options::ENABLE_TASKLISTS));
         assert!(opts.contains(Options::ENABLE_GFM));
         assert!(opts.contains(Options::ENABLE_YAML_STYLE_METADATA_BLOCKS));
+        assert!(!opts.contains(Options::ENABLE_MATH));
         assert!(!opts.contains(Options::ENABLE_WIKILINKS));
     }
 
@@ -808,6 +946,113 @@ mod tests {
         assert!(stream.iter().any(|e| e.contains("body")));
     }
 
+    #[test]
+    fn math_arrives_as_its_own_events_outside_code() {
+        let markdown = "Euler: $e^{i\\pi} + 1 = 0$ and $$\\sum_i x_i$$\n\n\
+                        `$not math$`\n\n```\n$also not$\n```\n\nCosts $5 and $10.\n";
+        let options = ParseOptions {
+            math: true,
+            ..ParseOptions::default()
+        };
+        let mut parser = Parser::new_ext(markdown, options);
+        let mut math = Vec::new();
+        let mut stream = Vec::new();
+        while let Some(event) = parser.next() {
+            match &event {
+                Event::InlineMath(tex) => math.push(format!("inline {tex}")),
+                Event::DisplayMath(tex) => math.push(format!("display {tex}")),
+                _ => {}
+            }
+            stream.push(format!("{event:?}"));
+        }
+        assert_eq!(math, ["inline e^{i\\pi} + 1 = 0", "display \\sum_i x_i"]);
+
+        assert!(
+            stream
+                .iter()
+                .any(|e| e.contains("Code(") && e.contains("$not math$"))
+        );
+        assert!(stream.iter().any(|e| e.contains("$also not$")));
+        assert!(
+            stream.iter().any(|e| e.contains("Costs $5 and $10.")),
+            "{stream:?}"
+        );
+    }
+
+    fn math_stream(markdown: &str) -> Vec<String> {
+        let options = ParseOptions {
+            directives: true,
+            math: true,
+            ..ParseOptions::default()
+        };
+        let mut parser = Parser::new_ext(markdown, options);
+        let mut out = Vec::new();
+        while let Some(event) = parser.next() {
+            out.push(format!("{event:?}"));
+        }
+        out
+    }
+
+    #[test]
+    fn display_math_splits_its_paragraph() {
+        assert_eq!(
+            math_stream("Let $a$ and $$x$$ hold.\n"),
+            [
+                "Start(Paragraph)",
+                "Text(Borrowed(\"Let \"))",
+                "InlineMath(Borrowed(\"a\"))",
+                "Text(Borrowed(\" and \"))",
+                "End(Paragraph)",
+                "DisplayMath(Borrowed(\"x\"))",
+                "Start(Paragraph)",
+                "Text(Borrowed(\"hold.\"))",
+                "End(Paragraph)",
+            ]
+        );
+    }
+
+    #[test]
+    fn display_math_alone_emits_no_empty_paragraphs() {
+        let stream = math_stream("$$\nx\n$$\n$$y$$\n");
+        assert_eq!(stream.len(), 2, "{stream:?}");
+        assert!(
+            stream.iter().all(|e| e.starts_with("DisplayMath(")),
+            "{stream:?}"
+        );
+    }
+
+    #[test]
+    fn display_math_inside_inline_markup_stays_put() {
+        let stream = math_stream("*$$x$$*\n");
+        assert_eq!(stream.first().map(String::as_str), Some("Start(Paragraph)"));
+        assert_eq!(stream.last().map(String::as_str), Some("End(Paragraph)"));
+        assert_eq!(
+            stream.iter().filter(|e| e.contains("Paragraph")).count(),
+            2,
+            "{stream:?}"
+        );
+    }
+
+    #[test]
+    fn prose_after_display_math_is_never_a_directive() {
+        let stream = math_stream("$$x$$\n:::note\n");
+        assert!(
+            !stream.iter().any(|e| e.contains("Container")),
+            "{stream:?}"
+        );
+        assert!(stream.iter().any(|e| e.contains(":::note")), "{stream:?}");
+    }
+
+    #[test]
+    fn dollars_stay_prose_with_math_off() {
+        let stream = debug_stream("Solve $x^2$ for $$y$$.\n");
+        assert!(!stream.iter().any(|e| e.contains("Math(")), "{stream:?}");
+        assert!(
+            stream.iter().any(|e| e.contains("Solve $x^2$ for $$y$$.")),
+            "{stream:?}"
+        );
+    }
+
     #[test]
     fn structure_translates_to_rw_tags() {
         let mut parser = Parser::new("# H\n", false, true);
@@ -1023,6 +1268,35 @@ mod tests {
         assert_eq!(seen.as_deref(), Some(raw), "raw must be byte-exact");
     }
 
+    #[test]
+    fn smart_punctuation_curls_prose_but_not_code_or_directive_values() {
+        let options = ParseOptions {
+            directives: true,
+            smart_punctuation: true,
+            ..ParseOptions::default()
+        };
+        let mut parser = Parser::new_ext(
+            "It's -- \"done\"... `a--b` :badge[x]{title=\"two words\"}",
+            options,
+        );
+        let mut text = String::new();
+        let mut code = None;
+        let mut title = None;
+        while let Some(event) = parser.next() {
+            match event {
+                Event::Text(t) => text.push_str(&t),
+                Event::Code(c) => code = Some(c.to_string()),
+                Event::InlineDirective(payload) => {
+                    title = payload.args.get("title").map(str::to_owned);
+                }
+                _ => {}
+            }
+        }
+        assert_eq!(text, "It’s – “done”…  ");
+        assert_eq!(code.as_deref(), Some("a--b"));
+        assert_eq!(title.as_deref(), Some("two words"));
+    }
+
     #[test]
     fn a_document_ending_mid_text_yields_its_final_text_before_none() {
         // No finish hook exists any more, so a document whose last bytes are
@@ -0,0 +1,183 @@
+//! Admonitions: `:::note`, `:::warning` and friends as callout containers.
+//!
+//! Each recognized kind wraps its content in
+//! `<div class="admonition admonition-{kind}">` with a title paragraph. The
+//! title is the directive's `[content]` when given (`:::warning[Careful!]`),
+//! else the kind, capitalized. Content between the delimiters renders as
+//! ordinary markdown, other directives included.
+
+use crate::directive::{ContainerDirective, DirectiveArgs, DirectiveContext, DirectiveOutput};
+use crate::util::escape_html;
+
+/// Kinds recognized by [`AdmonitionDirective::new`].
+pub const DEFAULT_ADMONITION_KINDS: [&str; 4] = ["note", "tip", "warning", "danger"];
+
+/// Admonition container directive for a configurable set of kinds.
+///
+/// # Example
+///
+/// ```
+/// use rw_renderer::{AdmonitionDirective, HtmlBackend, MarkdownRenderer, Pipeline};
+/// use rw_renderer::directive::DirectiveProcessor;
+///
+/// let directives = DirectiveProcessor::new()
+///     .with_container(AdmonitionDirective::new().with_kind("info"));
+/// let md = ":::warning[Careful!]\n\nMind the **gap**.\n\n:::";
+/// let result = MarkdownRenderer::<HtmlBackend>::new()
+///     .render(md, Pipeline::new().with_directives(directives));
+/// assert!(result.html.contains(r#"<div class="admonition admonition-warning">"#));
+/// assert!(result.html.contains("<strong>gap</strong>"));
+/// ```
+pub struct AdmonitionDirective {
+    kinds: Vec<String>,
+}
+
+impl AdmonitionDirective {
+    /// Create a handler for [`DEFAULT_ADMONITION_KINDS`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            kinds: DEFAULT_ADMONITION_KINDS.map(str::to_owned).to_vec(),
+        }
+    }
+
+    /// Also recognize `:::kind`, styled through `admonition-{kind}`.
+    #[must_use]
+    pub fn with_kind(mut self, kind: impl Into<String>) -> Self {
+        let kind = kind.into();
+        if !self.kinds.contains(&kind) {
+            self.kinds.push(kind);
+        }
+        self
+    }
+}
+
+impl Default for AdmonitionDirective {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ContainerDirective for AdmonitionDirective {
+    fn name(&self) -> &'static str {
+        "note"
+    }
+
+    fn matches(&self, name: &str) -> bool {
+        self.kinds.iter().any(|kind| kind == name)
+    }
+
+    fn start(&mut self, args: DirectiveArgs, ctx: &DirectiveContext) -> DirectiveOutput {
+        self.start_named("note", args, ctx)
+    }
+
+    fn start_named(
+        &mut self,
+        name: &str,
+        args: DirectiveArgs,
+        _ctx: &DirectiveContext,
+    ) -> DirectiveOutput {
+        let title = if args.content().is_empty() {
+            capitalize(name)
+        } else {
+            args.content().to_owned()
+        };
+        DirectiveOutput::html(format!(
+            r#"<div class="admonition admonition-{}"><p class="admonition-title">{}</p>"#,
+            escape_html(name),
+            escape_html(&title)
+        ))
+    }
+
+    fn end(&mut self, _line: usize) -> Option<String> {
+        Some("</div>".to_owned())
+    }
+}
+
+/// `name` with its first character uppercased.
+fn capitalize(name: &str) -> String {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::directive::DirectiveProcessor;
+    use crate::{HtmlBackend, MarkdownRenderer, Pipeline, TabsDirective};
+
+    fn render(markdown: &str, directive: AdmonitionDirective) -> String {
+        let directives = DirectiveProcessor::new()
+            .with_container(directive)
+            .with_container(TabsDirective::new());
+        MarkdownRenderer::<HtmlBackend>::new()
+            .render(markdown, Pipeline::new().with_directives(directives))
+            .html
+    }
+
+    #[test]
+    fn default_title_is_the_capitalized_kind() {
+        let html = render(
+            ":::tip\n\nUse `rw serve`.\n\n:::",
+            AdmonitionDirective::new(),
+        );
+
+        assert_eq!(
+            html,
+            "<div class=\"admonition admonition-tip\"><p class=\"admonition-title\">Tip</p>\
+             <p>Use <code>rw serve</code>.</p></div>"
+        );
+    }
+
+    #[test]
+    fn content_sets_an_escaped_title() {
+        let html = render(
+            ":::danger[Don't run \"rm\" if x > 0 & y]\n\nBody.\n\n:::",
+            AdmonitionDirective::new(),
+        );
+
+        assert!(
+            html.contains(
+                r#"<p class="admonition-title">Don&#x27;t run &quot;rm&quot; if x &gt; 0 &amp; y</p>"#
+            ),
+            "{html}"
+        );
+    }
+
+    #[test]
+    fn nests_inside_and_around_other_containers() {
+        let md = "::::note\n\n:::warning\n\nInner.\n\n:::\n\n::::\n\n\
+                  ::::tabs\n\n:::tab[A]\n\n:::tip\n\nIn a tab.\n\n:::\n\n:::\n\n::::";
+        let html = render(md, AdmonitionDirective::new());
+
+        assert!(
+            html.contains(
+                "<div class=\"admonition admonition-note\"><p class=\"admonition-title\">Note</p>\
+                 <div class=\"admonition admonition-warning\"><p class=\"admonition-title\">Warning</p>\
+                 <p>Inner.</p></div></div>"
+            ),
+            "{html}"
+        );
+        assert!(
+            html.contains(
+                "<div class=\"admonition admonition-tip\"><p class=\"admonition-title\">Tip</p>\
+                 <p>In a tab.</p></div>"
+            ),
+            "{html}"
+        );
+    }
+
+    #[test]
+    fn custom_kinds_are_opt_in() {
+        let md = ":::info\n\nFYI.\n\n:::";
+
+        assert!(!render(md, AdmonitionDirective::new()).contains("admonition"));
+        assert!(
+            render(md, AdmonitionDirective::new().with_kind("info"))
+                .contains(r#"<div class="admonition admonition-info">"#)
+        );
+    }
+}
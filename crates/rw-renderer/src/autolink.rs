@@ -0,0 +1,224 @@
+//! Bare URL and email detection for prose text, after GFM's extended
+//! autolinks.
+//!
+//! `https://…`, `http://…` and `www.…` run to the next whitespace or `<`,
+//! less trailing punctuation: `see https://x.com.` links `https://x.com`, and a
+//! closing `)` is kept only when it balances an opening one inside the URL.
+//! Emails are `local@domain` with a dotted domain whose last label has a
+//! letter, so package specs like `serde@1.0.2` stay text.
+//!
+//! The walker only feeds this text outside links and image alt text; code
+//! spans and code blocks never reach it.
+
+use std::ops::Range;
+
+/// A URL or email found in a text run.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct Autolink {
+    /// Byte range of the link text within the searched string.
+    pub(crate) range: Range<usize>,
+    /// Link target: the text itself, with `http://` before a `www.` URL or
+    /// `mailto:` before an email.
+    pub(crate) href: String,
+}
+
+/// The first URL or email in `text`.
+pub(crate) fn find(text: &str) -> Option<Autolink> {
+    match (find_url(text), find_email(text)) {
+        (Some(url), Some(email)) if email.range.start < url.range.start => Some(email),
+        (Some(url), _) => Some(url),
+        (None, email) => email,
+    }
+}
+
+fn find_url(text: &str) -> Option<Autolink> {
+    for (start, _) in text.match_indices(['h', 'w']) {
+        if !starts_word(text, start) {
+            continue;
+        }
+        let rest = &text[start..];
+        let (scheme_len, href_prefix) = if rest.starts_with("https://") {
+            ("https://".len(), "")
+        } else if rest.starts_with("http://") {
+            ("http://".len(), "")
+        } else if rest.starts_with("www.") {
+            (0, "http://")
+        } else {
+            continue;
+        };
+
+        let len = rest
+            .find(|c: char| c.is_whitespace() || c == '<')
+            .unwrap_or(rest.len());
+        let url = trim_trailing_punctuation(&rest[..len]);
+        let authority = url[scheme_len..]
+            .split(['/', '?', '#'])
+            .next()
+            .unwrap_or_default();
+        let host = authority
+            .rsplit('@')
+            .next()
+            .and_then(|host| host.split(':').next())
+            .unwrap_or_default();
+        // `http://localhost:8080` is a URL; a bare `www` is not a domain.
+        let dotted = !href_prefix.is_empty();
+        if !is_host(host) || (dotted && !host.trim_start_matches("www.").contains('.')) {
+            continue;
+        }
+
+        return Some(Autolink {
+            range: start..start + url.len(),
+            href: format!("{href_prefix}{url}"),
+        });
+    }
+    None
+}
+
+fn find_email(text: &str) -> Option<Autolink> {
+    for (at, _) in text.match_indices('@') {
+        let start = text[..at]
+            .char_indices()
+            .rev()
+            .find(|&(_, c)| !is_local_char(c))
+            .map_or(0, |(i, c)| i + c.len_utf8());
+        if start == at {
+            continue;
+        }
+
+        let domain = &text[at + 1..];
+        let len = domain
+            .find(|c: char| !is_domain_char(c))
+            .unwrap_or(domain.len());
+        let domain = domain[..len].trim_end_matches('.');
+        let labels_ok = domain.split('.').all(|label| !label.is_empty());
+        let has_tld = domain
+            .rsplit_once('.')
+            .is_some_and(|(_, tld)| tld.chars().any(|c| c.is_ascii_alphabetic()));
+        if !labels_ok || !has_tld || domain.ends_with(['-', '_']) {
+            continue;
+        }
+
+        let end = at + 1 + domain.len();
+        return Some(Autolink {
+            range: start..end,
+            href: format!("mailto:{}", &text[start..end]),
+        });
+    }
+    None
+}
+
+/// Whether a link may start at byte `i`: not glued to a preceding word.
+fn starts_word(text: &str, i: usize) -> bool {
+    text[..i]
+        .chars()
+        .next_back()
+        .is_none_or(|c| c.is_whitespace() || "(*_~\"'“‘".contains(c))
+}
+
+/// `url` less the punctuation that more likely ends the sentence around it.
+fn trim_trailing_punctuation(mut url: &str) -> &str {
+    loop {
+        match url.chars().next_back() {
+            Some(
+                c @ ('?' | '!' | '.' | ',' | ':' | ';' | '*' | '_' | '~' | '"' | '\'' | '”' | '’'),
+            ) => {
+                url = &url[..url.len() - c.len_utf8()];
+            }
+            Some(')') if url.matches('(').count() < url.matches(')').count() => {
+                url = &url[..url.len() - 1];
+            }
+            _ => return url,
+        }
+    }
+}
+
+fn is_host(host: &str) -> bool {
+    !host.is_empty()
+        && host
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+fn is_local_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '.' | '+' | '-' | '_')
+}
+
+fn is_domain_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn link(text: &str) -> Option<(&str, String)> {
+        find(text).map(|link| (&text[link.range], link.href))
+    }
+
+    #[test]
+    fn url_stops_before_trailing_punctuation() {
+        assert_eq!(
+            link("see https://x.com."),
+            Some(("https://x.com", "https://x.com".to_owned()))
+        );
+        assert_eq!(
+            link("(docs at https://x.com/a?b=1), then"),
+            Some(("https://x.com/a?b=1", "https://x.com/a?b=1".to_owned()))
+        );
+        assert_eq!(
+            link("https://en.wikipedia.org/wiki/Rust_(language)!"),
+            Some((
+                "https://en.wikipedia.org/wiki/Rust_(language)",
+                "https://en.wikipedia.org/wiki/Rust_(language)".to_owned()
+            ))
+        );
+    }
+
+    #[test]
+    fn www_gets_a_scheme() {
+        assert_eq!(
+            link("visit www.example.com/docs"),
+            Some((
+                "www.example.com/docs",
+                "http://www.example.com/docs".to_owned()
+            ))
+        );
+        assert_eq!(link("the www. prefix"), None);
+    }
+
+    #[test]
+    fn url_needs_a_host_and_a_word_boundary() {
+        assert_eq!(link("https:// is a scheme"), None);
+        assert_eq!(link("xhttps://x.com"), None);
+        assert_eq!(
+            link("run http://localhost:8080"),
+            Some(("http://localhost:8080", "http://localhost:8080".to_owned()))
+        );
+    }
+
+    #[test]
+    fn email() {
+        assert_eq!(
+            link("mail ops.team+rw@example.co.uk."),
+            Some((
+                "ops.team+rw@example.co.uk",
+                "mailto:ops.team+rw@example.co.uk".to_owned()
+            ))
+        );
+        assert_eq!(link("cargo add serde@1.0.2"), None);
+        assert_eq!(link("user@localhost"), None);
+        assert_eq!(link("@scope/pkg"), None);
+    }
+
+    #[test]
+    fn first_match_wins() {
+        assert_eq!(
+            link("a@b.io or https://x.com"),
+            Some(("a@b.io", "mailto:a@b.io".to_owned()))
+        );
+        assert_eq!(
+            link("https://user@x.com/ and a@b.io"),
+            Some(("https://user@x.com/", "https://user@x.com/".to_owned()))
+        );
+    }
+}
@@ -45,6 +45,22 @@ pub trait RenderBackend {
     /// `"rust"`, `"python"`), or `None` for plain code blocks.
     fn code_block(lang: Option<&str>, content: &str, out: &mut String);
 
+    /// Writes a fenced code block with per-line structure, when
+    /// [`with_code_block_lines`](crate::MarkdownRenderer::with_code_block_lines)
+    /// is on. The default writes a plain [`code_block`](Self::code_block).
+    fn code_block_with_lines(lang: Option<&str>, content: &str, out: &mut String) {
+        Self::code_block(lang, content, out);
+    }
+
+    /// Writes a fenced code block that
+    /// [`with_syntax_highlighting`](crate::MarkdownRenderer::with_syntax_highlighting)
+    /// highlighted as `_html`, a complete `<pre>` block. The default ignores
+    /// it and writes a plain [`code_block`](Self::code_block).
+    #[cfg(feature = "syntax-highlighting")]
+    fn highlighted_code_block(lang: &str, content: &str, _html: &str, out: &mut String) {
+        Self::code_block(Some(lang), content, out);
+    }
+
     /// Writes the opening tag for a blockquote.
     fn blockquote_start(out: &mut String);
 
@@ -108,6 +124,13 @@ pub trait RenderBackend {
         write!(out, r#"<h{level} id="{id}">"#).unwrap();
     }
 
+    /// Writes a link to the heading's own id, after its content and before
+    /// [`heading_end`](Self::heading_end), when
+    /// [`with_heading_anchors`](crate::MarkdownRenderer::with_heading_anchors)
+    /// is on. `title` is the heading's plain text, for an accessible label.
+    /// Default is a no-op, for backends without in-page links.
+    fn heading_anchor(_id: &str, _title: &str, _out: &mut String) {}
+
     /// Writes a heading closing tag.
     fn heading_end(level: u8, out: &mut String) {
         write!(out, "</h{level}>").unwrap();
@@ -282,6 +305,24 @@ pub trait RenderBackend {
         out.push_str("</code>");
     }
 
+    /// Writes `$…$` math, or with `display`, a `$$…$$` block.
+    ///
+    /// The default leaves typesetting to the browser: the escaped TeX goes in
+    /// a `<span class="math inline">` or a `<div class="math display">` for a
+    /// client-side `KaTeX` or `MathJax` pass. Display math arrives between
+    /// paragraphs, the parser having closed the one it sat in.
+    fn math(tex: &str, display: bool, out: &mut String) {
+        if display {
+            out.push_str(r#"<div class="math display">"#);
+            escape_into(tex, out);
+            out.push_str("</div>");
+        } else {
+            out.push_str(r#"<span class="math inline">"#);
+            escape_into(tex, out);
+            out.push_str("</span>");
+        }
+    }
+
     /// Writes the opening tag for a link.
     ///
     /// `section_ref` contains `(ref_string, section_path)` for cross-section
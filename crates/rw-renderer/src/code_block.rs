@@ -64,6 +64,7 @@
 //! ```
 
 use std::collections::BTreeSet;
+use std::time::Duration;
 
 use crate::directive::Fills;
 use rw_parser::FenceAttrs;
@@ -89,6 +90,25 @@ pub enum ProcessResult {
     Deferred,
 }
 
+/// How one code block's output was produced, reported by a processor through
+/// [`CodeBlockProcessor::stats`] (e.g. per-diagram render timings).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeBlockStats {
+    /// Zero-based index of the code block in the document, as passed to
+    /// [`CodeBlockProcessor::process`].
+    pub index: usize,
+    /// Language of the block as the processor names it (e.g., `"plantuml"`).
+    pub language: String,
+    /// `true` when the output came from the processor's cache rather than a
+    /// fresh render.
+    pub from_cache: bool,
+    /// Time spent producing the output: the cache lookup for a hit, the render
+    /// call for a miss.
+    pub duration: Duration,
+    /// Size of the produced output in bytes (e.g. SVG markup, PNG data).
+    pub bytes: usize,
+}
+
 /// Metadata extracted from code block for deferred processing.
 #[derive(Debug, PartialEq, Eq)]
 pub struct ExtractedCodeBlock {
@@ -214,6 +234,16 @@ pub trait CodeBlockProcessor: Send + Sync {
         false
     }
 
+    /// Per-block statistics for the output this processor produced, collected
+    /// in [`fills`](Self::fills). Collected by the renderer into
+    /// [`RenderResult::code_block_stats`](crate::RenderResult::code_block_stats).
+    ///
+    /// Only blocks that produced output are reported; failures surface through
+    /// [`warnings`](Self::warnings). Default implementation returns empty slice.
+    fn stats(&self) -> &[CodeBlockStats] {
+        &[]
+    }
+
     /// Canonical section refs (`"kind:namespace/name"`) this processor's output
     /// referenced (e.g. diagram `$link`s resolved to sections), collected in
     /// [`fills`](Self::fills). Collected by the renderer into
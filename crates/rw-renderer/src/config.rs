@@ -8,8 +8,13 @@
 
 use std::sync::Arc;
 
+use rw_parser::ParseOptions;
 use rw_sections::Sections;
 
+/// Custom heading slug function, set by
+/// [`with_slugify`](crate::MarkdownRenderer::with_slugify).
+pub(crate) type SlugifyFn = dyn Fn(&str) -> String + Send + Sync;
+
 /// Resolves page paths to their display titles for wikilink rendering.
 ///
 /// When a wikilink like `[[domain:billing::overview]]` has no explicit display
@@ -48,6 +53,39 @@ pub trait TitleResolver: Send + Sync {
     fn resolve_title(&self, path: &str) -> Option<String>;
 }
 
+/// Tells the renderer whether a link target is a known page.
+///
+/// Used to flag plain markdown links (`[text](../other.md)`) whose target
+/// does not exist. Each such link adds a warning to
+/// [`RenderResult::warnings`](crate::RenderResult::warnings); the link itself
+/// still renders normally.
+///
+/// # Examples
+///
+/// ```
+/// use rw_renderer::LinkChecker;
+///
+/// struct KnownPages(std::collections::HashSet<String>);
+///
+/// impl LinkChecker for KnownPages {
+///     fn page_exists(&self, path: &str) -> bool {
+///         self.0.contains(path)
+///     }
+/// }
+///
+/// let checker = KnownPages(["guide".to_owned()].into());
+///
+/// assert!(checker.page_exists("guide"));
+/// assert!(!checker.page_exists("missing"));
+/// ```
+pub trait LinkChecker: Send + Sync {
+    /// Returns `true` if a page exists at `path`.
+    ///
+    /// `path` is an absolute URL path without leading slash or fragment
+    /// (e.g., `"domains/billing/overview"`, `""` for the root page).
+    fn page_exists(&self, path: &str) -> bool;
+}
+
 /// Configuration for [`MarkdownRenderer`](crate::MarkdownRenderer).
 ///
 /// Built up via the renderer's `with_*` builders and read by both the
@@ -72,25 +110,68 @@ pub(crate) struct RenderConfig {
     pub(crate) is_dir: bool,
     /// `[[wikilink]]` parsing enabled.
     pub(crate) wikilinks: bool,
+    /// Link bare URLs and emails in prose.
+    pub(crate) autolinks: bool,
+    /// Typographic quotes, dashes and ellipses in prose.
+    pub(crate) smart_punctuation: bool,
+    /// Parse `$…$` / `$$…$$` as math.
+    pub(crate) math: bool,
     /// Extract title from first H1.
     pub(crate) extract_title: bool,
+    /// Skip the title H1 and shift later headings up, whatever the backend's
+    /// [`TITLE_AS_METADATA`](crate::RenderBackend::TITLE_AS_METADATA).
+    pub(crate) suppress_title_heading: bool,
+    /// Heading slug function; `None` uses the built-in kebab-case slugs.
+    pub(crate) slugify: Option<Arc<SlugifyFn>>,
+    /// Deepest heading level collected into the TOC; `None` collects all.
+    pub(crate) toc_max_depth: Option<u8>,
+    /// Highlighter for fenced code blocks in a known language.
+    #[cfg(feature = "syntax-highlighting")]
+    pub(crate) highlighter: Option<crate::SyntaxHighlighter>,
+    /// Render code blocks through `code_block_with_lines`.
+    pub(crate) code_block_lines: bool,
+    /// Emit a `#` link to each heading's id after its text.
+    pub(crate) heading_anchors: bool,
     /// Section registry for wikilink resolution and link annotation.
     pub(crate) sections: Option<Arc<Sections>>,
     /// Title resolver for wikilink display text.
     pub(crate) title_resolver: Option<Box<dyn TitleResolver>>,
+    /// Page-existence check for plain markdown links.
+    pub(crate) link_checker: Option<Box<dyn LinkChecker>>,
 }
 
 impl RenderConfig {
-    /// Defaults: no wikilinks, no title extraction.
+    /// Defaults: no wikilinks, no title extraction, bare URLs autolinked.
     pub(crate) fn new() -> Self {
         Self {
             base_path: None,
             origin_prefix: None,
             is_dir: true,
             wikilinks: false,
+            autolinks: true,
+            smart_punctuation: false,
+            math: false,
             extract_title: false,
+            suppress_title_heading: false,
+            slugify: None,
+            toc_max_depth: None,
+            heading_anchors: false,
+            code_block_lines: false,
+            #[cfg(feature = "syntax-highlighting")]
+            highlighter: None,
             sections: None,
             title_resolver: None,
+            link_checker: None,
+        }
+    }
+
+    /// Tokenizer switches for a render of this configuration.
+    pub(crate) fn parse_options(&self, directives: bool) -> ParseOptions {
+        ParseOptions {
+            wikilinks: self.wikilinks,
+            directives,
+            smart_punctuation: self.smart_punctuation,
+            math: self.math,
         }
     }
 }
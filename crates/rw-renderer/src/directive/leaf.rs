@@ -12,7 +12,8 @@ use super::{DirectiveArgs, DirectiveContext, DirectiveOutput, Fills};
 /// shares a paragraph with other text, or one indented into a code block, is
 /// treated as literal text and left to the markdown parser.
 ///
-/// They return HTML (for `::youtube`), a semantic marker, or deferred content.
+/// They return HTML (for `::youtube`), a semantic marker, deferred content, or
+/// markdown to render in their place (for `::include`).
 ///
 /// # Deferred Content
 ///
@@ -60,9 +61,19 @@ pub trait LeafDirective: Send {
     /// - [`DirectiveOutput::Html`] for HTML output that passes through pulldown-cmark
     /// - [`DirectiveOutput::Deferred`] for content that is not known during the
     ///   walk — it reserves holes that [`fills`](Self::fills) supplies afterwards
+    /// - [`DirectiveOutput::Markdown`] for markdown rendered in the directive's
+    ///   place, followed by a call to [`spliced`](Self::spliced)
     /// - [`DirectiveOutput::Skip`] to pass through unchanged
     fn process(&mut self, args: DirectiveArgs, ctx: &DirectiveContext) -> DirectiveOutput;
 
+    /// Called once the markdown returned as [`DirectiveOutput::Markdown`] has
+    /// been rendered.
+    ///
+    /// Calls nest: a directive inside the spliced markdown is processed, and
+    /// spliced, before the outer call arrives. Handlers that splice
+    /// recursively use this to track how deep they are.
+    fn spliced(&mut self) {}
+
     /// Supply content for holes this directive reserved during the walk.
     ///
     /// Called once, after the walk completes, before assembly. Override when
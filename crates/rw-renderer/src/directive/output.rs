@@ -6,13 +6,15 @@ use super::Part;
 
 /// Output from directive processing.
 ///
-/// Directives can produce three kinds of output:
+/// Directives can produce four kinds of output:
 ///
 /// - [`Html`](Self::Html): a single HTML blob passed verbatim to the backend's `raw_html`.
 /// - [`Deferred`](Self::Deferred): literal HTML interleaved with holes the handler fills after
 ///   the walk, via [`fills`](super::ContainerDirective::fills). Use this when the content depends
 ///   on material the walk has not reached yet — a tab bar needs every tab's label but is emitted
 ///   before the first tab.
+/// - [`Markdown`](Self::Markdown): markdown rendered in place of a leaf directive, as if it had
+///   been written there (`::include`).
 /// - [`Skip`](Self::Skip): the handler declines; the original directive syntax is preserved.
 ///
 /// # Example
@@ -39,6 +41,14 @@ pub enum DirectiveOutput {
     /// not been walked yet — a tab bar needs every tab's label, but is emitted
     /// before the first tab.
     Deferred(Vec<Part>),
+    /// Markdown rendered in place of the directive, as if it had been written
+    /// there — directives in it included.
+    ///
+    /// Honored for leaf directives only, which are told through
+    /// [`LeafDirective::spliced`](super::LeafDirective::spliced) once it has
+    /// been rendered. An inline or container handler returning it is treated
+    /// as [`Skip`](Self::Skip), with a warning.
+    Markdown(String),
     /// Don't handle this directive (pass through unchanged).
     Skip,
 }
@@ -77,6 +87,21 @@ impl DirectiveOutput {
     pub fn deferred(parts: impl Into<Vec<Part>>) -> Self {
         Self::Deferred(parts.into())
     }
+
+    /// Create a markdown output, rendered in place of a leaf directive.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rw_renderer::directive::DirectiveOutput;
+    ///
+    /// let output = DirectiveOutput::markdown("Shared *snippet*.");
+    /// assert!(matches!(output, DirectiveOutput::Markdown(_)));
+    /// ```
+    #[must_use]
+    pub fn markdown(s: impl Into<String>) -> Self {
+        Self::Markdown(s.into())
+    }
 }
 
 #[cfg(test)]
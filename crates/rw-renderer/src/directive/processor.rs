@@ -34,6 +34,10 @@ pub(crate) enum BlockDispatch {
     Deferred { parts: Vec<Part>, source: Source },
     /// Literal text the walker renders as an ordinary paragraph (`<p>…</p>`).
     PassThrough(String),
+    /// Markdown the walker parses and walks in place, then reports back
+    /// through [`DirectiveProcessor::leaf_spliced`] with `leaf`, the index of
+    /// the handler that returned it.
+    Markdown { markdown: String, leaf: usize },
 }
 
 /// One entry per open container scope, recording how the matching closing
@@ -269,6 +273,11 @@ impl DirectiveProcessor {
         self
     }
 
+    /// Whether a registered container handler accepts `name`.
+    pub(crate) fn has_container(&self, name: &str) -> bool {
+        self.container_handlers.iter().any(|h| h.matches(name))
+    }
+
     /// Dispatch a container-directive opener: invoke the registered handler
     /// and return owned [`BlockDispatch`] data for the walker to render.
     /// `ctx.line()` is always `0` — block directives carry no line number (no
@@ -307,7 +316,7 @@ impl DirectiveProcessor {
         let output = self.container_handlers[idx].start_named(name, args, &ctx);
         // A handled opener owns its scope unless it declined (`Skip`, which
         // pushes its own Literal frame below).
-        if !matches!(output, DirectiveOutput::Skip) {
+        if !matches!(output, DirectiveOutput::Skip | DirectiveOutput::Markdown(_)) {
             self.active_containers.push(ContainerFrame::Handled {
                 idx,
                 depth,
@@ -320,7 +329,12 @@ impl DirectiveProcessor {
                 parts,
                 source: Source::Container(idx),
             },
-            DirectiveOutput::Skip => {
+            output @ (DirectiveOutput::Skip | DirectiveOutput::Markdown(_)) => {
+                if matches!(output, DirectiveOutput::Markdown(_)) {
+                    self.warnings.push(format!(
+                        "container directive ':::{name}' returned Markdown; it was ignored (only leaf directives can splice markdown)"
+                    ));
+                }
                 // Handler declined: the opener renders literally, so
                 // track a Literal scope for its matching close.
                 self.active_containers
@@ -375,10 +389,20 @@ impl DirectiveProcessor {
                 parts,
                 source: Source::Leaf(idx),
             },
+            DirectiveOutput::Markdown(markdown) => BlockDispatch::Markdown {
+                markdown,
+                leaf: idx,
+            },
             DirectiveOutput::Skip => BlockDispatch::PassThrough(format!("::{name}{syntax}")),
         }
     }
 
+    /// Tell leaf handler `leaf` that the markdown it returned from
+    /// [`dispatch_leaf`](Self::dispatch_leaf) has been walked.
+    pub(crate) fn leaf_spliced(&mut self, leaf: usize) {
+        self.leaf_handlers[leaf].spliced();
+    }
+
     /// Dispatch an inline directive by name.
     ///
     /// Returns [`DirectiveOutput::Skip`] when no handler is registered for
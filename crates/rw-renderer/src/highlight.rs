@@ -0,0 +1,207 @@
+//! Build-time syntax highlighting for fenced code blocks, behind the
+//! `syntax-highlighting` feature.
+//!
+//! [`SyntaxHighlighter`] tokenizes code with syntect's bundled grammars and
+//! colors it with one of its bundled themes, producing inline-styled spans
+//! that need no stylesheet or script — what static output such as `TechDocs`
+//! wants. Languages syntect does not know render as plain code blocks.
+
+use std::fmt::Write;
+use std::sync::Arc;
+
+use syntect::easy::HighlightLines;
+use syntect::highlighting::{Theme, ThemeSet};
+use syntect::html::{IncludeBackground, styled_line_to_highlighted_html};
+use syntect::parsing::SyntaxSet;
+use syntect::util::LinesWithEndings;
+
+use crate::util::escape_into;
+
+/// Theme used by `rw`'s own static output; a light, GitHub-like palette.
+pub const DEFAULT_SYNTAX_THEME: &str = "InspiredGitHub";
+
+/// A theme name not among [`SyntaxHighlighter::theme_names`].
+#[derive(Debug, thiserror::Error, Clone, PartialEq, Eq)]
+#[error("unknown syntax theme '{name}', expected one of: {}", available.join(", "))]
+pub struct UnknownThemeError {
+    /// The requested name.
+    pub name: String,
+    /// The bundled theme names.
+    pub available: Vec<String>,
+}
+
+/// Grammars and a theme for
+/// [`with_syntax_highlighting`](crate::MarkdownRenderer::with_syntax_highlighting).
+///
+/// Loading the bundled grammars takes a few milliseconds, so build one
+/// highlighter and clone it into each renderer; clones share the loaded data.
+///
+/// # Example
+///
+/// ```
+/// use rw_renderer::{HtmlBackend, MarkdownRenderer, Pipeline, SyntaxHighlighter};
+///
+/// let highlighter = SyntaxHighlighter::new("InspiredGitHub").unwrap();
+/// let result = MarkdownRenderer::<HtmlBackend>::new()
+///     .with_syntax_highlighting(highlighter)
+///     .render("```rust\nfn main() {}\n```", Pipeline::new());
+/// assert!(result.html.contains("<span style=\"color:"));
+/// ```
+#[derive(Clone)]
+pub struct SyntaxHighlighter {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    syntaxes: SyntaxSet,
+    theme: Theme,
+}
+
+impl SyntaxHighlighter {
+    /// Load the bundled grammars and the bundled theme called `theme`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UnknownThemeError`] if no bundled theme has that name.
+    pub fn new(theme: &str) -> Result<Self, UnknownThemeError> {
+        let mut themes = ThemeSet::load_defaults().themes;
+        let Some(theme) = themes.remove(theme) else {
+            return Err(UnknownThemeError {
+                name: theme.to_owned(),
+                available: themes.into_keys().collect(),
+            });
+        };
+        Ok(Self {
+            inner: Arc::new(Inner {
+                syntaxes: SyntaxSet::load_defaults_newlines(),
+                theme,
+            }),
+        })
+    }
+
+    /// Names of the bundled themes [`new`](Self::new) accepts, sorted.
+    #[must_use]
+    pub fn theme_names() -> Vec<String> {
+        ThemeSet::load_defaults().themes.into_keys().collect()
+    }
+
+    /// `code` as a complete highlighted `<pre>` block, or `None` when `lang`
+    /// names no known grammar.
+    ///
+    /// The `<pre>` carries the theme's background color and the `<code>` the
+    /// usual `language-*` class; each token is a `<span style="…">` with its
+    /// text escaped.
+    pub(crate) fn highlight(&self, lang: &str, code: &str) -> Option<String> {
+        let Inner { syntaxes, theme } = &*self.inner;
+        let syntax = syntaxes.find_syntax_by_token(lang)?;
+
+        let mut html = String::with_capacity(code.len() * 4);
+        html.push_str(r#"<pre class="highlight""#);
+        if let Some(bg) = theme.settings.background {
+            write!(
+                html,
+                r#" style="background-color:#{:02x}{:02x}{:02x}""#,
+                bg.r, bg.g, bg.b
+            )
+            .unwrap();
+        }
+        html.push_str(r#"><code class="language-"#);
+        escape_into(lang, &mut html);
+        html.push_str(r#"">"#);
+
+        let mut lines = HighlightLines::new(syntax, theme);
+        for line in LinesWithEndings::from(code) {
+            let regions = lines.highlight_line(line, syntaxes).ok()?;
+            html.push_str(&styled_line_to_highlighted_html(&regions, IncludeBackground::No).ok()?);
+        }
+        html.push_str("</code></pre>");
+        Some(html)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{HtmlBackend, MarkdownRenderer, Pipeline, SearchDocumentBackend};
+
+    fn highlighter() -> SyntaxHighlighter {
+        SyntaxHighlighter::new(DEFAULT_SYNTAX_THEME).unwrap()
+    }
+
+    #[test]
+    fn known_language_renders_styled_spans() {
+        let result = MarkdownRenderer::<HtmlBackend>::new()
+            .with_syntax_highlighting(highlighter())
+            .render("```rust\nlet s = \"<a>\";\n```", Pipeline::new());
+
+        assert!(
+            result
+                .html
+                .starts_with(r#"<pre class="highlight" style="background-color:#"#),
+            "{}",
+            result.html
+        );
+        assert!(
+            result.html.contains(r#"<code class="language-rust">"#),
+            "{}",
+            result.html
+        );
+        assert!(
+            result.html.contains("<span style=\"color:"),
+            "{}",
+            result.html
+        );
+        assert!(result.html.contains("&lt;a&gt;"), "{}", result.html);
+        assert!(!result.html.contains("&amp;lt;"), "{}", result.html);
+    }
+
+    #[test]
+    fn unknown_language_falls_back_to_plain_block() {
+        let markdown = "```nonsense-lang\na < b\n```";
+        let plain = MarkdownRenderer::<HtmlBackend>::new().render(markdown, Pipeline::new());
+        let highlighted = MarkdownRenderer::<HtmlBackend>::new()
+            .with_syntax_highlighting(highlighter())
+            .render(markdown, Pipeline::new());
+
+        assert_eq!(highlighted.html, plain.html);
+        assert_eq!(
+            highlighted.html,
+            r#"<pre><code class="language-nonsense-lang">a &lt; b
+</code></pre>"#
+        );
+    }
+
+    #[test]
+    fn themes_differ() {
+        let markdown = "```rust\nfn main() {}\n```";
+        let render = |theme: &str| {
+            MarkdownRenderer::<HtmlBackend>::new()
+                .with_syntax_highlighting(SyntaxHighlighter::new(theme).unwrap())
+                .render(markdown, Pipeline::new())
+                .html
+        };
+
+        assert_ne!(render("InspiredGitHub"), render("base16-ocean.dark"));
+    }
+
+    #[test]
+    fn unknown_theme_lists_the_bundled_ones() {
+        let err = SyntaxHighlighter::new("no-such-theme").err().unwrap();
+
+        assert_eq!(err.name, "no-such-theme");
+        assert_eq!(err.available, SyntaxHighlighter::theme_names());
+        assert!(err.available.iter().any(|t| t == DEFAULT_SYNTAX_THEME));
+    }
+
+    #[test]
+    fn non_html_backend_keeps_plain_code() {
+        let markdown = "```rust\nfn main() {}\n```";
+        let plain =
+            MarkdownRenderer::<SearchDocumentBackend>::new().render(markdown, Pipeline::new());
+        let highlighted = MarkdownRenderer::<SearchDocumentBackend>::new()
+            .with_syntax_highlighting(highlighter())
+            .render(markdown, Pipeline::new());
+
+        assert_eq!(highlighted.html, plain.html);
+    }
+}
@@ -57,6 +57,16 @@ impl RenderBackend for HtmlBackend {
         out.push_str("</span>");
     }
 
+    fn heading_anchor(id: &str, title: &str, out: &mut String) {
+        write!(
+            out,
+            r##"<a class="heading-anchor" href="#{id}" aria-label="Link to section: "##
+        )
+        .unwrap();
+        escape_into(title, out);
+        out.push_str(r#"">#</a>"#);
+    }
+
     fn code_block(lang: Option<&str>, content: &str, out: &mut String) {
         if let Some(lang) = lang {
             out.push_str(r#"<pre><code class="language-"#);
@@ -71,6 +81,43 @@ impl RenderBackend for HtmlBackend {
         }
     }
 
+    /// Wraps the block in `<div class="code-block" data-lang="…">` with an
+    /// empty `<button class="copy">` for the viewer to wire up, and each line
+    /// in `<span class="line" data-line="N">`. Newlines stay between the
+    /// spans, so the code's text, trailing newline included, is unchanged.
+    fn code_block_with_lines(lang: Option<&str>, content: &str, out: &mut String) {
+        out.push_str(r#"<div class="code-block""#);
+        if let Some(lang) = lang {
+            out.push_str(r#" data-lang=""#);
+            escape_into(lang, out);
+            out.push('"');
+        }
+        out.push_str(r#"><button class="copy" type="button" aria-label="Copy code"></button>"#);
+        if let Some(lang) = lang {
+            out.push_str(r#"<pre><code class="language-"#);
+            escape_into(lang, out);
+            out.push_str(r#"">"#);
+        } else {
+            out.push_str("<pre><code>");
+        }
+        for (i, line) in content.split_inclusive('\n').enumerate() {
+            let (text, newline) = match line.strip_suffix('\n') {
+                Some(text) => (text, "\n"),
+                None => (line, ""),
+            };
+            write!(out, r#"<span class="line" data-line="{}">"#, i + 1).unwrap();
+            escape_into(text, out);
+            out.push_str("</span>");
+            out.push_str(newline);
+        }
+        out.push_str("</code></pre></div>");
+    }
+
+    #[cfg(feature = "syntax-highlighting")]
+    fn highlighted_code_block(_lang: &str, _content: &str, html: &str, out: &mut String) {
+        out.push_str(html);
+    }
+
     fn blockquote_start(out: &mut String) {
         out.push_str("<blockquote>");
     }
@@ -140,7 +187,7 @@ impl RenderBackend for HtmlBackend {
 ///
 /// External links, fragment-only links, and non-markdown links are returned unchanged.
 #[allow(clippy::case_sensitive_file_extension_comparisons)]
-fn resolve_link(url: &str, base_path: &str) -> String {
+pub(crate) fn resolve_link(url: &str, base_path: &str) -> String {
     // Skip external links, fragments, and non-local URLs
     if url.starts_with("http://")
         || url.starts_with("https://")
@@ -228,6 +275,34 @@ mod tests {
         assert_eq!(out, "<pre><code>plain code</code></pre>");
     }
 
+    #[test]
+    fn test_code_block_with_lines() {
+        let mut out = String::new();
+        HtmlBackend::code_block_with_lines(Some("html"), "<b>&amp;</b>\n\nend\n", &mut out);
+        assert_eq!(
+            out,
+            "<div class=\"code-block\" data-lang=\"html\">\
+             <button class=\"copy\" type=\"button\" aria-label=\"Copy code\"></button>\
+             <pre><code class=\"language-html\">\
+             <span class=\"line\" data-line=\"1\">&lt;b&gt;&amp;amp;&lt;/b&gt;</span>\n\
+             <span class=\"line\" data-line=\"2\"></span>\n\
+             <span class=\"line\" data-line=\"3\">end</span>\n\
+             </code></pre></div>"
+        );
+    }
+
+    #[test]
+    fn test_code_block_with_lines_without_language() {
+        let mut out = String::new();
+        HtmlBackend::code_block_with_lines(None, "plain", &mut out);
+        assert_eq!(
+            out,
+            "<div class=\"code-block\">\
+             <button class=\"copy\" type=\"button\" aria-label=\"Copy code\"></button>\
+             <pre><code><span class=\"line\" data-line=\"1\">plain</span></code></pre></div>"
+        );
+    }
+
     #[test]
     fn test_blockquote() {
         let mut out = String::new();
@@ -0,0 +1,289 @@
+//! File includes: `::include[./snippets/auth.md]` renders another markdown
+//! file in the directive's place.
+//!
+//! The path is relative to the file the directive appears in — the page's
+//! [`source_path`](crate::directive::DirectiveContext::source_path), or the
+//! including snippet for a nested include — and is sandboxed to the context's
+//! base directory by [`resolve_path`](crate::directive::DirectiveContext::resolve_path).
+//! Included markdown renders as if written in place, directives included, so
+//! snippets may include snippets of their own.
+//!
+//! An include that cannot be honored — missing file, path escaping the base
+//! directory, a cycle, or nesting more than ten levels deep — renders
+//! nothing and adds a warning, and the rest of the page renders as usual.
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::directive::{DirectiveArgs, DirectiveContext, DirectiveOutput, LeafDirective};
+
+/// How deeply includes may nest, matching `PlantUML`'s `!include` limit.
+const MAX_INCLUDE_DEPTH: usize = 10;
+
+/// `::include[path]` leaf directive.
+///
+/// # Example
+///
+/// ```
+/// use rw_renderer::directive::{DirectiveProcessor, DirectiveProcessorConfig};
+/// use rw_renderer::{HtmlBackend, IncludeDirective, MarkdownRenderer, Pipeline};
+///
+/// let config = DirectiveProcessorConfig::new()
+///     .with_base_dir("docs")
+///     .with_source_path("docs/api/auth.md")
+///     .with_read_file(|path| {
+///         assert_eq!(path, std::path::Path::new("docs/snippets/token.md"));
+///         Ok("Pass the token as a **bearer** header.".to_owned())
+///     });
+/// let directives = DirectiveProcessor::with_config(config).with_leaf(IncludeDirective::new());
+///
+/// let result = MarkdownRenderer::<HtmlBackend>::new().render(
+///     "# Auth\n\n::include[../snippets/token.md]",
+///     Pipeline::new().with_directives(directives),
+/// );
+/// assert!(result.html.contains("<strong>bearer</strong>"));
+/// ```
+#[derive(Default)]
+pub struct IncludeDirective {
+    /// Files being included, outermost first.
+    stack: Vec<PathBuf>,
+    warnings: Vec<String>,
+}
+
+impl IncludeDirective {
+    /// Create an include handler.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Directory of the including file, relative to `ctx`'s base directory.
+    fn current_dir(&self, ctx: &DirectiveContext) -> PathBuf {
+        let dir = match self.stack.last() {
+            Some(including) => including.parent(),
+            None => ctx.source_path().and_then(Path::parent),
+        };
+        let Some(dir) = dir else {
+            return PathBuf::new();
+        };
+        match dir.strip_prefix(ctx.base_dir()) {
+            Ok(relative) => relative.to_path_buf(),
+            // A relative source path is taken as relative to the base
+            // directory; an absolute one outside it falls back to the base.
+            Err(_) if dir.is_relative() => dir.to_path_buf(),
+            Err(_) => PathBuf::new(),
+        }
+    }
+
+    /// Whether including `path` would include a file already being rendered.
+    fn is_cycle(&self, path: &Path, ctx: &DirectiveContext) -> bool {
+        self.stack.iter().any(|p| p == path)
+            || ctx
+                .source_path()
+                .is_some_and(|source| source == path || ctx.base_dir().join(source) == path)
+    }
+}
+
+impl LeafDirective for IncludeDirective {
+    fn name(&self) -> &'static str {
+        "include"
+    }
+
+    fn process(&mut self, args: DirectiveArgs, ctx: &DirectiveContext) -> DirectiveOutput {
+        let target = args.content().trim();
+        if target.is_empty() {
+            self.warnings
+                .push("::include needs a path, e.g. ::include[./snippets/auth.md]".to_owned());
+            return DirectiveOutput::html("");
+        }
+        if self.stack.len() >= MAX_INCLUDE_DEPTH {
+            self.warnings.push(format!(
+                "::include[{target}]: include depth exceeded maximum of {MAX_INCLUDE_DEPTH}"
+            ));
+            return DirectiveOutput::html("");
+        }
+
+        let relative = self.current_dir(ctx).join(target);
+        let path = match ctx.resolve_path(&relative.to_string_lossy()) {
+            Ok(path) => path,
+            Err(e) => {
+                self.warnings.push(format!("::include[{target}]: {e}"));
+                return DirectiveOutput::html("");
+            }
+        };
+        if self.is_cycle(&path, ctx) {
+            self.warnings.push(format!(
+                "::include[{target}]: include cycle through {}",
+                path.display()
+            ));
+            return DirectiveOutput::html("");
+        }
+
+        match ctx.read(&path) {
+            Ok(markdown) => {
+                self.stack.push(path);
+                DirectiveOutput::Markdown(markdown)
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                self.warnings.push(format!(
+                    "::include[{target}]: file not found: {}",
+                    path.display()
+                ));
+                DirectiveOutput::html("")
+            }
+            Err(e) => {
+                self.warnings.push(format!(
+                    "::include[{target}]: cannot read {}: {e}",
+                    path.display()
+                ));
+                DirectiveOutput::html("")
+            }
+        }
+    }
+
+    fn spliced(&mut self) {
+        self.stack.pop();
+    }
+
+    fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::directive::{DirectiveProcessor, DirectiveProcessorConfig};
+    use crate::{HtmlBackend, MarkdownRenderer, Pipeline, RenderResult, TabsDirective};
+
+    /// Render `markdown` as `docs/guide/page.md`, reading files from `files`
+    /// (paths relative to `docs`).
+    fn render(markdown: &str, files: &[(&str, &str)]) -> RenderResult {
+        let files: HashMap<PathBuf, String> = files
+            .iter()
+            .map(|(path, content)| (Path::new("docs").join(path), (*content).to_owned()))
+            .collect();
+        let config = DirectiveProcessorConfig::new()
+            .with_base_dir("docs")
+            .with_source_path("docs/guide/page.md")
+            .with_read_file(move |path| {
+                files
+                    .get(path)
+                    .cloned()
+                    .ok_or_else(|| io::Error::from(io::ErrorKind::NotFound))
+            });
+        let directives = DirectiveProcessor::with_config(config)
+            .with_leaf(IncludeDirective::new())
+            .with_container(TabsDirective::new());
+        MarkdownRenderer::<HtmlBackend>::new()
+            .render(markdown, Pipeline::new().with_directives(directives))
+    }
+
+    #[test]
+    fn includes_file_relative_to_the_page() {
+        let result = render(
+            "Before.\n\n::include[./snippets/auth.md]\n\nAfter.",
+            &[("guide/snippets/auth.md", "## Auth\n\nUse a *token*.")],
+        );
+
+        assert_eq!(
+            result.html,
+            "<p>Before.</p><h2 id=\"auth\">Auth</h2><p>Use a <em>token</em>.</p><p>After.</p>"
+        );
+        assert_eq!(result.toc.len(), 1);
+        assert!(result.warnings.is_empty(), "{:?}", result.warnings);
+    }
+
+    #[test]
+    fn nested_include_is_relative_to_the_including_file() {
+        let result = render(
+            "::include[../shared/outer.md]",
+            &[
+                ("shared/outer.md", "Outer.\n\n::include[parts/inner.md]"),
+                (
+                    "shared/parts/inner.md",
+                    "::::tabs\n\n:::tab[A]\n\nInner.\n\n:::\n\n::::",
+                ),
+            ],
+        );
+
+        assert!(result.html.contains("<p>Outer.</p>"), "{}", result.html);
+        assert!(result.html.contains("<p>Inner.</p>"), "{}", result.html);
+        assert!(result.warnings.is_empty(), "{:?}", result.warnings);
+    }
+
+    #[test]
+    fn missing_file_warns_and_renders_the_rest() {
+        let result = render("::include[missing.md]\n\nStill here.", &[]);
+
+        assert_eq!(result.html, "<p>Still here.</p>");
+        assert_eq!(
+            result.warnings,
+            ["::include[missing.md]: file not found: docs/guide/missing.md"]
+        );
+    }
+
+    #[test]
+    fn path_outside_the_base_directory_is_rejected() {
+        let result = render("::include[../../etc/passwd]", &[]);
+
+        assert_eq!(result.html, "");
+        assert_eq!(
+            result.warnings,
+            ["::include[../../etc/passwd]: path escapes the base directory"]
+        );
+    }
+
+    #[test]
+    fn cycle_is_broken_with_a_warning() {
+        let result = render(
+            "::include[a.md]",
+            &[
+                ("guide/a.md", "A.\n\n::include[b.md]"),
+                ("guide/b.md", "B.\n\n::include[a.md]"),
+            ],
+        );
+
+        assert_eq!(result.html, "<p>A.</p><p>B.</p>");
+        assert_eq!(
+            result.warnings,
+            ["::include[a.md]: include cycle through docs/guide/a.md"]
+        );
+    }
+
+    #[test]
+    fn including_the_page_itself_is_a_cycle() {
+        let result = render("Page.\n\n::include[page.md]", &[]);
+
+        assert_eq!(result.html, "<p>Page.</p>");
+        assert_eq!(result.warnings.len(), 1);
+        assert!(result.warnings[0].contains("include cycle"));
+    }
+
+    #[test]
+    fn depth_is_limited() {
+        let files: Vec<(String, String)> = (0..=MAX_INCLUDE_DEPTH)
+            .map(|i| {
+                (
+                    format!("guide/{i}.md"),
+                    format!("Level {i}.\n\n::include[{}.md]", i + 1),
+                )
+            })
+            .collect();
+        let files: Vec<(&str, &str)> = files
+            .iter()
+            .map(|(path, content)| (path.as_str(), content.as_str()))
+            .collect();
+
+        let result = render("::include[0.md]", &files);
+
+        assert!(result.html.contains("<p>Level 9.</p>"), "{}", result.html);
+        assert!(!result.html.contains("Level 10."), "{}", result.html);
+        assert_eq!(
+            result.warnings,
+            ["::include[10.md]: include depth exceeded maximum of 10"]
+        );
+    }
+}
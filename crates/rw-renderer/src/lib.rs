@@ -30,7 +30,8 @@
 //!   and is dispatched straight to the backend. A handler whose markup depends
 //!   on content the walk has not reached yet — a tab strip needs every tab's
 //!   label — returns [`DirectiveOutput::Deferred`](directive::DirectiveOutput::Deferred),
-//!   reserving a hole at the current output offset.
+//!   reserving a hole at the current output offset. A leaf handler may also
+//!   return markdown to render in its place, as [`IncludeDirective`] does.
 //!
 //! Both extension points share the same deferral mechanism: a single assembly
 //! pass after the walk splices each reserved hole's content in, supplied by
@@ -66,11 +67,29 @@
 //! | `[[#fragment]]` | Same-page fragment link |
 //! | `[[target\|display text]]` | Any form above with explicit display text |
 //!
-//! Unresolved wikilinks render with a `class="rw-broken-link"` indicator.
+//! A target that names no section is also tried as a page name when a
+//! [`LinkChecker`] is set (via [`MarkdownRenderer::with_link_checker`]): each
+//! `/`-separated segment is slugified like a heading id, so `[[Getting
+//! Started]]` links to the `getting-started` page next to the current one, or
+//! else at the site root.
+//!
+//! Unresolved wikilinks render with a `class="rw-broken-link"` indicator and
+//! add a warning to [`RenderResult::warnings`].
 //! When no explicit display text is given, the renderer tries (in order):
 //! the [`TitleResolver`], the last path segment, the section name, or the
 //! raw href.
 //!
+//! ## Math
+//!
+//! With [`MarkdownRenderer::with_math`], `$tex$` and `$$tex$$` parse as math;
+//! it is off by default, leaving dollar signs as prose. [`RenderBackend::math`]
+//! decides the output; the HTML default emits the escaped TeX in `<span
+//! class="math inline">` / `<div class="math display">` for a client-side
+//! `KaTeX` or `MathJax` pass. Display math is a block: the paragraph around it
+//! is closed before it and reopened after it. Dollars inside code spans and
+//! code blocks stay literal, and so do prices: a `$` followed by a space can't
+//! open math, nor one preceded by a space close it.
+//!
 //! # Examples
 //!
 //! Render markdown to HTML:
@@ -124,15 +143,23 @@
 //!
 //! - **`serde`** — enables `Serialize`/`Deserialize` on [`TocEntry`] for
 //!   JSON serialization in HTTP API responses.
+//! - **`syntax-highlighting`** — enables [`SyntaxHighlighter`] and
+//!   [`MarkdownRenderer::with_syntax_highlighting`] for build-time code
+//!   highlighting with syntect.
 
+mod admonition;
+mod autolink;
 mod backend;
 mod bundle;
 mod code_block;
 mod comment;
 mod config;
 pub mod directive;
+#[cfg(feature = "syntax-highlighting")]
+mod highlight;
 mod holes;
 mod html;
+mod include;
 mod link;
 mod pipeline;
 mod renderer;
@@ -146,18 +173,22 @@ mod util;
 mod walker;
 mod wikilink;
 
+pub use admonition::{AdmonitionDirective, DEFAULT_ADMONITION_KINDS};
 pub use backend::RenderBackend;
 pub use bundle::bundle_markdown;
-pub use code_block::{CodeBlockProcessor, ExtractedCodeBlock, ProcessResult};
+pub use code_block::{CodeBlockProcessor, CodeBlockStats, ExtractedCodeBlock, ProcessResult};
 pub use comment::render_comment_body;
-pub use config::TitleResolver;
+pub use config::{LinkChecker, TitleResolver};
 /// Re-exported from [`directive`] for [`CodeBlockProcessor::fills`]
 /// implementations. Directives and code-block processors defer content through
 /// the same hole mechanism, so [`Fills`]/[`HoleKey`] belong to both extension
 /// points; this re-export lets a processor use them without reaching into the
 /// directive module. The `directive::` paths name the same types.
 pub use directive::{Fills, HoleKey};
+#[cfg(feature = "syntax-highlighting")]
+pub use highlight::{DEFAULT_SYNTAX_THEME, SyntaxHighlighter, UnknownThemeError};
 pub use html::HtmlBackend;
+pub use include::IncludeDirective;
 pub use pipeline::Pipeline;
 /// Re-exported for use in [`RenderBackend::table_cell_start`] implementations.
 pub use pulldown_cmark::Alignment;
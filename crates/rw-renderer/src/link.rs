@@ -8,6 +8,7 @@
 use std::borrow::Cow;
 
 use crate::config::RenderConfig;
+use crate::html;
 
 /// Strip the origin prefix from a URL if it matches.
 ///
@@ -64,6 +65,27 @@ pub(crate) fn section_ref_attrs(cfg: &RenderConfig, href: &str) -> Option<(Strin
     Some((sp.section.to_string(), sp.path.to_owned()))
 }
 
+/// The page a plain markdown link points at, for [`LinkChecker`] lookups.
+///
+/// Resolves `url` the way [`HtmlBackend`](crate::HtmlBackend) does and returns
+/// the target path without leading slash or fragment. Returns `None` when
+/// there is nothing to check: no base path configured, or `url` is external,
+/// fragment-only, or not a `.md` link.
+///
+/// [`LinkChecker`]: crate::LinkChecker
+pub(crate) fn checked_target(cfg: &RenderConfig, url: &str) -> Option<String> {
+    let base = link_base(cfg)?;
+    let href = html::resolve_link(url, base);
+    // `resolve_link` hands back anything it doesn't rewrite unchanged; a
+    // rewritten link always loses its `.md`, so equality means "not ours".
+    if href == url {
+        return None;
+    }
+    let path = href.strip_prefix('/')?;
+    let path = path.split_once('#').map_or(path, |(p, _)| p);
+    Some(path.to_owned())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,7 +2,7 @@
 //!
 //! See the [crate-level documentation](crate) for an overview and examples.
 
-use std::collections::BTreeSet;
+use std::collections::{BTreeSet, HashSet};
 use std::marker::PhantomData;
 use std::sync::Arc;
 
@@ -10,7 +10,8 @@ use rw_parser::Parser;
 use rw_sections::Sections;
 
 use crate::backend::RenderBackend;
-use crate::config::{RenderConfig, TitleResolver};
+use crate::code_block::CodeBlockStats;
+use crate::config::{LinkChecker, RenderConfig, TitleResolver};
 use crate::pipeline::Pipeline;
 use crate::toc::TocEntry;
 
@@ -47,7 +48,8 @@ pub struct RenderResult {
     /// Table-of-contents entries, one per heading (excluding the title heading).
     pub toc: Vec<TocEntry>,
     /// Warnings generated during conversion (e.g., unresolved includes,
-    /// unclosed container directives).
+    /// unclosed container directives), each reported once, in first-seen
+    /// order.
     pub warnings: Vec<String>,
     /// Whether any code-block processor hit a transient failure during this
     /// render (e.g. a diagram service was unreachable). Callers use this to
@@ -58,6 +60,10 @@ pub struct RenderResult {
     /// via prose links (markdown + wikilinks) and diagram `$link`s. Deduped and
     /// deterministically ordered. Empty when the page references no sections.
     pub section_refs: BTreeSet<String>,
+    /// Per-block statistics reported by code-block processors (e.g. whether
+    /// each diagram came from cache and how long it took), in processor
+    /// registration order. Empty when no processor reports any.
+    pub code_block_stats: Vec<CodeBlockStats>,
 }
 
 /// Generic markdown renderer with pluggable backend.
@@ -111,12 +117,105 @@ impl<B: RenderBackend> MarkdownRenderer<B> {
     /// Behavior depends on the backend:
     /// - HTML: First H1 is extracted as title but still rendered
     /// - Confluence: First H1 is extracted as title and skipped, levels shifted
+    ///
+    /// [`with_suppress_title_heading`](Self::with_suppress_title_heading)
+    /// gives the HTML backend the Confluence behavior.
     #[must_use]
     pub fn with_title_extraction(mut self) -> Self {
         self.config.extract_title = true;
         self
     }
 
+    /// Skip the extracted title H1 in the output, whatever the backend.
+    ///
+    /// For callers that show the title in their own page chrome: the first
+    /// H1 still populates [`RenderResult::title`] but is not rendered, and
+    /// every later heading shifts up one level (H2 → H1, etc.), as the
+    /// Confluence backend always does. Has no effect without
+    /// [`with_title_extraction`](Self::with_title_extraction). Defaults to
+    /// `false`.
+    #[must_use]
+    pub fn with_suppress_title_heading(mut self, suppress: bool) -> Self {
+        self.config.suppress_title_heading = suppress;
+        self
+    }
+
+    /// Derive heading ids with `slugify` instead of the built-in kebab-case
+    /// slugs — for example, to match GitHub's anchors.
+    ///
+    /// `slugify` receives the heading's plain text, trimmed. Ids are still
+    /// made unique on top of what it returns (`-1`, `-2`, … for repeats), an
+    /// empty result falls back to `section`, and [`TocEntry::id`] carries
+    /// the same id as the rendered heading. The result is used as-is in the
+    /// `id` attribute, so it should not contain `"`.
+    #[must_use]
+    pub fn with_slugify(
+        mut self,
+        slugify: impl Fn(&str) -> String + Send + Sync + 'static,
+    ) -> Self {
+        self.config.slugify = Some(Arc::new(slugify));
+        self
+    }
+
+    /// Leave headings deeper than level `max_depth` out of
+    /// [`RenderResult::toc`].
+    ///
+    /// Excluded headings still render with their `id`, so links to them keep
+    /// working. The depth is compared with the level a heading renders at, so
+    /// where the title H1 is skipped and later headings shift up (Confluence,
+    /// or [`with_suppress_title_heading`](Self::with_suppress_title_heading)),
+    /// an H3 counts as depth 2. Defaults to every level.
+    #[must_use]
+    pub fn with_toc_max_depth(mut self, max_depth: u8) -> Self {
+        self.config.toc_max_depth = Some(max_depth);
+        self
+    }
+
+    /// Add a clickable `#` anchor to each heading, for copying a link to its
+    /// section.
+    ///
+    /// The anchor follows the heading text and links to the heading's id:
+    /// `<h2 id="setup">Setup<a class="heading-anchor" href="#setup" …>#</a></h2>`.
+    /// The title H1 captured by
+    /// [`with_title_extraction`](Self::with_title_extraction) gets none. Only
+    /// [`HtmlBackend`](crate::HtmlBackend) emits anchors (see
+    /// [`RenderBackend::heading_anchor`]). Defaults to `false`.
+    #[must_use]
+    pub fn with_heading_anchors(mut self, enabled: bool) -> Self {
+        self.config.heading_anchors = enabled;
+        self
+    }
+
+    /// Give code blocks per-line markup and a copy-button placeholder.
+    ///
+    /// [`HtmlBackend`](crate::HtmlBackend) wraps each block in
+    /// `<div class="code-block" data-lang="…">` with an empty
+    /// `<button class="copy">`, and each line in
+    /// `<span class="line" data-line="N">`, for line anchors and copying. The
+    /// code's text is unchanged. Blocks a [`CodeBlockProcessor`](crate::CodeBlockProcessor)
+    /// renders, such as diagrams, are unaffected. Defaults to `false`: plain
+    /// `<pre><code>`.
+    #[must_use]
+    pub fn with_code_block_lines(mut self, enabled: bool) -> Self {
+        self.config.code_block_lines = enabled;
+        self
+    }
+
+    /// Highlight fenced code blocks at render time with `highlighter`.
+    ///
+    /// [`HtmlBackend`](crate::HtmlBackend) writes blocks in a language the
+    /// highlighter knows as inline-styled spans in the highlighter's theme, so
+    /// the output needs no client-side highlighting; other languages, and
+    /// other backends, keep plain code blocks. Takes precedence over
+    /// [`with_code_block_lines`](Self::with_code_block_lines) for highlighted
+    /// blocks. Requires the `syntax-highlighting` feature.
+    #[cfg(feature = "syntax-highlighting")]
+    #[must_use]
+    pub fn with_syntax_highlighting(mut self, highlighter: crate::SyntaxHighlighter) -> Self {
+        self.config.highlighter = Some(highlighter);
+        self
+    }
+
     /// Set base path for resolving relative links (URL path with leading `/`).
     ///
     /// Only used by HTML backend. Confluence backend ignores this.
@@ -193,6 +292,41 @@ impl<B: RenderBackend> MarkdownRenderer<B> {
         self
     }
 
+    /// Link bare `https://…`, `http://…` and `www.…` URLs and `user@host`
+    /// emails in prose, as GitHub does.
+    ///
+    /// Trailing sentence punctuation stays outside the link, so
+    /// `see https://x.com.` links `https://x.com`. Text inside links, image
+    /// alt text, code spans and code blocks is left alone. Defaults to `true`.
+    #[must_use]
+    pub fn with_autolinks(mut self, enabled: bool) -> Self {
+        self.config.autolinks = enabled;
+        self
+    }
+
+    /// Typeset prose punctuation: straight quotes become curly, `--` and
+    /// `---` en and em dashes, `...` an ellipsis.
+    ///
+    /// The replacements are Unicode characters, not named entities, so the
+    /// output stays valid XHTML for backends like Confluence's storage format.
+    /// Code spans and code blocks keep their text. Defaults to `false`.
+    #[must_use]
+    pub fn with_smart_punctuation(mut self, enabled: bool) -> Self {
+        self.config.smart_punctuation = enabled;
+        self
+    }
+
+    /// Parse `$tex$` and `$$tex$$` as math, rendered by
+    /// [`RenderBackend::math`](crate::RenderBackend::math).
+    ///
+    /// Off, dollar signs stay prose, so pages that mention prices or shell
+    /// variables render as written. Defaults to `false`.
+    #[must_use]
+    pub fn with_math(mut self, enabled: bool) -> Self {
+        self.config.math = enabled;
+        self
+    }
+
     /// Set a title resolver for wikilink display text.
     ///
     /// When a wikilink has no explicit display text (`[[target]]` vs.
@@ -209,6 +343,22 @@ impl<B: RenderBackend> MarkdownRenderer<B> {
         self
     }
 
+    /// Warn about markdown links to pages that don't exist.
+    ///
+    /// Each local `.md` link is resolved as usual and its target passed to
+    /// the checker; a target it doesn't know adds a warning to
+    /// [`RenderResult::warnings`]. The link still renders as a normal link.
+    /// External links, fragment-only links and images are never checked.
+    ///
+    /// Only takes effect together with [`with_base_path`](Self::with_base_path):
+    /// without one, relative links are left unresolved and there is no
+    /// target to check.
+    #[must_use]
+    pub fn with_link_checker(mut self, checker: impl LinkChecker + 'static) -> Self {
+        self.config.link_checker = Some(Box::new(checker));
+        self
+    }
+
     /// Renders raw markdown to the configured backend, applying the supplied
     /// [`Pipeline`]'s extensions.
     ///
@@ -225,11 +375,14 @@ impl<B: RenderBackend> MarkdownRenderer<B> {
     ///    a single pass.
     ///
     /// The supplied `Pipeline` is consumed: build a fresh one per render.
+    ///
+    /// A leading UTF-8 byte order mark is ignored, whatever storage the
+    /// markdown came from.
     pub fn render(&self, markdown: &str, mut pipeline: Pipeline) -> RenderResult {
-        let mut parser = Parser::new(
+        let markdown = strip_bom(markdown);
+        let mut parser = Parser::new_ext(
             markdown,
-            self.config.wikilinks,
-            pipeline.directives.is_some(),
+            self.config.parse_options(pipeline.directives.is_some()),
         );
         let mut result = {
             let mut walker = crate::walker::Walker::<B>::new(
@@ -250,11 +403,23 @@ impl<B: RenderBackend> MarkdownRenderer<B> {
             processor.finalize();
             result.warnings.extend(processor.warnings());
         }
+        // A snippet included twice, or one broken `!include` shared by several
+        // diagrams, would otherwise report the same problem once per use.
+        let mut seen = HashSet::new();
+        result
+            .warnings
+            .retain(|warning| seen.insert(warning.clone()));
 
         result
     }
 }
 
+/// `markdown` without a leading byte order mark, which would otherwise leak
+/// into the first block (`\u{FEFF}# Title` is a paragraph, not a heading).
+fn strip_bom(markdown: &str) -> &str {
+    markdown.strip_prefix('\u{FEFF}').unwrap_or(markdown)
+}
+
 impl<B: RenderBackend> Default for MarkdownRenderer<B> {
     fn default() -> Self {
         Self::new()
@@ -316,6 +481,16 @@ mod tests {
         MarkdownRenderer::<HtmlBackend>::new().render(markdown, Pipeline::new())
     }
 
+    #[test]
+    fn test_leading_bom_is_ignored() {
+        let result = render_html_with_title("\u{FEFF}# Title\n\nBody.");
+        let without_bom = render_html_with_title("# Title\n\nBody.");
+
+        assert_eq!(result.title.as_deref(), Some("Title"));
+        assert_eq!(result.html, without_bom.html);
+        assert!(!result.html.contains('\u{FEFF}'));
+    }
+
     #[test]
     fn test_html_basic_paragraph() {
         let result = render_html("Hello, world!");
@@ -345,6 +520,213 @@ mod tests {
         assert_eq!(result.toc[0].level, 2);
     }
 
+    #[test]
+    fn test_custom_slugify() {
+        let result = MarkdownRenderer::<HtmlBackend>::new()
+            .with_slugify(|text| text.replace(' ', "_"))
+            .render("## Read Me\n\n## Read Me", Pipeline::new());
+
+        assert_eq!(
+            result.html,
+            r#"<h2 id="Read_Me">Read Me</h2><h2 id="Read_Me-1">Read Me</h2>"#
+        );
+        let ids: Vec<&str> = result.toc.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, ["Read_Me", "Read_Me-1"]);
+    }
+
+    #[test]
+    fn test_toc_max_depth() {
+        let result = MarkdownRenderer::<HtmlBackend>::new()
+            .with_toc_max_depth(2)
+            .render(
+                "## Setup
+
+### Details
+
+## Usage",
+                Pipeline::new(),
+            );
+
+        let ids: Vec<&str> = result.toc.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, ["setup", "usage"]);
+        assert!(result.html.contains(r#"<h3 id="details">Details</h3>"#));
+    }
+
+    #[test]
+    fn test_html_code_block_lines() {
+        let markdown = "```rust\nlet a = 1;\nlet b = 2;\n```";
+        let plain = render_html(markdown);
+        let lines = MarkdownRenderer::<HtmlBackend>::new()
+            .with_code_block_lines(true)
+            .render(markdown, Pipeline::new());
+
+        assert!(!plain.html.contains("class=\"line\""));
+        assert!(
+            lines
+                .html
+                .starts_with(r#"<div class="code-block" data-lang="rust">"#)
+        );
+        assert!(lines.html.contains(
+            "<span class=\"line\" data-line=\"1\">let a = 1;</span>\n\
+             <span class=\"line\" data-line=\"2\">let b = 2;</span>\n</code>"
+        ));
+    }
+
+    #[test]
+    fn test_html_autolinks() {
+        let result = render_html("See https://x.com. Mail ops@example.com, or www.example.com!");
+
+        assert_eq!(
+            result.html,
+            "<p>See <a href=\"https://x.com\">https://x.com</a>. \
+             Mail <a href=\"mailto:ops@example.com\">ops@example.com</a>, \
+             or <a href=\"http://www.example.com\">www.example.com</a>!</p>"
+        );
+    }
+
+    #[test]
+    fn test_html_autolinks_skip_code_and_links() {
+        let markdown = "`https://a.com` [https://b.com](https://c.com) <https://d.com> \
+                        ![https://e.com](i.png)\n\n```\nhttps://f.com\n```";
+        let html = render_html(markdown).html;
+
+        assert_eq!(html.matches("<a ").count(), 2, "{html}");
+        assert!(html.contains("<code>https://a.com</code>"), "{html}");
+        assert!(
+            html.contains(r#"<a href="https://c.com">https://b.com</a>"#),
+            "{html}"
+        );
+        assert!(
+            html.contains(r#"<a href="https://d.com">https://d.com</a>"#),
+            "{html}"
+        );
+        assert!(html.contains(r#"alt="https://e.com""#), "{html}");
+        assert!(
+            html.contains("<pre><code>https://f.com\n</code></pre>"),
+            "{html}"
+        );
+    }
+
+    #[test]
+    fn test_html_autolinks_disabled() {
+        let result = MarkdownRenderer::<HtmlBackend>::new()
+            .with_autolinks(false)
+            .render("See https://x.com.", Pipeline::new());
+
+        assert_eq!(result.html, "<p>See https://x.com.</p>");
+    }
+
+    #[test]
+    fn test_html_smart_punctuation() {
+        let markdown = "\"Quotes\" -- it's 1--2... `a--b`\n\n```\n\"x\" -- ...\n```";
+        let smart = MarkdownRenderer::<HtmlBackend>::new()
+            .with_smart_punctuation(true)
+            .render(markdown, Pipeline::new());
+
+        assert_eq!(
+            smart.html,
+            "<p>“Quotes” – it’s 1–2… <code>a--b</code></p>\
+             <pre><code>&quot;x&quot; -- ...\n</code></pre>"
+        );
+        assert_eq!(
+            render_html(markdown).html,
+            "<p>&quot;Quotes&quot; -- it&#x27;s 1--2... <code>a--b</code></p>\
+             <pre><code>&quot;x&quot; -- ...\n</code></pre>"
+        );
+    }
+
+    #[test]
+    fn test_html_heading_anchors() {
+        let result = MarkdownRenderer::<HtmlBackend>::new()
+            .with_heading_anchors(true)
+            .render("## Setup & *Install*", Pipeline::new());
+
+        assert_eq!(
+            result.html,
+            r##"<h2 id="setup-install">Setup &amp; <em>Install</em><a class="heading-anchor" href="#setup-install" aria-label="Link to section: Setup &amp; Install">#</a></h2>"##
+        );
+        assert_eq!(result.toc[0].title, "Setup & Install");
+    }
+
+    #[test]
+    fn test_html_heading_anchors_skip_extracted_title() {
+        let result = MarkdownRenderer::<HtmlBackend>::new()
+            .with_title_extraction()
+            .with_heading_anchors(true)
+            .render(
+                "# My Title
+
+## Section",
+                Pipeline::new(),
+            );
+
+        assert!(result.html.contains(r#"<h1 id="my-title">My Title</h1>"#));
+        assert!(result.html.contains(r##"href="#section""##));
+        assert_eq!(result.html.matches("heading-anchor").count(), 1);
+    }
+
+    #[test]
+    fn test_html_heading_anchors_off_by_default() {
+        assert!(!render_html("## Section").html.contains("heading-anchor"));
+    }
+
+    #[test]
+    fn test_html_suppressed_title_heading() {
+        let markdown = "# My Title\n\nSome content\n\n## Section\n\n### Detail";
+        let result = MarkdownRenderer::<HtmlBackend>::new()
+            .with_title_extraction()
+            .with_suppress_title_heading(true)
+            .render(markdown, Pipeline::new());
+
+        assert_eq!(result.title.as_deref(), Some("My Title"));
+        assert_eq!(
+            result.html,
+            r#"<p>Some content</p><h1 id="section">Section</h1><h2 id="detail">Detail</h2>"#
+        );
+        let levels: Vec<u8> = result.toc.iter().map(|e| e.level).collect();
+        assert_eq!(levels, [1, 2]);
+    }
+
+    #[test]
+    fn test_html_suppressed_title_heading_needs_title_extraction() {
+        let markdown = "# My Title\n\n## Section";
+        let result = MarkdownRenderer::<HtmlBackend>::new()
+            .with_suppress_title_heading(true)
+            .render(markdown, Pipeline::new());
+
+        assert_eq!(result.title, None);
+        assert_eq!(
+            result.html,
+            r#"<h1 id="my-title">My Title</h1><h2 id="section">Section</h2>"#
+        );
+    }
+
+    #[test]
+    fn test_html_suppressed_title_heading_keeps_later_h1() {
+        let markdown = "# My Title\n\n# Another\n\n## Section";
+        let result = MarkdownRenderer::<HtmlBackend>::new()
+            .with_title_extraction()
+            .with_suppress_title_heading(true)
+            .render(markdown, Pipeline::new());
+
+        assert_eq!(result.title.as_deref(), Some("My Title"));
+        assert_eq!(
+            result.html,
+            r#"<h1 id="another">Another</h1><h1 id="section">Section</h1>"#
+        );
+    }
+
+    #[test]
+    fn test_html_empty_input_renders_nothing() {
+        for markdown in ["", "\n\n", "\u{FEFF}"] {
+            let result = render_html_with_title(markdown);
+            assert_eq!(result.html, "", "input: {markdown:?}");
+            assert_eq!(result.title, None);
+            assert!(result.toc.is_empty());
+            assert!(result.warnings.is_empty());
+        }
+    }
+
     #[test]
     fn test_html_code_block() {
         let result = render_html("```rust\nfn main() {}\n```");
@@ -464,6 +846,58 @@ mod tests {
         );
     }
 
+    fn render_math(markdown: &str) -> RenderResult {
+        MarkdownRenderer::<HtmlBackend>::new()
+            .with_math(true)
+            .render(markdown, Pipeline::new())
+    }
+
+    #[test]
+    fn test_math_renders_as_escaped_spans() {
+        let result = render_math("Let $a < b$ and $$\\frac{1}{2}$$.");
+        assert_eq!(
+            result.html,
+            r#"<p>Let <span class="math inline">a &lt; b</span> and </p><div class="math display">\frac{1}{2}</div><p>.</p>"#,
+        );
+    }
+
+    #[test]
+    fn test_display_math_paragraph_is_a_block() {
+        let result = render_math("Where\n\n$$\nx^2\n$$\n\nholds.");
+        assert_eq!(
+            result.html,
+            "<p>Where</p><div class=\"math display\">\nx^2\n</div><p>holds.</p>",
+        );
+    }
+
+    #[test]
+    fn test_math_is_off_by_default() {
+        let result = render_html("Costs $5, or $x$ with $$y$$.");
+        assert_eq!(result.html, "<p>Costs $5, or $x$ with $$y$$.</p>");
+    }
+
+    #[test]
+    fn test_dollars_in_code_are_not_math() {
+        let result = render_math("`$x$`\n\n```\n$y$\n```");
+        assert!(!result.html.contains("class=\"math"), "{}", result.html);
+        assert!(result.html.contains("<code>$x$</code>"), "{}", result.html);
+        assert!(result.html.contains("$y$"), "{}", result.html);
+    }
+
+    #[test]
+    fn test_math_in_heading_and_image_alt() {
+        let result = render_math("## Energy $E = mc^2$\n\n![$x$ plot](pic.png)");
+        assert!(
+            result
+                .html
+                .contains(r#"<span class="math inline">E = mc^2</span>"#),
+            "{}",
+            result.html
+        );
+        assert_eq!(result.toc[0].title, "Energy E = mc^2");
+        assert!(result.html.contains(r#"alt="x plot""#), "{}", result.html);
+    }
+
     #[test]
     fn test_image_alt_with_raw_html_drops_tags() {
         // Raw HTML inside alt text contributes its visible text but the
@@ -965,12 +1399,72 @@ mod tests {
         assert_eq!(result.warnings[1], "warning 2");
     }
 
+    #[test]
+    fn test_render_result_deduplicates_warnings() {
+        let result = MarkdownRenderer::<HtmlBackend>::new().render(
+            "Hello",
+            Pipeline::new()
+                .with_processor(WarningProcessor::new(vec![
+                    "b".into(),
+                    "a".into(),
+                    "b".into(),
+                ]))
+                .with_processor(WarningProcessor::new(vec!["a".into(), "c".into()])),
+        );
+
+        assert_eq!(result.warnings, ["b", "a", "c"]);
+    }
+
     #[test]
     fn test_render_result_empty_warnings_by_default() {
         let result = render_html("Hello");
         assert!(result.warnings.is_empty());
     }
 
+    struct StatsProcessor {
+        stats: Vec<CodeBlockStats>,
+    }
+
+    impl CodeBlockProcessor for StatsProcessor {
+        fn process(
+            &mut self,
+            _language: &str,
+            _attrs: &FenceAttrs,
+            _source: &str,
+            _index: usize,
+        ) -> ProcessResult {
+            ProcessResult::PassThrough
+        }
+
+        fn stats(&self) -> &[CodeBlockStats] {
+            &self.stats
+        }
+    }
+
+    #[test]
+    fn test_render_result_collects_processor_stats() {
+        let stat = |index| CodeBlockStats {
+            index,
+            language: "plantuml".to_owned(),
+            from_cache: index == 0,
+            duration: std::time::Duration::from_millis(5),
+            bytes: 100,
+        };
+        let result = MarkdownRenderer::<HtmlBackend>::new().render(
+            "Hello",
+            Pipeline::new()
+                .with_processor(StatsProcessor {
+                    stats: vec![stat(0)],
+                })
+                .with_processor(StatsProcessor {
+                    stats: vec![stat(1)],
+                }),
+        );
+
+        assert_eq!(result.code_block_stats, [stat(0), stat(1)]);
+        assert!(render_html("Hello").code_block_stats.is_empty());
+    }
+
     struct TransientErrorProcessor {
         transient: bool,
     }
@@ -1555,6 +2049,60 @@ Install with apt.
         assert!(result.html.contains(r#"href="/domains/billing/use-cases""#));
     }
 
+    // Link checker tests
+
+    struct KnownPages;
+
+    impl LinkChecker for KnownPages {
+        fn page_exists(&self, path: &str) -> bool {
+            matches!(path, "guide" | "guide/setup")
+        }
+    }
+
+    fn render_checked(markdown: &str) -> RenderResult {
+        MarkdownRenderer::<HtmlBackend>::new()
+            .with_base_path("/guide")
+            .with_link_checker(KnownPages)
+            .render(markdown, Pipeline::new())
+    }
+
+    #[test]
+    fn link_checker_warns_on_missing_page() {
+        let result = render_checked("[Missing](./missing.md)");
+
+        assert_eq!(
+            result.warnings,
+            vec!["broken link to ./missing.md: no page at /guide/missing".to_owned()]
+        );
+        assert!(result.html.contains(r#"href="/guide/missing""#));
+    }
+
+    #[test]
+    fn link_checker_accepts_existing_pages() {
+        let result =
+            render_checked("[Setup](setup.md#install) [Guide](index.md) [Abs](/guide/setup.md)");
+
+        assert!(result.warnings.is_empty(), "{:?}", result.warnings);
+    }
+
+    #[test]
+    fn link_checker_skips_external_anchor_and_image_links() {
+        let result = render_checked(
+            "[Ext](https://example.com/x.md) [Anchor](#section) [Pdf](./file.pdf) ![Img](./missing.md)",
+        );
+
+        assert!(result.warnings.is_empty(), "{:?}", result.warnings);
+    }
+
+    #[test]
+    fn link_checker_needs_base_path() {
+        let result = MarkdownRenderer::<HtmlBackend>::new()
+            .with_link_checker(KnownPages)
+            .render("[Missing](./missing.md)", Pipeline::new());
+
+        assert!(result.warnings.is_empty());
+    }
+
     // Wikilink tests
 
     struct StaticTitleResolver;
@@ -1780,6 +2328,45 @@ Install with apt.
         );
     }
 
+    #[test]
+    fn wikilink_broken_link_warns() {
+        let result = render_wikilink("[[nonexistent:unknown::page]]");
+        assert_eq!(
+            result.warnings,
+            vec![
+                "broken wikilink [[nonexistent:unknown::page]]: no matching section or page"
+                    .to_owned()
+            ]
+        );
+    }
+
+    #[test]
+    fn wikilink_page_name_resolves_through_link_checker() {
+        let result = MarkdownRenderer::<HtmlBackend>::new()
+            .with_wikilinks(true)
+            .with_base_path("/guide")
+            .with_link_checker(KnownPages)
+            .render(
+                "[[Setup|Setting up]] [[Guide]] `[[Setup]]`\n\n```\n[[Setup]]\n```",
+                Pipeline::new(),
+            );
+
+        assert!(
+            result
+                .html
+                .contains(r#"<a href="/guide/setup">Setting up</a>"#),
+            "html: {}",
+            result.html
+        );
+        assert!(
+            result.html.contains(r#"<a href="/guide">guide</a>"#),
+            "html: {}",
+            result.html
+        );
+        assert!(result.html.contains("<code>[[Setup]]</code>"));
+        assert!(result.warnings.is_empty(), "{:?}", result.warnings);
+    }
+
     #[test]
     fn wikilink_broken_link_display_text() {
         let result = render_wikilink("[[nonexistent:unknown::page]]");
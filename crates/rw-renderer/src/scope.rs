@@ -2,7 +2,7 @@
 //!
 //! [`Scope`] is the dispatch type the renderer pushes onto its stack when a
 //! heading or an image opens, and pops when it closes. The renderer's inline
-//! event methods (`text`, `inline_code`, `raw_html`, `soft_break`,
+//! event methods (`text`, `inline_code`, `math`, `raw_html`, `soft_break`,
 //! `hard_break`) and `with_markup_buffer` dispatch on `self.scopes.last_mut()`
 //! to choose where to write.
 //!
@@ -22,7 +22,7 @@
 ///
 /// Pushed in `start_tag` when an inline-capture region opens; popped in
 /// `end_tag` when it closes. Inline event methods (`text`, `inline_code`,
-/// `raw_html`, `soft_break`, `hard_break`) and `with_markup_buffer`
+/// `math`, `raw_html`, `soft_break`, `hard_break`) and `with_markup_buffer`
 /// dispatch on `self.scopes.last_mut()` to choose where to write.
 ///
 /// Cross-instance accumulators (TOC entries, title, `id_counts`,
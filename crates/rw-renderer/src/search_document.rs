@@ -140,6 +140,13 @@ impl RenderBackend for SearchDocumentBackend {
         out.push_str(code);
     }
 
+    fn math(tex: &str, display: bool, out: &mut String) {
+        out.push_str(tex);
+        if display {
+            out.push(' ');
+        }
+    }
+
     fn link_start(_href: &str, _section_ref: Option<(&str, &str)>, _out: &mut String) {}
     fn link_end(_out: &mut String) {}
     fn broken_link_start(_out: &mut String) {}
@@ -132,6 +132,14 @@ impl TabsDirective {
             return DirectiveOutput::Html(String::new());
         };
 
+        // Element ids come from the numeric tab id, so a repeated label never
+        // collides in the DOM, but readers can't tell the buttons apart.
+        if group.tabs.iter().any(|tab| tab.label == label) {
+            self.warnings.push(format!(
+                "duplicate tab label `{label}` in a `::::tabs` group; readers cannot tell these tabs apart"
+            ));
+        }
+
         let tab_id = self.next_tab_id;
         self.next_tab_id += 1;
         let is_first = group.tabs.is_empty();
@@ -382,4 +390,58 @@ mod tests {
             result.warnings
         );
     }
+
+    #[test]
+    fn duplicate_labels_get_distinct_ids_in_source_order_and_warn() {
+        let result = render(
+            "::::tabs\n\n:::tab[Same]\n\nfirst\n\n:::\n\n:::tab[Same]\n\nsecond\n\n:::\n\n::::",
+        );
+        let buttons: Vec<_> = result
+            .html
+            .match_indices(r#"<button role="tab" id="#)
+            .collect();
+        assert_eq!(buttons.len(), 2, "got: {}", result.html);
+        let tab0 = result.html.find(r#"id="tab-0-0""#).expect("first tab id");
+        let tab1 = result.html.find(r#"id="tab-0-1""#).expect("second tab id");
+        assert!(tab0 < tab1, "buttons out of order: {}", result.html);
+        let first = result.html.find("first").expect("first panel");
+        let second = result.html.find("second").expect("second panel");
+        assert!(first < second, "panels out of order: {}", result.html);
+        assert!(
+            result
+                .html
+                .contains(r#"id="panel-0-0" aria-labelledby="tab-0-0">"#),
+            "got: {}",
+            result.html
+        );
+        assert!(
+            result
+                .html
+                .contains(r#"id="panel-0-1" aria-labelledby="tab-0-1" hidden>"#),
+            "got: {}",
+            result.html
+        );
+        assert_eq!(
+            result
+                .warnings
+                .iter()
+                .filter(|w| w.contains("duplicate tab label `Same`"))
+                .count(),
+            1,
+            "expected one duplicate-label warning: {:?}",
+            result.warnings
+        );
+    }
+
+    #[test]
+    fn same_label_in_different_groups_does_not_warn() {
+        let result = render(
+            "::::tabs\n\n:::tab[A]\n\nx\n\n:::\n\n::::\n\n::::tabs\n\n:::tab[A]\n\ny\n\n:::\n\n::::",
+        );
+        assert!(
+            result.warnings.is_empty(),
+            "warnings: {:?}",
+            result.warnings
+        );
+    }
 }
@@ -0,0 +1,211 @@
+//! The fenced alternative to `::::tabs`: a ` ```tabs ` code block whose body
+//! is split into tabs by `== Label` lines.
+//!
+//! ````markdown
+//! ```tabs
+//! == macOS
+//! Install with Homebrew.
+//! == Linux
+//! Install with apt.
+//! ```
+//! ````
+//!
+//! # Grammar
+//!
+//! - A **delimiter** is a line that starts with `==` at column 0, followed by
+//!   at least one space or tab and a label. The label is the rest of the line
+//!   with surrounding whitespace removed, and must not be empty.
+//! - Each delimiter opens a tab. Its content is every line up to the next
+//!   delimiter or the end of the body, parsed as markdown.
+//! - Lines before the first delimiter must be blank. A body that breaks this,
+//!   or has no delimiter at all, is not a tab group: it renders as an ordinary
+//!   code block.
+//! - A `==` line inside a fenced code block within a tab is content, not a
+//!   delimiter. Such inner fences follow `CommonMark`: the outer ` ```tabs `
+//!   fence ends at the first line of at least as many backticks, so a tab
+//!   containing a ` ``` ` block needs an outer fence of four or more backticks
+//!   (or `~~~tabs`).
+//!
+//! The walker turns the split body into the same directive events `::::tabs`
+//! and `:::tab[Label]` produce, so both forms render identically. Each tab's
+//! content is parsed on its own: a reference-style link there cannot use a
+//! definition from outside the fence.
+
+use std::ops::Range;
+
+/// One tab of a fenced group: its label and the byte range of its content
+/// within the fence body.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct FencedTab<'a> {
+    pub(crate) label: &'a str,
+    pub(crate) content: Range<usize>,
+}
+
+/// Split a ` ```tabs ` body into tabs, or `None` if it is not a tab group (see
+/// the [module docs](self) for the grammar).
+pub(crate) fn split_tabs(body: &str) -> Option<Vec<FencedTab<'_>>> {
+    let mut tabs: Vec<FencedTab<'_>> = Vec::new();
+    // The open inner fence's character and length.
+    let mut inner_fence: Option<(u8, usize)> = None;
+    let mut offset = 0;
+
+    for line in body.split_inclusive('\n') {
+        let start = offset;
+        offset += line.len();
+
+        if let Some((ch, len)) = inner_fence {
+            if closes_fence(line, ch, len) {
+                inner_fence = None;
+            }
+            continue;
+        }
+        if let Some(label) = delimiter_label(line) {
+            if let Some(last) = tabs.last_mut() {
+                last.content.end = start;
+            }
+            tabs.push(FencedTab {
+                label,
+                content: offset..offset,
+            });
+            continue;
+        }
+        if tabs.is_empty() {
+            if !line.trim().is_empty() {
+                return None;
+            }
+            continue;
+        }
+        inner_fence = opens_fence(line);
+    }
+
+    let last = tabs.last_mut()?;
+    last.content.end = body.len();
+    Some(tabs)
+}
+
+/// The label of a `== Label` delimiter line.
+fn delimiter_label(line: &str) -> Option<&str> {
+    let rest = line.strip_prefix("==")?;
+    if !rest.starts_with([' ', '\t']) {
+        return None;
+    }
+    let label = rest.trim();
+    (!label.is_empty()).then_some(label)
+}
+
+/// The character and length of the fence `line` opens, if any.
+fn opens_fence(line: &str) -> Option<(u8, usize)> {
+    let trimmed = strip_indent(line)?;
+    let ch = *trimmed.as_bytes().first()?;
+    if ch != b'`' && ch != b'~' {
+        return None;
+    }
+    let len = trimmed.bytes().take_while(|&b| b == ch).count();
+    // A backtick fence's info string may not contain backticks.
+    if len < 3 || (ch == b'`' && trimmed[len..].contains('`')) {
+        return None;
+    }
+    Some((ch, len))
+}
+
+/// Whether `line` closes a fence of `len` or more `ch`s.
+fn closes_fence(line: &str, ch: u8, len: usize) -> bool {
+    let Some(trimmed) = strip_indent(line) else {
+        return false;
+    };
+    let run = trimmed.bytes().take_while(|&b| b == ch).count();
+    run >= len && trimmed[run..].trim().is_empty()
+}
+
+/// `line` without up to three spaces of indentation, or `None` if it is
+/// indented further (an indented code line, not a fence).
+fn strip_indent(line: &str) -> Option<&str> {
+    let spaces = line.bytes().take_while(|&b| b == b' ').count();
+    (spaces <= 3).then(|| &line[spaces..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels_and_content(body: &str) -> Vec<(&str, &str)> {
+        split_tabs(body)
+            .unwrap()
+            .into_iter()
+            .map(|tab| (tab.label, &body[tab.content]))
+            .collect()
+    }
+
+    #[test]
+    fn splits_on_delimiters() {
+        let body = "== macOS\nInstall with Homebrew.\n== Linux\nInstall with apt.\n";
+
+        assert_eq!(
+            labels_and_content(body),
+            [
+                ("macOS", "Install with Homebrew.\n"),
+                ("Linux", "Install with apt.\n"),
+            ]
+        );
+    }
+
+    #[test]
+    fn label_is_trimmed_and_may_contain_spaces() {
+        assert_eq!(
+            labels_and_content("==   Windows 11  \nText\n"),
+            [("Windows 11", "Text\n")]
+        );
+    }
+
+    #[test]
+    fn leading_blank_lines_are_allowed() {
+        assert_eq!(labels_and_content("\n  \n== A\nx\n"), [("A", "x\n")]);
+    }
+
+    #[test]
+    fn content_before_first_delimiter_is_not_a_group() {
+        assert_eq!(split_tabs("intro\n== A\nx\n"), None);
+    }
+
+    #[test]
+    fn body_without_delimiters_is_not_a_group() {
+        assert_eq!(split_tabs("just code\n"), None);
+        assert_eq!(split_tabs(""), None);
+    }
+
+    #[test]
+    fn not_delimiters() {
+        // No space after `==`, an empty label, or indentation.
+        assert_eq!(split_tabs("==A\n"), None);
+        assert_eq!(split_tabs("==  \n"), None);
+        assert_eq!(split_tabs(" == A\n"), None);
+    }
+
+    #[test]
+    fn delimiter_inside_inner_fence_is_content() {
+        let body = "== Shell\n```sh\n== not a tab\n```\n== Python\nprint()\n";
+
+        assert_eq!(
+            labels_and_content(body),
+            [
+                ("Shell", "```sh\n== not a tab\n```\n"),
+                ("Python", "print()\n"),
+            ]
+        );
+    }
+
+    #[test]
+    fn inner_fence_closes_only_on_a_long_enough_run() {
+        let body = "== A\n~~~~\n~~~\n== still code\n~~~~\n== B\nx\n";
+
+        assert_eq!(
+            labels_and_content(body),
+            [("A", "~~~~\n~~~\n== still code\n~~~~\n"), ("B", "x\n")]
+        );
+    }
+
+    #[test]
+    fn empty_tab_has_empty_content() {
+        assert_eq!(labels_and_content("== A\n== B\nx"), [("A", ""), ("B", "x")]);
+    }
+}
@@ -14,6 +14,10 @@
 //! ::::
 //! ```
 //!
+//! The same group can be written as a ` ```tabs ` fence with `== Label` lines
+//! between the tabs, which renders identically whenever [`TabsDirective`] is
+//! registered. See the `fence` module for its grammar.
+//!
 //! # Architecture
 //!
 //! A tab bar can only be rendered once every tab in the group is known, which
@@ -61,5 +65,6 @@
 //! ```
 
 mod directive;
+pub(crate) mod fence;
 
 pub use directive::TabsDirective;
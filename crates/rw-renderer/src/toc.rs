@@ -7,7 +7,9 @@
 //! "have we seen the first H1?" flag) across an entire document render.
 
 use std::collections::HashMap;
+use std::sync::Arc;
 
+use crate::config::SlugifyFn;
 use crate::util::slugify_into;
 
 /// A single heading in the table of contents.
@@ -39,7 +41,8 @@ use crate::util::slugify_into;
 pub struct TocEntry {
     /// Heading level (1–6), adjusted for the backend when
     /// [`TITLE_AS_METADATA`](crate::RenderBackend::TITLE_AS_METADATA) is `true`
-    /// (headings shift up by one after the title H1).
+    /// or the title heading is suppressed (headings shift up by one after the
+    /// title H1).
     pub level: u8,
     /// Plain-text heading content (inline formatting stripped).
     pub title: String,
@@ -64,6 +67,8 @@ pub(crate) struct CompletedHeading {
     /// the heading's open and close tags. Encoding/escaping is whatever the
     /// active `RenderBackend` produced during the inline phase.
     pub rendered_html: String,
+    /// Whether this is the HTML-mode first H1, captured as the page title.
+    pub is_title: bool,
 }
 
 /// Persistent accumulator for heading-related output across an entire
@@ -88,6 +93,10 @@ pub(crate) struct HeadingAccumulator {
     seen_first_h1: bool,
     /// Table of contents entries.
     toc: Vec<TocEntry>,
+    /// Deepest (adjusted) level collected into `toc`; `None` collects all.
+    toc_max_depth: Option<u8>,
+    /// Custom slug function; `None` uses [`slugify_into`].
+    slugify: Option<Arc<SlugifyFn>>,
     /// Every string already claimed as an id in this render, mapped to the
     /// next suffix to try when that string comes up again as a base slug.
     ///
@@ -113,11 +122,29 @@ impl HeadingAccumulator {
             title: None,
             seen_first_h1: false,
             toc: Vec::new(),
+            toc_max_depth: None,
+            slugify: None,
             claimed_ids: HashMap::new(),
             slug_scratch: String::new(),
         }
     }
 
+    /// Leave headings deeper than `max_depth` out of the TOC. Compared with
+    /// the adjusted level, so in Confluence mode an H3 counts as depth 2.
+    #[must_use]
+    pub fn with_toc_max_depth(mut self, max_depth: Option<u8>) -> Self {
+        self.toc_max_depth = max_depth;
+        self
+    }
+
+    /// Slug heading text with `slugify` instead of the built-in slugs.
+    /// De-duplication still applies to whatever it returns.
+    #[must_use]
+    pub fn with_slugify(mut self, slugify: Option<Arc<SlugifyFn>>) -> Self {
+        self.slugify = slugify;
+        self
+    }
+
     /// Whether `level` is the Confluence-mode first H1 that should be
     /// title-extracted and skipped from output. Consulted at
     /// `Tag::Heading` start time to set `Scope::Heading::in_first_h1`.
@@ -153,7 +180,10 @@ impl HeadingAccumulator {
             self.seen_first_h1 = true;
         }
         let adjusted_level = self.adjusted_level(level);
-        if !is_title {
+        let in_toc = self
+            .toc_max_depth
+            .is_none_or(|max_depth| adjusted_level <= max_depth);
+        if !is_title && in_toc {
             self.toc.push(TocEntry {
                 level: adjusted_level,
                 title: toc_text.trim().to_owned(),
@@ -164,6 +194,7 @@ impl HeadingAccumulator {
             adjusted_level,
             id,
             rendered_html,
+            is_title,
         }
     }
 
@@ -184,7 +215,12 @@ impl HeadingAccumulator {
     /// for this document: a numeric suffix is bumped until the candidate is
     /// unused, even when it would collide with another heading's slug.
     fn generate_id(&mut self, text: &str) -> String {
-        slugify_into(text, &mut self.slug_scratch);
+        if let Some(slugify) = &self.slugify {
+            self.slug_scratch.clear();
+            self.slug_scratch.push_str(&slugify(text.trim()));
+        } else {
+            slugify_into(text, &mut self.slug_scratch);
+        }
         if self.slug_scratch.is_empty() {
             self.slug_scratch.push_str("section");
         }
@@ -238,10 +274,12 @@ mod tests {
         assert_eq!(done.adjusted_level, 1);
         assert_eq!(done.id, "my-title");
         assert_eq!(done.rendered_html, "My Title");
+        assert!(done.is_title);
 
         // H2: rendered at level 2 (no shift in HTML mode).
         let done = acc.complete_heading(2, "Section", "Section".to_owned());
         assert_eq!(done.adjusted_level, 2);
+        assert!(!done.is_title);
 
         assert_eq!(acc.take_title(), Some("My Title".to_owned()));
         // Title is NOT in the TOC; only H2 is.
@@ -279,6 +317,58 @@ mod tests {
         assert_eq!(toc[0].id, "section");
     }
 
+    #[test]
+    fn test_toc_max_depth_keeps_ids_of_excluded_headings() {
+        let mut acc = HeadingAccumulator::new(false, false).with_toc_max_depth(Some(2));
+
+        acc.complete_heading(2, "Setup", "Setup".to_owned());
+        let deep = acc.complete_heading(3, "Details", "Details".to_owned());
+        assert_eq!(deep.id, "details");
+        // Ids are still claimed for excluded headings.
+        let again = acc.complete_heading(3, "Details", "Details".to_owned());
+        assert_eq!(again.id, "details-1");
+
+        let toc = acc.take_toc();
+        assert_eq!(toc.len(), 1);
+        assert_eq!(toc[0].id, "setup");
+    }
+
+    #[test]
+    fn test_toc_max_depth_applies_after_confluence_shift() {
+        let mut acc = HeadingAccumulator::new(true, true).with_toc_max_depth(Some(2));
+        acc.complete_first_h1("My Title");
+
+        acc.complete_heading(2, "Section", "Section".to_owned());
+        acc.complete_heading(3, "Subsection", "Subsection".to_owned());
+        acc.complete_heading(4, "Detail", "Detail".to_owned());
+
+        let levels: Vec<u8> = acc.take_toc().iter().map(|e| e.level).collect();
+        assert_eq!(
+            levels,
+            [1, 2],
+            "H2 and H3 shift to depths 1 and 2; H4 is out"
+        );
+    }
+
+    #[test]
+    fn test_custom_slugify_is_deduplicated() {
+        let slugify: Arc<SlugifyFn> = Arc::new(|text: &str| text.to_lowercase().replace(' ', "_"));
+        let mut acc = HeadingAccumulator::new(false, false).with_slugify(Some(slugify));
+
+        assert_eq!(
+            acc.complete_heading(2, " Foo Bar ", String::new()).id,
+            "foo_bar"
+        );
+        assert_eq!(
+            acc.complete_heading(2, "Foo Bar", String::new()).id,
+            "foo_bar-1"
+        );
+        assert_eq!(acc.complete_heading(2, "", String::new()).id, "section");
+
+        let ids: Vec<String> = acc.take_toc().into_iter().map(|e| e.id).collect();
+        assert_eq!(ids, ["foo_bar", "foo_bar-1", "section"]);
+    }
+
     #[test]
     fn test_generate_id_suffix_collision_is_unique() {
         // "Foo 1" slugifies to "foo-1", which must NOT collide with the
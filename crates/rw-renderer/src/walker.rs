@@ -37,6 +37,7 @@
 use std::collections::BTreeSet;
 use std::marker::PhantomData;
 
+use crate::autolink;
 use crate::backend::RenderBackend;
 use crate::code_block::{CodeBlockProcessor, ProcessResult};
 use crate::config::RenderConfig;
@@ -53,10 +54,11 @@ use crate::renderer::RenderResult;
 use crate::scope::Scope;
 use crate::status::{STATUS_NAME, StatusColor};
 use crate::table::TableState;
+use crate::tabs::fence::{FencedTab, split_tabs};
 use crate::toc::HeadingAccumulator;
 use crate::wikilink::{self, WikilinkResolution};
 use rw_parser::AlertKind;
-use rw_parser::{Event, LinkKind, Tag, TagEnd};
+use rw_parser::{BlockDirectivePayload, Event, LinkKind, Parser, Tag, TagEnd};
 use rw_parser::{InlineMatch, parse_line};
 
 pub(crate) struct Walker<'r, B: RenderBackend> {
@@ -77,15 +79,22 @@ pub(crate) struct Walker<'r, B: RenderBackend> {
     /// zero-capacity `String`s and grows them from empty.
     spare_heading_buffers: Option<(String, String)>,
     scopes: Vec<Scope>,
+    /// Links open around the current text; bare URLs are only autolinked at
+    /// zero.
+    link_depth: usize,
     /// Canonical section refs referenced by prose links in this document.
     section_refs: BTreeSet<String>,
+    /// Warnings raised by the walk itself (broken links), reported ahead of
+    /// code block processor warnings.
+    warnings: Vec<String>,
     _backend: PhantomData<B>,
 }
 
 impl<'r, B: RenderBackend> Walker<'r, B> {
     /// Construct a fresh walker. Per-render state starts empty;
-    /// `HeadingAccumulator` is built from the config's `extract_title` flag
-    /// and the backend's `TITLE_AS_METADATA` constant. `output` is pre-allocated
+    /// `HeadingAccumulator` is built from the config's `extract_title` and
+    /// `suppress_title_heading` flags and the backend's `TITLE_AS_METADATA`
+    /// constant. `output` is pre-allocated
     /// at 4 KiB to give average-sized documents a warm start.
     pub(crate) fn new(
         cfg: &'r RenderConfig,
@@ -104,12 +113,19 @@ impl<'r, B: RenderBackend> Walker<'r, B> {
             holes: Holes::default(),
             list_stack: Vec::new(),
             table: TableState::default(),
-            heading: HeadingAccumulator::new(cfg.extract_title, B::TITLE_AS_METADATA),
+            heading: HeadingAccumulator::new(
+                cfg.extract_title,
+                B::TITLE_AS_METADATA || cfg.suppress_title_heading,
+            )
+            .with_toc_max_depth(cfg.toc_max_depth)
+            .with_slugify(cfg.slugify.clone()),
             alert_stack: Vec::new(),
             code_block_index: 0,
             spare_heading_buffers: None,
             scopes: Vec::new(),
+            link_depth: 0,
             section_refs: BTreeSet::new(),
+            warnings: Vec::new(),
             _backend: PhantomData,
         }
     }
@@ -128,8 +144,8 @@ impl<'r, B: RenderBackend> Walker<'r, B> {
     /// 3. Collect fills from directive handlers and code-block processors, then
     ///    assemble: one pass, copying spans of the buffer and writing each fill
     ///    at its reserved offset.
-    /// 4. Collect code-block processor warnings, transient-error state, and
-    ///    section refs. `has_transient_error` is populated only during step 3
+    /// 4. Collect code-block processor warnings, transient-error state, stats,
+    ///    and section refs. `has_transient_error` is populated only during step 3
     ///    (by [`CodeBlockProcessor::fills`]). Section refs come from two
     ///    sources: `self.section_refs`, accumulated during the walk itself from
     ///    prose links and wikilinks, plus each processor's `section_refs()`
@@ -201,17 +217,19 @@ impl<'r, B: RenderBackend> Walker<'r, B> {
         // that transforms the buffer — see this function's doc comment.
         html = holes.assemble(html, &fills, B::raw_html);
 
-        let warnings = self
-            .processors
-            .iter()
-            .flat_map(|p| p.warnings())
-            .cloned()
-            .collect();
+        let mut warnings = std::mem::take(&mut self.warnings);
+        warnings.extend(self.processors.iter().flat_map(|p| p.warnings()).cloned());
         let has_transient_error = self.processors.iter().any(|p| p.has_transient_error());
         let mut section_refs = std::mem::take(&mut self.section_refs);
         for processor in self.processors.iter() {
             section_refs.extend(processor.section_refs().iter().cloned());
         }
+        let code_block_stats = self
+            .processors
+            .iter()
+            .flat_map(|p| p.stats())
+            .cloned()
+            .collect();
         RenderResult {
             html,
             title: self.heading.take_title(),
@@ -219,6 +237,7 @@ impl<'r, B: RenderBackend> Walker<'r, B> {
             warnings,
             has_transient_error,
             section_refs,
+            code_block_stats,
         }
     }
 
@@ -237,12 +256,25 @@ impl<'r, B: RenderBackend> Walker<'r, B> {
             Event::Code(code) => {
                 self.inline_code(&code);
             }
+            Event::InlineMath(tex) => self.math(&tex, false),
+            Event::DisplayMath(tex) => self.math(&tex, true),
             Event::RawHtml(html) => self.raw_html(&html),
             Event::SoftBreak => self.soft_break(),
             Event::HardBreak => self.hard_break(),
             Event::Rule => self.horizontal_rule(),
             Event::TaskListMarker(checked) => self.task_list_marker(checked),
             Event::CodeBlock(payload) => {
+                if payload.language.as_deref() == Some("tabs")
+                    && self
+                        .directives
+                        .as_ref()
+                        .is_some_and(|directives| directives.has_container("tabs"))
+                    && let Some(tabs) = split_tabs(&payload.source)
+                {
+                    self.fenced_tabs(&payload.source, &tabs);
+                    return;
+                }
+
                 let index = self.code_block_index;
                 self.code_block_index += 1;
 
@@ -294,11 +326,7 @@ impl<'r, B: RenderBackend> Walker<'r, B> {
                 }
 
                 if !handled {
-                    B::code_block(
-                        payload.language.as_deref(),
-                        &payload.source,
-                        &mut self.output,
-                    );
+                    self.code_block(payload.language.as_deref(), &payload.source);
                 }
             }
             // Block directives arrive already parsed: the decision is made
@@ -328,6 +356,31 @@ impl<'r, B: RenderBackend> Walker<'r, B> {
         }
     }
 
+    /// Render a ` ```tabs ` fence by replaying the events the equivalent
+    /// `::::tabs` group produces, so the two forms cannot render differently.
+    /// Each tab's content is parsed on its own and walked in place.
+    fn fenced_tabs(&mut self, body: &str, tabs: &[FencedTab<'_>]) {
+        self.handle(Event::ContainerDirectiveStart(BlockDirectivePayload {
+            name: "tabs".to_owned(),
+            args: DirectiveArgs::default(),
+            colon_count: 4,
+        }));
+        for tab in tabs {
+            self.handle(Event::ContainerDirectiveStart(BlockDirectivePayload {
+                name: "tab".to_owned(),
+                args: DirectiveArgs::parse(tab.label, ""),
+                colon_count: 3,
+            }));
+            let mut parser =
+                Parser::new_ext(&body[tab.content.clone()], self.cfg.parse_options(true));
+            while let Some(event) = parser.next() {
+                self.handle(event);
+            }
+            self.handle(Event::ContainerDirectiveEnd { colon_count: 3 });
+        }
+        self.handle(Event::ContainerDirectiveEnd { colon_count: 4 });
+    }
+
     /// Buffers for a heading's plain-text shadow and formatted HTML body,
     /// reusing the previous heading's pair when there is one. The initial
     /// capacities cover a typical heading without a growth chain.
@@ -394,11 +447,11 @@ impl<'r, B: RenderBackend> Walker<'r, B> {
         // no processor handles it. The open/close tags route through
         // `with_markup_buffer` like any other inline tag, so status obeys the same
         // scope rules (e.g. inside a heading), while the label goes through
-        // `self.text` for escaping and heading-slug/alt-text capture.
+        // `self.plain_text` for escaping and heading-slug/alt-text capture.
         if name == STATUS_NAME {
             let color = StatusColor::from(args.get("color").unwrap_or_default());
             self.with_markup_buffer(|out| B::status_open(color, out));
-            self.text(args.content().trim());
+            self.plain_text(args.content().trim());
             self.with_markup_buffer(B::status_close);
             return;
         }
@@ -439,13 +492,21 @@ impl<'r, B: RenderBackend> Walker<'r, B> {
                     }
                 }
             }
+            DirectiveOutput::Markdown(_) => {
+                if let Some(p) = self.directives.as_deref_mut() {
+                    p.push_warning(format!(
+                        "inline directive ':{name}' returned Markdown; it was ignored (only leaf directives can splice markdown)"
+                    ));
+                }
+                self.plain_text(raw);
+            }
             DirectiveOutput::Skip => {
                 if let Some(p) = self.directives.as_deref_mut() {
                     p.push_warning(format!(
                         "unknown inline directive ':{name}' — no handler registered (or handler returned Skip)"
                     ));
                 }
-                self.text(raw);
+                self.plain_text(raw);
             }
         }
     }
@@ -463,6 +524,24 @@ impl<'r, B: RenderBackend> Walker<'r, B> {
         B::paragraph_end(&mut self.output);
     }
 
+    /// Render a code block no processor claimed: highlighted when a
+    /// highlighter is set and knows `lang`, else plain or line-structured.
+    fn code_block(&mut self, lang: Option<&str>, source: &str) {
+        #[cfg(feature = "syntax-highlighting")]
+        if let Some(highlighter) = &self.cfg.highlighter
+            && let Some(lang) = lang
+            && let Some(html) = highlighter.highlight(lang, source)
+        {
+            B::highlighted_code_block(lang, source, &html, &mut self.output);
+            return;
+        }
+        if self.cfg.code_block_lines {
+            B::code_block_with_lines(lang, source, &mut self.output);
+        } else {
+            B::code_block(lang, source, &mut self.output);
+        }
+    }
+
     /// Dispatch a recognized block directive through the processor and render
     /// the result. Pattern-B borrow discipline: the `&mut self.directives`
     /// reborrow is dropped (owned `BlockDispatch` returned) before any
@@ -499,6 +578,19 @@ impl<'r, B: RenderBackend> Walker<'r, B> {
             BlockDispatch::Html(html) => self.raw_html(&html),
             BlockDispatch::Deferred { parts, source } => self.emit_parts(parts, source),
             BlockDispatch::PassThrough(text) => self.emit_text_paragraph(&text),
+            BlockDispatch::Markdown { markdown, leaf } => self.splice_markdown(&markdown, leaf),
+        }
+    }
+
+    /// Walk markdown a leaf directive returned, as if it had been written in
+    /// the directive's place, then report back to the handler.
+    fn splice_markdown(&mut self, markdown: &str, leaf: usize) {
+        let mut parser = Parser::new_ext(markdown, self.cfg.parse_options(true));
+        while let Some(event) = parser.next() {
+            self.handle(event);
+        }
+        if let Some(p) = self.directives.as_deref_mut() {
+            p.leaf_spliced(leaf);
         }
     }
 
@@ -606,6 +698,7 @@ impl<'r, B: RenderBackend> Walker<'r, B> {
                 kind: LinkKind::Wiki { has_pothole },
                 dest_url,
             } => {
+                self.link_depth += 1;
                 let resolution = wikilink::resolve(self.cfg, &dest_url);
                 match &resolution {
                     WikilinkResolution::Resolved {
@@ -625,8 +718,11 @@ impl<'r, B: RenderBackend> Walker<'r, B> {
                         let href = format!("#{fragment}");
                         self.with_markup_buffer(|out| B::link_start(&href, None, out));
                     }
-                    WikilinkResolution::Broken { .. } => {
+                    WikilinkResolution::Broken { raw_target } => {
                         self.with_markup_buffer(B::broken_link_start);
+                        self.warnings.push(format!(
+                            "broken wikilink [[{raw_target}]]: no matching section or page"
+                        ));
                     }
                 }
                 if !has_pothole {
@@ -638,6 +734,7 @@ impl<'r, B: RenderBackend> Walker<'r, B> {
                 kind: LinkKind::Other,
                 dest_url,
             } => {
+                self.link_depth += 1;
                 let dest_url = link::strip_origin(self.cfg, &dest_url);
                 let base = link::link_base(self.cfg);
                 let href = B::transform_link(&dest_url, base);
@@ -647,6 +744,13 @@ impl<'r, B: RenderBackend> Walker<'r, B> {
                 }
                 let section_attrs = section_ref.as_ref().map(|(r, p)| (r.as_str(), p.as_str()));
                 self.with_markup_buffer(|out| B::link_start(&href, section_attrs, out));
+                if let Some(checker) = &self.cfg.link_checker
+                    && let Some(target) = link::checked_target(self.cfg, &dest_url)
+                    && !checker.page_exists(&target)
+                {
+                    self.warnings
+                        .push(format!("broken link to {dest_url}: no page at /{target}"));
+                }
             }
             Tag::Image { dest_url, title } => {
                 let dest_url = link::strip_origin(self.cfg, &dest_url).into_owned();
@@ -694,6 +798,9 @@ impl<'r, B: RenderBackend> Walker<'r, B> {
                         .complete_heading(level, &toc_text, rendered_html);
                     B::heading_start(done.adjusted_level, &done.id, &mut self.output);
                     self.output.push_str(done.rendered_html.trim());
+                    if self.cfg.heading_anchors && !done.is_title {
+                        B::heading_anchor(&done.id, toc_text.trim(), &mut self.output);
+                    }
                     B::heading_end(done.adjusted_level, &mut self.output);
                     self.store_heading_buffers(toc_text, done.rendered_html);
                 }
@@ -768,6 +875,7 @@ impl<'r, B: RenderBackend> Walker<'r, B> {
                 self.with_markup_buffer(B::strikethrough_end);
             }
             TagEnd::Link => {
+                self.link_depth -= 1;
                 self.with_markup_buffer(B::link_end);
             }
             TagEnd::Superscript => {
@@ -780,6 +888,25 @@ impl<'r, B: RenderBackend> Walker<'r, B> {
     }
 
     fn text(&mut self, text: &str) {
+        if !self.cfg.autolinks
+            || self.link_depth > 0
+            || matches!(self.scopes.last(), Some(Scope::Image { .. }))
+        {
+            self.plain_text(text);
+            return;
+        }
+        let mut rest = text;
+        while let Some(link) = autolink::find(rest) {
+            self.plain_text(&rest[..link.range.start]);
+            self.with_markup_buffer(|out| B::link_start(&link.href, None, out));
+            self.plain_text(&rest[link.range.clone()]);
+            self.with_markup_buffer(B::link_end);
+            rest = &rest[link.range.end..];
+        }
+        self.plain_text(rest);
+    }
+
+    fn plain_text(&mut self, text: &str) {
         match self.scopes.last_mut() {
             Some(Scope::Heading {
                 rendered_html,
@@ -810,6 +937,21 @@ impl<'r, B: RenderBackend> Walker<'r, B> {
         }
     }
 
+    fn math(&mut self, tex: &str, display: bool) {
+        match self.scopes.last_mut() {
+            Some(Scope::Heading {
+                rendered_html,
+                toc_text,
+                ..
+            }) => {
+                toc_text.push_str(tex);
+                B::math(tex, display, rendered_html);
+            }
+            Some(Scope::Image { alt_text, .. }) => alt_text.push_str(tex),
+            None => B::math(tex, display, &mut self.output),
+        }
+    }
+
     fn raw_html(&mut self, html: &str) {
         self.with_markup_buffer(|out| B::raw_html(html, out));
     }
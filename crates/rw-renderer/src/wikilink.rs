@@ -6,6 +6,8 @@
 //! link helpers live in the sibling [`link`](crate::link) module.
 
 use crate::config::RenderConfig;
+use crate::link;
+use crate::util::slugify_into;
 
 /// Result of resolving a wikilink target.
 #[derive(Debug)]
@@ -30,6 +32,9 @@ pub(crate) enum WikilinkResolution {
 /// registry on `cfg`; without one the result is
 /// [`WikilinkResolution::Broken`]. Current-section links (`[[::path]]`)
 /// additionally need `cfg.base_path`.
+///
+/// A target that names no section falls back to a page name (see
+/// [`resolve_page_name`]) when a link checker is configured.
 pub(crate) fn resolve(cfg: &RenderConfig, dest_url: &str) -> WikilinkResolution {
     if let Some(fragment) = dest_url.strip_prefix('#') {
         return WikilinkResolution::Fragment(fragment.to_owned());
@@ -47,12 +52,64 @@ pub(crate) fn resolve(cfg: &RenderConfig, dest_url: &str) -> WikilinkResolution
             section_name: sp.section.name.clone(),
             subpath: sp.path.to_owned(),
         },
-        None => WikilinkResolution::Broken {
-            raw_target: dest_url.to_owned(),
+        None => match resolve_page_name(cfg, dest_url) {
+            Some(path) => WikilinkResolution::Resolved {
+                href: format!("/{path}"),
+                section_ref: String::new(),
+                section_name: String::new(),
+                subpath: path,
+            },
+            None => WikilinkResolution::Broken {
+                raw_target: dest_url.to_owned(),
+            },
         },
     }
 }
 
+/// Resolve a wiki-style page name such as `Getting Started` or
+/// `guides/First Steps` to the path of an existing page.
+///
+/// Each `/`-separated segment is slugified the way heading ids are, so
+/// `[[Getting Started]]` names the `getting-started` page. The path is tried
+/// next to the current page first, then from the site root; a leading `/`
+/// means the root only. Section refs (anything with a `:`) never fall back,
+/// and without a link checker there is no page set to resolve against.
+fn resolve_page_name(cfg: &RenderConfig, target: &str) -> Option<String> {
+    let checker = cfg.link_checker.as_ref()?;
+    if target.contains(':') {
+        return None;
+    }
+
+    let mut slug = String::new();
+    let mut segment = String::new();
+    for part in target.split('/') {
+        slugify_into(part, &mut segment);
+        if segment.is_empty() {
+            continue;
+        }
+        if !slug.is_empty() {
+            slug.push('/');
+        }
+        slug.push_str(&segment);
+    }
+    if slug.is_empty() {
+        return None;
+    }
+
+    let relative = if target.starts_with('/') {
+        None
+    } else {
+        link::link_base(cfg)
+            .map(|base| base.trim_matches('/'))
+            .filter(|dir| !dir.is_empty())
+            .map(|dir| format!("{dir}/{slug}"))
+    };
+    relative
+        .into_iter()
+        .chain([slug])
+        .find(|path| checker.page_exists(path))
+}
+
 /// Return the display text to render for a wikilink, given its resolution.
 ///
 /// For [`WikilinkResolution::Resolved`] the priority is: title resolver (when
@@ -123,6 +180,64 @@ mod tests {
         }
     }
 
+    struct KnownPages;
+
+    impl crate::LinkChecker for KnownPages {
+        fn page_exists(&self, path: &str) -> bool {
+            matches!(path, "getting-started" | "guide/first-steps" | "guide/faq")
+        }
+    }
+
+    fn cfg_with_pages(base_path: &str, is_dir: bool) -> RenderConfig {
+        let mut c = cfg();
+        c.base_path = Some(base_path.to_owned());
+        c.is_dir = is_dir;
+        c.link_checker = Some(Box::new(KnownPages));
+        c
+    }
+
+    fn resolved_href(c: &RenderConfig, target: &str) -> Option<String> {
+        match resolve(c, target) {
+            WikilinkResolution::Resolved { href, .. } => Some(href),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn resolve_page_name_slugifies_each_segment() {
+        let c = cfg_with_pages("/", true);
+        assert_eq!(
+            resolved_href(&c, "Getting Started").as_deref(),
+            Some("/getting-started")
+        );
+        assert_eq!(
+            resolved_href(&c, "Guide/First Steps").as_deref(),
+            Some("/guide/first-steps")
+        );
+    }
+
+    #[test]
+    fn resolve_page_name_prefers_the_current_directory() {
+        // A leaf page `guide/first-steps` links to its sibling by name.
+        let c = cfg_with_pages("/guide/first-steps", false);
+        assert_eq!(resolved_href(&c, "FAQ").as_deref(), Some("/guide/faq"));
+        // Not found next to the page: falls back to the root.
+        assert_eq!(
+            resolved_href(&c, "Getting Started").as_deref(),
+            Some("/getting-started")
+        );
+        // A leading slash skips the current directory.
+        assert_eq!(resolved_href(&c, "/FAQ"), None);
+    }
+
+    #[test]
+    fn resolve_page_name_needs_a_link_checker_and_a_known_page() {
+        assert_eq!(resolved_href(&cfg(), "Getting Started"), None);
+        let c = cfg_with_pages("/", true);
+        assert_eq!(resolved_href(&c, "Missing Page"), None);
+        assert_eq!(resolved_href(&c, "domain:getting-started"), None);
+    }
+
     #[test]
     fn display_text_fragment_replaces_dashes_with_spaces() {
         let c = cfg();
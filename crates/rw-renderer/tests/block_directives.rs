@@ -1065,3 +1065,57 @@ fn unclosed_tab_item_warning_names_tab_not_tabs() {
         result.warnings
     );
 }
+
+/// Render `fenced` and `directive` and require byte-identical results.
+fn assert_same_tabs(fenced: &str, directive: &str) {
+    let fenced = render_tabs(fenced);
+    let directive = render_tabs(directive);
+    assert_eq!(fenced.html, directive.html);
+    assert_eq!(fenced.warnings, directive.warnings);
+}
+
+#[test]
+fn tabs_fence_renders_like_the_directive() {
+    assert_same_tabs(
+        "```tabs\n== macOS\nInstall with **Homebrew**.\n== Linux\nInstall with apt.\n```",
+        "::::tabs\n\n:::tab[macOS]\n\nInstall with **Homebrew**.\n\n:::\n\n:::tab[Linux]\n\nInstall with apt.\n\n:::\n\n::::",
+    );
+}
+
+#[test]
+fn tabs_fence_with_code_fence_in_a_tab() {
+    assert_same_tabs(
+        "````tabs\n== Shell\n```sh\n== not a tab\n```\n== Python\n```python\nprint()\n```\n````",
+        "::::tabs\n\n:::tab[Shell]\n\n```sh\n== not a tab\n```\n\n:::\n\n:::tab[Python]\n\n```python\nprint()\n```\n\n:::\n\n::::",
+    );
+}
+
+#[test]
+fn tabs_fence_between_directive_groups_numbers_ids_in_order() {
+    let directive_group = "::::tabs\n\n:::tab[A]\n\nOne.\n\n:::\n\n::::";
+    assert_same_tabs(
+        &format!("{directive_group}\n\n```tabs\n== B\nTwo.\n```\n\n{directive_group}"),
+        &format!(
+            "{directive_group}\n\n::::tabs\n\n:::tab[B]\n\nTwo.\n\n:::\n\n::::\n\n{directive_group}"
+        ),
+    );
+}
+
+#[test]
+fn tabs_fence_without_delimiters_stays_a_code_block() {
+    let result = render_tabs("```tabs\nplain text\n```");
+
+    assert!(!result.html.contains("tablist"), "got: {}", result.html);
+    assert!(result.html.contains("plain text"), "got: {}", result.html);
+}
+
+#[test]
+fn tabs_fence_without_tabs_directive_stays_a_code_block() {
+    let result = MarkdownRenderer::<HtmlBackend>::new().render(
+        "```tabs\n== A\nx\n```",
+        Pipeline::new().with_directives(DirectiveProcessor::new()),
+    );
+
+    assert!(!result.html.contains("tablist"), "got: {}", result.html);
+    assert!(result.html.contains("== A"), "got: {}", result.html);
+}
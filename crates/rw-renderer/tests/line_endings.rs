@@ -0,0 +1,122 @@
+//! CRLF input renders byte-identically to LF input.
+//!
+//! pulldown-cmark normalizes line endings in the events it emits: soft breaks,
+//! code-block text, and HTML-block text all arrive with `\n`. Nothing in the
+//! walker or the backends re-reads the raw source. These tests pin that, so a
+//! file saved on Windows yields the same HTML, search text, and code-block
+//! source (and so the same diagram cache keys) as one saved on Unix.
+
+use rw_renderer::directive::DirectiveProcessor;
+use rw_renderer::{
+    CodeBlockProcessor, FenceAttrs, HtmlBackend, MarkdownRenderer, Pipeline, ProcessResult,
+    RenderBackend, SearchDocumentBackend, TabsDirective,
+};
+
+/// Exercises every path that carries a newline through to output: soft and
+/// hard breaks, fenced and indented code, block quotes, lazy list
+/// continuation, raw HTML blocks, and directive lines.
+const DOC: &str = "# Title
+
+A paragraph
+with a soft break.
+
+```rust
+fn main() {
+    println!(\"hi\");
+}
+```
+
+    indented
+    code
+
+> quote
+> continues
+
+- item one
+  more
+- item two
+
+<div>
+raw
+html
+</div>
+
+::::tabs
+
+:::tab[A]
+
+line one
+line two
+
+:::
+
+::::
+
+Hard  
+break
+";
+
+fn render<B: RenderBackend>(md: &str) -> String {
+    let directives = DirectiveProcessor::new().with_container(TabsDirective::new());
+    MarkdownRenderer::<B>::new()
+        .with_title_extraction()
+        .render(md, Pipeline::new().with_directives(directives))
+        .html
+}
+
+fn crlf(md: &str) -> String {
+    md.replace('\n', "\r\n")
+}
+
+#[test]
+fn html_is_identical_for_lf_and_crlf() {
+    let lf = render::<HtmlBackend>(DOC);
+    let crlf = render::<HtmlBackend>(&crlf(DOC));
+    assert_eq!(lf, crlf);
+    assert!(!crlf.contains('\r'), "got: {crlf:?}");
+}
+
+#[test]
+fn search_text_is_identical_for_lf_and_crlf() {
+    assert_eq!(
+        render::<SearchDocumentBackend>(DOC),
+        render::<SearchDocumentBackend>(&crlf(DOC))
+    );
+}
+
+/// Echoes each code block's source so the test can see exactly what a
+/// processor (e.g. the diagram processor, which hashes it) is handed.
+struct EchoSource;
+
+impl CodeBlockProcessor for EchoSource {
+    fn process(
+        &mut self,
+        language: &str,
+        _attrs: &FenceAttrs,
+        source: &str,
+        _index: usize,
+    ) -> ProcessResult {
+        if language == "echo" {
+            ProcessResult::Inline(format!("<pre>{source:?}</pre>"))
+        } else {
+            ProcessResult::PassThrough
+        }
+    }
+}
+
+#[test]
+fn code_block_processor_source_is_identical_for_lf_and_crlf() {
+    let md = "```echo\n@startuml\nA -> B\n@enduml\n```\n";
+    let render = |md: &str| {
+        MarkdownRenderer::<HtmlBackend>::new()
+            .render(md, Pipeline::new().with_processor(EchoSource))
+            .html
+    };
+
+    let lf = render(md);
+    assert_eq!(lf, render(&crlf(md)));
+    assert!(
+        lf.contains(r#""@startuml\nA -> B\n@enduml\n""#),
+        "got: {lf}"
+    );
+}
@@ -294,7 +294,10 @@ fn a_directive_cannot_straddle_a_wikilink() {
     );
     assert_eq!(
         result.warnings,
-        vec!["unknown inline directive ':sta' — no handler registered (or handler returned Skip)"]
+        vec![
+            "broken wikilink [[foo]]: no matching section or page",
+            "unknown inline directive ':sta' — no handler registered (or handler returned Skip)",
+        ]
     );
 }
 
@@ -10,7 +10,7 @@ use tower::ServiceBuilder;
 
 use crate::handlers;
 use crate::live_reload;
-use crate::middleware::security;
+use crate::middleware::{auth, compression, moved_pages, request_log, security, trailing_slash};
 use crate::state::AppState;
 use crate::static_files;
 
@@ -26,8 +26,11 @@ pub(crate) fn create_router(state: Arc<AppState>) -> Router {
             "/_api/navigation",
             get(handlers::navigation::get_navigation),
         )
+        .route("/_api/info", get(handlers::info::get_info))
+        .route("/_api/site-status", get(handlers::status::get_site_status))
         .route("/_api/pages/", get(handlers::pages::get_root_page))
         .route("/_api/pages/{*path}", get(handlers::pages::get_page))
+        .route("/_api/search", get(handlers::search::get_search))
         .route(
             "/_api/comments",
             get(handlers::comments::list_comments).post(handlers::comments::create_comment),
@@ -43,6 +46,12 @@ pub(crate) fn create_router(state: Arc<AppState>) -> Router {
             post(handlers::internal::post_event),
         );
 
+    // CORS covers only the API routes added so far: `layer` wraps existing
+    // routes, so the SPA, live reload and probes below stay without it.
+    if let Some(cors) = &state.cors {
+        router = router.layer(cors.clone());
+    }
+
     // WebSocket for live reload
     if state.live_reload.is_some() {
         router = router.route("/ws/live-reload", get(live_reload::ws_handler));
@@ -66,14 +75,50 @@ pub(crate) fn create_router(state: Arc<AppState>) -> Router {
         router = router.merge(static_files::static_router());
     }
 
-    // Add security headers middleware
+    // Request logging (outermost, so latency covers every layer), security
+    // headers, authentication when a token is set, then the trailing-slash
+    // and moved-page redirects (innermost, so redirects carry the security
+    // headers too and reveal no page URLs without the token; a moved page's
+    // old URL with a trailing slash is first made canonical). `layer` wraps
+    // what is already there, so the innermost layers are added first.
+    router = router.layer(
+        ServiceBuilder::new()
+            .layer(axum::middleware::from_fn(
+                trailing_slash::redirect_trailing_slash,
+            ))
+            .layer(axum::middleware::from_fn_with_state(
+                Arc::clone(&state),
+                moved_pages::redirect_moved_pages,
+            )),
+    );
+    if let Some(token) = &state.auth_token {
+        router = router.layer(axum::middleware::from_fn_with_state(
+            auth::Auth {
+                token: Arc::clone(token),
+                cors: state.cors.clone(),
+            },
+            auth::require_bearer_token,
+        ));
+    }
+    router = router.layer(
+        ServiceBuilder::new()
+            .layer(axum::middleware::from_fn(request_log::log_requests))
+            .layer(security::csp_layer())
+            .layer(security::content_type_options_layer())
+            .layer(security::frame_options_layer())
+            .layer(security::cache_control_layer()),
+    );
+
+    // Compression wraps the whole stack, API JSON and static assets alike.
+    if state.compression {
+        router = router.layer(compression::compression_layer());
+    }
+
     router
-        .layer(
-            ServiceBuilder::new()
-                .layer(security::csp_layer())
-                .layer(security::content_type_options_layer())
-                .layer(security::frame_options_layer())
-                .layer(security::cache_control_layer()),
-        )
+        // Probes are added after the layers so no middleware (request logging
+        // every few seconds, redirects, auth) stands between them and
+        // the orchestrator; as explicit routes they never reach the fallback.
+        .route("/healthz", get(handlers::health::get_healthz))
+        .route("/readyz", get(handlers::health::get_readyz))
         .with_state(state)
 }
@@ -0,0 +1,132 @@
+//! `Cache-Control` for pages and build assets, from `[server.cache]`.
+//!
+//! Page responses and the viewer's content-hashed build assets carry the
+//! configured values. Everything else keeps the server-wide `no-cache` from
+//! [`security::cache_control_layer`](crate::middleware::security::cache_control_layer).
+
+use std::sync::Arc;
+
+use axum::extract::FromRef;
+use axum::http::HeaderValue;
+
+use crate::CacheConfig;
+use crate::error::ServerError;
+use crate::state::AppState;
+
+/// Parsed `Cache-Control` values, ready to attach to responses.
+#[derive(Debug, Clone)]
+pub(crate) struct CacheControl {
+    /// For page responses from `/_api/pages/…`.
+    pub(crate) pages: HeaderValue,
+    /// For content-hashed build assets under `assets/`.
+    pub(crate) assets: HeaderValue,
+}
+
+impl CacheControl {
+    /// Parse `config`. With `live_reload` on, pages get
+    /// [`CacheConfig::live_reload`] in place of [`CacheConfig::pages`], so an
+    /// edit shows on the next load whatever the page policy.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ServerError::CacheControl`] for a value that is not a valid
+    /// header value.
+    pub(crate) fn new(config: &CacheConfig, live_reload: bool) -> Result<Self, ServerError> {
+        let pages = if live_reload {
+            parse("live_reload", &config.live_reload)?
+        } else {
+            parse("pages", &config.pages)?
+        };
+        Ok(Self {
+            pages,
+            assets: parse("assets", &config.assets)?,
+        })
+    }
+}
+
+impl FromRef<Arc<AppState>> for CacheControl {
+    fn from_ref(state: &Arc<AppState>) -> Self {
+        state.cache_control.clone()
+    }
+}
+
+/// Parse the value of the `[server.cache]` setting `name`.
+fn parse(name: &str, value: &str) -> Result<HeaderValue, ServerError> {
+    HeaderValue::from_str(value)
+        .map_err(|_| ServerError::CacheControl(format!("invalid {name} value \"{value}\"")))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::StatusCode;
+    use rw_storage::MockStorage;
+
+    use super::*;
+    use crate::testing::TestServer;
+
+    #[test]
+    fn test_live_reload_replaces_the_page_policy() {
+        let config = CacheConfig {
+            pages: "max-age=300".to_owned(),
+            live_reload: "no-store".to_owned(),
+            ..CacheConfig::default()
+        };
+
+        let served = CacheControl::new(&config, false).unwrap();
+        let live = CacheControl::new(&config, true).unwrap();
+
+        assert_eq!(served.pages, "max-age=300");
+        assert_eq!(live.pages, "no-store");
+        assert_eq!(live.assets, "public, max-age=31536000, immutable");
+    }
+
+    #[test]
+    fn test_invalid_value_is_rejected() {
+        let config = CacheConfig {
+            assets: "no-cache\nx".to_owned(),
+            ..CacheConfig::default()
+        };
+
+        let err = CacheControl::new(&config, false).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "invalid cache setting: invalid assets value \"no-cache\nx\""
+        );
+    }
+
+    #[tokio::test]
+    async fn test_page_response_carries_the_page_policy() {
+        let config = CacheConfig {
+            pages: "max-age=60, must-revalidate".to_owned(),
+            ..CacheConfig::default()
+        };
+        let storage = MockStorage::new()
+            .with_file("guide", "Guide", "# Guide\n\nContent.")
+            .with_mtime("guide", 1000.0);
+        let server = TestServer::with_cache_config(storage, &config).await;
+
+        let resp = server.get("/_api/pages/guide").await;
+        let etag = resp.header("etag").unwrap();
+        let revalidated = server
+            .get_with_header("/_api/pages/guide", "if-none-match", &etag)
+            .await;
+
+        assert_eq!(resp.status, StatusCode::OK);
+        assert_eq!(
+            resp.header("cache-control").as_deref(),
+            Some("max-age=60, must-revalidate")
+        );
+        assert_eq!(revalidated.status, StatusCode::NOT_MODIFIED);
+        assert_eq!(
+            revalidated.header("cache-control").as_deref(),
+            Some("max-age=60, must-revalidate")
+        );
+        // Other API routes stay `no-cache`.
+        let navigation = server.get("/_api/navigation").await;
+        assert_eq!(
+            navigation.header("cache-control").as_deref(),
+            Some("no-cache")
+        );
+    }
+}
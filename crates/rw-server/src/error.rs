@@ -23,10 +23,11 @@ pub enum ServerError {
     #[error("port {0} is already in use")]
     PortInUse(u16),
 
-    /// No free port was found in the fallback range starting at the default.
+    /// No free port was found in the fallback range starting at the requested
+    /// port (the default, or an explicit one with `port_auto`).
     #[error("no free port available in {start}-{end} (all in use)")]
     NoFreePort {
-        /// First port tried (the default).
+        /// First port tried (the requested port).
         start: u16,
         /// Last port tried.
         end: u16,
@@ -36,6 +37,14 @@ pub enum ServerError {
     #[error("{0}")]
     Io(#[from] std::io::Error),
 
+    /// A `CorsConfig` value is not a valid origin, method or header name.
+    #[error("invalid CORS setting: {0}")]
+    Cors(String),
+
+    /// A `CacheConfig` value is not a valid `Cache-Control` header value.
+    #[error("invalid cache setting: {0}")]
+    CacheControl(String),
+
     /// Failed to initialize comment store.
     #[error("failed to initialize comment store: {0}")]
     CommentStore(#[from] rw_comments::StoreError),
@@ -0,0 +1,83 @@
+//! Entity tags for conditional requests.
+//!
+//! Responses that carry an `ETag` from [`for_body`] answer a matching
+//! `If-None-Match` with `304 Not Modified`, so a browser revalidating under
+//! `Cache-Control: no-cache` skips the download when nothing changed.
+//!
+//! The tag hashes the exact bytes of the response body. A page served from
+//! the page cache and the same page rendered fresh produce the same body, and
+//! so the same tag; any change that reaches the response — the page's own
+//! source, a title it resolves from another page, its metadata or
+//! breadcrumbs — produces a new one. Tags are weak because the compression
+//! layer may re-encode the body after it is tagged.
+
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use axum::http::header::{self, HeaderMap, HeaderValue};
+
+/// Weak entity tag for `body`.
+///
+/// Uses `DefaultHasher`, whose fixed seed keeps tags stable across restarts
+/// of the same binary; a new Rust release changing it costs one re-download.
+pub(crate) fn for_body(body: &[u8]) -> HeaderValue {
+    let mut hasher = DefaultHasher::new();
+    body.hash(&mut hasher);
+    HeaderValue::from_str(&format!("W/\"{:016x}\"", hasher.finish()))
+        .expect("hex digits are a valid header value")
+}
+
+/// Whether the request's `If-None-Match` lists `etag` (or is `*`), using the
+/// weak comparison RFC 9110 prescribes for `If-None-Match`.
+pub(crate) fn matches(request: &HeaderMap, etag: &HeaderValue) -> bool {
+    let Ok(etag) = etag.to_str() else {
+        return false;
+    };
+    let ours = opaque(etag);
+    request
+        .get_all(header::IF_NONE_MATCH)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .any(|tag| tag == "*" || opaque(tag) == ours)
+}
+
+/// The quoted part of an entity tag, without any `W/` prefix.
+fn opaque(tag: &str) -> &str {
+    tag.strip_prefix("W/").unwrap_or(tag)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn if_none_match(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_NONE_MATCH, HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_tag_is_weak_and_tracks_content() {
+        let tag = for_body(b"<h1>Guide</h1>");
+
+        assert!(tag.to_str().unwrap().starts_with("W/\""));
+        assert_eq!(tag, for_body(b"<h1>Guide</h1>"));
+        assert_ne!(tag, for_body(b"<h1>Guide!</h1>"));
+    }
+
+    #[test]
+    fn test_matches_listed_tag_weakly() {
+        let tag = for_body(b"body");
+        let strong = tag.to_str().unwrap().trim_start_matches("W/").to_owned();
+
+        assert!(matches(&if_none_match(tag.to_str().unwrap()), &tag));
+        assert!(matches(
+            &if_none_match(&format!("\"other\", {strong}")),
+            &tag
+        ));
+        assert!(matches(&if_none_match("*"), &tag));
+        assert!(!matches(&if_none_match("\"other\""), &tag));
+        assert!(!matches(&HeaderMap::new(), &tag));
+    }
+}
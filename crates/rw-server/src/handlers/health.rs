@@ -0,0 +1,134 @@
+//! Health and readiness probes.
+//!
+//! `GET /healthz` answers as long as the process serves requests and never
+//! touches the site or cache. `GET /readyz` answers 200 once the site
+//! structure has loaded successfully, and 503 until then, so a readiness
+//! probe holds traffic back from a server whose storage is unreachable.
+
+use std::sync::Arc;
+
+use axum::Json;
+use axum::extract::State;
+use axum::http::StatusCode;
+use serde::Serialize;
+
+use crate::state::AppState;
+
+/// Response for GET /healthz.
+#[derive(Serialize)]
+pub(crate) struct HealthResponse {
+    /// Always `"ok"`.
+    status: &'static str,
+    /// Running `rw` version.
+    version: &'static str,
+}
+
+/// Response for GET /readyz.
+#[derive(Serialize)]
+pub(crate) struct ReadyResponse {
+    /// `"ready"` or `"unavailable"`.
+    status: &'static str,
+    /// Why the site failed to load, when it did.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Handle GET /healthz.
+pub(crate) async fn get_healthz() -> Json<HealthResponse> {
+    Json(HealthResponse {
+        status: "ok",
+        version: env!("CARGO_PKG_VERSION"),
+    })
+}
+
+/// Handle GET /readyz.
+///
+/// The server loads the site on first use, so a probe arriving before any
+/// page request performs that first load itself. Once loaded, the site stays
+/// ready: a later failed rescan keeps serving the last good structure.
+pub(crate) async fn get_readyz(
+    State(state): State<Arc<AppState>>,
+) -> (StatusCode, Json<ReadyResponse>) {
+    let loaded = if state.site.is_loaded() {
+        Ok(())
+    } else {
+        state.site.ensure_loaded()
+    };
+    match loaded {
+        Ok(()) => (
+            StatusCode::OK,
+            Json(ReadyResponse {
+                status: "ready",
+                error: None,
+            }),
+        ),
+        Err(e) => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ReadyResponse {
+                status: "unavailable",
+                error: Some(e.to_string()),
+            }),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::StatusCode;
+    use rw_storage::{MockStorage, StorageErrorKind};
+
+    use crate::testing::TestServer;
+
+    #[tokio::test]
+    async fn test_healthz_reports_version() {
+        let server = TestServer::with_comments().await;
+
+        let resp = server.get("/healthz").await;
+
+        assert_eq!(resp.status, StatusCode::OK);
+        let json = resp.json();
+        assert_eq!(json["status"], "ok");
+        assert_eq!(json["version"], env!("CARGO_PKG_VERSION"));
+    }
+
+    #[tokio::test]
+    async fn test_readyz_loads_the_site() {
+        let server =
+            TestServer::with_storage(MockStorage::new().with_document("guide", "Guide")).await;
+
+        let resp = server.get("/readyz").await;
+
+        assert_eq!(resp.status, StatusCode::OK, "body: {}", resp.text());
+        assert_eq!(resp.json()["status"], "ready");
+        let status = server.get("/_api/site-status").await.json();
+        assert_eq!(status["pageCount"], 1);
+    }
+
+    #[tokio::test]
+    async fn test_readyz_unavailable_until_storage_loads() {
+        let storage = MockStorage::new().with_scan_error(StorageErrorKind::Unavailable);
+        let server = TestServer::with_storage(storage).await;
+
+        let resp = server.get("/readyz").await;
+
+        assert_eq!(resp.status, StatusCode::SERVICE_UNAVAILABLE);
+        let json = resp.json();
+        assert_eq!(json["status"], "unavailable");
+        assert!(json["error"].is_string(), "json: {json}");
+    }
+
+    #[tokio::test]
+    async fn test_probes_take_precedence_over_the_spa_fallback() {
+        let server = TestServer::with_comments().await;
+
+        for path in ["/healthz", "/readyz"] {
+            let resp = server.get(path).await;
+            assert_eq!(
+                resp.header("content-type").as_deref(),
+                Some("application/json"),
+                "{path}: {}",
+                resp.text()
+            );
+        }
+    }
+}
@@ -0,0 +1,66 @@
+//! Server info API endpoint.
+//!
+//! Reports which build is serving and how it is configured, so a redeploy
+//! can be confirmed from the API. Everything comes from memory; nothing is
+//! read from disk or rendered.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use axum::Json;
+use axum::extract::State;
+use serde::Serialize;
+
+use crate::state::AppState;
+
+/// Git commit the binary was built from, if the build set `RW_GIT_COMMIT`.
+const GIT_COMMIT: Option<&str> = option_env!("RW_GIT_COMMIT");
+
+/// Response for GET /_api/info.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct InfoResponse {
+    /// Version of the `rw` binary.
+    version: String,
+    /// Git commit of the build, or `null` when the build did not record it.
+    commit: Option<&'static str>,
+    /// Source directories, in merge order.
+    source_dirs: Vec<PathBuf>,
+    /// Whether rendered pages and metadata are cached on disk.
+    cache_enabled: bool,
+    /// Whether diagram fences render as diagrams rather than code blocks.
+    diagrams_enabled: bool,
+}
+
+/// Handle GET /_api/info.
+pub(crate) async fn get_info(State(state): State<Arc<AppState>>) -> Json<InfoResponse> {
+    Json(InfoResponse {
+        version: state.version.clone(),
+        commit: GIT_COMMIT.filter(|commit| !commit.is_empty()),
+        source_dirs: state.source_dirs.clone(),
+        cache_enabled: state.cache_enabled,
+        diagrams_enabled: state.site.renders_diagrams(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::StatusCode;
+
+    use crate::testing::TestServer;
+
+    #[tokio::test]
+    async fn test_info_reports_build_and_settings() {
+        let server = TestServer::with_comments().await;
+
+        let resp = server.get("/_api/info").await;
+
+        assert_eq!(resp.status, StatusCode::OK);
+        let json = resp.json();
+        assert_eq!(json["version"], "test");
+        assert_eq!(json["sourceDirs"], serde_json::json!(["docs"]));
+        assert_eq!(json["cacheEnabled"], false);
+        assert_eq!(json["diagramsEnabled"], false);
+        assert!(json.get("commit").is_some());
+    }
+}
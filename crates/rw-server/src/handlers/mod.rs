@@ -2,6 +2,10 @@
 
 pub(crate) mod comments;
 pub(crate) mod config;
+pub(crate) mod health;
+pub(crate) mod info;
 pub(crate) mod internal;
 pub(crate) mod navigation;
 pub(crate) mod pages;
+pub(crate) mod search;
+pub(crate) mod status;
@@ -1,8 +1,14 @@
 //! Navigation API endpoint.
 //!
-//! Returns the navigation tree for the documentation site.
+//! Returns the navigation tree for the documentation site. By default the
+//! whole tree of the requested scope is returned; `depth` and `expand` let a
+//! client on a very large site load it a level at a time instead, and
+//! `current` marks the trail to the page being viewed. The viewer loads it
+//! this way: a depth-limited scope plus the items above the current page,
+//! then each further level when it is expanded.
 
 use std::collections::HashMap;
+use std::num::NonZeroUsize;
 use std::sync::Arc;
 
 use axum::Json;
@@ -20,6 +26,18 @@ pub(crate) struct NavigationQuery {
     /// Format: "kind:namespace/name" (e.g., "domain:default/billing", "domain:payments/billing").
     #[serde(rename = "sectionRef")]
     section_ref: Option<String>,
+    /// Levels of items to return (1 = only the top level). Items whose
+    /// children were cut off carry `hasChildren: true`. Omitted returns the
+    /// whole tree.
+    depth: Option<NonZeroUsize>,
+    /// URL path of an item in the scope (e.g., "/domain/billing"). If
+    /// provided, returns that item's children instead of the top level.
+    expand: Option<String>,
+    /// URL path of the page being viewed (e.g., "/guide/setup"). If provided,
+    /// its item carries `isCurrent: true`, and it and every item above it
+    /// carry `active: true`. Items above it keep their children past the
+    /// `depth` cut, so the page's own item is always in the response.
+    current: Option<String>,
 }
 
 /// Response for GET /_api/navigation.
@@ -74,21 +92,93 @@ struct NavItemResponse {
     /// Child navigation items.
     #[serde(skip_serializing_if = "Vec::is_empty")]
     children: Vec<NavItemResponse>,
+    /// Whether children exist but were left out by the depth limit; fetch
+    /// them with `expand`.
+    #[serde(rename = "hasChildren", skip_serializing_if = "std::ops::Not::not")]
+    has_children: bool,
+    /// Whether this item is the `current` page.
+    #[serde(rename = "isCurrent", skip_serializing_if = "std::ops::Not::not")]
+    is_current: bool,
+    /// Whether this item is on the trail to the `current` page: the page
+    /// itself and every item above it. When the page has no item of its own
+    /// (it sits inside a nested section, or below the `depth` cut), the
+    /// trail ends at the nearest item above it.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    active: bool,
 }
 
-impl From<NavItem> for NavItemResponse {
-    fn from(item: NavItem) -> Self {
+impl NavItemResponse {
+    /// Convert `item`, keeping at most `depth` levels (`None` keeps all) and
+    /// marking the trail to `current` (a path without leading slash). Items
+    /// above `current` keep one more level past the cut.
+    fn convert(item: NavItem, depth: Option<NonZeroUsize>, current: Option<&str>) -> Self {
+        let is_current = current == Some(item.path.as_str());
+        let active = current.is_some_and(|current| is_on_trail(&item.path, current));
+        let child_depth = depth.map(|d| NonZeroUsize::new(d.get() - 1));
+        let (children, has_children) = match child_depth {
+            Some(None) if active && !is_current => (
+                Self::convert_all(item.children, Some(NonZeroUsize::MIN), current),
+                false,
+            ),
+            Some(None) => (Vec::new(), !item.children.is_empty()),
+            Some(Some(d)) => (Self::convert_all(item.children, Some(d), current), false),
+            None => (Self::convert_all(item.children, None, current), false),
+        };
         Self {
             title: item.title,
             path: to_url_path(&item.path),
             section: item.section,
-            children: item
-                .children
-                .into_iter()
-                .map(NavItemResponse::from)
-                .collect(),
+            children,
+            has_children,
+            is_current,
+            active,
         }
     }
+
+    /// Convert `item`, keeping at most `depth` levels (`None` keeps all).
+    fn with_depth(item: NavItem, depth: Option<NonZeroUsize>) -> Self {
+        Self::convert(item, depth, None)
+    }
+
+    fn convert_all(
+        items: Vec<NavItem>,
+        depth: Option<NonZeroUsize>,
+        current: Option<&str>,
+    ) -> Vec<Self> {
+        items
+            .into_iter()
+            .map(|item| Self::convert(item, depth, current))
+            .collect()
+    }
+}
+
+impl From<NavItem> for NavItemResponse {
+    fn from(item: NavItem) -> Self {
+        Self::with_depth(item, None)
+    }
+}
+
+/// Whether the item at `item_path` is `current` or one of its ancestors.
+///
+/// The root page (`""`) is never an item: the tree lists its children, so
+/// viewing it leaves every item inactive.
+fn is_on_trail(item_path: &str, current: &str) -> bool {
+    !item_path.is_empty()
+        && current
+            .strip_prefix(item_path)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+/// Children of the item at `path` anywhere in `items`, or `None` if no item
+/// has that path.
+fn take_children(items: Vec<NavItem>, path: &str) -> Option<Vec<NavItem>> {
+    items.into_iter().find_map(|item| {
+        if item.path == path {
+            Some(item.children)
+        } else {
+            take_children(item.children, path)
+        }
+    })
 }
 
 /// Handle GET /_api/navigation.
@@ -98,12 +188,15 @@ pub(crate) async fn get_navigation(
 ) -> Result<Json<NavigationResponse>, HandlerError> {
     let scoped_nav = state.site.navigation(query.section_ref.as_deref())?;
 
+    let items = match query.expand.as_deref().map(|p| p.trim_matches('/')) {
+        Some(path) if !path.is_empty() => take_children(scoped_nav.items, path)
+            .ok_or_else(|| HandlerError::PageNotFound(path.to_owned()))?,
+        _ => scoped_nav.items,
+    };
+
+    let current = query.current.as_deref().map(|p| p.trim_matches('/'));
     Ok(Json(NavigationResponse {
-        items: scoped_nav
-            .items
-            .into_iter()
-            .map(NavItemResponse::from)
-            .collect(),
+        items: NavItemResponse::convert_all(items, query.depth, current),
         scope: scoped_nav.scope.map(ScopeInfoResponse::from),
         parent_scope: scoped_nav.parent_scope.map(ScopeInfoResponse::from),
         section_ancestry: scoped_nav.section_ancestry,
@@ -220,4 +313,285 @@ mod tests {
 
         assert!(json.get("sectionAncestry").is_none());
     }
+
+    fn nav(path: &str, children: Vec<NavItem>) -> NavItem {
+        NavItem {
+            title: path.to_owned(),
+            path: path.to_owned(),
+            section: None,
+            children,
+        }
+    }
+
+    #[test]
+    fn test_depth_limit_marks_truncated_items() {
+        let item = nav(
+            "guide",
+            vec![nav(
+                "guide/setup",
+                vec![nav("guide/setup/advanced", vec![])],
+            )],
+        );
+
+        let json =
+            serde_json::to_value(NavItemResponse::with_depth(item, NonZeroUsize::new(2))).unwrap();
+
+        let setup = &json["children"][0];
+        assert_eq!(setup["path"], "/guide/setup");
+        assert_eq!(setup["hasChildren"], true);
+        assert!(setup.get("children").is_none());
+        // Items shown with their children do not carry the flag.
+        assert!(json.get("hasChildren").is_none());
+    }
+
+    #[test]
+    fn test_depth_limit_does_not_flag_leaves() {
+        let json = serde_json::to_value(NavItemResponse::with_depth(
+            nav("guide", vec![]),
+            NonZeroUsize::new(1),
+        ))
+        .unwrap();
+
+        assert!(json.get("hasChildren").is_none());
+    }
+
+    #[test]
+    fn test_is_on_trail() {
+        assert!(is_on_trail("guide", "guide"));
+        assert!(is_on_trail("guide", "guide/setup"));
+        assert!(!is_on_trail("guide", "guides"));
+        assert!(!is_on_trail("guide/setup", "guide"));
+        assert!(!is_on_trail("guide", ""));
+    }
+
+    #[test]
+    fn test_current_marks_item_and_ancestors() {
+        let items = vec![
+            nav("api", vec![]),
+            nav(
+                "guide",
+                vec![
+                    nav("guide/install", vec![]),
+                    nav("guide/setup", vec![nav("guide/setup/advanced", vec![])]),
+                ],
+            ),
+        ];
+
+        let json = serde_json::to_value(NavItemResponse::convert_all(
+            items,
+            None,
+            Some("guide/setup/advanced"),
+        ))
+        .unwrap();
+
+        assert!(json[0].get("active").is_none());
+        let guide = &json[1];
+        assert_eq!(guide["active"], true);
+        assert!(guide.get("isCurrent").is_none());
+        assert!(guide["children"][0].get("active").is_none());
+        let setup = &guide["children"][1];
+        assert_eq!(setup["active"], true);
+        assert!(setup.get("isCurrent").is_none());
+        let advanced = &setup["children"][0];
+        assert_eq!(advanced["active"], true);
+        assert_eq!(advanced["isCurrent"], true);
+    }
+
+    #[test]
+    fn test_current_keeps_its_trail_past_depth_limit() {
+        let items = vec![
+            nav("api", vec![nav("api/auth", vec![])]),
+            nav(
+                "guide",
+                vec![
+                    nav("guide/install", vec![nav("guide/install/linux", vec![])]),
+                    nav("guide/setup", vec![nav("guide/setup/advanced", vec![])]),
+                ],
+            ),
+        ];
+
+        let json = serde_json::to_value(NavItemResponse::convert_all(
+            items,
+            NonZeroUsize::new(1),
+            Some("guide/setup/advanced"),
+        ))
+        .unwrap();
+
+        // Off the trail, the cut applies as usual.
+        assert_eq!(json[0]["hasChildren"], true);
+        assert!(json[0].get("children").is_none());
+        let guide = &json[1];
+        assert!(guide.get("hasChildren").is_none());
+        assert_eq!(guide["children"][0]["hasChildren"], true);
+        let advanced = &guide["children"][1]["children"][0];
+        assert_eq!(advanced["isCurrent"], true);
+    }
+
+    #[test]
+    fn test_current_without_own_item_ends_trail_at_nearest_item() {
+        // A nested section is a leaf in its parent's navigation, so a page
+        // inside it has no item here.
+        let mut billing = nav("domains/billing", vec![]);
+        billing.section = Some(Section {
+            kind: "domain".to_owned(),
+            namespace: rw_site::Namespace::default(),
+            name: "billing".to_owned(),
+        });
+        let items = vec![nav("domains", vec![billing])];
+
+        let json = serde_json::to_value(NavItemResponse::convert_all(
+            items,
+            None,
+            Some("domains/billing/invoices"),
+        ))
+        .unwrap();
+
+        let billing = &json[0]["children"][0];
+        assert_eq!(json[0]["active"], true);
+        assert_eq!(billing["active"], true);
+        assert!(billing.get("isCurrent").is_none());
+    }
+
+    #[test]
+    fn test_take_children_finds_nested_item() {
+        let items = || {
+            vec![
+                nav("api", vec![]),
+                nav(
+                    "guide",
+                    vec![nav(
+                        "guide/setup",
+                        vec![nav("guide/setup/advanced", vec![])],
+                    )],
+                ),
+            ]
+        };
+
+        let children = take_children(items(), "guide/setup").unwrap();
+        assert_eq!(children.len(), 1);
+        assert_eq!(children[0].path, "guide/setup/advanced");
+        assert!(take_children(items(), "missing").is_none());
+    }
+
+    #[test]
+    fn test_navigation_query_deserializes_depth_and_expand() {
+        let query: NavigationQuery =
+            serde_urlencoded::from_str("depth=1&expand=/domain/billing").unwrap();
+        assert_eq!(query.depth, NonZeroUsize::new(1));
+        assert_eq!(query.expand.as_deref(), Some("/domain/billing"));
+        assert!(serde_urlencoded::from_str::<NavigationQuery>("depth=0").is_err());
+    }
+
+    mod endpoint {
+        use axum::http::StatusCode;
+        use rw_storage::MockStorage;
+
+        use crate::testing::TestServer;
+
+        fn storage() -> MockStorage {
+            MockStorage::new()
+                .with_document("guide", "Guide")
+                .with_document("guide/setup", "Setup")
+                .with_document("guide/setup/advanced", "Advanced")
+                .with_document("api", "API")
+        }
+
+        #[tokio::test]
+        async fn test_navigation_returns_full_tree_by_default() {
+            let server = TestServer::with_storage(storage()).await;
+
+            let json = server.get("/_api/navigation").await.json();
+
+            let guide = json["items"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .find(|i| i["path"] == "/guide")
+                .unwrap();
+            assert_eq!(
+                guide["children"][0]["children"][0]["path"],
+                "/guide/setup/advanced"
+            );
+        }
+
+        #[tokio::test]
+        async fn test_navigation_expands_one_level_at_a_time() {
+            let server = TestServer::with_storage(storage()).await;
+
+            let json = server.get("/_api/navigation?depth=1").await.json();
+            let guide = json["items"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .find(|i| i["path"] == "/guide")
+                .unwrap();
+            assert_eq!(guide["hasChildren"], true, "json: {json}");
+            assert!(guide.get("children").is_none());
+
+            let json = server
+                .get("/_api/navigation?expand=/guide&depth=1")
+                .await
+                .json();
+            assert_eq!(json["items"][0]["path"], "/guide/setup");
+            assert_eq!(json["items"][0]["hasChildren"], true);
+        }
+
+        #[tokio::test]
+        async fn test_navigation_marks_current_trail() {
+            let server = TestServer::with_storage(storage()).await;
+
+            let json = server
+                .get("/_api/navigation?current=/guide/setup")
+                .await
+                .json();
+
+            let items = json["items"].as_array().unwrap();
+            let guide = items.iter().find(|i| i["path"] == "/guide").unwrap();
+            let api = items.iter().find(|i| i["path"] == "/api").unwrap();
+            assert_eq!(guide["active"], true, "json: {json}");
+            assert_eq!(guide["children"][0]["isCurrent"], true);
+            assert!(guide["children"][0]["children"][0].get("active").is_none());
+            assert!(api.get("active").is_none());
+        }
+
+        #[tokio::test]
+        async fn test_navigation_depth_limit_keeps_current_trail() {
+            let server = TestServer::with_storage(storage()).await;
+
+            let json = server
+                .get("/_api/navigation?depth=1&current=/guide/setup/advanced")
+                .await
+                .json();
+
+            let guide = json["items"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .find(|i| i["path"] == "/guide")
+                .unwrap();
+            let advanced = &guide["children"][0]["children"][0];
+            assert_eq!(advanced["path"], "/guide/setup/advanced", "json: {json}");
+            assert_eq!(advanced["isCurrent"], true);
+        }
+
+        #[tokio::test]
+        async fn test_navigation_root_page_marks_nothing() {
+            let server = TestServer::with_storage(storage()).await;
+
+            let json = server.get("/_api/navigation?current=/").await.json();
+
+            let text = json.to_string();
+            assert!(!text.contains("active"), "json: {json}");
+            assert!(!text.contains("isCurrent"), "json: {json}");
+        }
+
+        #[tokio::test]
+        async fn test_navigation_expand_unknown_path_is_not_found() {
+            let server = TestServer::with_storage(storage()).await;
+
+            let resp = server.get("/_api/navigation?expand=/missing").await;
+
+            assert_eq!(resp.status, StatusCode::NOT_FOUND, "body: {}", resp.text());
+        }
+    }
 }
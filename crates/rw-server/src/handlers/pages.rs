@@ -3,18 +3,23 @@
 //! Handles page rendering and returns JSON responses with metadata,
 //! table of contents, and HTML content.
 
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
-use axum::Json;
 use axum::extract::{Path, State};
-use axum::response::IntoResponse;
+use axum::http::StatusCode;
+use axum::http::header::{self, HeaderMap, HeaderValue};
+use axum::response::{IntoResponse, Response};
+use axum::{Extension, Json};
 use rw_renderer::TocEntry;
 use rw_site::{BreadcrumbItem, SectionAnchor, to_url_path};
 use rw_storage::mtime_to_datetime;
 use serde::Serialize;
 
 use crate::error::HandlerError;
+use crate::etag;
+use crate::middleware::moved_pages::page_api_location;
+use crate::middleware::request_log::CacheStatus;
 use crate::state::AppState;
 
 /// Response for GET /_api/pages/{path}.
@@ -31,9 +36,10 @@ struct PageResponse {
     /// Ancestry chains for the sections this page is connected to (including the
     /// page's own section), keyed by section ref; each chain starts with the
     /// section itself (empty subpath), then its ancestors, root last. Omitted
-    /// when empty.
-    #[serde(rename = "sectionAncestry", skip_serializing_if = "HashMap::is_empty")]
-    section_ancestry: HashMap<String, Vec<SectionAnchor>>,
+    /// when empty. Sorted, so the same page always serializes to the same
+    /// bytes and keeps its `ETag`.
+    #[serde(rename = "sectionAncestry", skip_serializing_if = "BTreeMap::is_empty")]
+    section_ancestry: BTreeMap<String, Vec<SectionAnchor>>,
 }
 
 /// Page metadata.
@@ -110,23 +116,119 @@ impl From<&TocEntry> for TocResponse {
 /// Handle GET /_api/pages/ (root page).
 pub(crate) async fn get_root_page(
     State(state): State<Arc<AppState>>,
-) -> Result<impl IntoResponse, HandlerError> {
-    get_page_impl(String::new(), state)
+    headers: HeaderMap,
+) -> Result<Response, HandlerError> {
+    get_page_impl(String::new(), state, &headers)
 }
 
 /// Handle GET /_api/pages/{path}.
+///
+/// A trailing slash is ignored: `guide/` is the `guide` page.
 pub(crate) async fn get_page(
-    Path(path): Path<String>,
+    Path(mut path): Path<String>,
     State(state): State<Arc<AppState>>,
-) -> Result<impl IntoResponse, HandlerError> {
-    get_page_impl(path, state)
+    headers: HeaderMap,
+) -> Result<Response, HandlerError> {
+    path.truncate(path.trim_end_matches('/').len());
+    get_page_impl(path, state, &headers)
 }
 
 /// Shared implementation for page rendering.
+///
+/// The response carries an [`ETag`](etag) of its body and the configured page
+/// `Cache-Control`; a request whose `If-None-Match` already holds the tag gets
+/// `304 Not Modified` instead. The page is still rendered (usually from the
+/// page cache) to compute the tag, so a 304 saves the transfer, not the
+/// lookup.
+///
+/// A missing page that lists `path` among its former URLs is answered with a
+/// `301` to that page, which `fetch` follows; any other missing page by
+/// [`not_found_response`].
 #[allow(clippy::needless_pass_by_value)]
-fn get_page_impl(path: String, state: Arc<AppState>) -> Result<impl IntoResponse, HandlerError> {
+fn get_page_impl(
+    path: String,
+    state: Arc<AppState>,
+    request: &HeaderMap,
+) -> Result<Response, HandlerError> {
+    let (response, from_cache) = match page_response(&path, &state) {
+        Err(HandlerError::PageNotFound(missing)) => {
+            // Rendering loaded the site, so its redirects are current.
+            return Ok(match state.site.resolve_redirect(&missing) {
+                Some(target) => moved_response(&state, &target),
+                None => not_found_response(&state, missing),
+            });
+        }
+        result => result?,
+    };
+    let body = serde_json::to_vec(&response).map_err(std::io::Error::from)?;
+    let etag = etag::for_body(&body);
+
+    let cache_status = Extension(CacheStatus { hit: from_cache });
+    let cache_control = state.cache_control.pages.clone();
+    if etag::matches(request, &etag) {
+        return Ok((
+            StatusCode::NOT_MODIFIED,
+            cache_status,
+            [(header::ETAG, etag), (header::CACHE_CONTROL, cache_control)],
+        )
+            .into_response());
+    }
+    Ok((
+        cache_status,
+        [
+            (
+                header::CONTENT_TYPE,
+                HeaderValue::from_static("application/json"),
+            ),
+            (header::ETAG, etag),
+            (header::CACHE_CONTROL, cache_control),
+        ],
+        body,
+    )
+        .into_response())
+}
+
+/// Redirect a former page URL to `target`, the page that lists it. The page
+/// `Cache-Control` applies, so a browser does not keep the redirect after the
+/// page drops it.
+fn moved_response(state: &AppState, target: &str) -> Response {
+    let mut response = (
+        StatusCode::MOVED_PERMANENTLY,
+        [(header::LOCATION, page_api_location(target))],
+    )
+        .into_response();
+    response
+        .headers_mut()
+        .insert(header::CACHE_CONTROL, state.cache_control.pages.clone());
+    response
+}
+
+/// Respond to a request for `missing`, a page the site does not have: with
+/// the configured not-found page (`404.md` by default) under a 404 status,
+/// or with the plain JSON error when the site has no such page. A not-found
+/// page that fails to render is logged and never turns the 404 into a 500.
+fn not_found_response(state: &AppState, missing: String) -> Response {
+    let not_found_page = state.not_found_page.as_str();
+    if !not_found_page.is_empty() && not_found_page != missing {
+        match page_response(not_found_page, state) {
+            Ok((page, from_cache)) => {
+                let cache_status = Extension(CacheStatus { hit: from_cache });
+                return (StatusCode::NOT_FOUND, cache_status, Json(page)).into_response();
+            }
+            Err(HandlerError::PageNotFound(_)) => {}
+            Err(e) => {
+                tracing::warn!(page = %not_found_page, error = %e, "Failed to render not-found page");
+            }
+        }
+    }
+    HandlerError::PageNotFound(missing).into_response()
+}
+
+/// Render the page at `path` into its response, and whether it came from the
+/// page cache.
+fn page_response(path: &str, state: &AppState) -> Result<(PageResponse, bool), HandlerError> {
     // Render the page using unified Site API (path is already without leading slash)
-    let result = state.site.render(&path).map_err(|e| match e {
+    let result = state.site.render(path).map_err(|e| match e {
         // A page known to the navigation tree but whose source file is missing
         // from storage (FileNotFound — e.g. deleted under a stale snapshot) is a
         // not-found, not a server error; map it to 404 like an unknown page.
@@ -143,6 +245,19 @@ fn get_page_impl(path: String, state: Arc<AppState>) -> Result<impl IntoResponse
             tracing::warn!(path = %path, warning = %warning, "Page render warning");
         }
     }
+    if state.verbose {
+        for stat in &result.code_block_stats {
+            tracing::info!(
+                path = %path,
+                index = stat.index,
+                language = %stat.language,
+                cache = if stat.from_cache { "hit" } else { "miss" },
+                duration_ms = format_args!("{:.1}", stat.duration.as_secs_f64() * 1000.0),
+                bytes = stat.bytes,
+                "Diagram rendered"
+            );
+        }
+    }
 
     // Get last modified time from render result
     let last_modified = mtime_to_datetime(result.source_mtime);
@@ -155,14 +270,14 @@ fn get_page_impl(path: String, state: Arc<AppState>) -> Result<impl IntoResponse
         (None, None)
     };
 
-    let (section_ref, subpath) = state.site.section_location(&path)?;
+    let (section_ref, subpath) = state.site.section_location(path)?;
 
     let response = PageResponse {
         meta: PageMeta {
             title: result.title,
-            path: to_url_path(&path),
+            path: to_url_path(path),
             source_file: if result.has_content {
-                path.clone()
+                path.to_owned()
             } else {
                 String::new()
             },
@@ -179,17 +294,15 @@ fn get_page_impl(path: String, state: Arc<AppState>) -> Result<impl IntoResponse
             .collect(),
         toc: result.toc.iter().map(TocResponse::from).collect(),
         content: result.html,
-        section_ancestry: result.section_ancestry,
+        section_ancestry: result.section_ancestry.into_iter().collect(),
     };
-
-    Ok(Json(response))
+    Ok((response, result.from_cache))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    use axum::http::StatusCode;
     use rw_storage::MockStorage;
 
     use crate::testing::TestServer;
@@ -203,7 +316,7 @@ mod tests {
 
     /// A `PageResponse` with fixed `content` (identical HTML), so tests can vary
     /// only `section_ancestry` and observe its effect on serialization.
-    fn page_response_with(section_ancestry: HashMap<String, Vec<SectionAnchor>>) -> PageResponse {
+    fn page_response_with(section_ancestry: BTreeMap<String, Vec<SectionAnchor>>) -> PageResponse {
         PageResponse {
             meta: PageMeta {
                 title: Some("Same".to_owned()),
@@ -237,6 +350,19 @@ mod tests {
         assert_eq!(resp.status, StatusCode::NOT_FOUND, "body: {}", resp.text());
     }
 
+    #[tokio::test]
+    async fn test_trailing_slash_serves_the_same_page() {
+        let storage = MockStorage::new()
+            .with_file("guide", "Guide", "# Guide\n\nContent.")
+            .with_mtime("guide", 1000.0);
+        let server = TestServer::with_storage(storage).await;
+
+        let resp = server.get("/_api/pages/guide/").await;
+
+        assert_eq!(resp.status, StatusCode::OK, "body: {}", resp.text());
+        assert_eq!(resp.json()["meta"]["path"], "/guide");
+    }
+
     #[tokio::test]
     async fn test_unknown_page_returns_404() {
         // A path absent from the tree raises PageNotFound (distinct from the
@@ -248,6 +374,56 @@ mod tests {
         assert_eq!(resp.status, StatusCode::NOT_FOUND, "body: {}", resp.text());
     }
 
+    #[tokio::test]
+    async fn test_former_url_redirects_to_page_api() {
+        let server = TestServer::with_storage(
+            MockStorage::new()
+                .with_file("guides/setup", "Setup", "# Setup")
+                .with_redirects("guides/setup", &["old/setup"]),
+        )
+        .await;
+
+        // The first request: nothing has loaded the site before it.
+        let resp = server.get("/_api/pages/old/setup").await;
+
+        assert_eq!(resp.status, StatusCode::MOVED_PERMANENTLY);
+        assert_eq!(
+            resp.header("location").as_deref(),
+            Some("/_api/pages/guides/setup")
+        );
+        assert_eq!(resp.header("cache-control").as_deref(), Some("no-cache"));
+    }
+
+    #[tokio::test]
+    async fn test_missing_page_serves_not_found_page_with_404() {
+        let storage = MockStorage::new()
+            .with_file("guide", "Guide", "# Guide\n\nContent.")
+            .with_file("404", "Lost", "# Lost\n\nTry the [guide](guide.md).")
+            .with_mtime("guide", 1000.0)
+            .with_mtime("404", 1000.0);
+        let server = TestServer::with_storage(storage).await;
+
+        let resp = server.get("/_api/pages/does-not-exist").await;
+
+        assert_eq!(resp.status, StatusCode::NOT_FOUND, "body: {}", resp.text());
+        let json = resp.json();
+        assert_eq!(json["meta"]["title"], "Lost");
+        assert!(json["content"].as_str().unwrap().contains("Try the"));
+    }
+
+    #[tokio::test]
+    async fn test_unrenderable_not_found_page_falls_back_to_plain_404() {
+        // The not-found page is in the tree but its source is gone, so
+        // rendering it fails too; the request must still get the plain 404.
+        let storage = MockStorage::new().with_document("404", "Lost");
+        let server = TestServer::with_storage(storage).await;
+
+        let resp = server.get("/_api/pages/does-not-exist").await;
+
+        assert_eq!(resp.status, StatusCode::NOT_FOUND, "body: {}", resp.text());
+        assert_eq!(resp.json()["error"], "Page not found");
+    }
+
     #[tokio::test]
     async fn test_normal_page_returns_200() {
         let storage = MockStorage::new()
@@ -298,21 +474,37 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_page_response_carries_no_etag() {
+    async fn test_matching_if_none_match_returns_304() {
         let storage = MockStorage::new()
             .with_file("guide", "Guide", "# Guide\n\nContent.")
             .with_mtime("guide", 1000.0);
         let server = TestServer::with_storage(storage).await;
 
-        let resp = server.get("/_api/pages/guide").await;
+        let first = server.get("/_api/pages/guide").await;
+        let etag = first.header("etag").expect("page responses carry an ETag");
+        // The second request is a page-cache hit; its tag must not differ.
+        let second = server
+            .get_with_header("/_api/pages/guide", "if-none-match", &etag)
+            .await;
 
-        // `rw serve` is a local dev server. An HTTP validator would only save a
-        // loopback write of a string the handler has already rendered and
-        // serialized, so page responses deliberately carry none.
-        assert!(
-            resp.header("etag").is_none(),
-            "page responses carry no ETag"
-        );
+        assert_eq!(second.status, StatusCode::NOT_MODIFIED);
+        assert_eq!(second.header("etag"), Some(etag));
+        assert!(second.text().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_stale_if_none_match_returns_page() {
+        let storage = MockStorage::new()
+            .with_file("guide", "Guide", "# Guide\n\nContent.")
+            .with_mtime("guide", 1000.0);
+        let server = TestServer::with_storage(storage).await;
+
+        let resp = server
+            .get_with_header("/_api/pages/guide", "if-none-match", "W/\"0\"")
+            .await;
+
+        assert_eq!(resp.status, StatusCode::OK);
+        assert_eq!(resp.json()["meta"]["title"], "Guide");
     }
 
     #[tokio::test]
@@ -414,7 +606,7 @@ mod tests {
 
     #[test]
     fn test_page_response_serializes_section_ancestry() {
-        let resp = page_response_with(HashMap::from([(
+        let resp = page_response_with(BTreeMap::from([(
             "domain:default/billing".to_owned(),
             vec![
                 anchor("domain:default/billing", "overview"),
@@ -433,7 +625,7 @@ mod tests {
 
     #[test]
     fn test_page_response_omits_empty_section_ancestry() {
-        let json = serde_json::to_value(page_response_with(HashMap::new())).unwrap();
+        let json = serde_json::to_value(page_response_with(BTreeMap::new())).unwrap();
 
         assert!(json.get("sectionAncestry").is_none());
     }
@@ -0,0 +1,133 @@
+//! Full-text search API endpoint.
+//!
+//! Searches the titles and text of every page with content through
+//! [`Site::search`](rw_site::Site::search), whose index is built on the first
+//! request and kept current as pages change.
+
+use std::sync::Arc;
+
+use axum::Json;
+use axum::extract::{Query, State};
+use rw_site::{SearchHit, to_url_path};
+use serde::{Deserialize, Serialize};
+
+use crate::error::HandlerError;
+use crate::state::AppState;
+
+/// Hits returned when the request sets no `limit`.
+const DEFAULT_LIMIT: usize = 10;
+
+/// Query parameters for GET /_api/search.
+#[derive(Deserialize)]
+pub(crate) struct SearchQuery {
+    /// Words to search for; a page must match all of them. Missing or blank
+    /// returns no results.
+    #[serde(default)]
+    q: String,
+    /// Maximum hits to return (default 10, at most
+    /// [`MAX_SEARCH_RESULTS`](rw_site::MAX_SEARCH_RESULTS)).
+    limit: Option<usize>,
+}
+
+/// Response for GET /_api/search.
+#[derive(Serialize)]
+pub(crate) struct SearchResponse {
+    /// Matching pages, best first.
+    results: Vec<SearchResultResponse>,
+}
+
+/// One matching page for JSON response.
+#[derive(Serialize)]
+struct SearchResultResponse {
+    /// URL path (with leading slash for frontend).
+    path: String,
+    /// Page title.
+    title: String,
+    /// Text around the first match.
+    snippet: String,
+    /// Relevance, only comparable within one response.
+    score: usize,
+}
+
+impl From<SearchHit> for SearchResultResponse {
+    fn from(hit: SearchHit) -> Self {
+        Self {
+            path: to_url_path(&hit.path),
+            title: hit.title,
+            snippet: hit.snippet,
+            score: hit.score,
+        }
+    }
+}
+
+/// Handle GET /_api/search.
+pub(crate) async fn get_search(
+    Query(query): Query<SearchQuery>,
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<SearchResponse>, HandlerError> {
+    let hits = state
+        .site
+        .search(&query.q, query.limit.unwrap_or(DEFAULT_LIMIT))?;
+    Ok(Json(SearchResponse {
+        results: hits.into_iter().map(SearchResultResponse::from).collect(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::StatusCode;
+    use rw_storage::{MockStorage, StorageErrorKind};
+
+    use crate::testing::TestServer;
+
+    fn storage() -> MockStorage {
+        MockStorage::new()
+            .with_file("", "Home", "# Home\n\nStart with the deployment guide.")
+            .with_file(
+                "guide/deploy",
+                "Deployment",
+                "# Deployment\n\nRoll out with the deploy script.",
+            )
+            .with_file("api", "API", "# API\n\nEndpoints and tokens.")
+    }
+
+    #[tokio::test]
+    async fn test_search_returns_ranked_results() {
+        let server = TestServer::with_storage(storage()).await;
+
+        let json = server.get("/_api/search?q=deploy").await.json();
+
+        let results = json["results"].as_array().unwrap();
+        assert_eq!(results.len(), 2, "json: {json}");
+        assert_eq!(results[0]["path"], "/guide/deploy");
+        assert_eq!(results[0]["title"], "Deployment");
+        assert!(
+            results[0]["snippet"]
+                .as_str()
+                .unwrap()
+                .contains("deploy script")
+        );
+        assert_eq!(results[1]["path"], "/");
+    }
+
+    #[tokio::test]
+    async fn test_search_honors_limit_and_empty_query() {
+        let server = TestServer::with_storage(storage()).await;
+
+        let json = server.get("/_api/search?q=deploy&limit=1").await.json();
+        assert_eq!(json["results"].as_array().unwrap().len(), 1);
+
+        let json = server.get("/_api/search").await.json();
+        assert_eq!(json["results"], serde_json::json!([]));
+    }
+
+    #[tokio::test]
+    async fn test_search_storage_failure_is_unavailable() {
+        let storage = MockStorage::new().with_scan_error(StorageErrorKind::Unavailable);
+        let server = TestServer::with_storage(storage).await;
+
+        let resp = server.get("/_api/search?q=deploy").await;
+
+        assert_eq!(resp.status, StatusCode::SERVICE_UNAVAILABLE);
+    }
+}
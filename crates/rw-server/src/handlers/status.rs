@@ -0,0 +1,137 @@
+//! Site status API endpoint.
+//!
+//! Reports whether the site loaded cleanly and how many warnings rendered
+//! pages produced, so operators can spot a broken site from the API.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use axum::Json;
+use axum::extract::State;
+use rw_cache::CacheStats;
+use rw_site::SiteStatus;
+use serde::Serialize;
+
+use crate::state::AppState;
+
+/// Response for GET /_api/site-status.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SiteStatusResponse {
+    /// When the site structure was last scanned (Unix seconds), or `null`
+    /// before the first scan.
+    last_scan: Option<f64>,
+    /// Pages in the site structure, virtual pages included.
+    page_count: usize,
+    /// Whether the cached site structure is current (`false` between a
+    /// change on disk and the next request that reloads it).
+    structure_fresh: bool,
+    /// Pages rendered since the server started.
+    rendered_pages: usize,
+    /// Rendered pages whose latest render produced warnings.
+    pages_with_warnings: usize,
+    /// Total warnings across the latest render of each rendered page.
+    warning_count: usize,
+    /// Problems found by the latest storage scan (e.g. a file shadowed by
+    /// another that maps to the same URL path).
+    scan_warnings: Vec<String>,
+    /// Lookups and writes of each cache bucket since the server started, by
+    /// bucket name. Empty when caching is disabled.
+    cache_stats: BTreeMap<String, CacheStatsResponse>,
+}
+
+/// Counters of one cache bucket.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CacheStatsResponse {
+    hits: u64,
+    misses: u64,
+    sets: u64,
+    /// Fraction of lookups that hit, or `null` before the first lookup.
+    hit_rate: Option<f64>,
+}
+
+impl From<CacheStats> for CacheStatsResponse {
+    fn from(stats: CacheStats) -> Self {
+        Self {
+            hits: stats.hits,
+            misses: stats.misses,
+            sets: stats.sets,
+            hit_rate: stats.hit_rate(),
+        }
+    }
+}
+
+impl From<SiteStatus> for SiteStatusResponse {
+    fn from(status: SiteStatus) -> Self {
+        Self {
+            last_scan: status.last_scan,
+            page_count: status.page_count,
+            structure_fresh: status.structure_fresh,
+            rendered_pages: status.rendered_pages,
+            pages_with_warnings: status.pages_with_warnings,
+            warning_count: status.warning_count,
+            scan_warnings: status.scan_warnings,
+            cache_stats: status
+                .cache_stats
+                .into_iter()
+                .map(|(name, stats)| (name, stats.into()))
+                .collect(),
+        }
+    }
+}
+
+/// Handle GET /_api/site-status.
+///
+/// Reads state the site already tracks; never rescans or renders.
+pub(crate) async fn get_site_status(
+    State(state): State<Arc<AppState>>,
+) -> Json<SiteStatusResponse> {
+    Json(state.site.status().into())
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::StatusCode;
+    use rw_storage::MockStorage;
+
+    use crate::testing::TestServer;
+
+    #[tokio::test]
+    async fn test_site_status_counts_warnings_of_rendered_pages() {
+        let storage = MockStorage::new()
+            .with_file("guide", "Guide", ":::tab[A]\n\nOrphan.\n\n:::")
+            .with_mtime("guide", 1000.0)
+            .with_scan_warning("guide.md is ignored: guide/index.md also maps to /guide");
+        let server = TestServer::with_storage(storage).await;
+
+        let resp = server.get("/_api/pages/guide").await;
+        assert_eq!(resp.status, StatusCode::OK, "body: {}", resp.text());
+
+        let resp = server.get("/_api/site-status").await;
+        assert_eq!(resp.status, StatusCode::OK, "body: {}", resp.text());
+        let json = resp.json();
+        assert!(json["lastScan"].is_f64(), "json: {json}");
+        assert_eq!(json["pageCount"], 1);
+        assert_eq!(json["structureFresh"], true);
+        assert_eq!(json["renderedPages"], 1);
+        assert_eq!(json["pagesWithWarnings"], 1);
+        assert_eq!(json["warningCount"], 1);
+        assert_eq!(
+            json["scanWarnings"][0],
+            "guide.md is ignored: guide/index.md also maps to /guide"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_site_status_before_first_scan() {
+        let server = TestServer::with_comments().await;
+
+        let json = server.get("/_api/site-status").await.json();
+
+        assert!(json["lastScan"].is_null(), "json: {json}");
+        assert_eq!(json["renderedPages"], 0);
+        // The test server runs without a cache.
+        assert_eq!(json["cacheStats"], serde_json::json!({}));
+    }
+}
@@ -57,10 +57,14 @@
 //! ```
 
 mod app;
+mod cache_control;
 mod error;
+mod etag;
 mod handlers;
 mod live_reload;
 mod middleware;
+#[cfg(unix)]
+mod reload;
 mod state;
 mod static_files;
 #[cfg(test)]
@@ -68,20 +72,30 @@ mod testing;
 
 pub use error::ServerError;
 
+/// `Cache-Control` settings for [`ServerConfig::cache`], as read from
+/// `[server.cache]`.
+pub use rw_config::CacheConfig;
+/// Command-line overrides for [`ServerConfig::cli_settings`].
+pub use rw_config::CliSettings;
+/// CORS settings for [`ServerConfig::cors`], as read from `[server.cors]`.
+pub use rw_config::CorsConfig;
+
 use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::str::FromStr;
 use std::sync::Arc;
 
+use cache_control::CacheControl;
 use rw_comments::SqliteCommentStore;
 use rw_server_info::ServerInfo;
-use rw_site::{PageRendererConfig, Site};
+use rw_site::{DiagramFormat, PageRendererConfig, Site};
 use rw_storage_fs::FsStorage;
 use state::AppState;
 use tokio::sync::broadcast;
 
 /// Server configuration.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct ServerConfig {
     /// Host address to bind to.
     pub host: String,
@@ -92,12 +106,24 @@ pub struct ServerConfig {
     pub project_dir: PathBuf,
     /// Documentation source directory.
     pub source_dir: PathBuf,
+    /// Further source directories merged after `source_dir`. A page in an
+    /// earlier directory wins over the same page in a later one.
+    pub extra_source_dirs: Vec<PathBuf>,
     /// Cache directory (`None` disables caching).
     pub cache_dir: Option<PathBuf>,
     /// Kroki URL for diagrams (`None` disables diagrams).
     pub kroki_url: Option<String>,
     /// `PlantUML` include directories.
     pub include_dirs: Vec<PathBuf>,
+    /// Format for diagrams whose fence has no `format` attribute.
+    pub diagram_format: DiagramFormat,
+    /// Extra HTTP headers sent with every Kroki request.
+    pub kroki_headers: Vec<(String, String)>,
+    /// Deepest heading level in a page's table of contents (`None`: every
+    /// level).
+    pub toc_max_depth: Option<u8>,
+    /// Render `$tex$` and `$$tex$$` as math.
+    pub math: bool,
     /// Enable live reload.
     pub live_reload_enabled: bool,
     /// Enable verbose output.
@@ -106,12 +132,41 @@ pub struct ServerConfig {
     pub version: String,
     /// Metadata file name (default: "meta.yaml").
     pub meta_filename: String,
+    /// Markdown file extensions without the dot (default: `["md"]`).
+    pub extensions: Vec<String>,
+    /// Fall back to case-insensitive path resolution (default: off).
+    pub case_insensitive: bool,
+    /// Homepage fallback files, tried in order when there is no `index.md`.
+    pub homepage: Vec<PathBuf>,
     /// Path to `SQLite` database for comments.
     pub comments_db: PathBuf,
     /// Enable embedded preview mode (serves Backstage-like shell at /).
     pub embedded_preview: bool,
     /// The `.rw` data directory (holds `server.json`, `comments/`, cache).
     pub data_dir: PathBuf,
+    /// Cross-origin access to the `/_api` routes (default: none, so no CORS
+    /// headers are sent).
+    pub cors: CorsConfig,
+    /// `Cache-Control` for page responses and content-hashed build assets
+    /// (default: pages `no-cache`, assets cached for a year as immutable).
+    /// With live reload on, pages get [`CacheConfig::live_reload`] instead.
+    pub cache: CacheConfig,
+    /// Compress responses with gzip or brotli when the client accepts it
+    /// (default: on).
+    pub compression: bool,
+    /// URL path of the page rendered as the body of 404 responses for
+    /// missing pages (default: `"404"`, i.e. `404.md`). Empty, or a page the
+    /// site does not have, sends the plain JSON error instead.
+    pub not_found_page: String,
+    /// Shared token every request must carry as `Authorization: Bearer
+    /// <token>`, except the `/healthz` and `/readyz` probes (default: `None`,
+    /// no authentication). An empty token counts as none.
+    pub auth_token: Option<String>,
+    /// The `rw.toml` the configuration came from, re-read on `SIGHUP` (Unix
+    /// only). `None` re-reads `project_dir/rw.toml` if there is one by then.
+    pub config_file: Option<PathBuf>,
+    /// Command-line overrides, applied again on top of a reloaded file.
+    pub cli_settings: CliSettings,
 }
 
 impl Default for ServerConfig {
@@ -124,22 +179,37 @@ impl Default for ServerConfig {
             port: 7979,
             project_dir: PathBuf::from("."),
             source_dir: PathBuf::from("docs"),
+            extra_source_dirs: Vec::new(),
             cache_dir: None,
             kroki_url: None,
             include_dirs: Vec::new(),
+            diagram_format: DiagramFormat::default(),
+            kroki_headers: Vec::new(),
+            toc_max_depth: None,
+            math: false,
             live_reload_enabled: false,
             verbose: false,
             version: String::new(),
             meta_filename: "meta.yaml".to_owned(),
+            extensions: vec!["md".to_owned()],
+            case_insensitive: false,
+            homepage: vec![PathBuf::from("README.md")],
             comments_db: SqliteCommentStore::default_path(&data_dir),
             embedded_preview: false,
             data_dir,
+            cors: CorsConfig::default(),
+            cache: CacheConfig::default(),
+            compression: true,
+            not_found_page: "404".to_owned(),
+            auth_token: None,
+            config_file: None,
+            cli_settings: CliSettings::default(),
         }
     }
 }
 
 /// Number of sequential ports tried when the requested port is busy and port
-/// fallback is enabled: the default port and the next 19 above it.
+/// fallback is enabled: the requested port and the next 19 above it.
 const PORT_FALLBACK_RANGE: u16 = 20;
 
 /// Bind a TCP listener on `host:port`, optionally falling back to the next free
@@ -147,8 +217,8 @@ const PORT_FALLBACK_RANGE: u16 = 20;
 ///
 /// When `allow_fallback` is `true` and `port` is already in use, the next
 /// sequential ports are tried (up to [`PORT_FALLBACK_RANGE`] total) and the
-/// first free one is used — this is how `rw serve` copes with a busy *default*
-/// port. When `allow_fallback` is `false`, a busy port is a hard error
+/// first free one is used — this is how `rw serve` copes with a busy default
+/// port, or with any busy port under `port_auto`. When `allow_fallback` is `false`, a busy port is a hard error
 /// ([`ServerError::PortInUse`]): the caller asked for a specific port and must
 /// get it or nothing.
 ///
@@ -174,9 +244,8 @@ pub async fn bind_listener(
 
     let mut last_candidate = port;
     for offset in 0..attempts {
-        // Stop early if incrementing would overflow past the last port. Only
-        // reachable with a near-max explicit-but-fallback port, which we never
-        // produce today, but keep it total rather than panicking.
+        // Stop early if incrementing would overflow past the last port: an
+        // explicit port near 65535 with `port_auto` on reaches this.
         let Some(candidate) = port.checked_add(offset) else {
             break;
         };
@@ -218,17 +287,15 @@ pub async fn bind_listener(
 ///
 /// # Errors
 ///
-/// Returns an error if the server fails to start.
+/// Returns an error if the server fails to start, including
+/// [`ServerError::Cors`] for an invalid [`ServerConfig::cors`] and
+/// [`ServerError::CacheControl`] for an invalid [`ServerConfig::cache`].
 pub async fn run_server(
     config: ServerConfig,
     listener: tokio::net::TcpListener,
 ) -> Result<(), ServerError> {
-    // Create shared storage backend
-    let storage: Arc<dyn rw_storage::Storage> = Arc::new(FsStorage::with_meta_filename(
-        config.project_dir.clone(),
-        config.source_dir.clone(),
-        &config.meta_filename,
-    ));
+    let cors = middleware::cors::cors_layer(&config.cors)?;
+    let cache_control = CacheControl::new(&config.cache, config.live_reload_enabled)?;
 
     // Construct cache
     let cache: Arc<dyn rw_cache::Cache> = match &config.cache_dir {
@@ -236,13 +303,50 @@ pub async fn run_server(
         None => Arc::new(rw_cache::NullCache),
     };
 
+    // Create shared storage backend, one layer per source directory; resolved
+    // titles survive restarts through the same cache (a no-op bucket when
+    // caching is disabled)
+    let source_dirs: Vec<PathBuf> = std::iter::once(&config.source_dir)
+        .chain(&config.extra_source_dirs)
+        .cloned()
+        .collect();
+    let storage = rw_storage_fs::source_dirs_storage(&source_dirs, |i, source_dir| {
+        let meta_bucket = if i == 0 {
+            "meta".to_owned()
+        } else {
+            format!("meta-{i}")
+        };
+        FsStorage::with_meta_filename(
+            config.project_dir.clone(),
+            source_dir.to_path_buf(),
+            &config.meta_filename,
+        )
+        .with_extensions(&config.extensions)
+        .with_case_insensitive(config.case_insensitive)
+        .with_homepage(config.homepage.clone())
+        .with_meta_cache(cache.bucket(&meta_bucket))
+    });
+
     // Create unified Site with storage and configuration
-    let site_config = PageRendererConfig {
-        extract_title: true,
-        kroki_url: config.kroki_url.clone(),
-        include_dirs: config.include_dirs.clone(),
-    };
-    let site = Arc::new(Site::new(Arc::clone(&storage), cache, site_config));
+    let site = Arc::new(Site::new(
+        Arc::clone(&storage),
+        cache,
+        renderer_config(&config),
+    ));
+
+    // Load the site in the background, so moved-page redirects, which only
+    // read the loaded site, answer from the first request. A failure is
+    // retried by the first request that needs the site.
+    let loader = Arc::clone(&site);
+    tokio::task::spawn_blocking(move || {
+        if let Err(e) = loader.ensure_loaded() {
+            tracing::warn!(error = %e, "Failed to load site at startup");
+        }
+    });
+
+    // Re-read rw.toml on SIGHUP for as long as the server runs
+    #[cfg(unix)]
+    let reload_task = tokio::spawn(reload::reload_on_sighup(Arc::clone(&site), config.clone()));
 
     // Create live reload manager if enabled
     let live_reload = if config.live_reload_enabled {
@@ -278,6 +382,18 @@ pub async fn run_server(
         comment_store,
         notify_token,
         embedded_preview: config.embedded_preview,
+        cors,
+        compression: config.compression,
+        not_found_page: config.not_found_page.trim_matches('/').to_owned(),
+        auth_token: config
+            .auth_token
+            .as_deref()
+            .filter(|token| !token.is_empty())
+            .map(Arc::from),
+        version: config.version.clone(),
+        source_dirs,
+        cache_enabled: config.cache_dir.is_some(),
+        cache_control,
     });
 
     // Create router
@@ -293,13 +409,32 @@ pub async fn run_server(
         }
     });
 
-    axum::serve(listener, app)
+    let served = axum::serve(listener, app)
         .with_graceful_shutdown(shutdown_signal())
-        .await?;
+        .await;
+    #[cfg(unix)]
+    reload_task.abort();
+    served?;
 
     Ok(())
 }
 
+/// The page renderer settings in `config`.
+fn renderer_config(config: &ServerConfig) -> PageRendererConfig {
+    PageRendererConfig {
+        extract_title: true,
+        kroki_url: config.kroki_url.clone(),
+        include_dirs: config.include_dirs.clone(),
+        diagram_format: config.diagram_format,
+        kroki_headers: config.kroki_headers.clone(),
+        toc_max_depth: config.toc_max_depth,
+        math: config.math,
+        // `rw serve` does not support client-side Mermaid: its viewer loads no
+        // mermaid.js, so the diagram would show as its source.
+        client_side_mermaid: false,
+    }
+}
+
 /// Wait for a shutdown signal: Ctrl-C (SIGINT) on all platforms, plus SIGTERM
 /// on Unix (the default `kill` / `docker stop` / systemd-stop signal). Handling
 /// SIGTERM lets graceful shutdown run so the server-info file guard cleans up
@@ -349,6 +484,13 @@ pub fn server_config_from_rw_config(
         port: config.server.port,
         project_dir: config.project_dir.clone(),
         source_dir: config.docs_resolved.source_dir.clone(),
+        extra_source_dirs: config
+            .docs_resolved
+            .source_dirs
+            .iter()
+            .skip(1)
+            .cloned()
+            .collect(),
         cache_dir: if config.docs_resolved.cache_enabled {
             Some(config.docs_resolved.cache_dir())
         } else {
@@ -356,12 +498,35 @@ pub fn server_config_from_rw_config(
         },
         kroki_url: config.diagrams_resolved.kroki_url.clone(),
         include_dirs: config.diagrams_resolved.include_dirs.clone(),
+        diagram_format: config
+            .diagrams_resolved
+            .default_format
+            .as_deref()
+            .and_then(DiagramFormat::parse)
+            .unwrap_or_default(),
+        kroki_headers: config
+            .diagrams_resolved
+            .kroki_headers
+            .clone()
+            .into_iter()
+            .collect(),
+        toc_max_depth: config.docs_resolved.toc_max_depth,
+        math: config.docs_resolved.math,
         live_reload_enabled: config.live_reload.enabled,
         verbose,
         version,
         meta_filename: config.metadata.name.clone(),
+        extensions: config.docs_resolved.extensions.clone(),
+        case_insensitive: config.docs_resolved.case_insensitive,
+        homepage: config.docs_resolved.homepage.clone(),
         comments_db: SqliteCommentStore::default_path(&config.docs_resolved.data_dir),
         data_dir: config.docs_resolved.data_dir.clone(),
+        cors: config.server.cors.clone(),
+        cache: config.server.cache.clone(),
+        compression: config.server.compression,
+        not_found_page: config.server.not_found_page.clone(),
+        auth_token: config.server.auth_token.clone(),
+        config_file: config.config_file.clone(),
         ..Default::default()
     }
 }
@@ -376,6 +541,23 @@ mod tests {
         assert_eq!(cfg.data_dir, std::path::PathBuf::from(".rw"));
     }
 
+    #[test]
+    fn server_config_splits_source_dir_list() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("docs")).unwrap();
+        std::fs::write(
+            dir.path().join("rw.toml"),
+            "[docs]\nsource_dir = [\"docs\", \"extra\"]\n",
+        )
+        .unwrap();
+        let rw_config = rw_config::Config::load_from_dir(dir.path(), None).unwrap();
+
+        let cfg = server_config_from_rw_config(&rw_config, String::new(), false);
+
+        assert_eq!(cfg.source_dir, dir.path().join("docs"));
+        assert_eq!(cfg.extra_source_dirs, vec![dir.path().join("extra")]);
+    }
+
     #[tokio::test]
     async fn bind_listener_uses_requested_free_port() {
         // Port 0 asks the OS for any free port — always succeeds.
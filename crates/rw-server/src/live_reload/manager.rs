@@ -88,9 +88,13 @@ impl LiveReloadManager {
             StorageEventKind::Modified {
                 title: new_title,
                 pages: new_pages,
+                order: new_order,
+                redirects: new_redirects,
             } => {
                 let old_title = site.page_title(&event.path);
                 let old_pages = site.page_pages(&event.path);
+                let old_order = site.page_order(&event.path);
+                let old_redirects = site.page_redirects(&event.path);
 
                 // If page is known, always send content event
                 if old_title.is_some() {
@@ -100,9 +104,20 @@ impl LiveReloadManager {
                 }
 
                 let title_changed = old_title.as_deref() != Some(new_title);
-                let pages_changed = old_pages.as_ref() != new_pages.as_ref();
-                if title_changed || pages_changed {
-                    site.invalidate();
+                // Either reorders the navigation.
+                let order_changed =
+                    old_pages.as_ref() != new_pages.as_ref() || old_order != *new_order;
+                let redirects_changed = old_redirects.as_ref() != new_redirects.as_ref();
+                if title_changed || order_changed || redirects_changed {
+                    // A retitle alone is patched into the loaded structure;
+                    // anything else (or a structure that cannot take the
+                    // patch) costs a full rescan on the next read.
+                    if order_changed
+                        || redirects_changed
+                        || !site.apply_title_change(&event.path, new_title)
+                    {
+                        site.invalidate();
+                    }
                     let _ = broadcaster.send(ReloadEvent::Structure { path: url_path });
                 }
             }
@@ -272,4 +287,107 @@ mod tests {
             "Created should produce exactly one Structure broadcast",
         );
     }
+
+    #[test]
+    fn title_change_patches_structure_without_rescan() {
+        let storage = Arc::new(MockStorage::new().with_document("guide", "Guide"));
+        let site = loaded_site(&storage);
+        let scans = storage.scan_count();
+        let (tx, mut rx) = broadcast::channel(8);
+
+        LiveReloadManager::handle_storage_event(
+            &StorageEvent {
+                path: "guide".into(),
+                kind: StorageEventKind::Modified {
+                    title: "User Guide".into(),
+                    pages: None,
+                    order: None,
+                    redirects: None,
+                },
+            },
+            &site,
+            &tx,
+        );
+
+        assert_matches!(rx.try_recv().unwrap(), ReloadEvent::Content { .. });
+        assert_matches!(rx.try_recv().unwrap(), ReloadEvent::Structure { ref path } if path == "/guide");
+        site.navigation(None).unwrap();
+        assert_eq!(site.page_title("guide").as_deref(), Some("User Guide"));
+        assert_eq!(storage.scan_count(), scans, "a retitle must not rescan");
+    }
+
+    #[test]
+    fn pages_change_falls_back_to_rescan() {
+        let storage = Arc::new(MockStorage::new().with_document("guide", "Guide"));
+        let site = loaded_site(&storage);
+        let scans = storage.scan_count();
+        let (tx, _rx) = broadcast::channel(8);
+
+        LiveReloadManager::handle_storage_event(
+            &StorageEvent {
+                path: "guide".into(),
+                kind: StorageEventKind::Modified {
+                    title: "Guide".into(),
+                    pages: Some(vec!["setup".into()]),
+                    order: None,
+                    redirects: None,
+                },
+            },
+            &site,
+            &tx,
+        );
+
+        site.navigation(None).unwrap();
+        assert_eq!(storage.scan_count(), scans + 1);
+    }
+
+    #[test]
+    fn order_change_falls_back_to_rescan() {
+        let storage = Arc::new(MockStorage::new().with_document("guide", "Guide"));
+        let site = loaded_site(&storage);
+        let scans = storage.scan_count();
+        let (tx, _rx) = broadcast::channel(8);
+
+        LiveReloadManager::handle_storage_event(
+            &StorageEvent {
+                path: "guide".into(),
+                kind: StorageEventKind::Modified {
+                    title: "Guide".into(),
+                    pages: None,
+                    order: Some(1),
+                    redirects: None,
+                },
+            },
+            &site,
+            &tx,
+        );
+
+        site.navigation(None).unwrap();
+        assert_eq!(storage.scan_count(), scans + 1);
+    }
+
+    #[test]
+    fn redirects_change_falls_back_to_rescan() {
+        let storage = Arc::new(MockStorage::new().with_document("guide", "Guide"));
+        let site = loaded_site(&storage);
+        let scans = storage.scan_count();
+        let (tx, _rx) = broadcast::channel(8);
+
+        LiveReloadManager::handle_storage_event(
+            &StorageEvent {
+                path: "guide".into(),
+                kind: StorageEventKind::Modified {
+                    title: "Guide".into(),
+                    pages: None,
+                    order: None,
+                    redirects: Some(vec!["legacy".into()]),
+                },
+            },
+            &site,
+            &tx,
+        );
+
+        site.navigation(None).unwrap();
+        assert_eq!(storage.scan_count(), scans + 1);
+    }
 }
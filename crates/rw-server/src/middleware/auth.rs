@@ -0,0 +1,207 @@
+//! Bearer-token authentication.
+//!
+//! With [`ServerConfig::auth_token`](crate::ServerConfig::auth_token) set,
+//! every request must carry `Authorization: Bearer <token>` or is answered
+//! with `401 Unauthorized`. The router only adds this layer when a token is
+//! configured, so an open server does no header checks at all. The health
+//! probes are routed outside it.
+//!
+//! Browsers send CORS preflights without credentials. When `[server.cors]` is
+//! on, a preflight for an `/_api` route is answered here by the CORS layer,
+//! so it never reaches the redirects or handlers behind this layer. Without
+//! CORS, a preflight needs the token like any other request.
+
+use std::convert::Infallible;
+use std::sync::Arc;
+
+use axum::Json;
+use axum::extract::{Request, State};
+use axum::http::{HeaderMap, Method, StatusCode, header};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use serde_json::json;
+use tower::{Layer, ServiceExt, service_fn};
+use tower_http::cors::CorsLayer;
+
+/// What [`require_bearer_token`] checks requests against.
+#[derive(Clone)]
+pub(crate) struct Auth {
+    /// The token every request must carry.
+    pub(crate) token: Arc<str>,
+    /// CORS for the `/_api` routes, which answers their preflights without a
+    /// token (`None`: CORS is off).
+    pub(crate) cors: Option<CorsLayer>,
+}
+
+/// Reject requests without the configured bearer token.
+pub(crate) async fn require_bearer_token(
+    State(auth): State<Auth>,
+    req: Request,
+    next: Next,
+) -> Response {
+    if let Some(cors) = &auth.cors
+        && is_api_preflight(&req)
+    {
+        return answer_preflight(cors, req).await;
+    }
+    if bearer_token_matches(req.headers(), &auth.token) {
+        return next.run(req).await;
+    }
+    (
+        StatusCode::UNAUTHORIZED,
+        [(header::WWW_AUTHENTICATE, "Bearer")],
+        Json(json!({"error": "Unauthorized"})),
+    )
+        .into_response()
+}
+
+/// Whether `req` is a CORS preflight for an `/_api` route, the only routes
+/// that carry CORS headers.
+fn is_api_preflight(req: &Request) -> bool {
+    let path = req.uri().path();
+    req.method() == Method::OPTIONS
+        && req
+            .headers()
+            .contains_key(header::ACCESS_CONTROL_REQUEST_METHOD)
+        && (path == "/_api" || path.starts_with("/_api/"))
+}
+
+/// Answer the preflight `req` with `cors` alone. The CORS layer responds to a
+/// preflight itself, so the inner service is never called.
+async fn answer_preflight(cors: &CorsLayer, req: Request) -> Response {
+    cors.layer(service_fn(|_: Request| async {
+        Ok::<_, Infallible>(StatusCode::NO_CONTENT.into_response())
+    }))
+    .oneshot(req)
+    .await
+    .unwrap_or_else(|never| match never {})
+}
+
+/// Whether `headers` carry `Authorization: Bearer <expected>`. The scheme is
+/// case-insensitive, as in RFC 9110.
+fn bearer_token_matches(headers: &HeaderMap, expected: &str) -> bool {
+    let Some((scheme, token)) = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split_once(' '))
+    else {
+        return false;
+    };
+    scheme.eq_ignore_ascii_case("Bearer")
+        && constant_time_eq(token.trim().as_bytes(), expected.as_bytes())
+}
+
+/// Compare in time that depends only on the lengths, not on where the inputs
+/// first differ, so response timing does not reveal a token prefix.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let diff = a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y));
+    std::hint::black_box(diff) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::StatusCode;
+
+    use super::*;
+    use crate::CorsConfig;
+    use crate::testing::TestServer;
+
+    const TOKEN: &str = "s3cret-token";
+
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq(b"token", b"token"));
+        assert!(!constant_time_eq(b"token", b"tokem"));
+        assert!(!constant_time_eq(b"token", b"token2"));
+        assert!(constant_time_eq(b"", b""));
+    }
+
+    #[test]
+    fn test_bearer_scheme_is_case_insensitive() {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::AUTHORIZATION, "bearer s3cret".parse().unwrap());
+        assert!(bearer_token_matches(&headers, "s3cret"));
+
+        headers.insert(header::AUTHORIZATION, "Basic s3cret".parse().unwrap());
+        assert!(!bearer_token_matches(&headers, "s3cret"));
+    }
+
+    #[tokio::test]
+    async fn test_requests_without_token_are_unauthorized() {
+        let server = TestServer::with_auth_token(TOKEN).await;
+
+        let missing = server.get("/_api/config").await;
+        let wrong = server
+            .get_with_header("/_api/config", "authorization", "Bearer nope")
+            .await;
+        let page = server.get("/guide").await;
+
+        assert_eq!(missing.status, StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            missing.header("www-authenticate").as_deref(),
+            Some("Bearer")
+        );
+        assert_eq!(wrong.status, StatusCode::UNAUTHORIZED);
+        assert_eq!(page.status, StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_request_with_token_is_served() {
+        let server = TestServer::with_auth_token(TOKEN).await;
+
+        let resp = server
+            .get_with_header("/_api/config", "authorization", &format!("Bearer {TOKEN}"))
+            .await;
+
+        assert_eq!(resp.status, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_probes_stay_open() {
+        let server = TestServer::with_auth_token(TOKEN).await;
+
+        assert_eq!(server.get("/healthz").await.status, StatusCode::OK);
+        assert_ne!(server.get("/readyz").await.status, StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_preflight_needs_token_without_cors() {
+        let server = TestServer::with_auth_token(TOKEN).await;
+
+        let api = server
+            .preflight("/_api/comments", "http://localhost:3000", "POST")
+            .await;
+        let page = server
+            .preflight("/guide/", "http://localhost:3000", "GET")
+            .await;
+
+        assert_eq!(api.status, StatusCode::UNAUTHORIZED);
+        assert_eq!(page.status, StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_api_preflight_is_answered_with_cors() {
+        let cors = CorsConfig {
+            allowed_origins: vec!["*".to_owned()],
+            ..CorsConfig::default()
+        };
+        let server = TestServer::with_auth_token_and_cors(TOKEN, cors).await;
+
+        let api = server
+            .preflight("/_api/comments", "http://localhost:3000", "POST")
+            .await;
+        let page = server
+            .preflight("/guide/", "http://localhost:3000", "GET")
+            .await;
+
+        assert_eq!(api.status, StatusCode::OK);
+        assert_eq!(
+            api.header("access-control-allow-origin").as_deref(),
+            Some("*")
+        );
+        assert_eq!(page.status, StatusCode::UNAUTHORIZED);
+    }
+}
@@ -0,0 +1,114 @@
+//! Response compression.
+//!
+//! Compresses responses with gzip or brotli, whichever the request's
+//! `Accept-Encoding` prefers, so pages with inline SVG diagrams and the
+//! frontend bundle go over the wire at a fraction of their size. A compressed
+//! response drops `Content-Length` and is sent chunked.
+
+use axum::http::{Extensions, HeaderMap, StatusCode, Version};
+use tower_http::compression::CompressionLayer;
+use tower_http::compression::predicate::{DefaultPredicate, Predicate};
+
+/// Create the compression layer.
+pub(crate) fn compression_layer() -> CompressionLayer<impl Predicate> {
+    CompressionLayer::new().compress_when(should_compress())
+}
+
+/// Which responses to compress: tower-http's defaults — nothing under 32
+/// bytes and no images but SVG, as formats like PNG are compressed already —
+/// and never a protocol switch, whose connection becomes the live-reload
+/// WebSocket.
+fn should_compress() -> impl Predicate {
+    DefaultPredicate::new().and(
+        |status: StatusCode, _: Version, _: &HeaderMap, _: &Extensions| {
+            status != StatusCode::SWITCHING_PROTOCOLS
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+    use axum::http::{Response, header};
+    use rw_storage::MockStorage;
+
+    use super::*;
+    use crate::testing::TestServer;
+
+    fn response(status: StatusCode, content_type: &str) -> Response<Body> {
+        Response::builder()
+            .status(status)
+            .header(header::CONTENT_TYPE, content_type)
+            .body(Body::from(vec![b'x'; 1024]))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_compresses_text_and_svg_but_not_raster_images() {
+        let predicate = should_compress();
+
+        for content_type in ["application/json", "text/html", "image/svg+xml"] {
+            assert!(
+                predicate.should_compress(&response(StatusCode::OK, content_type)),
+                "{content_type}"
+            );
+        }
+        for content_type in ["image/png", "image/jpeg"] {
+            assert!(
+                !predicate.should_compress(&response(StatusCode::OK, content_type)),
+                "{content_type}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_never_compresses_protocol_switch() {
+        let predicate = should_compress();
+
+        assert!(!predicate.should_compress(&response(
+            StatusCode::SWITCHING_PROTOCOLS,
+            "application/octet-stream"
+        )));
+    }
+
+    fn storage() -> MockStorage {
+        let body = "Diagrams and prose. ".repeat(100);
+        MockStorage::new()
+            .with_file("guide", "Guide", format!("# Guide\n\n{body}"))
+            .with_mtime("guide", 1000.0)
+    }
+
+    #[tokio::test]
+    async fn test_negotiates_encoding_from_accept_encoding() {
+        let server = TestServer::with_storage(storage()).await;
+
+        let gzip = server
+            .get_with_header("/_api/pages/guide", "accept-encoding", "gzip")
+            .await;
+        let br = server
+            .get_with_header("/_api/pages/guide", "accept-encoding", "gzip;q=0.5, br")
+            .await;
+
+        assert_eq!(gzip.status, StatusCode::OK);
+        assert_eq!(gzip.header("content-encoding").as_deref(), Some("gzip"));
+        assert_eq!(br.header("content-encoding").as_deref(), Some("br"));
+        // The compressed length is unknown until the body is sent.
+        assert_eq!(gzip.header("content-length"), None);
+    }
+
+    #[tokio::test]
+    async fn test_identity_without_accept_encoding() {
+        let server = TestServer::with_storage(storage()).await;
+
+        let resp = server.get("/_api/pages/guide").await;
+
+        assert_eq!(resp.status, StatusCode::OK);
+        assert_eq!(resp.header("content-encoding"), None);
+        assert!(
+            resp.json()["content"]
+                .as_str()
+                .unwrap()
+                .contains("Diagrams")
+        );
+    }
+}
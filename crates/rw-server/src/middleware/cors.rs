@@ -0,0 +1,167 @@
+//! CORS for the API routes.
+//!
+//! Builds a [`CorsLayer`] from [`CorsConfig`]. The router applies it to the
+//! `/_api` routes only, so the SPA, its assets and the probes never carry
+//! CORS headers.
+
+use axum::http::{HeaderName, HeaderValue, Method};
+use tower_http::cors::{AllowHeaders, AllowMethods, AllowOrigin, CorsLayer};
+
+use crate::CorsConfig;
+use crate::error::ServerError;
+
+/// Create the CORS layer for `config`, or `None` when no origin is allowed.
+///
+/// `"*"` anywhere in a list allows every value. Credentials are never
+/// allowed, which is what makes the wildcard legal for browsers.
+///
+/// # Errors
+///
+/// Returns [`ServerError::Cors`] for an origin, method or header name that is
+/// not a valid HTTP value.
+pub(crate) fn cors_layer(config: &CorsConfig) -> Result<Option<CorsLayer>, ServerError> {
+    if config.is_disabled() {
+        return Ok(None);
+    }
+
+    let origin = if is_wildcard(&config.allowed_origins) {
+        AllowOrigin::any()
+    } else {
+        AllowOrigin::list(parse_all(&config.allowed_origins, "origin", |o| {
+            HeaderValue::from_str(o).ok()
+        })?)
+    };
+    let methods = if is_wildcard(&config.allowed_methods) {
+        AllowMethods::any()
+    } else {
+        AllowMethods::list(parse_all(&config.allowed_methods, "method", |m| {
+            Method::from_bytes(m.as_bytes()).ok()
+        })?)
+    };
+    let headers = if is_wildcard(&config.allowed_headers) {
+        AllowHeaders::any()
+    } else {
+        AllowHeaders::list(parse_all(&config.allowed_headers, "header", |h| {
+            HeaderName::from_bytes(h.as_bytes()).ok()
+        })?)
+    };
+
+    Ok(Some(
+        CorsLayer::new()
+            .allow_origin(origin)
+            .allow_methods(methods)
+            .allow_headers(headers),
+    ))
+}
+
+fn is_wildcard(values: &[String]) -> bool {
+    values.iter().any(|v| v == "*")
+}
+
+/// Parse every value with `parse`, naming the first that fails.
+fn parse_all<T>(
+    values: &[String],
+    what: &str,
+    parse: impl Fn(&str) -> Option<T>,
+) -> Result<Vec<T>, ServerError> {
+    values
+        .iter()
+        .map(|value| {
+            parse(value).ok_or_else(|| ServerError::Cors(format!("invalid {what} \"{value}\"")))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::StatusCode;
+
+    use super::*;
+    use crate::testing::TestServer;
+
+    fn allow(origins: &[&str]) -> CorsConfig {
+        CorsConfig {
+            allowed_origins: origins.iter().map(|&o| o.to_owned()).collect(),
+            ..CorsConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_disabled_by_default() {
+        assert!(cors_layer(&CorsConfig::default()).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_invalid_method_is_rejected() {
+        let config = CorsConfig {
+            allowed_methods: vec!["GET POST".to_owned()],
+            ..allow(&["*"])
+        };
+
+        let err = cors_layer(&config).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "invalid CORS setting: invalid method \"GET POST\""
+        );
+    }
+
+    #[tokio::test]
+    async fn test_no_cors_headers_by_default() {
+        let server = TestServer::with_cors(CorsConfig::default()).await;
+
+        let resp = server
+            .get_with_header("/_api/config", "origin", "http://localhost:3000")
+            .await;
+
+        assert_eq!(resp.status, StatusCode::OK);
+        assert_eq!(resp.header("access-control-allow-origin"), None);
+    }
+
+    #[tokio::test]
+    async fn test_allowlist_echoes_listed_origin_only() {
+        let server = TestServer::with_cors(allow(&["http://localhost:3000"])).await;
+
+        let allowed = server
+            .get_with_header("/_api/config", "origin", "http://localhost:3000")
+            .await;
+        let other = server
+            .get_with_header("/_api/config", "origin", "http://evil.example")
+            .await;
+
+        assert_eq!(
+            allowed.header("access-control-allow-origin").as_deref(),
+            Some("http://localhost:3000")
+        );
+        assert_eq!(other.header("access-control-allow-origin"), None);
+    }
+
+    #[tokio::test]
+    async fn test_wildcard_answers_preflight() {
+        let server = TestServer::with_cors(allow(&["*"])).await;
+
+        let resp = server
+            .preflight("/_api/comments", "http://localhost:3000", "POST")
+            .await;
+
+        assert_eq!(resp.status, StatusCode::OK);
+        assert_eq!(
+            resp.header("access-control-allow-origin").as_deref(),
+            Some("*")
+        );
+        let methods = resp.header("access-control-allow-methods").unwrap();
+        assert!(methods.contains("POST"), "{methods}");
+    }
+
+    #[tokio::test]
+    async fn test_non_api_routes_get_no_cors_headers() {
+        let server = TestServer::with_cors(allow(&["*"])).await;
+
+        let resp = server
+            .get_with_header("/healthz", "origin", "http://localhost:3000")
+            .await;
+
+        assert_eq!(resp.status, StatusCode::OK);
+        assert_eq!(resp.header("access-control-allow-origin"), None);
+    }
+}
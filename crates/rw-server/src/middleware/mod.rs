@@ -1,3 +1,9 @@
 //! HTTP middleware layers.
 
+pub(crate) mod auth;
+pub(crate) mod compression;
+pub(crate) mod cors;
+pub(crate) mod moved_pages;
+pub(crate) mod request_log;
 pub(crate) mod security;
+pub(crate) mod trailing_slash;
@@ -0,0 +1,156 @@
+//! Moved-page redirect middleware.
+//!
+//! A page lists its former URL paths in `redirects` metadata. A request for
+//! one of them is answered with a `301 Moved Permanently` to the page's
+//! current URL, query string kept, so old bookmarks keep working. The lookup
+//! is [`Site::resolve_redirect`](rw_site::Site::resolve_redirect), which only
+//! holds paths that are no longer pages. It reads the loaded site as it is and
+//! never rescans storage, so it costs a map lookup per request; the server
+//! loads the site at startup, and the page and navigation API requests the
+//! viewer makes keep it current.
+//!
+//! The root, the API and WebSocket prefixes and the viewer's build assets
+//! pass through: none of them can be a former page URL. The pages API answers
+//! a former URL itself, with [`page_api_location`].
+
+use std::sync::Arc;
+
+use axum::extract::{Request, State};
+use axum::http::{StatusCode, header};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use percent_encoding::{AsciiSet, CONTROLS, percent_decode_str, utf8_percent_encode};
+use rw_site::{Site, to_url_path};
+
+use crate::state::AppState;
+use crate::static_files::is_hashed_asset;
+
+/// Bytes escaped in the `Location` path. Non-ASCII is always escaped.
+const PATH: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'%')
+    .add(b'<')
+    .add(b'>')
+    .add(b'?')
+    .add(b'`')
+    .add(b'{')
+    .add(b'}');
+
+/// Redirect a former page URL to the page that now lists it.
+pub(crate) async fn redirect_moved_pages(
+    State(state): State<Arc<AppState>>,
+    req: Request,
+    next: Next,
+) -> Response {
+    if let Some(location) = moved_location(&state.site, req.uri().path(), req.uri().query()) {
+        return (
+            StatusCode::MOVED_PERMANENTLY,
+            [(header::LOCATION, location)],
+        )
+            .into_response();
+    }
+    next.run(req).await
+}
+
+/// The pages API URL of `target`, a page path, for a `Location` header.
+pub(crate) fn page_api_location(target: &str) -> String {
+    format!("/_api/pages/{}", utf8_percent_encode(target, PATH))
+}
+
+/// The redirect target for `path`, or `None` if it is not a former page URL.
+fn moved_location(site: &Site, path: &str, query: Option<&str>) -> Option<String> {
+    if path == "/"
+        || path.starts_with("/_api/")
+        || path.starts_with("/ws/")
+        || is_hashed_asset(path.trim_start_matches('/'))
+    {
+        return None;
+    }
+    let path = percent_decode_str(path).decode_utf8().ok()?;
+    let target = site.resolve_redirect(&path)?;
+
+    let mut location = utf8_percent_encode(&to_url_path(&target), PATH).to_string();
+    if let Some(query) = query {
+        location.push('?');
+        location.push_str(query);
+    }
+    Some(location)
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::StatusCode;
+    use rw_storage::MockStorage;
+
+    use crate::testing::TestServer;
+
+    fn storage() -> MockStorage {
+        MockStorage::new()
+            .with_file("guides/setup", "Setup", "# Setup")
+            .with_redirects("guides/setup", &["old/setup"])
+            .with_file("справка", "Справка", "# Справка")
+            .with_redirects("справка", &["help"])
+            .with_file("assets", "Assets", "# Assets")
+            .with_redirects("assets", &["assets/old.js"])
+    }
+
+    /// A server whose site has loaded, as it has once the viewer fetched
+    /// navigation.
+    async fn loaded_server() -> TestServer {
+        let server = TestServer::with_storage(storage()).await;
+        server.get("/_api/navigation").await;
+        server
+    }
+
+    #[tokio::test]
+    async fn test_former_url_redirects_to_page() {
+        let server = loaded_server().await;
+
+        let resp = server.get("/old/setup?comment=42").await;
+
+        assert_eq!(resp.status, StatusCode::MOVED_PERMANENTLY);
+        assert_eq!(
+            resp.header("location").as_deref(),
+            Some("/guides/setup?comment=42")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_location_is_percent_encoded() {
+        let server = loaded_server().await;
+
+        let resp = server.get("/help").await;
+
+        assert_eq!(resp.status, StatusCode::MOVED_PERMANENTLY);
+        assert_eq!(
+            resp.header("location").as_deref(),
+            Some("/%D1%81%D0%BF%D1%80%D0%B0%D0%B2%D0%BA%D0%B0")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_current_url_api_and_assets_are_not_redirected() {
+        let server = loaded_server().await;
+
+        for path in [
+            "/guides/setup",
+            "/_api/navigation",
+            "/unknown",
+            "/assets/old.js",
+        ] {
+            let resp = server.get(path).await;
+            assert_ne!(resp.status, StatusCode::MOVED_PERMANENTLY, "{path}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_lookup_does_not_load_the_site() {
+        let server = TestServer::with_storage(storage()).await;
+
+        let resp = server.get("/old/setup").await;
+
+        assert_ne!(resp.status, StatusCode::MOVED_PERMANENTLY);
+    }
+}
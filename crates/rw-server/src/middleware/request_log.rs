@@ -0,0 +1,105 @@
+//! Request logging middleware.
+//!
+//! Logs one event per request — method, path, status, latency and, for
+//! rendered pages, whether the render cache served it — inside a `request`
+//! span, so the fields stay structured for whatever `tracing` subscriber the
+//! binary installs.
+//!
+//! API and WebSocket requests log at INFO (visible with `rw serve --verbose`);
+//! static assets log at DEBUG so a page load doesn't bury them under a dozen
+//! JS/CSS/font lines. Server errors log at WARN. `RUST_LOG` can retarget any of
+//! it via the `rw_server::middleware::request_log` target.
+//!
+//! Only the URI path is recorded: no query string and no headers, so tokens
+//! such as `X-RW-Token` never reach the logs.
+
+use std::time::Instant;
+
+use axum::extract::Request;
+use axum::http::StatusCode;
+use axum::middleware::Next;
+use axum::response::Response;
+use tracing::{Instrument, Level};
+
+/// Response extension a handler sets to report whether its body came from
+/// the render cache.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct CacheStatus {
+    /// `true` for a cache hit.
+    pub(crate) hit: bool,
+}
+
+/// Log each request once its response is ready.
+pub(crate) async fn log_requests(req: Request, next: Next) -> Response {
+    let start = Instant::now();
+    let method = req.method().clone();
+    let path = req.uri().path().to_owned();
+    let span = tracing::info_span!("request", %method, path = %path);
+
+    let response = next.run(req).instrument(span.clone()).await;
+
+    let status = response.status();
+    let latency_ms = start.elapsed().as_secs_f64() * 1000.0;
+    let cache = match response.extensions().get::<CacheStatus>() {
+        Some(CacheStatus { hit: true }) => "hit",
+        Some(CacheStatus { hit: false }) => "miss",
+        None => "-",
+    };
+
+    let _enter = span.enter();
+    let status = status.as_u16();
+    let latency_ms = format_args!("{latency_ms:.1}");
+    match level_for(&path, response.status()) {
+        Level::WARN => tracing::warn!(status, latency_ms, cache, "request failed"),
+        Level::INFO => tracing::info!(status, latency_ms, cache, "request"),
+        _ => tracing::debug!(status, latency_ms, cache, "request"),
+    }
+    response
+}
+
+/// Pick the level a request is logged at.
+fn level_for(path: &str, status: StatusCode) -> Level {
+    if status.is_server_error() {
+        Level::WARN
+    } else if path.starts_with("/_api/") || path.starts_with("/ws/") {
+        Level::INFO
+    } else {
+        Level::DEBUG
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_api_requests_log_at_info() {
+        assert_eq!(level_for("/_api/pages/guide", StatusCode::OK), Level::INFO);
+        assert_eq!(
+            level_for("/_api/pages/missing", StatusCode::NOT_FOUND),
+            Level::INFO
+        );
+        assert_eq!(level_for("/ws/live-reload", StatusCode::OK), Level::INFO);
+    }
+
+    #[test]
+    fn test_static_assets_log_at_debug() {
+        assert_eq!(
+            level_for("/assets/index-abc.js", StatusCode::OK),
+            Level::DEBUG
+        );
+        assert_eq!(level_for("/", StatusCode::OK), Level::DEBUG);
+    }
+
+    #[test]
+    fn test_server_errors_log_at_warn() {
+        assert_eq!(
+            level_for("/_api/pages/guide", StatusCode::SERVICE_UNAVAILABLE),
+            Level::WARN
+        );
+        assert_eq!(
+            level_for("/assets/x.js", StatusCode::INTERNAL_SERVER_ERROR),
+            Level::WARN
+        );
+    }
+}
@@ -46,6 +46,8 @@ pub(crate) fn frame_options_layer() -> SetResponseHeaderLayer<HeaderValue> {
 ///
 /// Forces browsers to revalidate every request with the server, preventing
 /// stale content when switching between projects or restarting the server.
+/// Responses that already set `Cache-Control` keep theirs — content-hashed
+/// build assets are marked immutable in `static_files`.
 pub(crate) fn cache_control_layer() -> SetResponseHeaderLayer<HeaderValue> {
     SetResponseHeaderLayer::if_not_present(
         axum::http::header::CACHE_CONTROL,
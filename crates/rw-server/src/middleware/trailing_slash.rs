@@ -0,0 +1,115 @@
+//! Trailing-slash redirect middleware.
+//!
+//! Page URLs are canonically slash-less (`/guide`, as produced by
+//! [`to_url_path`](rw_site::to_url_path) and used in every navigation link).
+//! A request for `/guide/` is answered with a `308 Permanent Redirect` to
+//! `/guide`, query string kept, so bookmarks and crawlers settle on one URL.
+//!
+//! The root `/` is already canonical and passes through, as do the API and
+//! WebSocket prefixes: API clients get the same response either way (the pages
+//! handler trims the slash itself), and a redirect there would only cost a
+//! round trip.
+
+use axum::extract::Request;
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Redirect, Response};
+
+/// Redirect a non-root page URL ending in `/` to its slash-less form.
+pub(crate) async fn redirect_trailing_slash(req: Request, next: Next) -> Response {
+    if let Some(location) = canonical_location(req.uri().path(), req.uri().query()) {
+        return Redirect::permanent(&location).into_response();
+    }
+    next.run(req).await
+}
+
+/// The redirect target for `path`, or `None` if it is already canonical or
+/// exempt.
+fn canonical_location(path: &str, query: Option<&str>) -> Option<String> {
+    if path == "/" || !path.ends_with('/') {
+        return None;
+    }
+    if path.starts_with("/_api/") || path.starts_with("/ws/") {
+        return None;
+    }
+    // Trim both ends and re-add exactly one leading slash: `//evil.example/`
+    // must become `/evil.example`, never the protocol-relative
+    // `//evil.example` that would send the browser off-site.
+    let mut location = format!("/{}", path.trim_matches('/'));
+    if let Some(query) = query {
+        location.push('?');
+        location.push_str(query);
+    }
+    Some(location)
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::StatusCode;
+    use rw_storage::MockStorage;
+
+    use super::*;
+    use crate::testing::TestServer;
+
+    #[test]
+    fn test_trailing_slash_is_removed() {
+        assert_eq!(
+            canonical_location("/guide/", None).as_deref(),
+            Some("/guide")
+        );
+        assert_eq!(
+            canonical_location("/domain/billing//", None).as_deref(),
+            Some("/domain/billing")
+        );
+    }
+
+    #[test]
+    fn test_query_string_is_kept() {
+        assert_eq!(
+            canonical_location("/guide/", Some("comment=42")).as_deref(),
+            Some("/guide?comment=42")
+        );
+    }
+
+    #[test]
+    fn test_canonical_and_exempt_paths_pass_through() {
+        for path in ["/", "/guide", "/_api/pages/guide/", "/ws/live-reload/"] {
+            assert_eq!(canonical_location(path, None), None, "{path}");
+        }
+    }
+
+    #[test]
+    fn test_slash_only_paths_redirect_to_root() {
+        assert_eq!(canonical_location("//", None).as_deref(), Some("/"));
+    }
+
+    #[test]
+    fn test_redirect_never_leaves_the_site() {
+        assert_eq!(
+            canonical_location("//evil.example/", None).as_deref(),
+            Some("/evil.example")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_page_url_with_trailing_slash_redirects() {
+        let server = TestServer::with_storage(MockStorage::new()).await;
+
+        let resp = server.get("/guide/?comment=42").await;
+
+        assert_eq!(resp.status, StatusCode::PERMANENT_REDIRECT);
+        assert_eq!(
+            resp.header("location").as_deref(),
+            Some("/guide?comment=42")
+        );
+        assert_eq!(resp.header("x-frame-options").as_deref(), Some("DENY"));
+    }
+
+    #[tokio::test]
+    async fn test_root_is_not_redirected() {
+        let server = TestServer::with_storage(MockStorage::new()).await;
+
+        let resp = server.get("/").await;
+
+        assert_ne!(resp.status, StatusCode::PERMANENT_REDIRECT);
+    }
+}
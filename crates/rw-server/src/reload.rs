@@ -0,0 +1,234 @@
+//! Configuration reload on `SIGHUP`.
+//!
+//! A long-running `rw serve` re-reads its `rw.toml` when sent `SIGHUP`, with
+//! the same command-line overrides it started with. Settings the running site
+//! can take on safely, the diagram, TOC and math settings behind the page
+//! renderer, are applied at once. The rest (address, source directories,
+//! extensions and the file watcher built from them, cache, live reload, HTTP
+//! behavior) are only reported as needing a restart. A file that fails to load or validate is
+//! logged and the running configuration is kept.
+
+use std::sync::Arc;
+
+use rw_site::Site;
+use tokio::signal::unix::{SignalKind, signal};
+
+use crate::{ServerConfig, renderer_config, server_config_from_rw_config};
+
+/// Reload the configuration each time the process receives `SIGHUP`.
+///
+/// `running` is the configuration the server was started with; it tracks
+/// what has been applied since.
+pub(crate) async fn reload_on_sighup(site: Arc<Site>, mut running: ServerConfig) {
+    let mut hangups = match signal(SignalKind::hangup()) {
+        Ok(hangups) => hangups,
+        Err(e) => {
+            tracing::warn!(error = %e, "Failed to install SIGHUP handler; config reload is off");
+            return;
+        }
+    };
+    while hangups.recv().await.is_some() {
+        reload(&site, &mut running);
+    }
+}
+
+/// Re-read the configuration file and apply what changed.
+fn reload(site: &Site, running: &mut ServerConfig) {
+    let loaded = match &running.config_file {
+        Some(path) => rw_config::Config::load(Some(path), Some(&running.cli_settings)),
+        None => rw_config::Config::load_from_dir(&running.project_dir, Some(&running.cli_settings)),
+    };
+    let loaded = match loaded {
+        Ok(loaded) => loaded,
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to reload configuration; keeping the current one");
+            return;
+        }
+    };
+    let new = server_config_from_rw_config(&loaded, running.version.clone(), running.verbose);
+
+    let changes = Changes::between(running, &new);
+    if changes.applied.is_empty() && changes.need_restart.is_empty() {
+        tracing::info!("Configuration reloaded; nothing changed");
+        return;
+    }
+    if !changes.applied.is_empty() {
+        running.kroki_url = new.kroki_url;
+        running.include_dirs = new.include_dirs;
+        running.diagram_format = new.diagram_format;
+        running.kroki_headers = new.kroki_headers;
+        running.toc_max_depth = new.toc_max_depth;
+        running.math = new.math;
+        site.set_renderer_config(renderer_config(running));
+        tracing::info!(changed = ?changes.applied, "Configuration reloaded");
+    }
+    if !changes.need_restart.is_empty() {
+        tracing::warn!(
+            changed = ?changes.need_restart,
+            "Configuration changes need a restart of rw serve to take effect"
+        );
+    }
+}
+
+/// Settings that differ between two configurations, by `rw.toml` name.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Changes {
+    /// Changes applied to the running server.
+    applied: Vec<&'static str>,
+    /// Changes that only take effect after a restart.
+    need_restart: Vec<&'static str>,
+}
+
+impl Changes {
+    fn between(old: &ServerConfig, new: &ServerConfig) -> Self {
+        let changed = |fields: &[(&'static str, bool)]| -> Vec<&'static str> {
+            fields
+                .iter()
+                .filter_map(|&(name, differs)| differs.then_some(name))
+                .collect()
+        };
+        Self {
+            applied: changed(&[
+                ("diagrams.kroki_url", old.kroki_url != new.kroki_url),
+                (
+                    "diagrams.include_dirs",
+                    old.include_dirs != new.include_dirs,
+                ),
+                (
+                    "diagrams.default_format",
+                    old.diagram_format != new.diagram_format,
+                ),
+                (
+                    "diagrams.kroki_headers",
+                    old.kroki_headers != new.kroki_headers,
+                ),
+                ("docs.toc_max_depth", old.toc_max_depth != new.toc_max_depth),
+                ("docs.math", old.math != new.math),
+            ]),
+            need_restart: changed(&[
+                ("server.host", old.host != new.host),
+                ("server.port", old.port != new.port),
+                ("server.compression", old.compression != new.compression),
+                (
+                    "server.not_found_page",
+                    old.not_found_page != new.not_found_page,
+                ),
+                ("server.auth_token", old.auth_token != new.auth_token),
+                ("server.cors", old.cors != new.cors),
+                ("server.cache", old.cache != new.cache),
+                (
+                    "docs.source_dir",
+                    old.source_dir != new.source_dir
+                        || old.extra_source_dirs != new.extra_source_dirs,
+                ),
+                ("docs.cache_enabled", old.cache_dir != new.cache_dir),
+                ("docs.extensions", old.extensions != new.extensions),
+                (
+                    "docs.case_insensitive",
+                    old.case_insensitive != new.case_insensitive,
+                ),
+                ("docs.homepage", old.homepage != new.homepage),
+                ("docs.data_dir", old.data_dir != new.data_dir),
+                (
+                    "live_reload.enabled",
+                    old.live_reload_enabled != new.live_reload_enabled,
+                ),
+                ("metadata.name", old.meta_filename != new.meta_filename),
+            ]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use rw_storage::MockStorage;
+
+    use super::*;
+
+    #[test]
+    fn test_no_changes() {
+        let config = ServerConfig::default();
+
+        assert_eq!(
+            Changes::between(&config, &ServerConfig::default()),
+            Changes::default()
+        );
+    }
+
+    #[test]
+    fn test_toc_max_depth_applies() {
+        let new = ServerConfig {
+            toc_max_depth: Some(2),
+            ..ServerConfig::default()
+        };
+
+        let changes = Changes::between(&ServerConfig::default(), &new);
+
+        assert_eq!(changes.applied, ["docs.toc_max_depth"]);
+        assert!(changes.need_restart.is_empty());
+    }
+
+    #[test]
+    fn test_math_applies() {
+        let new = ServerConfig {
+            math: true,
+            ..ServerConfig::default()
+        };
+
+        let changes = Changes::between(&ServerConfig::default(), &new);
+
+        assert_eq!(changes.applied, ["docs.math"]);
+        assert!(changes.need_restart.is_empty());
+    }
+
+    #[test]
+    fn test_diagram_settings_apply_and_address_needs_restart() {
+        let old = ServerConfig::default();
+        let new = ServerConfig {
+            kroki_url: Some("https://kroki.example.com".to_owned()),
+            include_dirs: vec![PathBuf::from("diagrams")],
+            port: 8080,
+            ..ServerConfig::default()
+        };
+
+        let changes = Changes::between(&old, &new);
+
+        assert_eq!(
+            changes.applied,
+            ["diagrams.kroki_url", "diagrams.include_dirs"]
+        );
+        assert_eq!(changes.need_restart, ["server.port"]);
+    }
+
+    #[test]
+    fn test_reload_applies_diagram_settings_to_site() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("rw.toml"),
+            "[diagrams]\nkroki_url = \"http://127.0.0.1:1\"\n",
+        )
+        .unwrap();
+        let storage = MockStorage::new()
+            .with_file("diag", "Diagram", "```plantuml\nA -> B\n```\n")
+            .with_mtime("diag", 1000.0);
+        let mut running = ServerConfig {
+            project_dir: dir.path().to_path_buf(),
+            ..ServerConfig::default()
+        };
+        let site = Site::new(
+            Arc::new(storage),
+            Arc::new(rw_cache::NullCache),
+            renderer_config(&running),
+        );
+        assert!(!site.render("diag").unwrap().html.contains("diagram-error"));
+
+        reload(&site, &mut running);
+
+        assert_eq!(running.kroki_url.as_deref(), Some("http://127.0.0.1:1"));
+        // The unreachable Kroki is now asked for the diagram.
+        let html = site.render("diag").unwrap().html;
+        assert!(html.contains("diagram-error"), "{html}");
+    }
+}
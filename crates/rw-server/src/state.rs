@@ -2,14 +2,18 @@
 //!
 //! Shared state for all request handlers.
 
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use rw_comments::SqliteCommentStore;
 use rw_site::Site;
+use tower_http::cors::CorsLayer;
 
+use crate::cache_control::CacheControl;
 use crate::live_reload::LiveReloadManager;
 
 /// Application state shared across all handlers.
+#[allow(clippy::struct_excessive_bools)]
 pub(crate) struct AppState {
     /// Unified site structure and page renderer.
     pub(crate) site: Arc<Site>,
@@ -26,6 +30,24 @@ pub(crate) struct AppState {
     pub(crate) notify_token: Option<String>,
     /// Enable embedded preview page at /.
     pub(crate) embedded_preview: bool,
+    /// CORS applied to the `/_api` routes (`None`: no CORS headers).
+    pub(crate) cors: Option<CorsLayer>,
+    /// Compress responses the client accepts compressed.
+    pub(crate) compression: bool,
+    /// URL path (no leading slash) of the page served with 404 responses for
+    /// missing pages; empty for the plain JSON error.
+    pub(crate) not_found_page: String,
+    /// Bearer token every request except the probes must carry (`None`: no
+    /// authentication).
+    pub(crate) auth_token: Option<Arc<str>>,
+    /// Version of the `rw` binary, reported by `/_api/info`.
+    pub(crate) version: String,
+    /// Source directories, in merge order.
+    pub(crate) source_dirs: Vec<PathBuf>,
+    /// Whether the on-disk cache is enabled.
+    pub(crate) cache_enabled: bool,
+    /// `Cache-Control` for pages and build assets, from `[server.cache]`.
+    pub(crate) cache_control: CacheControl,
 }
 
 impl AppState {
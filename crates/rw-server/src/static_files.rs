@@ -7,30 +7,73 @@ use std::sync::Arc;
 
 use axum::Router;
 use axum::body::Body;
-use axum::http::{Request, StatusCode, header};
+use axum::extract::State;
+use axum::http::{HeaderMap, Request, StatusCode, header};
 use axum::response::{IntoResponse, Response};
 
+use crate::cache_control::CacheControl;
+use crate::etag;
 use crate::state::AppState;
 
+/// Whether `path` (relative, no leading slash) is a content-hashed build
+/// asset that is safe to cache indefinitely.
+///
+/// Vite names its build output under `assets/` after the content, so a
+/// changed file is a new URL. Everything else (`index.html`, unhashed files,
+/// the API) keeps the server-wide `no-cache` so edits show up on the next
+/// load.
+pub(crate) fn is_hashed_asset(path: &str) -> bool {
+    path.starts_with("assets/")
+}
+
+/// Build the response for a static asset: `200` with an `ETag`, or `304`
+/// when `request` already holds that tag. Hashed assets carry the configured
+/// asset `Cache-Control`.
+fn asset_response(
+    path: &str,
+    content: Vec<u8>,
+    request: &HeaderMap,
+    cache_control: &CacheControl,
+) -> Response {
+    let etag = etag::for_body(&content);
+    let mut builder = Response::builder();
+    if is_hashed_asset(path) {
+        builder = builder.header(header::CACHE_CONTROL, cache_control.assets.clone());
+    }
+    if etag::matches(request, &etag) {
+        return builder
+            .status(StatusCode::NOT_MODIFIED)
+            .header(header::ETAG, etag)
+            .body(Body::empty())
+            .unwrap();
+    }
+    builder
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, rw_assets::mime_for(path))
+        .header(header::ETAG, etag)
+        .body(Body::from(content))
+        .unwrap()
+}
+
 /// Create router for static file serving with SPA fallback.
 pub(crate) fn static_router() -> Router<Arc<AppState>> {
     Router::new().fallback(serve_asset)
 }
 
 /// Serve a static asset or fall back to `index.html` for SPA routing.
-async fn serve_asset(req: Request<Body>) -> Response {
+async fn serve_asset(State(cache_control): State<CacheControl>, req: Request<Body>) -> Response {
     let path = req.uri().path().trim_start_matches('/');
 
     // Map root to index.html for SPA
     let file_path = if path.is_empty() { "index.html" } else { path };
 
     if let Some(content) = rw_assets::get(file_path) {
-        let mime = rw_assets::mime_for(file_path);
-        return Response::builder()
-            .status(StatusCode::OK)
-            .header(header::CONTENT_TYPE, mime)
-            .body(Body::from(content.into_owned()))
-            .unwrap();
+        return asset_response(
+            file_path,
+            content.into_owned(),
+            req.headers(),
+            &cache_control,
+        );
     }
 
     // SPA fallback: serve index.html for client-side routing. Unmatched
@@ -56,19 +99,17 @@ async fn serve_asset(req: Request<Body>) -> Response {
 /// that doesn't match a real asset, returns the preview shell HTML —
 /// except unmatched requests under the reserved `/_api/` prefix, which
 /// 404 (mirroring [`serve_asset`]) so a bad API path never yields HTML.
-pub(crate) async fn asset_or_preview_fallback(req: Request<Body>) -> Response {
+pub(crate) async fn asset_or_preview_fallback(
+    State(cache_control): State<CacheControl>,
+    req: Request<Body>,
+) -> Response {
     let path = req.uri().path().trim_start_matches('/');
 
     // Only serve real asset files — don't map root or SPA routes to index.html.
     if !path.is_empty()
         && let Some(content) = rw_assets::get(path)
     {
-        let mime = rw_assets::mime_for(path);
-        return Response::builder()
-            .status(StatusCode::OK)
-            .header(header::CONTENT_TYPE, mime)
-            .body(Body::from(content.into_owned()))
-            .unwrap();
+        return asset_response(path, content.into_owned(), req.headers(), &cache_control);
     }
 
     if path == "_api" || path.starts_with("_api/") {
@@ -88,6 +129,63 @@ mod tests {
         let _router: Router<Arc<AppState>> = static_router();
     }
 
+    fn cache_control() -> CacheControl {
+        CacheControl::new(&crate::CacheConfig::default(), false).unwrap()
+    }
+
+    #[test]
+    fn hashed_assets_are_immutable_everything_else_is_not() {
+        let response = asset_response(
+            "assets/index-Bx3k9a.js",
+            b"js".to_vec(),
+            &HeaderMap::new(),
+            &cache_control(),
+        );
+        assert_eq!(
+            response.headers().get(header::CACHE_CONTROL).unwrap(),
+            "public, max-age=31536000, immutable"
+        );
+
+        for path in ["index.html", "favicon.svg"] {
+            let response = asset_response(path, Vec::new(), &HeaderMap::new(), &cache_control());
+            assert!(
+                response.headers().get(header::CACHE_CONTROL).is_none(),
+                "{path} must fall through to the server-wide no-cache"
+            );
+        }
+    }
+
+    #[test]
+    fn asset_matching_if_none_match_is_not_modified() {
+        let response = asset_response(
+            "favicon.svg",
+            b"<svg/>".to_vec(),
+            &HeaderMap::new(),
+            &cache_control(),
+        );
+        assert_eq!(response.status(), StatusCode::OK);
+        let etag = response.headers().get(header::ETAG).unwrap().clone();
+
+        let mut request = HeaderMap::new();
+        request.insert(header::IF_NONE_MATCH, etag.clone());
+        let response = asset_response(
+            "favicon.svg",
+            b"<svg/>".to_vec(),
+            &request,
+            &cache_control(),
+        );
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(response.headers().get(header::ETAG), Some(&etag));
+
+        let response = asset_response(
+            "favicon.svg",
+            b"<svg />".to_vec(),
+            &request,
+            &cache_control(),
+        );
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
     mod embedded_preview {
         use super::*;
         use axum::http::Uri;
@@ -101,7 +199,8 @@ mod tests {
 
         #[tokio::test]
         async fn fallback_returns_preview_for_root() {
-            let response = asset_or_preview_fallback(request_for("/")).await;
+            let response =
+                asset_or_preview_fallback(State(cache_control()), request_for("/")).await;
             assert_eq!(response.status(), StatusCode::OK);
             assert_eq!(
                 response.headers().get(header::CONTENT_TYPE).unwrap(),
@@ -111,7 +210,9 @@ mod tests {
 
         #[tokio::test]
         async fn fallback_returns_preview_for_unknown_path() {
-            let response = asset_or_preview_fallback(request_for("/some/doc/path")).await;
+            let response =
+                asset_or_preview_fallback(State(cache_control()), request_for("/some/doc/path"))
+                    .await;
             assert_eq!(response.status(), StatusCode::OK);
             assert_eq!(
                 response.headers().get(header::CONTENT_TYPE).unwrap(),
@@ -125,7 +226,8 @@ mod tests {
             // not return the preview shell HTML — including the bare
             // `/_api` form (no trailing slash).
             for path in ["/_api/does-not-exist", "/_api"] {
-                let response = asset_or_preview_fallback(request_for(path)).await;
+                let response =
+                    asset_or_preview_fallback(State(cache_control()), request_for(path)).await;
                 assert_eq!(response.status(), StatusCode::NOT_FOUND, "for {path}");
             }
         }
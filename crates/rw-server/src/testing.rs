@@ -7,6 +7,7 @@
 //!
 //! Test-only — gated under `#[cfg(test)]` so it never ships in release builds.
 
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use axum::Router;
@@ -23,8 +24,11 @@ use tower::ServiceExt;
 use uuid::Uuid;
 
 use crate::app;
+use crate::cache_control::CacheControl;
 use crate::live_reload::{LiveReloadManager, ReloadEvent};
+use crate::middleware::cors::cors_layer;
 use crate::state::AppState;
+use crate::{CacheConfig, CorsConfig};
 
 /// Test-only HTTP harness: wraps the production router around an in-memory
 /// comment store and a `MockStorage`-backed site (empty via
@@ -63,7 +67,68 @@ impl TestServer {
         Self::build_with_token(MockStorage::new(), None).await
     }
 
+    /// Build a server with an empty site whose `/_api` routes apply `cors`.
+    pub(crate) async fn with_cors(cors: CorsConfig) -> Self {
+        Self::build_full(
+            MockStorage::new(),
+            Some(Self::TEST_NOTIFY_TOKEN.to_owned()),
+            &cors,
+            None,
+            &CacheConfig::default(),
+        )
+        .await
+    }
+
+    /// Build a server with an empty site that requires `Authorization: Bearer
+    /// <auth_token>`.
+    pub(crate) async fn with_auth_token(auth_token: &str) -> Self {
+        Self::with_auth_token_and_cors(auth_token, CorsConfig::default()).await
+    }
+
+    /// Build a server with an empty site that requires `Authorization: Bearer
+    /// <auth_token>` and whose `/_api` routes apply `cors`.
+    pub(crate) async fn with_auth_token_and_cors(auth_token: &str, cors: CorsConfig) -> Self {
+        Self::build_full(
+            MockStorage::new(),
+            Some(Self::TEST_NOTIFY_TOKEN.to_owned()),
+            &cors,
+            Some(auth_token),
+            &CacheConfig::default(),
+        )
+        .await
+    }
+
+    /// Build a server backed by `storage` that sends the `Cache-Control`
+    /// values in `cache`.
+    pub(crate) async fn with_cache_config(storage: MockStorage, cache: &CacheConfig) -> Self {
+        Self::build_full(
+            storage,
+            Some(Self::TEST_NOTIFY_TOKEN.to_owned()),
+            &CorsConfig::default(),
+            None,
+            cache,
+        )
+        .await
+    }
+
     async fn build_with_token(storage: MockStorage, notify_token: Option<String>) -> Self {
+        Self::build_full(
+            storage,
+            notify_token,
+            &CorsConfig::default(),
+            None,
+            &CacheConfig::default(),
+        )
+        .await
+    }
+
+    async fn build_full(
+        storage: MockStorage,
+        notify_token: Option<String>,
+        cors: &CorsConfig,
+        auth_token: Option<&str>,
+        cache: &CacheConfig,
+    ) -> Self {
         let site = Arc::new(Site::new(
             Arc::new(storage),
             Arc::new(NullCache),
@@ -78,6 +143,14 @@ impl TestServer {
             comment_store,
             notify_token,
             embedded_preview: false,
+            cors: cors_layer(cors).unwrap(),
+            compression: true,
+            not_found_page: "404".to_owned(),
+            auth_token: auth_token.map(Arc::from),
+            version: "test".to_owned(),
+            source_dirs: vec![PathBuf::from("docs")],
+            cache_enabled: false,
+            cache_control: CacheControl::new(cache, false).unwrap(),
         });
 
         Self {
@@ -105,6 +178,14 @@ impl TestServer {
             comment_store,
             notify_token: Some(Self::TEST_NOTIFY_TOKEN.to_owned()),
             embedded_preview: false,
+            cors: None,
+            compression: true,
+            not_found_page: "404".to_owned(),
+            auth_token: None,
+            version: "test".to_owned(),
+            source_dirs: vec![PathBuf::from("docs")],
+            cache_enabled: false,
+            cache_control: CacheControl::new(&CacheConfig::default(), true).unwrap(),
         });
 
         Self {
@@ -143,6 +224,34 @@ impl TestServer {
         self.send(req).await
     }
 
+    /// `GET <path>` with a single header.
+    pub(crate) async fn get_with_header(
+        &self,
+        path: &str,
+        header: &str,
+        value: &str,
+    ) -> TestResponse {
+        let req = Request::builder()
+            .method(Method::GET)
+            .uri(path)
+            .header(header, value)
+            .body(Body::empty())
+            .unwrap();
+        self.send(req).await
+    }
+
+    /// CORS preflight: `OPTIONS <path>` from `origin` asking to use `method`.
+    pub(crate) async fn preflight(&self, path: &str, origin: &str, method: &str) -> TestResponse {
+        let req = Request::builder()
+            .method(Method::OPTIONS)
+            .uri(path)
+            .header("origin", origin)
+            .header("access-control-request-method", method)
+            .body(Body::empty())
+            .unwrap();
+        self.send(req).await
+    }
+
     /// `DELETE <path>`.
     pub(crate) async fn delete(&self, path: &str) -> TestResponse {
         let req = Request::builder()
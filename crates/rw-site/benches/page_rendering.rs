@@ -14,6 +14,10 @@
 //! memo empty, so every timed render is a real render (or, for `cache_hit`, a
 //! real persistent-cache read) rather than a memo hit.
 //!
+//! `whole_site` renders every page of a small site across a number of threads,
+//! the way `rw export pdf` does, to show how well `Site::render` scales when
+//! called concurrently.
+//!
 //! Local:    cargo bench -p rw-site --bench page_rendering
 //! Under CI: instrumented via CodSpeed (the `divan` dep is the compat shim).
 
@@ -131,3 +135,33 @@ fn caching(bencher: Bencher, kind: &str) {
         _ => unreachable!(),
     }
 }
+
+/// Render every page of a 32-page site, spread over `threads` threads. Each
+/// run uses a fresh, scan-primed site so no render is a memo hit.
+#[divan::bench(args = [1, 4])]
+fn whole_site(bencher: Bencher, threads: usize) {
+    const PAGES: usize = 32;
+
+    let dir = tempfile::tempdir().unwrap();
+    let source_dir = dir.path().to_path_buf();
+    let markdown = generate_markdown(20, 3);
+    let paths: Vec<String> = (0..PAGES).map(|i| format!("page-{i}")).collect();
+    for path in &paths {
+        fs::write(source_dir.join(format!("{path}.md")), &markdown).unwrap();
+    }
+
+    bencher
+        .with_inputs(|| scan_primed(create_site(source_dir.clone())))
+        .bench_values(|site| {
+            std::thread::scope(|scope| {
+                for chunk in paths.chunks(PAGES.div_ceil(threads)) {
+                    let site = &site;
+                    scope.spawn(move || {
+                        for path in chunk {
+                            let _ = black_box(site.render(path));
+                        }
+                    });
+                }
+            });
+        });
+}
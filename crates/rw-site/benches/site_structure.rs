@@ -79,8 +79,10 @@ fn navigation(bencher: Bencher) {
 }
 
 /// Reload cost: cached (nothing changed, fast revalidation) vs. forced re-scan
-/// after invalidation (the live-reload edit path). Depth 3, breadth 5.
-#[divan::bench(args = ["cached", "after_invalidate"])]
+/// after invalidation (the live-reload path for structural edits) vs. a
+/// retitle patched in with `apply_title_change` (the live-reload path for an
+/// edited H1). Depth 3, breadth 5.
+#[divan::bench(args = ["cached", "after_invalidate", "after_title_change"])]
 fn reload(bencher: Bencher, kind: &str) {
     let (_dir, site) = primed_site(3, 5);
     match kind {
@@ -89,6 +91,10 @@ fn reload(bencher: Bencher, kind: &str) {
             site.invalidate();
             site.navigation(None)
         }),
+        "after_title_change" => bencher.bench(|| {
+            site.apply_title_change("section-0/section-1", "Retitled");
+            site.navigation(None)
+        }),
         _ => unreachable!(),
     }
 }
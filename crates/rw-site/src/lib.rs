@@ -7,9 +7,10 @@
 //! # Core types
 //!
 //! - [`Site`] — the main entry point. Owns storage, cache, and renderer;
-//!   provides [`navigation`](Site::navigation), [`render`](Site::render), and
-//!   page lookup methods. Designed for shared ownership (`Arc<Site>`) and
-//!   concurrent access.
+//!   provides [`navigation`](Site::navigation), [`render`](Site::render),
+//!   [`render_with`](Site::render_with) for other output formats,
+//!   [`search`](Site::search), and page lookup methods. Designed for
+//!   shared ownership (`Arc<Site>`) and concurrent access.
 //! - [`PageRendererConfig`] — controls title extraction, diagram rendering
 //!   (Kroki URL, DPI), and `PlantUML` include directories.
 //! - [`PageRenderResult`] — the output of rendering a page: HTML, title,
@@ -96,11 +97,15 @@
 
 pub(crate) mod page;
 pub(crate) mod path;
+pub(crate) mod search;
 pub(crate) mod site;
 pub(crate) mod site_state;
 
 pub use page::{BreadcrumbItem, PageRenderResult, PageRendererConfig, RenderError, SearchDocument};
 
+/// Re-exported for [`PageRendererConfig::diagram_format`].
+pub use rw_kroki::DiagramFormat;
+
 /// A section identity consisting of a freeform `kind`, a validated
 /// [`Namespace`], and a `name` (the last path segment of the section root).
 /// Parsed from and serialized to section ref strings like
@@ -122,7 +127,7 @@ pub use rw_sections::SectionPath;
 /// section ref string and prefix-based path matching.
 pub use rw_sections::Sections;
 
-pub use site::Site;
+pub use site::{Site, SiteStatus};
 pub use site_state::{NavItem, Navigation, PageEntry, ScopeInfo, SectionEntry};
 
 /// A heading entry for building a table-of-contents sidebar.
@@ -130,4 +135,17 @@ pub use site_state::{NavItem, Navigation, PageEntry, ScopeInfo, SectionEntry};
 /// Contains the heading `title`, `id` (anchor), and `level` (2–6).
 pub use rw_renderer::TocEntry;
 
+/// How one diagram in a render was produced (cache hit or fresh render,
+/// duration, size). See [`PageRenderResult::code_block_stats`].
+pub use rw_renderer::CodeBlockStats;
+
+/// The output format plugged into [`Site::render_with`] — see
+/// [`rw_renderer::RenderBackend`].
+pub use rw_renderer::RenderBackend;
+
+/// Output of [`Site::render_with`]: rendered markup, title, table of contents
+/// and warnings. See [`rw_renderer::RenderResult`].
+pub use rw_renderer::RenderResult;
+
 pub use path::to_url_path;
+pub use search::{MAX_SEARCH_RESULTS, SearchHit};
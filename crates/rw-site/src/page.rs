@@ -7,19 +7,24 @@
 
 use std::collections::{BTreeSet, HashMap};
 use std::hash::{DefaultHasher, Hash, Hasher};
-use std::path::PathBuf;
+use std::io;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+use parking_lot::Mutex;
 use rw_cache::{Cache, CacheBucket, CacheBucketExt};
-use rw_kroki::{DiagramProcessor, MetaIncludeSource, SearchDiagramProcessor};
-use rw_renderer::directive::DirectiveProcessor;
+use rw_kroki::{
+    ClientMermaidProcessor, DiagramFormat, DiagramProcessor, MetaIncludeSource,
+    SearchDiagramProcessor,
+};
+use rw_renderer::directive::{DirectiveProcessor, DirectiveProcessorConfig};
 use rw_renderer::{
-    HtmlBackend, MarkdownRenderer, Pipeline, RenderBackend, SearchDocumentBackend, TabsDirective,
-    TocEntry, escape_html,
+    AdmonitionDirective, CodeBlockStats, HtmlBackend, IncludeDirective, MarkdownRenderer, Pipeline,
+    RenderBackend, RenderResult, SearchDocumentBackend, TabsDirective, TocEntry, escape_html,
 };
 use rw_sections::{SectionAnchor, Sections};
 
-use crate::site::{SiteSnapshot, SiteTitleResolver};
+use crate::site::{SiteLinkChecker, SiteSnapshot, SiteTitleResolver};
 use rw_storage::{Metadata, Storage, StorageError, StorageErrorKind};
 use serde::{Deserialize, Serialize};
 
@@ -73,6 +78,25 @@ pub struct PageRendererConfig {
     /// Directories to search when resolving `PlantUML` `!include` directives.
     /// Defaults to empty (no include resolution).
     pub include_dirs: Vec<PathBuf>,
+    /// Output format for diagrams whose fence has no `{format=...}`
+    /// attribute. Defaults to [`DiagramFormat::Svg`].
+    pub diagram_format: DiagramFormat,
+    /// Extra `(name, value)` HTTP headers sent with every Kroki request
+    /// (e.g., an API key for an auth proxy). Defaults to none.
+    pub kroki_headers: Vec<(String, String)>,
+    /// Deepest heading level listed in [`PageRenderResult::toc`]; deeper
+    /// headings keep their `id` but are left out. `None` (the default) lists
+    /// every level.
+    pub toc_max_depth: Option<u8>,
+    /// When `true`, `$tex$` and `$$tex$$` render as math for a client-side
+    /// `KaTeX` or `MathJax` pass. Defaults to `false`, leaving dollar signs as
+    /// prose.
+    pub math: bool,
+    /// When `true`, ```` ```mermaid ```` fences render as
+    /// `<pre class="mermaid">` for mermaid.js in the browser, whether or not
+    /// `kroki_url` is set; other diagram languages still go to Kroki.
+    /// Defaults to `false`.
+    pub client_side_mermaid: bool,
 }
 
 impl Default for PageRendererConfig {
@@ -81,6 +105,11 @@ impl Default for PageRendererConfig {
             extract_title: true,
             kroki_url: None,
             include_dirs: Vec::new(),
+            diagram_format: DiagramFormat::default(),
+            kroki_headers: Vec::new(),
+            toc_max_depth: None,
+            math: false,
+            client_side_mermaid: false,
         }
     }
 }
@@ -91,7 +120,7 @@ impl Default for PageRendererConfig {
 /// markdown content have [`has_content`](Self::has_content) set to `true`;
 /// [virtual pages](crate#virtual-pages) (directories without `index.md`)
 /// have it set to `false`.
-#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Page {
     /// Display title, resolved from (in priority order): metadata `title`
     /// field, first `# H1` heading, or filename.
@@ -111,6 +140,13 @@ pub struct Page {
     /// Ordered list of child page slugs for navigation ordering.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub pages: Option<Vec<String>>,
+    /// Position among siblings from metadata `order` (or `weight`). Siblings
+    /// with one come first, lowest first; ties and the rest go by path.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub order: Option<i64>,
+    /// Former URL paths that redirect here, from metadata `redirects`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub redirects: Option<Vec<String>>,
     /// Whether this page's content is backed by a directory index (`index.md`
     /// or the root/README homepage) rather than a leaf `name.md`. Controls how
     /// the renderer resolves relative `.md` links (see
@@ -211,6 +247,11 @@ pub struct PageRenderResult {
     /// without extra lookups. Rebuilt from live sections on every render, so it
     /// is identical for cached and freshly-rendered pages.
     pub section_ancestry: HashMap<String, Vec<SectionAnchor>>,
+    /// Per-diagram statistics from this render: whether each came from the
+    /// diagram cache, how long it took, and its size. Empty when the page
+    /// itself was served from cache ([`from_cache`](Self::from_cache)) or has
+    /// no diagrams.
+    pub code_block_stats: Vec<CodeBlockStats>,
 }
 
 /// Plain text representation of a page for search indexing.
@@ -278,7 +319,8 @@ pub(crate) fn apply_breadcrumb_sections(breadcrumbs: &mut [BreadcrumbItem], sect
 /// Fingerprint of the diagram configuration that affects rendered output.
 ///
 /// Folded into the page-cache etag so that changing `kroki_url` (including
-/// unset→set) or `include_dirs` invalidates cached pages — otherwise a
+/// unset→set), `include_dirs`, the default diagram format or client-side
+/// Mermaid invalidates cached pages — otherwise a
 /// page rendered while diagrams were misconfigured would be served from cache
 /// even after the config is fixed.
 ///
@@ -288,13 +330,20 @@ pub(crate) fn apply_breadcrumb_sections(breadcrumbs: &mut [BreadcrumbItem], sect
 /// hash identically across restarts of the same binary; a stdlib change would
 /// only cause a one-time safe re-render, and a crate version bump wipes the
 /// cache anyway.
-fn diagram_config_fingerprint(kroki_url: Option<&str>, include_dirs: &[PathBuf]) -> u64 {
+fn diagram_config_fingerprint(
+    kroki_url: Option<&str>,
+    include_dirs: &[PathBuf],
+    diagram_format: DiagramFormat,
+    client_side_mermaid: bool,
+) -> u64 {
     let mut hasher = DefaultHasher::new();
     // `Option<&str>` hashes `None` and `Some(_)` distinctly, so presence and
     // value are both captured.
     kroki_url.hash(&mut hasher);
     // Order is significant (include search order), so do not sort.
     include_dirs.hash(&mut hasher);
+    diagram_format.as_str().hash(&mut hasher);
+    client_side_mermaid.hash(&mut hasher);
     hasher.finish()
 }
 
@@ -310,6 +359,11 @@ pub(crate) struct PageRenderer {
     extract_title: bool,
     kroki_url: Option<String>,
     include_dirs: Vec<PathBuf>,
+    diagram_format: DiagramFormat,
+    kroki_headers: Vec<(String, String)>,
+    toc_max_depth: Option<u8>,
+    math: bool,
+    client_side_mermaid: bool,
     diagram_config_fingerprint: u64,
 }
 
@@ -320,8 +374,12 @@ impl PageRenderer {
         cache: Arc<dyn Cache>,
         config: PageRendererConfig,
     ) -> Self {
-        let diagram_config_fingerprint =
-            diagram_config_fingerprint(config.kroki_url.as_deref(), &config.include_dirs);
+        let diagram_config_fingerprint = diagram_config_fingerprint(
+            config.kroki_url.as_deref(),
+            &config.include_dirs,
+            config.diagram_format,
+            config.client_side_mermaid,
+        );
         Self {
             storage,
             page_bucket: cache.bucket("pages"),
@@ -329,10 +387,21 @@ impl PageRenderer {
             extract_title: config.extract_title,
             kroki_url: config.kroki_url,
             include_dirs: config.include_dirs,
+            diagram_format: config.diagram_format,
+            kroki_headers: config.kroki_headers,
+            toc_max_depth: config.toc_max_depth,
+            math: config.math,
+            client_side_mermaid: config.client_side_mermaid,
             diagram_config_fingerprint,
         }
     }
 
+    /// Whether any diagram fence renders as a diagram: through Kroki, or
+    /// Mermaid in the browser.
+    pub(crate) fn renders_diagrams(&self) -> bool {
+        self.kroki_url.is_some() || self.client_side_mermaid
+    }
+
     /// Render a page with full pipeline: mtime, metadata, cache check, render, cache write.
     ///
     /// # Errors
@@ -406,17 +475,31 @@ impl PageRenderer {
         // Etag combines the page's own source mtime, the snapshot's resolution
         // fingerprint (a cross-page change — another page's title/description/
         // section that this render resolves — invalidates this page even though
-        // its own file is unchanged), and the diagram-config fingerprint (a
-        // A `kroki_url`/`include_dirs` change invalidates every page so a
-        // page rendered under a broken diagram config is not served stale).
-        // `mtime` (f64) never contains ':', and both fingerprints are decimal
+        // its own file is unchanged), the diagram-config fingerprint (a
+        // `kroki_url`/`include_dirs` change invalidates every page so a
+        // page rendered under a broken diagram config is not served stale),
+        // the TOC depth limit (empty for none), which shapes the cached TOC,
+        // and whether math is on (`0`/`1`), which changes how dollar signs
+        // render. `mtime` (f64) never contains ':', and the rest are decimal
         // digits, so the ':' delimiter stays unambiguous.
+        let toc_max_depth = self
+            .toc_max_depth
+            .map_or_else(String::new, |depth| depth.to_string());
         let etag = format!(
-            "{source_mtime}:{}:{}",
-            ctx.resolution_fingerprint, self.diagram_config_fingerprint
+            "{source_mtime}:{}:{}:{toc_max_depth}:{}",
+            ctx.resolution_fingerprint,
+            self.diagram_config_fingerprint,
+            u8::from(self.math)
         );
 
-        if let Some(cached) = self.page_bucket.get_json::<CachedPage>(path, &etag) {
+        // Files pulled in by `::include` are not known until the page renders,
+        // so they are checked against the entry rather than folded into the
+        // etag: an include edited, created or deleted since makes it a miss.
+        if let Some(cached) = self
+            .page_bucket
+            .get_json::<CachedPage>(path, &etag)
+            .filter(|cached| self.includes_unchanged(&cached.includes))
+        {
             return Ok(PageRenderResult {
                 html: cached.html,
                 title: cached.title,
@@ -430,12 +513,15 @@ impl PageRenderer {
                 section_refs: cached.section_refs,
                 // Overwritten in `render()` after breadcrumb sections resolve.
                 section_ancestry: HashMap::new(),
+                code_block_stats: Vec::new(),
             });
         }
 
         let markdown_text = self.storage.read(path)?;
-        let renderer = self.create_renderer(path, page.origin.as_deref(), page.is_dir, ctx);
-        let pipeline = self.create_pipeline(ctx);
+        let renderer =
+            self.create_renderer::<HtmlBackend>(path, page.origin.as_deref(), page.is_dir, ctx);
+        let includes = IncludeLog::default();
+        let pipeline = self.create_pipeline(path, page.is_dir, &includes, ctx);
         let result = renderer.render(&markdown_text, pipeline);
 
         // A transient diagram failure (Kroki unreachable, a 5xx, or a retryable
@@ -453,6 +539,7 @@ impl PageRenderer {
                     title: result.title.as_deref(),
                     toc: &result.toc,
                     section_refs: &result.section_refs,
+                    includes: &includes.lock(),
                 },
             );
         }
@@ -470,6 +557,7 @@ impl PageRenderer {
             section_refs: result.section_refs,
             // Overwritten in `render()` after breadcrumb sections resolve.
             section_ancestry: HashMap::new(),
+            code_block_stats: result.code_block_stats,
         })
     }
 
@@ -495,6 +583,7 @@ impl PageRenderer {
             section_refs: BTreeSet::new(),
             // Overwritten in `render()` after breadcrumb sections resolve.
             section_ancestry: HashMap::new(),
+            code_block_stats: Vec::new(),
         }
     }
 
@@ -524,7 +613,9 @@ impl PageRenderer {
         // descriptions) instead of the regular DiagramProcessor (HTML/SVG
         // via Kroki) — so start from the directives-only base pipeline
         // and add just the search processor.
-        let pipeline = Self::create_directives_pipeline().with_processor(search_processor);
+        let pipeline = self
+            .create_directives_pipeline(path, page.is_dir, &IncludeLog::default())
+            .with_processor(search_processor);
         let result = renderer.render(&markdown_text, pipeline);
 
         let title = metadata
@@ -539,14 +630,41 @@ impl PageRenderer {
         }))
     }
 
-    fn create_renderer(
+    /// Render a page through backend `B` with the same storage read,
+    /// metadata, directives and diagram processor as [`render`](Self::render).
+    ///
+    /// Uncached: the page cache holds HTML only.
+    pub(crate) fn render_with<B: RenderBackend>(
+        &self,
+        path: &str,
+        page: &Page,
+        ctx: &RenderContext,
+    ) -> Result<Option<RenderResult>, RenderError> {
+        if !page.has_content {
+            return Ok(None);
+        }
+
+        let markdown_text = self.storage.read(path)?;
+        let metadata = self.load_metadata(path);
+
+        let renderer = self.create_renderer::<B>(path, page.origin.as_deref(), page.is_dir, ctx);
+        let pipeline = self.create_pipeline(path, page.is_dir, &IncludeLog::default(), ctx);
+        let mut result = renderer.render(&markdown_text, pipeline);
+        if let Some(title) = metadata.and_then(|m| m.title) {
+            result.title = Some(title);
+        }
+
+        Ok(Some(result))
+    }
+
+    fn create_renderer<B: RenderBackend>(
         &self,
         base_path: &str,
         origin: Option<&str>,
         is_dir: bool,
         ctx: &RenderContext,
-    ) -> MarkdownRenderer<HtmlBackend> {
-        let mut renderer = MarkdownRenderer::<HtmlBackend>::new()
+    ) -> MarkdownRenderer<B> {
+        let mut renderer = MarkdownRenderer::<B>::new()
             .with_base_path(format!("/{base_path}"))
             .with_is_dir(is_dir);
 
@@ -558,23 +676,77 @@ impl PageRenderer {
             renderer = renderer.with_title_extraction();
         }
 
+        if let Some(depth) = self.toc_max_depth {
+            renderer = renderer.with_toc_max_depth(depth);
+        }
+
+        if self.math {
+            renderer = renderer.with_math(true);
+        }
+
         Self::configure_renderer_settings(renderer, ctx)
     }
 
     /// Pipeline preloaded with the directives shared by every render path
-    /// (tabs container; status is built in and needs no registration).
+    /// (tabs and admonition containers, and `::include`; status is built in
+    /// and needs no registration).
     /// Callers add their own code-block processors on top (regular
     /// `DiagramProcessor` for HTML rendering, `SearchDiagramProcessor` for
     /// search indexing).
-    fn create_directives_pipeline() -> Pipeline {
-        let directives = DirectiveProcessor::new().with_container(TabsDirective::new());
+    ///
+    /// Includes resolve against the source file of the page at `path` and are
+    /// read through storage, sandboxed to the source root; each one is
+    /// recorded in `includes` with its mtime.
+    fn create_directives_pipeline(
+        &self,
+        path: &str,
+        is_dir: bool,
+        includes: &IncludeLog,
+    ) -> Pipeline {
+        let storage = Arc::clone(&self.storage);
+        let includes = Arc::clone(includes);
+        let config = DirectiveProcessorConfig::new()
+            .with_base_dir("")
+            .with_source_path(source_file(path, is_dir))
+            .with_read_file(move |file| {
+                let url_path = include_url_path(file);
+                includes
+                    .lock()
+                    .push((url_path.clone(), storage.mtime(&url_path).ok()));
+                storage.read(&url_path).map_err(|e| match e.kind {
+                    StorageErrorKind::NotFound => io::Error::from(io::ErrorKind::NotFound),
+                    _ => io::Error::other(e.display_chain()),
+                })
+            });
+        let directives = DirectiveProcessor::with_config(config)
+            .with_container(TabsDirective::new())
+            .with_container(AdmonitionDirective::new())
+            .with_leaf(IncludeDirective::new());
         Pipeline::new().with_directives(directives)
     }
 
-    /// Pipeline for HTML rendering: directives + the regular
-    /// `DiagramProcessor` (when configured).
-    fn create_pipeline(&self, ctx: &RenderContext) -> Pipeline {
-        let mut pipeline = Self::create_directives_pipeline();
+    /// Whether every file a cached render included still has the mtime it
+    /// was read at (`None`: still missing).
+    fn includes_unchanged(&self, includes: &[(String, Option<f64>)]) -> bool {
+        includes.iter().all(|(url_path, mtime)| {
+            self.storage.mtime(url_path).ok().map(f64::to_bits) == mtime.map(f64::to_bits)
+        })
+    }
+
+    /// Pipeline for HTML rendering: directives + `ClientMermaidProcessor`
+    /// and the regular `DiagramProcessor` (each when configured). The Mermaid
+    /// processor goes first so Kroki never sees the fences it claims.
+    fn create_pipeline(
+        &self,
+        path: &str,
+        is_dir: bool,
+        includes: &IncludeLog,
+        ctx: &RenderContext,
+    ) -> Pipeline {
+        let mut pipeline = self.create_directives_pipeline(path, is_dir, includes);
+        if self.client_side_mermaid {
+            pipeline = pipeline.with_processor(ClientMermaidProcessor);
+        }
         if let Some(processor) = self.create_diagram_processor(ctx.meta_include_source.clone()) {
             pipeline = pipeline.with_processor(processor.with_sections(Arc::clone(&ctx.sections)));
         }
@@ -582,7 +754,7 @@ impl PageRenderer {
     }
 
     /// Apply settings shared between renderer creation paths: sections,
-    /// wikilinks/title resolver.
+    /// wikilinks/title resolver, broken-link checks.
     fn configure_renderer_settings<B: RenderBackend>(
         renderer: MarkdownRenderer<B>,
         ctx: &RenderContext,
@@ -594,6 +766,9 @@ impl PageRenderer {
                 .with_wikilinks(true)
                 .with_title_resolver(SiteTitleResolver {
                     snapshot: Arc::clone(snapshot),
+                })
+                .with_link_checker(SiteLinkChecker {
+                    snapshot: Arc::clone(snapshot),
                 });
         }
 
@@ -608,6 +783,8 @@ impl PageRenderer {
 
         let mut processor = DiagramProcessor::new(url)
             .include_dirs(&self.include_dirs)
+            .default_format(self.diagram_format)
+            .kroki_headers(self.kroki_headers.clone())
             .with_cache(self.cache.bucket("diagrams"));
 
         if let Some(source) = meta_include_source {
@@ -639,6 +816,10 @@ struct CachedPage {
     /// rendered.
     #[serde(default)]
     section_refs: BTreeSet<String>,
+    /// URL paths the render pulled in with `::include`, each with its mtime
+    /// then (`None`: missing). Defaults to none for older entries.
+    #[serde(default)]
+    includes: Vec<(String, Option<f64>)>,
 }
 
 /// Borrowed view of cached page data for serialization (zero-copy).
@@ -648,6 +829,36 @@ struct CachedPageRef<'a> {
     title: Option<&'a str>,
     toc: &'a [TocEntry],
     section_refs: &'a BTreeSet<String>,
+    includes: &'a [(String, Option<f64>)],
+}
+
+/// Files a render read through `::include`: each URL path with its mtime at
+/// read time (`None` when it did not exist).
+type IncludeLog = Arc<Mutex<Vec<(String, Option<f64>)>>>;
+
+/// Source file of the page at URL path `path`, relative to the source root:
+/// `guide/index.md` for a directory page, `guide.md` for a leaf. Only its
+/// directory matters, which is what includes resolve against.
+fn source_file(path: &str, is_dir: bool) -> PathBuf {
+    if is_dir {
+        Path::new(path).join("index.md")
+    } else {
+        PathBuf::from(format!("{path}.md"))
+    }
+}
+
+/// URL path of an included file, given relative to the source root: the path
+/// without its extension, and a directory's for an `index` file.
+fn include_url_path(file: &Path) -> String {
+    let page = if file.file_stem().is_some_and(|stem| stem == "index") {
+        file.parent().map(Path::to_path_buf).unwrap_or_default()
+    } else {
+        file.with_extension("")
+    };
+    page.components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
 }
 
 #[cfg(test)]
@@ -673,6 +884,8 @@ mod tests {
             description: None,
             origin: None,
             pages: None,
+            order: None,
+            redirects: None,
             is_dir: true,
         }
     }
@@ -704,6 +917,63 @@ mod tests {
         assert!(result.has_content);
     }
 
+    #[test]
+    fn test_render_page_limits_toc_depth() {
+        let storage = MockStorage::new()
+            .with_file("test", "Hello", "# Hello\n\n## Setup\n\n### Details")
+            .with_mtime("test", 1000.0);
+        let config = PageRendererConfig {
+            toc_max_depth: Some(2),
+            ..Default::default()
+        };
+        let renderer = PageRenderer::new(Arc::new(storage), Arc::new(NullCache), config);
+
+        let page = make_page("Hello", "test", true);
+        let result = renderer
+            .render("test", &page, vec![], &RenderContext::default())
+            .unwrap();
+
+        assert_eq!(result.toc.len(), 1);
+        assert_eq!(result.toc[0].id, "setup");
+        assert!(result.html.contains(r#"<h3 id="details">"#));
+    }
+
+    #[test]
+    fn test_render_page_with_math() {
+        let storage = || {
+            MockStorage::new()
+                .with_file("test", "Hello", "# Hello\n\nCosts $5, or $x$ in $$y$$.")
+                .with_mtime("test", 1000.0)
+        };
+        let renderer = create_renderer(storage());
+        let page = make_page("Hello", "test", true);
+        let result = renderer
+            .render("test", &page, vec![], &RenderContext::default())
+            .unwrap();
+        assert!(!result.html.contains("math"), "{}", result.html);
+
+        let config = PageRendererConfig {
+            math: true,
+            ..Default::default()
+        };
+        let renderer = PageRenderer::new(Arc::new(storage()), Arc::new(NullCache), config);
+        let result = renderer
+            .render("test", &page, vec![], &RenderContext::default())
+            .unwrap();
+        assert!(
+            result
+                .html
+                .contains(r#"<span class="math inline">x</span>"#),
+            "{}",
+            result.html
+        );
+        assert!(
+            result.html.contains(r#"<div class="math display">y</div>"#),
+            "{}",
+            result.html
+        );
+    }
+
     #[test]
     fn test_render_readme_with_origin_resolves_links_correctly() {
         let storage = MockStorage::new()
@@ -779,6 +1049,73 @@ mod tests {
         assert_eq!(result1.html, result2.html);
     }
 
+    #[test]
+    fn test_render_page_includes_file_from_storage() {
+        let storage = MockStorage::new()
+            .with_file(
+                "guide/setup",
+                "Setup",
+                "# Setup\n\n::include[snippets/auth.md]",
+            )
+            .with_mtime("guide/setup", 1000.0)
+            .with_content("guide/snippets/auth", "Use a *token*.");
+        let renderer = create_renderer(storage);
+        let mut page = make_page("Setup", "guide/setup", true);
+        page.is_dir = false;
+
+        let result = renderer
+            .render("guide/setup", &page, vec![], &RenderContext::default())
+            .unwrap();
+
+        assert!(
+            result.html.contains("<p>Use a <em>token</em>.</p>"),
+            "{}",
+            result.html
+        );
+        assert!(result.warnings.is_empty(), "{:?}", result.warnings);
+    }
+
+    #[test]
+    fn test_changed_include_invalidates_cached_page() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let cache: Arc<dyn rw_cache::Cache> = Arc::new(rw_cache::FileCache::new(
+            temp_dir.path().join("cache"),
+            "1.0.0",
+        ));
+        let renderer = |snippet: &str, mtime: f64| {
+            let storage = MockStorage::new()
+                .with_file("guide", "Guide", "# Guide\n\n::include[snippet.md]")
+                .with_mtime("guide", 1000.0)
+                .with_content("guide/snippet", snippet)
+                .with_mtime("guide/snippet", mtime);
+            PageRenderer::new(
+                Arc::new(storage),
+                Arc::clone(&cache),
+                PageRendererConfig::default(),
+            )
+        };
+        let page = make_page("Guide", "guide", true);
+        let render = |renderer: &PageRenderer| {
+            renderer
+                .render("guide", &page, vec![], &RenderContext::default())
+                .unwrap()
+        };
+
+        assert!(!render(&renderer("Old.", 1000.0)).from_cache);
+        assert!(render(&renderer("Old.", 1000.0)).from_cache);
+
+        let result = render(&renderer("New.", 2000.0));
+        assert!(!result.from_cache);
+        assert!(result.html.contains("New."), "{}", result.html);
+    }
+
+    #[test]
+    fn test_include_url_path() {
+        assert_eq!(include_url_path(Path::new("guide/auth.md")), "guide/auth");
+        assert_eq!(include_url_path(Path::new("guide/index.md")), "guide");
+        assert_eq!(include_url_path(Path::new("index.md")), "");
+    }
+
     #[test]
     fn cache_hit_preserves_referenced_section_refs() {
         use rw_sections::{Namespace, Section};
@@ -846,22 +1183,38 @@ mod tests {
     fn diagram_config_fingerprint_distinguishes_inputs() {
         use std::path::PathBuf;
 
-        let base = diagram_config_fingerprint(None, &[]);
+        let base = diagram_config_fingerprint(None, &[], DiagramFormat::Svg, false);
 
         // Presence of kroki_url matters (unset vs set).
-        assert_ne!(base, diagram_config_fingerprint(Some("http://k"), &[]));
+        assert_ne!(
+            base,
+            diagram_config_fingerprint(Some("http://k"), &[], DiagramFormat::Svg, false)
+        );
         // Value of kroki_url matters (switching servers).
         assert_ne!(
-            diagram_config_fingerprint(Some("http://a"), &[]),
-            diagram_config_fingerprint(Some("http://b"), &[]),
+            diagram_config_fingerprint(Some("http://a"), &[], DiagramFormat::Svg, false),
+            diagram_config_fingerprint(Some("http://b"), &[], DiagramFormat::Svg, false),
         );
         // include_dirs matter.
         assert_ne!(
             base,
-            diagram_config_fingerprint(None, &[PathBuf::from("/inc")]),
+            diagram_config_fingerprint(None, &[PathBuf::from("/inc")], DiagramFormat::Svg, false),
+        );
+        // The default diagram format matters.
+        assert_ne!(
+            base,
+            diagram_config_fingerprint(None, &[], DiagramFormat::Png, false),
+        );
+        // Client-side Mermaid matters.
+        assert_ne!(
+            base,
+            diagram_config_fingerprint(None, &[], DiagramFormat::Svg, true),
         );
         // Stable for identical inputs.
-        assert_eq!(base, diagram_config_fingerprint(None, &[]));
+        assert_eq!(
+            base,
+            diagram_config_fingerprint(None, &[], DiagramFormat::Svg, false)
+        );
     }
 
     #[test]
@@ -955,6 +1308,45 @@ mod tests {
         );
     }
 
+    #[test]
+    fn client_side_mermaid_renders_without_kroki() {
+        let storage: Arc<dyn rw_storage::Storage> = Arc::new(
+            MockStorage::new()
+                .with_file(
+                    "diag",
+                    "Diagram",
+                    "```mermaid\ngraph TD; A-->B\n```\n\n```plantuml\nA -> B\n```\n",
+                )
+                .with_mtime("diag", 1000.0),
+        );
+        let page = make_page("Diagram", "diag", true);
+        let renderer = PageRenderer::new(
+            storage,
+            Arc::new(NullCache),
+            PageRendererConfig {
+                client_side_mermaid: true,
+                ..PageRendererConfig::default()
+            },
+        );
+
+        let result = renderer
+            .render("diag", &page, vec![], &RenderContext::default())
+            .unwrap();
+
+        assert!(
+            result
+                .html
+                .contains("<pre class=\"mermaid\">graph TD; A--&gt;B\n</pre>"),
+            "{}",
+            result.html
+        );
+        assert!(
+            result.html.contains(r#"<code class="language-plantuml">"#),
+            "{}",
+            result.html
+        );
+    }
+
     #[test]
     fn page_without_transient_failure_is_cached() {
         let temp_dir = tempfile::tempdir().unwrap();
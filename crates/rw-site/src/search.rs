@@ -0,0 +1,342 @@
+//! In-memory full-text search over page titles and text.
+//!
+//! [`SearchIndex`] keeps the plain text of every page with content, as
+//! rendered for [`Site::render_search_document`](crate::Site::render_search_document).
+//! It is built on the first [`Site::search`](crate::Site::search), dropped
+//! whenever the site installs a new snapshot, and otherwise refreshed page by
+//! page as source files change.
+//!
+//! A query matches a page when each of its terms starts a word of the page's
+//! title or text. Matches in the title outrank any number in the body.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Weak};
+
+use crate::page::{Page, RenderError, SearchDocument};
+use crate::site::SiteSnapshot;
+
+/// Most hits [`Site::search`](crate::Site::search) returns, whatever limit
+/// the caller asks for.
+pub const MAX_SEARCH_RESULTS: usize = 50;
+
+/// Score of a term found in the title. Larger than [`MAX_TERM_HITS`], so a
+/// title match always outranks body matches alone.
+const TITLE_BOOST: usize = 10;
+
+/// Body occurrences of one term that count towards the score, so a page
+/// repeating a word is not ranked above one that names it in its title.
+const MAX_TERM_HITS: usize = 5;
+
+/// Bytes of text kept before the first match in a snippet.
+const SNIPPET_LEAD: usize = 60;
+
+/// Length of a snippet in bytes, before trimming to word boundaries.
+const SNIPPET_LEN: usize = 200;
+
+/// A page matching a [`Site::search`](crate::Site::search) query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchHit {
+    /// URL path without leading slash (e.g., `"guide/setup"`).
+    pub path: String,
+    /// Page title (from metadata or first H1 heading).
+    pub title: String,
+    /// Text around the first match in the body, cut at word boundaries with
+    /// `…` marking the cuts. The start of the text when only the title
+    /// matched.
+    pub snippet: String,
+    /// Relevance; hits are sorted by it, highest first. Only comparable
+    /// between hits of the same query.
+    pub score: usize,
+}
+
+/// One page in the index.
+struct IndexedPage {
+    /// Source mtime the page was indexed at.
+    mtime: f64,
+    title: String,
+    text: String,
+    /// Lowercased words of `title`.
+    title_words: Vec<String>,
+    /// Lowercased words of `text`, each with its byte offset in `text`.
+    words: Vec<(String, usize)>,
+}
+
+impl IndexedPage {
+    fn new(mtime: f64, doc: SearchDocument) -> Self {
+        Self {
+            mtime,
+            title_words: words(&doc.title).map(|(word, _)| word).collect(),
+            words: words(&doc.text).collect(),
+            title: doc.title,
+            text: doc.text,
+        }
+    }
+
+    /// Score of this page for `terms`, and the byte offset of its first body
+    /// match, or `None` if some term matches neither title nor text.
+    fn score(&self, terms: &[String]) -> Option<(usize, Option<usize>)> {
+        let mut score = 0;
+        let mut first_match: Option<usize> = None;
+        for term in terms {
+            let in_title = self
+                .title_words
+                .iter()
+                .any(|w| w.starts_with(term.as_str()));
+            let mut hits = self
+                .words
+                .iter()
+                .filter(|(w, _)| w.starts_with(term.as_str()))
+                .map(|&(_, offset)| offset);
+            let first = hits.next();
+            if !in_title && first.is_none() {
+                return None;
+            }
+            if let Some(offset) = first {
+                first_match = Some(first_match.map_or(offset, |f| f.min(offset)));
+            }
+            let body_hits = usize::from(first.is_some()) + hits.take(MAX_TERM_HITS - 1).count();
+            score += body_hits + if in_title { TITLE_BOOST } else { 0 };
+        }
+        Some((score, first_match))
+    }
+}
+
+/// Search index over a site's pages, owned by [`Site`](crate::Site).
+#[derive(Default)]
+pub(crate) struct SearchIndex {
+    /// Snapshot the entries were rendered from. Holding it weakly keeps the
+    /// allocation, so a later snapshot can never reuse its address.
+    snapshot: Weak<SiteSnapshot>,
+    /// Indexed pages by URL path.
+    pages: HashMap<String, IndexedPage>,
+}
+
+impl SearchIndex {
+    /// Bring the index up to date with `snapshot`.
+    ///
+    /// A snapshot other than the last one (after [`Site::invalidate`](crate::Site::invalidate)
+    /// or a title change) empties the index first, so every page is rendered
+    /// again. Otherwise only pages whose `mtime` changed are. A page that
+    /// fails to render is logged and left out.
+    pub(crate) fn sync(
+        &mut self,
+        snapshot: &Arc<SiteSnapshot>,
+        mtime: impl Fn(&str) -> f64,
+        render: impl Fn(&Page) -> Result<Option<SearchDocument>, RenderError>,
+    ) {
+        if !std::ptr::eq(self.snapshot.as_ptr(), Arc::as_ptr(snapshot)) {
+            self.pages.clear();
+            self.snapshot = Arc::downgrade(snapshot);
+        }
+
+        for page in snapshot.state.pages().iter().filter(|p| p.has_content) {
+            let mtime = mtime(&page.path);
+            if self
+                .pages
+                .get(&page.path)
+                .is_some_and(|indexed| indexed.mtime.to_bits() == mtime.to_bits())
+            {
+                continue;
+            }
+            match render(page) {
+                Ok(Some(doc)) => {
+                    self.pages
+                        .insert(page.path.clone(), IndexedPage::new(mtime, doc));
+                }
+                Ok(None) => {
+                    self.pages.remove(&page.path);
+                }
+                Err(e) => {
+                    tracing::warn!(path = %page.path, error = %e, "Failed to index page for search");
+                    self.pages.remove(&page.path);
+                }
+            }
+        }
+    }
+
+    /// Pages matching every term of `query`, best first, at most `limit`
+    /// (capped at [`MAX_SEARCH_RESULTS`]). Ties go by path. A query without
+    /// words matches nothing.
+    pub(crate) fn search(&self, query: &str, limit: usize) -> Vec<SearchHit> {
+        let terms: Vec<String> = words(query).map(|(word, _)| word).collect();
+        if terms.is_empty() {
+            return Vec::new();
+        }
+
+        let mut hits: Vec<SearchHit> = self
+            .pages
+            .iter()
+            .filter_map(|(path, page)| {
+                let (score, first_match) = page.score(&terms)?;
+                Some(SearchHit {
+                    path: path.clone(),
+                    title: page.title.clone(),
+                    snippet: snippet(&page.text, first_match.unwrap_or(0)),
+                    score,
+                })
+            })
+            .collect();
+        hits.sort_unstable_by(|a, b| b.score.cmp(&a.score).then_with(|| a.path.cmp(&b.path)));
+        hits.truncate(limit.min(MAX_SEARCH_RESULTS));
+        hits
+    }
+}
+
+/// Lowercased alphanumeric runs of `text` with their byte offsets.
+fn words(text: &str) -> impl Iterator<Item = (String, usize)> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(move |word| {
+            // `split` yields subslices of `text`, so the pointer difference is
+            // the word's offset.
+            let offset = word.as_ptr() as usize - text.as_ptr() as usize;
+            (word.to_lowercase(), offset)
+        })
+}
+
+/// Up to [`SNIPPET_LEN`] bytes of `text` around byte offset `at` (a word
+/// start), cut at word boundaries, whitespace collapsed.
+fn snippet(text: &str, at: usize) -> String {
+    let mut start = at.saturating_sub(SNIPPET_LEAD);
+    while !text.is_char_boundary(start) {
+        start -= 1;
+    }
+    if start > 0 {
+        // Begin at a word, not partway through one.
+        start = text[start..at]
+            .find(char::is_whitespace)
+            .map_or(at, |i| start + i);
+    }
+
+    let mut end = (start + SNIPPET_LEN).min(text.len());
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    if end < text.len() {
+        end = text[at..end]
+            .rfind(char::is_whitespace)
+            .map_or(end, |i| at + i);
+    }
+
+    let body = text[start..end]
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    let lead = if text[..start].trim().is_empty() {
+        ""
+    } else {
+        "…"
+    };
+    let tail = if text[end..].trim().is_empty() {
+        ""
+    } else {
+        "…"
+    };
+    format!("{lead}{body}{tail}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn index(pages: &[(&str, &str, &str)]) -> SearchIndex {
+        SearchIndex {
+            snapshot: Weak::new(),
+            pages: pages
+                .iter()
+                .map(|&(path, title, text)| {
+                    let doc = SearchDocument {
+                        title: title.to_owned(),
+                        text: text.to_owned(),
+                    };
+                    (path.to_owned(), IndexedPage::new(0.0, doc))
+                })
+                .collect(),
+        }
+    }
+
+    fn paths(hits: &[SearchHit]) -> Vec<&str> {
+        hits.iter().map(|hit| hit.path.as_str()).collect()
+    }
+
+    #[test]
+    fn every_term_must_match() {
+        let index = index(&[
+            ("deploy", "Deploying", "Push the image, then roll out."),
+            ("images", "Images", "Build the image locally."),
+        ]);
+
+        assert_eq!(paths(&index.search("image roll", 10)), ["deploy"]);
+        assert_eq!(paths(&index.search("IMAGE", 10)), ["images", "deploy"]);
+        assert!(index.search("image kubernetes", 10).is_empty());
+    }
+
+    #[test]
+    fn terms_match_word_prefixes() {
+        let index = index(&[("config", "Setup", "Edit the configuration file.")]);
+
+        assert_eq!(paths(&index.search("config", 10)), ["config"]);
+        assert!(index.search("figuration", 10).is_empty());
+    }
+
+    #[test]
+    fn title_matches_outrank_body_matches() {
+        let index = index(&[
+            ("a", "Overview", "cache cache cache cache cache cache cache"),
+            ("b", "Cache", "How entries expire."),
+        ]);
+
+        let hits = index.search("cache", 10);
+
+        assert_eq!(paths(&hits), ["b", "a"]);
+        assert_eq!(hits[0].score, TITLE_BOOST);
+        assert_eq!(hits[1].score, MAX_TERM_HITS);
+    }
+
+    #[test]
+    fn limit_is_capped() {
+        let pages: Vec<(String, &str, &str)> = (0..=MAX_SEARCH_RESULTS)
+            .map(|i| (format!("page-{i:03}"), "Page", "text"))
+            .collect();
+        let pages: Vec<(&str, &str, &str)> = pages
+            .iter()
+            .map(|(path, title, text)| (path.as_str(), *title, *text))
+            .collect();
+        let index = index(&pages);
+
+        assert_eq!(index.search("page", 2).len(), 2);
+        assert_eq!(index.search("page", usize::MAX).len(), MAX_SEARCH_RESULTS);
+        assert_eq!(index.search("page", 3)[0].path, "page-000");
+        assert!(index.search(" -- ", 10).is_empty());
+    }
+
+    #[test]
+    fn snippet_surrounds_the_first_match() {
+        let filler = "lorem ipsum ".repeat(20);
+        let text = format!("{filler}the needle is here {filler}");
+        let index = index(&[("p", "Page", &text)]);
+
+        let snippet = &index.search("needle", 1)[0].snippet;
+
+        assert!(snippet.starts_with('…'), "{snippet}");
+        assert!(snippet.ends_with('…'), "{snippet}");
+        assert!(snippet.contains("the needle is here"), "{snippet}");
+        assert!(!snippet.contains("  "), "{snippet}");
+        let body = snippet.trim_matches('…');
+        assert!(
+            body.starts_with("lorem") || body.starts_with("ipsum"),
+            "{snippet}"
+        );
+        assert!(
+            body.ends_with("lorem") || body.ends_with("ipsum"),
+            "{snippet}"
+        );
+    }
+
+    #[test]
+    fn snippet_of_short_text_is_the_whole_text() {
+        let index = index(&[("p", "Needle", "Short\n\npage text.")]);
+
+        assert_eq!(index.search("needle", 1)[0].snippet, "Short page text.");
+    }
+}
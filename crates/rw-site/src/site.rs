@@ -5,17 +5,22 @@
 //! and the lazy reload pattern.
 
 use parking_lot::{Mutex, RwLock};
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use crate::page::{
     Page, PageRenderResult, PageRenderer, PageRendererConfig, RenderContext, RenderError,
     SearchDocument,
 };
-use crate::site_state::{Navigation, PageEntry, SectionEntry, SiteState, SiteStateBuilder};
-use rw_cache::{Cache, CacheBucket};
+use crate::search::{SearchHit, SearchIndex};
+use crate::site_state::{
+    Navigation, PageEntry, SectionEntry, SiteState, SiteStateBuilder, cmp_nav_order,
+};
+use rw_cache::{Cache, CacheBucket, CacheStats};
 use rw_kroki::{EntityInfo, MetaIncludeSource};
-use rw_renderer::TitleResolver;
+use rw_renderer::{LinkChecker, RenderBackend, RenderResult, TitleResolver};
 use rw_sections::Namespace;
 use rw_storage::{Storage, StorageError};
 
@@ -39,6 +44,9 @@ fn url_depth(path: &str) -> usize {
 /// include resolution using the state's name-based section index.
 pub(crate) struct SiteSnapshot {
     pub(crate) state: SiteState,
+    /// When `state` was loaded, as a Unix timestamp. `None` for the empty
+    /// placeholder installed before the first load.
+    pub(crate) loaded_at: Option<f64>,
 }
 
 impl MetaIncludeSource for SiteSnapshot {
@@ -82,6 +90,51 @@ impl TitleResolver for SiteTitleResolver {
     }
 }
 
+/// Answers page-existence checks for plain markdown links using the site
+/// snapshot. Virtual pages count as existing: they are navigable.
+pub(crate) struct SiteLinkChecker {
+    pub(crate) snapshot: Arc<SiteSnapshot>,
+}
+
+impl LinkChecker for SiteLinkChecker {
+    fn page_exists(&self, path: &str) -> bool {
+        self.snapshot.state.get_page(path).is_some()
+    }
+}
+
+/// Point-in-time health of a [`Site`], returned by [`Site::status`].
+///
+/// Everything here is read from state the site already keeps: computing it
+/// never scans storage or renders a page.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SiteStatus {
+    /// When the site structure was last loaded, as a Unix timestamp (seconds
+    /// since epoch, `f64` like [`PageRenderResult::source_mtime`]). `None`
+    /// until the first load.
+    pub last_scan: Option<f64>,
+    /// Pages in the loaded structure, [virtual pages](crate#virtual-pages)
+    /// included.
+    pub page_count: usize,
+    /// `false` after [`Site::invalidate`] until the next read method reloads
+    /// the structure.
+    pub structure_fresh: bool,
+    /// Pages rendered at least once since startup that still exist.
+    pub rendered_pages: usize,
+    /// Of `rendered_pages`, those whose latest render produced warnings.
+    pub pages_with_warnings: usize,
+    /// Sum of warnings over the latest render of each page in
+    /// `rendered_pages`.
+    pub warning_count: usize,
+    /// Problems the storage backend found in its most recent scan, such as
+    /// two source files mapping to one URL path (see
+    /// [`Storage::scan_warnings`]).
+    pub scan_warnings: Vec<String>,
+    /// Hits, misses and sets of each cache bucket since startup, by bucket
+    /// name (see [`Cache::stats`]). Empty for caches that don't count, such
+    /// as [`NullCache`](rw_cache::NullCache).
+    pub cache_stats: BTreeMap<String, CacheStats>,
+}
+
 /// Manages the document hierarchy and renders pages on demand.
 ///
 /// `Site` scans documents from a [`Storage`] backend, builds a tree of
@@ -130,6 +183,7 @@ impl TitleResolver for SiteTitleResolver {
 /// ```
 pub struct Site {
     storage: Arc<dyn Storage>,
+    cache: Arc<dyn Cache>,
     // Buckets
     #[allow(clippy::struct_field_names)]
     site_bucket: Box<dyn CacheBucket>,
@@ -146,8 +200,14 @@ pub struct Site {
     loaded_generation: AtomicU64,
     /// Whether the site has successfully loaded at least once.
     has_loaded: AtomicBool,
-    /// Page rendering pipeline.
-    renderer: PageRenderer,
+    /// Page rendering pipeline, replaced whole by
+    /// [`set_renderer_config`](Self::set_renderer_config).
+    renderer: RwLock<Arc<PageRenderer>>,
+    /// Warning count of the latest render of each page, keyed by page path.
+    /// Feeds [`status`](Self::status) without re-rendering anything.
+    page_warnings: RwLock<HashMap<String, usize>>,
+    /// Full-text index behind [`search`](Self::search), built on first use.
+    search_index: Mutex<SearchIndex>,
 }
 
 impl Site {
@@ -165,19 +225,23 @@ impl Site {
         let initial_state = SiteStateBuilder::new().build();
         let initial_snapshot = Arc::new(SiteSnapshot {
             state: initial_state,
+            loaded_at: None,
         });
         let site_bucket = cache.bucket("site");
-        let renderer = PageRenderer::new(Arc::clone(&storage), cache, config);
+        let renderer = PageRenderer::new(Arc::clone(&storage), Arc::clone(&cache), config);
 
         Self {
             storage,
+            cache,
             site_bucket,
             generation: AtomicU64::new(0),
             reload_lock: Mutex::new(()),
             current_snapshot: RwLock::new(initial_snapshot),
             loaded_generation: AtomicU64::new(u64::MAX),
             has_loaded: AtomicBool::new(false),
-            renderer,
+            renderer: RwLock::new(Arc::new(renderer)),
+            page_warnings: RwLock::new(HashMap::new()),
+            search_index: Mutex::new(SearchIndex::default()),
         }
     }
 
@@ -185,6 +249,30 @@ impl Site {
         Arc::clone(&self.current_snapshot.read())
     }
 
+    fn renderer(&self) -> Arc<PageRenderer> {
+        Arc::clone(&self.renderer.read())
+    }
+
+    /// Whether diagram fences render as diagrams, through Kroki or, for
+    /// Mermaid, in the browser. `false` when they render as code blocks.
+    #[must_use]
+    pub fn renders_diagrams(&self) -> bool {
+        self.renderer().renders_diagrams()
+    }
+
+    /// Replaces the rendering configuration, such as the Kroki URL or the
+    /// `PlantUML` include directories, without rebuilding the site.
+    ///
+    /// Renders already in progress finish with the old configuration. Cached
+    /// pages are keyed on the diagram settings, so pages rendered under the
+    /// old ones are rendered again on their next request rather than served
+    /// stale.
+    pub fn set_renderer_config(&self, config: PageRendererConfig) {
+        let renderer =
+            PageRenderer::new(Arc::clone(&self.storage), Arc::clone(&self.cache), config);
+        *self.renderer.write() = Arc::new(renderer);
+    }
+
     /// Returns the navigation tree scoped to a section.
     ///
     /// Pass `None` for root navigation, or a
@@ -351,6 +439,45 @@ impl Site {
             .and_then(|p| p.pages.clone())
     }
 
+    /// Returns the `redirects` of a page from the current cached snapshot,
+    /// or `None` if the page does not exist or declares none.
+    ///
+    /// Like [`page_title`](Self::page_title), does **not** trigger a reload.
+    #[must_use]
+    pub fn page_redirects(&self, path: &str) -> Option<Vec<String>> {
+        self.snapshot()
+            .state
+            .get_page(path)
+            .and_then(|p| p.redirects.clone())
+    }
+
+    /// Returns the metadata `order` of a page from the current cached
+    /// snapshot, or `None` if the page does not exist or sets none.
+    ///
+    /// Like [`page_title`](Self::page_title), does **not** trigger a reload.
+    #[must_use]
+    pub fn page_order(&self, path: &str) -> Option<i64> {
+        self.snapshot().state.get_page(path).and_then(|p| p.order)
+    }
+
+    /// Returns the path of the page that `path` now lives at, if `path` is a
+    /// former URL listed in that page's `redirects` metadata.
+    ///
+    /// Leading and trailing slashes in `path` are ignored. A redirect whose
+    /// source is still a page is dropped with a warning when the site loads,
+    /// so redirects never chain.
+    ///
+    /// Like [`page_title`](Self::page_title), reads the current cached
+    /// snapshot and does **not** trigger a reload, so it is cheap enough to
+    /// ask on every request. Before the first load there are no redirects.
+    #[must_use]
+    pub fn resolve_redirect(&self, path: &str) -> Option<String> {
+        self.snapshot()
+            .state
+            .resolve_redirect(path)
+            .map(str::to_owned)
+    }
+
     /// Returns the current snapshot, reloading from storage if stale.
     ///
     /// Validity is derived, not stored: the installed snapshot is fresh iff
@@ -427,7 +554,13 @@ impl Site {
             state
         };
 
-        let snapshot = Arc::new(SiteSnapshot { state });
+        let loaded_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0.0, |d| d.as_secs_f64());
+        let snapshot = Arc::new(SiteSnapshot {
+            state,
+            loaded_at: Some(loaded_at),
+        });
 
         *self.current_snapshot.write() = Arc::clone(&snapshot);
         // Stamp the generation this snapshot satisfies. If an invalidate raced
@@ -442,6 +575,24 @@ impl Site {
         Ok(snapshot)
     }
 
+    /// Whether the site structure has loaded successfully at least once.
+    ///
+    /// Reads the installed structure only; never scans storage.
+    #[must_use]
+    pub fn is_loaded(&self) -> bool {
+        self.snapshot().loaded_at.is_some()
+    }
+
+    /// Loads the site structure if it is not current, as every read method
+    /// does before answering. A no-op when it is.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the initial site load fails.
+    pub fn ensure_loaded(&self) -> Result<(), StorageError> {
+        self.reload_if_needed().map(|_| ())
+    }
+
     /// Reloads the site, optionally checking for changes first.
     ///
     /// - `reload(true)` — unconditional reload. Always returns `Ok(true)`.
@@ -467,6 +618,39 @@ impl Site {
         Ok(true)
     }
 
+    /// Applies a page title change without rescanning storage.
+    ///
+    /// Live editing changes a page's H1 (or metadata title) far more often
+    /// than it adds, removes or reorders pages. For that case the installed
+    /// structure is copied with only `path`'s title replaced and swapped in,
+    /// instead of [`invalidate`](Self::invalidate) followed by a full
+    /// `storage.scan()` on the next read.
+    ///
+    /// Returns `false`, changing nothing, when the structure is already stale
+    /// (a pending full reload will pick the title up) or `path` is not in it.
+    /// Callers fall back to [`invalidate`](Self::invalidate) then.
+    ///
+    /// An [`invalidate`](Self::invalidate) racing this call is never lost:
+    /// the swap does not touch the generation bookkeeping, so the next read
+    /// still sees the structure as stale and rescans.
+    pub fn apply_title_change(&self, path: &str, title: &str) -> bool {
+        // Serialize with reloads so a scan cannot install over the swap.
+        let _guard = self.reload_lock.lock();
+        if self.loaded_generation.load(Ordering::Acquire) != self.generation.load(Ordering::Acquire)
+        {
+            return false;
+        }
+        let snapshot = self.snapshot();
+        let Some(state) = snapshot.state.with_page_title(path, title) else {
+            return false;
+        };
+        *self.current_snapshot.write() = Arc::new(SiteSnapshot {
+            state,
+            loaded_at: snapshot.loaded_at,
+        });
+        true
+    }
+
     /// Marks the cached site structure as stale.
     ///
     /// The next call to any read method ([`navigation`](Self::navigation),
@@ -505,7 +689,57 @@ impl Site {
             .ok_or_else(|| RenderError::PageNotFound(path.to_owned()))?;
         let breadcrumbs = snapshot.state.get_breadcrumbs(path);
         let ctx = Self::render_context(&snapshot);
-        self.renderer.render(path, page, breadcrumbs, &ctx)
+        let result = self.renderer().render(path, page, breadcrumbs, &ctx)?;
+        self.record_warnings(path, result.warnings.len());
+        Ok(result)
+    }
+
+    /// Remember how many warnings the latest render of `path` produced.
+    fn record_warnings(&self, path: &str, count: usize) {
+        if self.page_warnings.read().get(path) == Some(&count) {
+            return;
+        }
+        self.page_warnings.write().insert(path.to_owned(), count);
+    }
+
+    /// Reports the site's current health.
+    ///
+    /// Cheap enough to call on every request: it reads the installed
+    /// snapshot and the warning counts [`render`](Self::render) records as
+    /// pages are served. It does not reload a stale structure (see
+    /// [`SiteStatus::structure_fresh`]) and only knows about pages that have
+    /// been rendered.
+    #[must_use]
+    pub fn status(&self) -> SiteStatus {
+        let snapshot = self.snapshot();
+        let structure_fresh = self.loaded_generation.load(Ordering::Acquire)
+            == self.generation.load(Ordering::Acquire);
+
+        let mut rendered_pages = 0;
+        let mut pages_with_warnings = 0;
+        let mut warning_count = 0;
+        for (path, &count) in self.page_warnings.read().iter() {
+            // Pages deleted since their last render no longer count.
+            if snapshot.state.get_page(path).is_none() {
+                continue;
+            }
+            rendered_pages += 1;
+            if count > 0 {
+                pages_with_warnings += 1;
+                warning_count += count;
+            }
+        }
+
+        SiteStatus {
+            last_scan: snapshot.loaded_at,
+            page_count: snapshot.state.page_count(),
+            structure_fresh,
+            rendered_pages,
+            pages_with_warnings,
+            warning_count,
+            scan_warnings: self.storage.scan_warnings(),
+            cache_stats: self.cache.stats(),
+        }
     }
 
     /// Render a page as plain text for search indexing.
@@ -526,7 +760,68 @@ impl Site {
             .get_page(path)
             .ok_or_else(|| RenderError::PageNotFound(path.to_owned()))?;
         let ctx = Self::render_context(&snapshot);
-        self.renderer.render_search_document(path, page, &ctx)
+        self.renderer().render_search_document(path, page, &ctx)
+    }
+
+    /// Full-text search over the titles and text of all pages with content.
+    ///
+    /// A page matches when every word of `query` starts a word of its title
+    /// or text, case-insensitively; pages matching in the title rank first.
+    /// Returns at most `limit` hits, and never more than
+    /// [`MAX_SEARCH_RESULTS`](crate::MAX_SEARCH_RESULTS).
+    ///
+    /// The index behind it is built from
+    /// [`render_search_document()`](Self::render_search_document) on the first
+    /// call and rebuilt after [`invalidate()`](Self::invalidate). Between
+    /// reloads, each call checks every page's mtime and re-indexes the pages
+    /// that changed, so, as with [`list_pages()`](Self::list_pages), prefer
+    /// the filesystem mtime source over git for large sites. Pages that fail
+    /// to render are logged and left out of the results.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the initial site load fails (storage
+    /// unreachable). Subsequent reload failures are logged and stale data is
+    /// searched instead.
+    pub fn search(&self, query: &str, limit: usize) -> Result<Vec<SearchHit>, StorageError> {
+        let snapshot = self.reload_if_needed()?;
+        let ctx = Self::render_context(&snapshot);
+        let renderer = self.renderer();
+        let mut index = self.search_index.lock();
+        index.sync(
+            &snapshot,
+            |path| self.storage.mtime(path).unwrap_or(0.0),
+            |page| renderer.render_search_document(&page.path, page, &ctx),
+        );
+        Ok(index.search(query, limit))
+    }
+
+    /// Render a page through a caller-chosen [`RenderBackend`].
+    ///
+    /// Runs the same pipeline as [`render()`](Self::render) — storage read,
+    /// link resolution, directives, and the configured diagram processor — but
+    /// emits whatever markup `B` produces instead of HTML. A metadata title
+    /// takes precedence over the extracted H1, as in
+    /// [`render_search_document()`](Self::render_search_document).
+    ///
+    /// Returns `None` for virtual pages (directories without content).
+    /// Results are not cached and warnings are not recorded in
+    /// [`status()`](Self::status); both are specific to the HTML render.
+    ///
+    /// # Errors
+    ///
+    /// Same error conditions as [`render()`](Self::render).
+    pub fn render_with<B: RenderBackend>(
+        &self,
+        path: &str,
+    ) -> Result<Option<RenderResult>, RenderError> {
+        let snapshot = self.reload_if_needed().map_err(RenderError::Storage)?;
+        let page = snapshot
+            .state
+            .get_page(path)
+            .ok_or_else(|| RenderError::PageNotFound(path.to_owned()))?;
+        let ctx = Self::render_context(&snapshot);
+        self.renderer().render_with::<B>(path, page, &ctx)
     }
 
     /// Returns a page's markdown source, exactly as authored.
@@ -578,11 +873,20 @@ impl Site {
         let mut builder = SiteStateBuilder::new();
         let mut documents = self.storage.scan()?;
 
-        // Sort documents: parents before children, real pages before virtual, by path
+        // Sort documents: parents before children, then siblings by metadata
+        // `order`, real pages before virtual (unordered pages only; ordered
+        // ties go straight to the path), by path
         documents.sort_by(|a, b| {
             url_depth(&a.path)
                 .cmp(&url_depth(&b.path))
-                .then_with(|| a.has_content.cmp(&b.has_content).reverse())
+                .then_with(|| cmp_nav_order(a.order, b.order))
+                .then_with(|| {
+                    if a.order.is_some() {
+                        std::cmp::Ordering::Equal
+                    } else {
+                        a.has_content.cmp(&b.has_content).reverse()
+                    }
+                })
                 .then_with(|| a.path.cmp(&b.path))
         });
 
@@ -615,6 +919,8 @@ impl Site {
                     description: doc.description.clone(),
                     origin: doc.origin.clone(),
                     pages: doc.pages.clone(),
+                    order: doc.order,
+                    redirects: doc.redirects.clone(),
                     is_dir: doc.is_dir,
                 },
                 doc.page_kind.as_deref(),
@@ -640,7 +946,7 @@ mod tests {
 
     use std::sync::Arc;
 
-    use rw_storage::{MockStorage, StorageErrorKind};
+    use rw_storage::{Metadata, MockStorage, StorageErrorKind};
 
     use super::*;
     use crate::page::RenderError;
@@ -943,6 +1249,267 @@ mod tests {
         assert!(result.has_content);
     }
 
+    #[test]
+    fn test_set_renderer_config_rerenders_cached_pages() {
+        let storage = MockStorage::new()
+            .with_file("diag", "Diagram", "```mermaid\ngraph TD; A-->B\n```\n")
+            .with_mtime("diag", 1000.0);
+        let site = Site::new(
+            Arc::new(storage),
+            Arc::new(rw_cache::MemoryCache::new()),
+            PageRendererConfig::default(),
+        );
+        site.render("diag").unwrap();
+        assert!(site.render("diag").unwrap().from_cache);
+
+        site.set_renderer_config(PageRendererConfig {
+            client_side_mermaid: true,
+            ..PageRendererConfig::default()
+        });
+        let result = site.render("diag").unwrap();
+
+        assert!(!result.from_cache);
+        assert!(
+            result.html.contains("<pre class=\"mermaid\">"),
+            "{}",
+            result.html
+        );
+    }
+
+    #[test]
+    fn test_renders_diagrams_follows_renderer_config() {
+        let site = create_site_with_storage(MockStorage::new());
+        assert!(!site.renders_diagrams());
+
+        site.set_renderer_config(PageRendererConfig {
+            kroki_url: Some("https://kroki.example.com".to_owned()),
+            ..PageRendererConfig::default()
+        });
+
+        assert!(site.renders_diagrams());
+    }
+
+    #[test]
+    fn test_status_before_first_load_is_empty() {
+        let site = create_site_with_storage(MockStorage::new().with_document("guide", "Guide"));
+
+        let status = site.status();
+
+        assert_eq!(status.last_scan, None);
+        assert_eq!(status.page_count, 0);
+        assert!(!status.structure_fresh);
+        assert_eq!(status.rendered_pages, 0);
+    }
+
+    #[test]
+    fn test_status_aggregates_warnings_from_rendered_pages() {
+        let storage = MockStorage::new()
+            .with_file("clean", "Clean", "# Clean\n\nFine.")
+            .with_mtime("clean", 1000.0)
+            .with_file("lone-tab", "Lone", ":::tab[A]\n\nOrphan.\n\n:::")
+            .with_mtime("lone-tab", 1000.0)
+            .with_document("unrendered", "Unrendered");
+        let site = create_site_with_storage(storage);
+
+        site.render("clean").unwrap();
+        let lone = site.render("lone-tab").unwrap();
+        assert_eq!(lone.warnings.len(), 1, "warnings: {:?}", lone.warnings);
+        // Re-rendering replaces a page's count rather than adding to it.
+        site.render("lone-tab").unwrap();
+
+        let status = site.status();
+        assert!(status.last_scan.is_some());
+        assert_eq!(status.page_count, 3);
+        assert!(status.structure_fresh);
+        assert_eq!(status.rendered_pages, 2);
+        assert_eq!(status.pages_with_warnings, 1);
+        assert_eq!(status.warning_count, 1);
+
+        site.invalidate();
+        assert!(!site.status().structure_fresh);
+    }
+
+    #[test]
+    fn test_render_warns_about_links_to_missing_pages() {
+        let storage = MockStorage::new()
+            .with_file(
+                "guide",
+                "Guide",
+                "# Guide\n\n[Setup](./setup.md) [Gone](./gone.md#intro) [Ext](https://example.com/a.md)",
+            )
+            .with_mtime("guide", 1000.0)
+            .with_document("guide/setup", "Setup");
+        let site = create_site_with_storage(storage);
+
+        let result = site.render("guide").unwrap();
+
+        assert_eq!(
+            result.warnings,
+            vec!["broken link to ./gone.md#intro: no page at /guide/gone".to_owned()]
+        );
+        assert_eq!(site.status().warning_count, 1);
+    }
+
+    #[test]
+    fn test_status_reports_storage_scan_warnings() {
+        let storage = MockStorage::new()
+            .with_document("guide", "Guide")
+            .with_scan_warning("guide.md is ignored: guide/index.md also maps to /guide");
+        let site = create_site_with_storage(storage);
+
+        assert_eq!(
+            site.status().scan_warnings,
+            ["guide.md is ignored: guide/index.md also maps to /guide"]
+        );
+    }
+
+    #[test]
+    fn test_status_reports_cache_stats() {
+        let storage = MockStorage::new()
+            .with_file("guide", "Guide", "# Guide\n\nHello.")
+            .with_mtime("guide", 1000.0);
+        let site = Site::new(
+            Arc::new(storage),
+            Arc::new(rw_cache::MemoryCache::new()),
+            PageRendererConfig::default(),
+        );
+
+        assert!(!site.render("guide").unwrap().from_cache);
+        assert!(site.render("guide").unwrap().from_cache);
+
+        let pages = site.status().cache_stats["pages"];
+        assert_eq!(pages.hits, 1);
+        assert_eq!(pages.misses, 1);
+        assert_eq!(pages.sets, 1);
+    }
+
+    #[test]
+    fn test_apply_title_change_updates_structure_without_scan() {
+        let storage = Arc::new(
+            MockStorage::new()
+                .with_document("guide", "Guide")
+                .with_document("guide/setup", "Setup"),
+        );
+        let site = Site::new(
+            Arc::clone(&storage) as Arc<dyn Storage>,
+            Arc::new(rw_cache::NullCache),
+            PageRendererConfig::default(),
+        );
+        site.reload_if_needed().unwrap();
+        let fingerprint = site.snapshot().state.resolution_fingerprint();
+        let scans = storage.scan_count();
+
+        assert!(site.apply_title_change("guide", "User Guide"));
+
+        assert_eq!(site.page_title("guide").as_deref(), Some("User Guide"));
+        let breadcrumbs = site.snapshot().state.get_breadcrumbs("guide/setup");
+        assert_eq!(breadcrumbs.last().unwrap().title, "User Guide");
+        assert_ne!(site.snapshot().state.resolution_fingerprint(), fingerprint);
+        assert_eq!(storage.scan_count(), scans);
+    }
+
+    #[test]
+    fn test_apply_title_change_declines_unknown_page_and_stale_structure() {
+        let site = create_site_with_storage(MockStorage::new().with_document("guide", "Guide"));
+        site.reload_if_needed().unwrap();
+
+        assert!(!site.apply_title_change("missing", "Missing"));
+
+        site.invalidate();
+        assert!(!site.apply_title_change("guide", "User Guide"));
+        assert_eq!(site.page_title("guide").as_deref(), Some("Guide"));
+    }
+
+    #[test]
+    fn test_invalidate_after_title_change_still_rescans() {
+        let storage = Arc::new(MockStorage::new().with_document("guide", "Guide"));
+        let site = Site::new(
+            Arc::clone(&storage) as Arc<dyn Storage>,
+            Arc::new(rw_cache::NullCache),
+            PageRendererConfig::default(),
+        );
+        site.reload_if_needed().unwrap();
+        assert!(site.apply_title_change("guide", "Edited"));
+
+        site.invalidate();
+        site.reload_if_needed().unwrap();
+
+        // Storage still says "Guide": the rescan replaces the patched title.
+        assert_eq!(site.page_title("guide").as_deref(), Some("Guide"));
+    }
+
+    #[test]
+    fn test_render_with_html_backend_matches_render() {
+        let storage = MockStorage::new()
+            .with_file("test", "Hello", "# Hello\n\nSee [[test]].")
+            .with_mtime("test", 1000.0);
+        let config = PageRendererConfig {
+            extract_title: true,
+            ..Default::default()
+        };
+        let site = Site::new(Arc::new(storage), Arc::new(rw_cache::NullCache), config);
+
+        let page = site.render("test").unwrap();
+        let result = site
+            .render_with::<rw_renderer::HtmlBackend>("test")
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(result.html, page.html);
+        assert_eq!(result.title, page.title);
+    }
+
+    #[test]
+    fn test_render_with_swaps_backend() {
+        let storage = MockStorage::new()
+            .with_file("test", "Hello", "# Hello\n\nSome **bold** words.")
+            .with_mtime("test", 1000.0);
+        let site = create_site_with_storage(storage);
+
+        let result = site
+            .render_with::<rw_renderer::SearchDocumentBackend>("test")
+            .unwrap()
+            .unwrap();
+
+        assert!(result.html.contains("bold"));
+        assert!(!result.html.contains('<'));
+    }
+
+    #[test]
+    fn test_render_with_metadata_title_wins() {
+        let meta = Metadata {
+            title: Some("From Meta".to_owned()),
+            ..Default::default()
+        };
+        let storage = MockStorage::new()
+            .with_file("test", "Hello", "# Hello\n\nWorld")
+            .with_metadata("test", meta);
+        let site = create_site_with_storage(storage);
+
+        let result = site
+            .render_with::<rw_renderer::HtmlBackend>("test")
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(result.title.as_deref(), Some("From Meta"));
+    }
+
+    #[test]
+    fn test_render_with_virtual_page_returns_none() {
+        let storage = MockStorage::new().with_virtual_page("domain", "Domain");
+        let site = create_site_with_storage(storage);
+
+        let result = site
+            .render_with::<rw_renderer::HtmlBackend>("domain")
+            .unwrap();
+
+        assert!(result.is_none());
+        assert_matches!(
+            site.render_with::<rw_renderer::HtmlBackend>("missing"),
+            Err(RenderError::PageNotFound(_))
+        );
+    }
+
     #[test]
     fn test_render_page_not_found() {
         let storage = MockStorage::new().with_document("exists", "Exists");
@@ -1255,6 +1822,20 @@ mod tests {
         assert_eq!(result.err().unwrap().kind, StorageErrorKind::Unavailable);
     }
 
+    #[test]
+    fn test_is_loaded_after_first_successful_load_only() {
+        let failing = create_site_with_storage(
+            MockStorage::new().with_scan_error(StorageErrorKind::Unavailable),
+        );
+        assert!(failing.ensure_loaded().is_err());
+        assert!(!failing.is_loaded());
+
+        let site = create_site_with_storage(MockStorage::new().with_document("guide", "Guide"));
+        assert!(!site.is_loaded());
+        site.ensure_loaded().unwrap();
+        assert!(site.is_loaded());
+    }
+
     #[test]
     fn test_reload_keeps_stale_data_on_subsequent_scan_error() {
         let storage = Arc::new(MockStorage::new().with_document("guide", "Guide"));
@@ -1676,6 +2257,93 @@ mod tests {
         assert_eq!(nav.items[2].path, "advanced"); // unlisted
     }
 
+    #[test]
+    fn test_navigation_ordered_by_order_field() {
+        let storage = MockStorage::new()
+            .with_document("", "Home")
+            .with_document("alpha", "Alpha")
+            .with_document("beta", "Beta")
+            .with_document("gamma", "Gamma")
+            .with_order("gamma", 1)
+            .with_virtual_page("delta", "Delta")
+            .with_order("delta", 2)
+            .with_document("delta/child", "Child")
+            .with_document("epsilon", "Epsilon")
+            .with_order("epsilon", 2);
+
+        let site = create_site_with_storage(storage);
+        let snapshot = site.reload_if_needed().unwrap();
+        let paths: Vec<_> = snapshot
+            .state
+            .navigation("")
+            .items
+            .into_iter()
+            .map(|item| item.path)
+            .collect();
+
+        // Ordered pages first (ties by path, virtual pages included), then
+        // the rest alphabetically.
+        assert_eq!(paths, ["gamma", "delta", "epsilon", "alpha", "beta"]);
+    }
+
+    #[test]
+    fn test_navigation_orders_pages_unlisted_in_parent_by_order_field() {
+        let storage = MockStorage::new()
+            .with_document_and_pages("", "Home", vec!["config".to_owned()])
+            .with_document("advanced", "Advanced")
+            .with_document("basics", "Basics")
+            .with_order("basics", 1)
+            .with_document("config", "Configuration");
+
+        let site = create_site_with_storage(storage);
+        let snapshot = site.reload_if_needed().unwrap();
+        let nav = snapshot.state.navigation("");
+
+        assert_eq!(nav.items[0].path, "config");
+        assert_eq!(nav.items[1].path, "basics");
+        assert_eq!(nav.items[2].path, "advanced");
+    }
+
+    #[test]
+    fn test_resolve_redirect_from_page_metadata() {
+        let storage = MockStorage::new()
+            .with_document("", "Home")
+            .with_document("guides/setup", "Setup")
+            .with_redirects("guides/setup", &["old/path", "legacy"])
+            .with_document("guide", "Guide")
+            .with_redirects("guide", &["guides/setup"]);
+
+        let site = create_site_with_storage(storage);
+        site.ensure_loaded().unwrap();
+
+        assert_eq!(
+            site.resolve_redirect("old/path").as_deref(),
+            Some("guides/setup")
+        );
+        assert_eq!(
+            site.resolve_redirect("/legacy").as_deref(),
+            Some("guides/setup")
+        );
+        // A live page is never redirected away.
+        assert_eq!(site.resolve_redirect("guides/setup"), None);
+        assert_eq!(
+            site.page_redirects("guide"),
+            Some(vec!["guides/setup".to_owned()])
+        );
+    }
+
+    #[test]
+    fn test_resolve_redirect_does_not_load_the_site() {
+        let storage = MockStorage::new()
+            .with_document("guides/setup", "Setup")
+            .with_redirects("guides/setup", &["old/path"]);
+
+        let site = create_site_with_storage(storage);
+
+        assert_eq!(site.resolve_redirect("old/path"), None);
+        assert!(!site.is_loaded());
+    }
+
     #[test]
     fn test_render_cache_busts_when_referenced_page_title_changes() {
         use std::fs;
@@ -1847,6 +2515,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_bom_prefixed_source_renders_without_bom() {
+        use rw_storage_fs::FsStorage;
+
+        let temp = tempfile::tempdir().unwrap();
+        let docs = temp.path().join("docs");
+        std::fs::create_dir_all(&docs).unwrap();
+        // As saved by a Windows editor with "UTF-8 with BOM".
+        std::fs::write(docs.join("guide.md"), "\u{FEFF}# Guide\n\nBody.\n").unwrap();
+
+        let storage = FsStorage::new(temp.path().to_path_buf(), docs);
+        let config = PageRendererConfig {
+            extract_title: true,
+            ..Default::default()
+        };
+        let site = Site::new(Arc::new(storage), Arc::new(rw_cache::NullCache), config);
+
+        let snapshot = site.reload_if_needed().unwrap();
+        assert_eq!(snapshot.state.get_page("guide").unwrap().title, "Guide");
+
+        let result = site.render("guide").unwrap();
+        assert_eq!(result.title.as_deref(), Some("Guide"));
+        assert!(!result.html.contains('\u{FEFF}'), "html: {:?}", result.html);
+        assert!(result.html.contains("<h1"), "html: {}", result.html);
+    }
+
     // ========================================================================
     // page_markdown
     // ========================================================================
@@ -1902,4 +2596,61 @@ mod tests {
             Err(RenderError::PageNotFound(_))
         );
     }
+
+    #[test]
+    fn test_search_ranks_titles_and_skips_virtual_pages() {
+        let storage = MockStorage::new()
+            .with_file("", "Home", "# Home\n\nSee the caching guide.")
+            .with_file("cache", "Cache", "# Cache\n\nHow entries expire.")
+            .with_virtual_page("domains", "Cache domains");
+        let site = create_site_with_storage(storage);
+
+        let hits = site.search("cach", 10).unwrap();
+
+        let paths: Vec<&str> = hits.iter().map(|hit| hit.path.as_str()).collect();
+        assert_eq!(paths, ["cache", ""]);
+        assert_eq!(hits[0].title, "Cache");
+        assert!(hits[1].snippet.contains("See the caching guide."));
+        assert_eq!(site.search("cache expire", 10).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_search_reindexes_after_invalidate_and_source_edits() {
+        use std::fs;
+        use std::time::{Duration, SystemTime};
+
+        use rw_storage_fs::FsStorage;
+
+        let dir = tempfile::tempdir().unwrap();
+        let docs = dir.path().join("docs");
+        fs::create_dir_all(&docs).unwrap();
+        fs::write(docs.join("index.md"), "# Home").unwrap();
+        fs::write(docs.join("guide.md"), "# Guide\n\nTune the cache.").unwrap();
+        let site = Site::new(
+            Arc::new(FsStorage::new(dir.path().to_path_buf(), docs.clone())),
+            Arc::new(rw_cache::NullCache),
+            PageRendererConfig::default(),
+        );
+        assert_eq!(site.search("cache", 10).unwrap().len(), 1);
+
+        // A new page only shows up once the structure is reloaded.
+        fs::write(docs.join("ops.md"), "# Ops\n\nFlush the cache.").unwrap();
+        assert_eq!(site.search("cache", 10).unwrap().len(), 1);
+        site.invalidate();
+        assert_eq!(site.search("cache", 10).unwrap().len(), 2);
+
+        // A content edit needs no invalidate: the page's mtime moved.
+        let guide = docs.join("guide.md");
+        fs::write(&guide, "# Guide\n\nTune the pool.").unwrap();
+        fs::File::options()
+            .write(true)
+            .open(&guide)
+            .unwrap()
+            .set_modified(SystemTime::now() + Duration::from_mins(1))
+            .unwrap();
+        let hits = site.search("cache", 10).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].path, "ops");
+        assert_eq!(site.search("pool", 10).unwrap()[0].path, "guide");
+    }
 }
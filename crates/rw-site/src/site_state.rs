@@ -8,6 +8,8 @@
 //! This module also defines the navigation types ([`NavItem`], [`Navigation`],
 //! [`ScopeInfo`]) that the frontend consumes.
 
+use std::cmp::Ordering;
+use std::collections::hash_map::Entry;
 use std::collections::{HashMap, HashSet};
 use std::hash::{DefaultHasher, Hash, Hasher};
 use std::sync::Arc;
@@ -23,6 +25,13 @@ fn last_segment(path: &str) -> &str {
     path.rsplit('/').next().unwrap_or(path)
 }
 
+/// Compares two siblings' metadata `order`: pages with one come first,
+/// lowest first; pages without one compare equal, leaving the tie to the
+/// caller's own fallback.
+pub(crate) fn cmp_nav_order(a: Option<i64>, b: Option<i64>) -> Ordering {
+    a.is_none().cmp(&b.is_none()).then(a.cmp(&b))
+}
+
 /// A node in the navigation tree sent to the frontend.
 ///
 /// Each `NavItem` maps to a page. Items that are
@@ -185,6 +194,9 @@ pub struct SiteState {
     sections_by_name: HashMap<String, Vec<usize>>,
     subtree_has_content: Vec<bool>,
     root_namespace: Namespace,
+    /// Former URL paths mapped to the page that lists them in its
+    /// `redirects` metadata. Derived from `pages` by [`index_redirects`].
+    redirects: HashMap<String, String>,
     /// Hash of the cross-page inputs that page rendering resolves from this
     /// state (page title/description/`has_content`, the sections map, and the
     /// root namespace). Folded into the page render cache etag so that changing
@@ -271,6 +283,42 @@ fn compute_resolution_fingerprint(
     hasher.finish()
 }
 
+/// Map every page's declared `redirects` to the page's path.
+///
+/// Sources are trimmed of slashes. A redirect is dropped with a warning when
+/// its source is empty or itself a page (a live page is never shadowed), or
+/// when an earlier page already claimed it. Targets are the declaring pages,
+/// and no page is a source, so redirects never chain and a cycle cannot
+/// survive.
+fn index_redirects(pages: &[Page], path_index: &HashMap<String, usize>) -> HashMap<String, String> {
+    let mut redirects: HashMap<String, String> = HashMap::new();
+    for page in pages {
+        for from in page.redirects.iter().flatten() {
+            let from = from.trim_matches('/');
+            if from.is_empty() || path_index.contains_key(from) {
+                tracing::warn!(
+                    from,
+                    to = page.path.as_str(),
+                    "redirect source is an existing page, skipping"
+                );
+                continue;
+            }
+            match redirects.entry(from.to_owned()) {
+                Entry::Occupied(existing) => tracing::warn!(
+                    from,
+                    to = page.path.as_str(),
+                    existing = existing.get().as_str(),
+                    "duplicate redirect source, keeping the first"
+                ),
+                Entry::Vacant(slot) => {
+                    slot.insert(page.path.clone());
+                }
+            }
+        }
+    }
+    redirects
+}
+
 impl SiteState {
     /// Create a new site state from components.
     ///
@@ -291,6 +339,7 @@ impl SiteState {
             .map(|(i, page)| (page.path.clone(), i))
             .collect();
         let subtree_has_content = compute_subtree_has_content(&pages, &children, &roots);
+        let redirects = index_redirects(&pages, &path_index);
 
         let sections = Arc::new(Sections::with_implicit_root(
             sections,
@@ -327,6 +376,7 @@ impl SiteState {
             sections_by_name,
             subtree_has_content,
             root_namespace,
+            redirects,
             resolution_fingerprint,
         }
     }
@@ -340,6 +390,26 @@ impl SiteState {
         self.path_index.get(path).map(|&i| &self.pages[i])
     }
 
+    /// Returns the path of the page that `path` redirects to, or `None` if
+    /// `path` is not a former URL listed in some page's `redirects` metadata.
+    /// Leading and trailing slashes in `path` are ignored.
+    #[must_use]
+    pub fn resolve_redirect(&self, path: &str) -> Option<&str> {
+        self.redirects
+            .get(path.trim_matches('/'))
+            .map(String::as_str)
+    }
+
+    /// Number of pages, virtual pages included.
+    pub(crate) fn page_count(&self) -> usize {
+        self.pages.len()
+    }
+
+    /// Every page, virtual pages included, in no particular order.
+    pub(crate) fn pages(&self) -> &[Page] {
+        &self.pages
+    }
+
     /// Returns the page title at `path`, falling back to `default` if the page
     /// doesn't exist.
     #[must_use]
@@ -769,6 +839,34 @@ impl SiteState {
         &self.sections
     }
 
+    /// Returns a copy of this state with the page at `path` retitled, or
+    /// `None` if there is no such page.
+    ///
+    /// A title touches no index — only the page itself and the resolution
+    /// fingerprint — so this skips the storage scan and the rebuild a full
+    /// reload would do. Sections are shared with `self`, not copied.
+    #[must_use]
+    pub(crate) fn with_page_title(&self, path: &str, title: &str) -> Option<Self> {
+        let &idx = self.path_index.get(path)?;
+        let mut pages = self.pages.clone();
+        title.clone_into(&mut pages[idx].title);
+        let resolution_fingerprint =
+            compute_resolution_fingerprint(&pages, &self.sections, &self.root_namespace);
+        Some(Self {
+            pages,
+            children: self.children.clone(),
+            parents: self.parents.clone(),
+            roots: self.roots.clone(),
+            path_index: self.path_index.clone(),
+            sections: Arc::clone(&self.sections),
+            sections_by_name: self.sections_by_name.clone(),
+            subtree_has_content: self.subtree_has_content.clone(),
+            root_namespace: self.root_namespace.clone(),
+            redirects: self.redirects.clone(),
+            resolution_fingerprint,
+        })
+    }
+
     /// Returns the resolution fingerprint — a hash of the cross-page inputs
     /// that page rendering resolves from this state. Used as part of the page
     /// render cache etag so cross-page changes invalidate stale renders.
@@ -930,8 +1028,9 @@ impl SiteStateBuilder {
     /// Reorder children of `parent_idx` according to `slugs`.
     ///
     /// Listed slugs appear first in declared order, unlisted children
-    /// appear after sorted alphabetically by path. Section directories,
-    /// missing slugs, and duplicates are warned and skipped.
+    /// appear after sorted by their metadata `order`, then alphabetically by
+    /// path. Section directories, missing slugs, and duplicates are warned
+    /// and skipped.
     fn reorder_children(&mut self, parent_idx: usize, slugs: &[String]) {
         let children = &self.children[parent_idx];
         if children.is_empty() || slugs.is_empty() {
@@ -990,7 +1089,10 @@ impl SiteStateBuilder {
             .filter(|idx| !listed.contains(idx))
             .copied()
             .collect();
-        unlisted.sort_by(|&a, &b| self.pages[a].path.cmp(&self.pages[b].path));
+        unlisted.sort_by(|&a, &b| {
+            let (a, b) = (&self.pages[a], &self.pages[b]);
+            cmp_nav_order(a.order, b.order).then_with(|| a.path.cmp(&b.path))
+        });
 
         let mut reordered = listed;
         reordered.extend(unlisted);
@@ -2706,6 +2808,8 @@ mod tests {
             description: desc.map(str::to_owned),
             origin: None,
             pages: None,
+            order: None,
+            redirects: None,
             is_dir: true,
         }
     }
@@ -2965,4 +3069,63 @@ mod tests {
         let site = SiteStateBuilder::new().build();
         assert_eq!(site.page_path_for("domain:default/nope", "api"), None);
     }
+
+    /// Build a `SiteState` from `(path, redirects)` content pages, in order.
+    fn site_with_redirects(pages: &[(&str, &[&str])]) -> SiteState {
+        let mut builder = SiteStateBuilder::new();
+        for (path, redirects) in pages {
+            builder.add_page(
+                Page {
+                    title: (*path).to_owned(),
+                    path: (*path).to_owned(),
+                    has_content: true,
+                    redirects: (!redirects.is_empty())
+                        .then(|| redirects.iter().map(|&r| r.to_owned()).collect()),
+                    ..Default::default()
+                },
+                None,
+                None,
+            );
+        }
+        builder.build()
+    }
+
+    #[test]
+    fn resolve_redirect_maps_former_path_to_page() {
+        let site = site_with_redirects(&[("guides/setup", &["old/setup", "/install/"])]);
+
+        assert_eq!(site.resolve_redirect("old/setup"), Some("guides/setup"));
+        assert_eq!(site.resolve_redirect("install"), Some("guides/setup"));
+        assert_eq!(site.resolve_redirect("/install/"), Some("guides/setup"));
+        assert_eq!(site.resolve_redirect("guides/setup"), None);
+    }
+
+    #[test]
+    fn redirect_never_shadows_an_existing_page() {
+        // Two pages claiming each other's path would form a cycle; both are
+        // dropped because each source is a live page.
+        let site = site_with_redirects(&[("", &[]), ("a", &["b", "/"]), ("b", &["a"])]);
+
+        assert_eq!(site.resolve_redirect("a"), None);
+        assert_eq!(site.resolve_redirect("b"), None);
+        assert_eq!(site.resolve_redirect(""), None);
+    }
+
+    #[test]
+    fn duplicate_redirect_source_keeps_the_first() {
+        let site = site_with_redirects(&[("a", &["old"]), ("b", &["old"])]);
+
+        assert_eq!(site.resolve_redirect("old"), Some("a"));
+    }
+
+    #[test]
+    fn redirects_survive_cache_roundtrip() {
+        let fresh = site_with_redirects(&[("guide", &["legacy"])]);
+
+        let json = serde_json::to_string(&CachedSiteStateRef::from(&fresh)).unwrap();
+        let cached: CachedSiteState = serde_json::from_str(&json).unwrap();
+        let reloaded: SiteState = cached.into();
+
+        assert_eq!(reloaded.resolve_redirect("legacy"), Some("guide"));
+    }
 }
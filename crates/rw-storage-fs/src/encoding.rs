@@ -0,0 +1,127 @@
+//! Decoding of source files into text.
+//!
+//! Every markdown and metadata read goes through [`read_source`], so a file
+//! saved by a Windows editor reads the same as one saved anywhere else: a
+//! UTF-8 byte order mark is dropped, and a UTF-16 file (Notepad's "Unicode")
+//! is decoded by its byte order mark. Anything else that is not UTF-8 is an
+//! error naming the problem, never a lossy read.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+const UTF16_LE_BOM: &[u8] = b"\xFF\xFE";
+const UTF16_BE_BOM: &[u8] = b"\xFE\xFF";
+
+/// Read a source file as text.
+///
+/// # Errors
+///
+/// Returns the underlying I/O error, or [`io::ErrorKind::InvalidData`] when the
+/// content is neither UTF-8 nor UTF-16 with a byte order mark.
+pub(crate) fn read_source(path: &Path) -> io::Result<String> {
+    decode(&fs::read(path)?)
+}
+
+/// Decode file bytes; see [`read_source`].
+fn decode(bytes: &[u8]) -> io::Result<String> {
+    if let Some(rest) = bytes.strip_prefix(UTF16_LE_BOM) {
+        return decode_utf16(rest, u16::from_le_bytes);
+    }
+    if let Some(rest) = bytes.strip_prefix(UTF16_BE_BOM) {
+        return decode_utf16(rest, u16::from_be_bytes);
+    }
+
+    // Offsets are reported against the file as saved, BOM included.
+    let (bom_len, text) = match bytes.strip_prefix(UTF8_BOM) {
+        Some(rest) => (UTF8_BOM.len(), rest),
+        None => (0, bytes),
+    };
+    String::from_utf8(text.to_vec()).map_err(|e| {
+        invalid_data(format!(
+            "not valid UTF-8 (first invalid byte at offset {}); re-save the file as UTF-8",
+            bom_len + e.utf8_error().valid_up_to()
+        ))
+    })
+}
+
+fn decode_utf16(bytes: &[u8], unit: fn([u8; 2]) -> u16) -> io::Result<String> {
+    let (pairs, remainder) = bytes.as_chunks::<2>();
+    if !remainder.is_empty() {
+        return Err(invalid_data(
+            "truncated UTF-16 (odd byte count); re-save the file as UTF-8".to_owned(),
+        ));
+    }
+    let units: Vec<u16> = pairs.iter().map(|&pair| unit(pair)).collect();
+    String::from_utf16(&units).map_err(|_| {
+        invalid_data(
+            "not valid UTF-16 despite its byte order mark; re-save the file as UTF-8".to_owned(),
+        )
+    })
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_utf8_is_unchanged() {
+        assert_eq!(decode("# Título".as_bytes()).unwrap(), "# Título");
+    }
+
+    #[test]
+    fn utf8_bom_is_stripped() {
+        assert_eq!(decode(b"\xEF\xBB\xBF# Title").unwrap(), "# Title");
+    }
+
+    #[test]
+    fn only_a_leading_bom_is_stripped() {
+        let text = "a\u{FEFF}b";
+        assert_eq!(decode(text.as_bytes()).unwrap(), text);
+    }
+
+    #[test]
+    fn utf16_is_decoded_by_its_bom() {
+        let le: Vec<u8> = UTF16_LE_BOM
+            .iter()
+            .copied()
+            .chain("# Tïtle".encode_utf16().flat_map(u16::to_le_bytes))
+            .collect();
+        let be: Vec<u8> = UTF16_BE_BOM
+            .iter()
+            .copied()
+            .chain("# Tïtle".encode_utf16().flat_map(u16::to_be_bytes))
+            .collect();
+
+        assert_eq!(decode(&le).unwrap(), "# Tïtle");
+        assert_eq!(decode(&be).unwrap(), "# Tïtle");
+    }
+
+    #[test]
+    fn legacy_encoding_is_a_clear_error() {
+        // "café" in Windows-1252.
+        let err = decode(b"caf\xE9").unwrap_err();
+
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(err.to_string().contains("not valid UTF-8"), "{err}");
+        assert!(err.to_string().contains("offset 3"), "{err}");
+    }
+
+    #[test]
+    fn invalid_utf8_offset_counts_the_bom() {
+        let err = decode(b"\xEF\xBB\xBFcaf\xE9").unwrap_err();
+
+        assert!(err.to_string().contains("offset 6"), "{err}");
+    }
+
+    #[test]
+    fn truncated_utf16_is_an_error() {
+        let err = decode(b"\xFF\xFE#\x00T").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
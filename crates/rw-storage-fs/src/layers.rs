@@ -0,0 +1,88 @@
+//! Storage over several source directories.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use rw_storage::{MergedStorage, Storage};
+
+use crate::FsStorage;
+
+/// Build the storage for a list of source directories, merged into one site.
+///
+/// `layer` builds the [`FsStorage`] for one directory, given its position in
+/// the list. A single directory is served by its own storage; several are
+/// merged with [`MergedStorage`], where a page in an earlier directory wins
+/// over the same page in a later one.
+///
+/// Only the first directory keeps its homepage fallback. It stays a layer
+/// even when missing, so it picks up the directory once created; later
+/// directories that do not exist are skipped with a warning.
+pub fn source_dirs_storage(
+    source_dirs: &[PathBuf],
+    mut layer: impl FnMut(usize, &Path) -> FsStorage,
+) -> Arc<dyn Storage> {
+    let [first, rest @ ..] = source_dirs else {
+        return Arc::new(MergedStorage::new());
+    };
+    let primary = layer(0, first);
+    if rest.is_empty() {
+        return Arc::new(primary);
+    }
+
+    if !first.is_dir() {
+        tracing::warn!(dir = %first.display(), "source directory does not exist");
+    }
+    let mut merged =
+        MergedStorage::new().with_layer(first.display().to_string(), Arc::new(primary));
+    for (i, dir) in rest.iter().enumerate() {
+        if !dir.is_dir() {
+            tracing::warn!(dir = %dir.display(), "source directory does not exist, skipping");
+            continue;
+        }
+        let storage = layer(i + 1, dir).with_homepage(Vec::new());
+        merged = merged.with_layer(dir.display().to_string(), Arc::new(storage));
+    }
+    Arc::new(merged)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    #[test]
+    fn earlier_directory_wins() {
+        let dir = tempfile::tempdir().unwrap();
+        let (docs, shared) = (dir.path().join("docs"), dir.path().join("shared"));
+        fs::create_dir_all(&docs).unwrap();
+        fs::create_dir_all(&shared).unwrap();
+        fs::write(docs.join("guide.md"), "# Local").unwrap();
+        fs::write(shared.join("guide.md"), "# Shared").unwrap();
+        fs::write(shared.join("extra.md"), "# Extra").unwrap();
+
+        let storage = source_dirs_storage(&[docs, shared], |_, source_dir| {
+            FsStorage::new(dir.path().to_path_buf(), source_dir.to_path_buf())
+        });
+
+        assert_eq!(storage.read("guide").unwrap(), "# Local");
+        assert_eq!(storage.read("extra").unwrap(), "# Extra");
+    }
+
+    #[test]
+    fn missing_later_directory_is_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        let docs = dir.path().join("docs");
+        fs::create_dir_all(&docs).unwrap();
+        fs::write(docs.join("guide.md"), "# Guide").unwrap();
+
+        let mut layers = Vec::new();
+        let storage = source_dirs_storage(&[docs, dir.path().join("missing")], |i, source_dir| {
+            layers.push(i);
+            FsStorage::new(dir.path().to_path_buf(), source_dir.to_path_buf())
+        });
+
+        assert_eq!(layers, [0]);
+        assert_eq!(storage.read("guide").unwrap(), "# Guide");
+    }
+}
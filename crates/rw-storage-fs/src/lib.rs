@@ -1,10 +1,12 @@
 //! Filesystem storage implementation for RW documentation engine.
 //!
 //! This crate provides [`FsStorage`], a filesystem-based implementation of the
-//! [`Storage`](rw_storage::Storage) trait. It handles:
+//! [`Storage`](rw_storage::Storage) trait, and [`source_dirs_storage`], which
+//! merges one per source directory. It handles:
 //!
 //! - Recursive directory scanning for markdown files
-//! - Metadata extraction (title, description, kind) with mtime caching
+//! - Metadata extraction (title, description, kind) with mtime caching,
+//!   optionally persisted across restarts
 //! - Metadata loading from YAML sidecar files
 //! - File watching with event debouncing
 //!
@@ -26,28 +28,38 @@
 //! ```
 
 mod debouncer;
+mod encoding;
+mod layers;
+mod rwignore;
 mod scanner;
 mod source;
-use parking_lot::RwLock;
+use parking_lot::{RwLock, RwLockWriteGuard};
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc;
 use std::time::{Duration, Instant, SystemTime};
 
 use glob::Pattern;
 use notify::{RecommendedWatcher, RecursiveMode, Watcher};
 use rayon::prelude::*;
+use rw_cache::{CacheBucket, CacheBucketExt};
 use rw_meta::Meta;
 use rw_sections::Namespace;
 use rw_vcs::{Vcs, fs_mtime};
+use serde::{Deserialize, Serialize};
+
+pub use layers::source_dirs_storage;
 
 use debouncer::{DebouncedEvent, EventDebouncer, RawEventKind};
+use encoding::read_source;
 use rw_storage::{
     Document, Metadata, MetadataError, Storage, StorageError, StorageErrorKind, StorageEvent,
     StorageEventKind, StorageEventReceiver, WatchHandle,
 };
-use scanner::{DocumentRef, Scanner};
+use rwignore::{IGNORE_FILENAME, IgnoreCache};
+use scanner::{DocumentRef, Scanner, ShadowedFile};
 use source::{Classification, PathResolver, file_path_to_url};
 
 /// Backend identifier for error messages.
@@ -65,8 +77,18 @@ fn storage_event_kind(kind: notify::EventKind) -> Option<RawEventKind> {
     }
 }
 
+/// Cache key of the persisted metadata snapshot.
+const META_CACHE_KEY: &str = "mtime";
+
+/// Format version of the persisted snapshot, used as its etag.
+///
+/// Bump when [`CachedMeta`] or [`Meta`] changes shape, or metadata resolves
+/// differently, so a snapshot written by an older build is ignored instead of
+/// misread or served stale.
+const META_CACHE_FORMAT: &str = "4";
+
 /// Cached resolved metadata for incremental extraction.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 struct CachedMeta {
     /// Markdown file modification time.
     md_mtime: SystemTime,
@@ -76,6 +98,15 @@ struct CachedMeta {
     meta: Meta,
 }
 
+/// What [`FsStorage::build_document`] made of one discovered file.
+enum Built {
+    Document(Document),
+    /// Nothing to serve, e.g. an empty meta.yaml.
+    Nothing,
+    /// A markdown file that is not text; carries the scan warning.
+    Unreadable(String),
+}
+
 /// Filesystem storage implementation.
 ///
 /// Scans a source directory recursively for markdown files and extracts
@@ -102,15 +133,22 @@ pub struct FsStorage {
     /// Cloned into the watch drain thread, which resolves through the same rules.
     resolver: PathResolver,
     /// Project root — see `rw_config::Config::project_dir`. Stored for git
-    /// repository discovery in [`FsStorage::with_mtime_source`]; the `README.md`
-    /// homepage candidate is baked into `resolver` at construction and does not
-    /// read this field.
+    /// repository discovery in [`FsStorage::with_mtime_source`]; the default
+    /// `README.md` homepage candidate is baked into `resolver` at construction
+    /// and does not read this field.
     project_dir: PathBuf,
     /// Scanner for document discovery.
     scanner: Scanner,
     /// Mtime cache for incremental metadata extraction.
     mtime_cache: RwLock<HashMap<PathBuf, CachedMeta>>,
-    /// Glob patterns for file watching (`**/*.md` and metadata files).
+    /// Where `mtime_cache` is persisted between runs, if anywhere.
+    meta_cache: Option<Box<dyn CacheBucket>>,
+    /// Whether `mtime_cache` changed since it was last persisted.
+    mtime_cache_dirty: AtomicBool,
+    /// Warnings from the most recent `scan()`, see [`Storage::scan_warnings`].
+    scan_warnings: RwLock<Vec<String>>,
+    /// Glob patterns for file watching (`**/*.<ext>` per content extension and
+    /// metadata files).
     watch_patterns: Vec<Pattern>,
     /// How this storage computes modification times (filesystem or git).
     mtime: MtimeStrategy,
@@ -166,11 +204,13 @@ impl FsStorage {
     /// Create a new filesystem storage with a custom metadata filename.
     ///
     /// Watches `**/*.md`, `**/{meta_filename}`, and `**/*.{meta_filename}` files for changes.
+    /// Call [`with_extensions`](Self::with_extensions) to recognize content
+    /// files other than `.md`.
     ///
     /// # Arguments
     ///
-    /// * `project_dir` - Project root; the `README.md` homepage fallback and
-    ///   git repository discovery are resolved from it
+    /// * `project_dir` - Project root; the default `README.md` homepage
+    ///   fallback and git repository discovery are resolved from it
     /// * `source_dir` - Root directory containing markdown files
     /// * `meta_filename` - Name of metadata files (e.g., "meta.yaml")
     ///
@@ -182,8 +222,9 @@ impl FsStorage {
         source_dir: PathBuf,
         meta_filename: &str,
     ) -> Self {
-        let scanner = Scanner::new(&source_dir, meta_filename);
         let resolver = PathResolver::new(&project_dir, source_dir, meta_filename);
+        let scanner = Scanner::new(resolver.source_dir(), meta_filename)
+            .with_ignore(std::sync::Arc::clone(resolver.ignore()));
 
         Self {
             watch_patterns: resolver.watch_patterns(),
@@ -191,10 +232,129 @@ impl FsStorage {
             resolver,
             project_dir,
             mtime_cache: RwLock::new(HashMap::new()),
+            meta_cache: None,
+            mtime_cache_dirty: AtomicBool::new(false),
+            scan_warnings: RwLock::new(Vec::new()),
             mtime: MtimeStrategy::Filesystem,
         }
     }
 
+    /// Replaces the recognized content file extensions (default `["md"]`).
+    ///
+    /// Entries are given without the leading dot, in precedence order: when
+    /// one url path has files in several extensions, the earliest listed
+    /// wins, and any `index.<ext>` still wins over a standalone `<name>.<ext>`.
+    /// Scanning, `read()` resolution, and the watch patterns all follow the
+    /// list. An empty list is ignored; the caller validates configuration.
+    #[must_use]
+    pub fn with_extensions(mut self, extensions: &[String]) -> Self {
+        if extensions.is_empty() {
+            return self;
+        }
+        self.scanner = self.scanner.with_extensions(extensions.to_vec());
+        self.resolver = self.resolver.with_extensions(extensions.to_vec());
+        self.watch_patterns = self.resolver.watch_patterns();
+        self
+    }
+
+    /// Resolve URL paths case-insensitively when the exact path misses
+    /// (default off).
+    ///
+    /// `read`, `exists`, `meta` and `mtime` for `Guide` then find `guide.md`
+    /// on a case-sensitive filesystem, as they would on macOS, and log a
+    /// warning so the link can be fixed. An exact match always wins, and a
+    /// path whose case-folded form matches several entries resolves to
+    /// nothing. Scanning is unaffected: document paths keep the case the
+    /// files have on disk.
+    #[must_use]
+    pub fn with_case_insensitive(mut self, enabled: bool) -> Self {
+        self.resolver = self.resolver.with_case_insensitive(enabled);
+        self
+    }
+
+    /// Persist resolved metadata in `bucket` across restarts.
+    ///
+    /// Loads the snapshot a previous run left in `bucket`, so the first scan
+    /// reads only files changed since then; every entry is still validated by
+    /// mtime before use. Each [`scan`](Storage::scan) that resolved something
+    /// new writes the snapshot back. A missing, corrupt or older-format
+    /// snapshot is ignored. Pass a bucket from
+    /// [`NullCache`](rw_cache::NullCache) (or skip this call) to keep the cache
+    /// in memory only.
+    #[must_use]
+    pub fn with_meta_cache(mut self, bucket: Box<dyn CacheBucket>) -> Self {
+        if let Some(entries) =
+            bucket.get_json::<HashMap<PathBuf, CachedMeta>>(META_CACHE_KEY, META_CACHE_FORMAT)
+        {
+            tracing::debug!(entries = entries.len(), "Loaded persisted metadata cache");
+            *self.mtime_cache.get_mut() = entries;
+        }
+        self.meta_cache = Some(bucket);
+        self
+    }
+
+    /// Write `mtime_cache` to the persistent bucket if it changed.
+    ///
+    /// Entries for files that no longer exist are dropped first, so the
+    /// snapshot does not grow with every deleted or renamed page. The
+    /// filesystem checks run without holding the lock, so concurrent reads
+    /// are not stalled behind one `stat` per cached file.
+    fn persist_meta_cache(&self) {
+        let Some(bucket) = &self.meta_cache else {
+            return;
+        };
+        if !self.mtime_cache_dirty.swap(false, Ordering::Relaxed) {
+            return;
+        }
+        let paths: Vec<PathBuf> = self.mtime_cache.read().keys().cloned().collect();
+        let stale: Vec<PathBuf> = paths.into_iter().filter(|path| !path.exists()).collect();
+        let mut cache = self.mtime_cache.write();
+        for path in &stale {
+            cache.remove(path);
+        }
+        let cache = RwLockWriteGuard::downgrade(cache);
+        bucket.set_json(META_CACHE_KEY, META_CACHE_FORMAT, &*cache);
+    }
+
+    /// Log and describe a content file that another file shadows.
+    ///
+    /// Paths are shown relative to `source_dir`, as authors see them.
+    fn shadowed_warning(&self, file: &ShadowedFile) -> String {
+        let source_dir = self.resolver.source_dir();
+        let relative = |path: &Path| {
+            path.strip_prefix(source_dir)
+                .unwrap_or(path)
+                .display()
+                .to_string()
+        };
+        let used = relative(&file.used);
+        let ignored = relative(&file.ignored);
+        tracing::warn!(
+            url_path = %file.url_path,
+            used = %used,
+            ignored = %ignored,
+            "Two source files map to the same URL path, ignoring one"
+        );
+        format!(
+            "{ignored} is ignored: {used} also maps to /{}",
+            file.url_path
+        )
+    }
+
+    /// Replaces the homepage fallback chain (default: `README.md` in
+    /// `project_dir`).
+    ///
+    /// When `source_dir` has no `index.<ext>`, the first candidate that exists
+    /// serves as the homepage: `read("")`, `scan()`'s root document and live
+    /// reload all follow it. Paths are used as given, so resolve relative ones
+    /// against the project root first (as `rw_config` does). An empty list
+    /// disables the fallback.
+    #[must_use]
+    pub fn with_homepage(mut self, candidates: Vec<PathBuf>) -> Self {
+        self.resolver = self.resolver.with_homepage(candidates);
+        self
+    }
+
     /// Selects the modification-time source (default
     /// [`Filesystem`](MtimeSource::Filesystem)).
     ///
@@ -247,9 +407,11 @@ impl FsStorage {
     /// Converts discovery results (file references) into full Document structs
     /// by reading file contents and extracting titles/metadata.
     ///
-    /// Returns `Ok(None)` if the ref produces no valid document (e.g., empty meta.yaml
-    /// for a virtual page). Returns `Err` if the namespace declared in metadata is invalid.
-    fn build_document(&self, doc_ref: &DocumentRef) -> Result<Option<Document>, StorageError> {
+    /// Returns [`Built::Nothing`] if the ref produces no valid document (e.g.,
+    /// empty meta.yaml for a virtual page), and [`Built::Unreadable`] for a
+    /// content file that is not text. Returns `Err` if the namespace declared in
+    /// metadata is invalid.
+    fn build_document(&self, doc_ref: &DocumentRef) -> Result<Built, StorageError> {
         let validate = |meta: &Meta, file: &Path| -> Result<(), StorageError> {
             if let Some(ns) = &meta.namespace {
                 ns.parse::<Namespace>().map_err(|e| {
@@ -263,11 +425,16 @@ impl FsStorage {
         };
 
         if let Some(md_path) = &doc_ref.content_path {
+            // The stem, so a `.markdown` page falls back to the same title
+            // its `.md` twin would.
             let name_lower = md_path
-                .file_name()
+                .file_stem()
                 .map_or(String::new(), |n| n.to_string_lossy().to_lowercase());
 
-            let meta = self.get_meta(md_path, doc_ref.meta_path.as_deref(), &name_lower);
+            let meta = match self.get_meta(md_path, doc_ref.meta_path.as_deref(), &name_lower) {
+                Ok(meta) => meta,
+                Err(e) => return Ok(Built::Unreadable(self.unreadable_warning(md_path, &e))),
+            };
 
             // Namespace declarations almost always live in the sidecar
             // meta.yaml; attribute validation errors there when one exists,
@@ -278,7 +445,7 @@ impl FsStorage {
             let validation_file = doc_ref.meta_path.as_deref().unwrap_or(md_path);
             validate(&meta, validation_file)?;
 
-            Ok(Some(Document {
+            Ok(Built::Document(Document {
                 path: doc_ref.url_path.clone(),
                 title: meta.title,
                 has_content: true,
@@ -287,15 +454,17 @@ impl FsStorage {
                 description: meta.description,
                 origin: None,
                 pages: meta.pages,
-                is_dir: name_lower == "index.md",
+                order: meta.order,
+                redirects: meta.redirects,
+                is_dir: name_lower == "index",
             }))
         } else if let Some(meta_path) = &doc_ref.meta_path {
-            let Ok(meta_yaml) = fs::read_to_string(meta_path) else {
-                return Ok(None);
+            let Ok(meta_yaml) = read_source(meta_path) else {
+                return Ok(Built::Nothing);
             };
 
             if meta_yaml.trim().is_empty() {
-                return Ok(None);
+                return Ok(Built::Nothing);
             }
 
             let dir_name = Path::new(&doc_ref.url_path)
@@ -306,7 +475,7 @@ impl FsStorage {
 
             validate(&meta, meta_path)?;
 
-            Ok(Some(Document {
+            Ok(Built::Document(Document {
                 path: doc_ref.url_path.clone(),
                 title: meta.title,
                 has_content: false,
@@ -315,19 +484,41 @@ impl FsStorage {
                 description: meta.description,
                 origin: None,
                 pages: meta.pages,
+                order: meta.order,
+                redirects: meta.redirects,
                 is_dir: true,
             }))
         } else {
-            Ok(None)
+            Ok(Built::Nothing)
         }
     }
 
+    /// Log and describe a content file that cannot be decoded as text.
+    fn unreadable_warning(&self, file: &Path, error: &std::io::Error) -> String {
+        let relative = file
+            .strip_prefix(self.resolver.source_dir())
+            .unwrap_or(file)
+            .display()
+            .to_string();
+        tracing::warn!(file = %relative, error = %error, "Skipping a source file that is not text");
+        format!("{relative} is skipped: {error}")
+    }
+
     /// Get resolved metadata for a file, using mtime cache when possible.
     ///
     /// Only reads the markdown file content on cache miss, avoiding unnecessary
     /// I/O for unchanged files during scans. Invalidates when either the markdown
     /// file or its associated meta.yaml changes.
-    fn get_meta(&self, file_path: &Path, meta_path: Option<&Path>, filename: &str) -> Meta {
+    ///
+    /// Returns the decoding error for a markdown file that is not text (binary
+    /// content saved with a markdown extension). Such a file is never cached,
+    /// so every scan reports it until it is fixed.
+    fn get_meta(
+        &self,
+        file_path: &Path,
+        meta_path: Option<&Path>,
+        filename: &str,
+    ) -> std::io::Result<Meta> {
         let current_md_mtime = fs::metadata(file_path).ok().and_then(|m| m.modified().ok());
         let current_meta_mtime = meta_path
             .and_then(|p| fs::metadata(p).ok())
@@ -340,13 +531,19 @@ impl FsStorage {
                 && cached.md_mtime == md_mtime
                 && cached.meta_mtime == current_meta_mtime
             {
-                return cached.meta.clone();
+                return Ok(cached.meta.clone());
             }
         }
 
-        // Cache miss — read file content now
-        let markdown = fs::read_to_string(file_path).ok();
-        let meta_yaml = meta_path.and_then(|p| fs::read_to_string(p).ok());
+        // Cache miss — read file content now. A file that vanished since the
+        // walk falls back to its filename title; one that is not text is an
+        // error.
+        let markdown = match read_source(file_path) {
+            Ok(markdown) => Some(markdown),
+            Err(e) if e.kind() == std::io::ErrorKind::InvalidData => return Err(e),
+            Err(_) => None,
+        };
+        let meta_yaml = meta_path.and_then(|p| read_source(p).ok());
         let meta = Meta::resolve(markdown.as_deref(), meta_yaml.as_deref(), filename);
 
         // Update cache
@@ -360,9 +557,10 @@ impl FsStorage {
                     meta: meta.clone(),
                 },
             );
+            self.mtime_cache_dirty.store(true, Ordering::Relaxed);
         }
 
-        meta
+        Ok(meta)
     }
 
     /// URL paths of the existing page(s) a markdown source file could refer to.
@@ -378,12 +576,13 @@ impl FsStorage {
         self.resolver.url_paths_for_source(file_path)
     }
 
-    /// Set up a file watcher for README.md (outside `source_dir`).
+    /// Set up a file watcher for a homepage candidate outside `source_dir`
+    /// (`README.md` by default).
     ///
-    /// Watches the README.md file directly. Events are recorded into the
-    /// shared debouncer.
-    fn watch_readme(
-        readme_path: &Path,
+    /// Watches the file directly. Events are recorded into the shared
+    /// debouncer.
+    fn watch_homepage_file(
+        path: &Path,
         debouncer: &std::sync::Arc<EventDebouncer>,
     ) -> Result<notify::RecommendedWatcher, StorageError> {
         let debouncer = std::sync::Arc::clone(debouncer);
@@ -407,7 +606,7 @@ impl FsStorage {
             })?;
 
         watcher
-            .watch(readme_path, RecursiveMode::NonRecursive)
+            .watch(path, RecursiveMode::NonRecursive)
             .map_err(|e| {
                 StorageError::new(StorageErrorKind::Other)
                     .with_backend(BACKEND)
@@ -433,12 +632,10 @@ impl FsStorage {
 fn resolve_event_meta(resolver: &PathResolver, url_path: &str) -> Meta {
     let meta_yaml = resolver
         .resolve_meta(url_path)
-        .and_then(|p| fs::read_to_string(p).ok());
+        .and_then(|p| read_source(&p).ok());
 
     let content_path = resolver.resolve_content(url_path);
-    let markdown = content_path
-        .as_deref()
-        .and_then(|p| fs::read_to_string(p).ok());
+    let markdown = content_path.as_deref().and_then(|p| read_source(p).ok());
 
     let fallback = resolver.content_fallback_name(url_path, content_path.as_deref());
 
@@ -458,6 +655,57 @@ fn is_hidden_rel_path(rel_path: &std::path::Path) -> bool {
     })
 }
 
+/// Record a changed `path` under `source_dir` with `debouncer`, unless the
+/// scan would not see it either.
+fn record_watched_path(
+    debouncer: &EventDebouncer,
+    source_dir: &Path,
+    ignore: &IgnoreCache,
+    patterns: &[Pattern],
+    path: PathBuf,
+    kind: RawEventKind,
+) {
+    let Ok(rel_path) = path.strip_prefix(source_dir) else {
+        return;
+    };
+
+    // An edited `.rwignore` can add or remove any page below its directory:
+    // drop its parsed copy and report the directory so it is rescanned.
+    if rel_path.file_name().is_some_and(|n| n == IGNORE_FILENAME) {
+        if let Some(dir) = path.parent() {
+            ignore.invalidate(dir);
+            debouncer.record(dir.to_path_buf(), RawEventKind::Created);
+        }
+        return;
+    }
+
+    // A directory moved into place brings `.rwignore` files that raised no
+    // event of their own.
+    let is_dir = path.is_dir();
+    if is_dir {
+        ignore.invalidate(&path);
+    }
+
+    // Mirror the scanner's hidden-file and `.rwignore` filtering so an
+    // excluded file (e.g. `.meta.yaml`) never produces an event the scan
+    // would not.
+    if is_hidden_rel_path(rel_path) || ignore.is_ignored(&path, is_dir) {
+        return;
+    }
+
+    // Directory events (e.g., renames) signal structural changes that must
+    // trigger a rescan.
+    let matches_pattern = patterns.is_empty()
+        || is_dir
+        || patterns
+            .iter()
+            .any(|pattern| pattern.matches_path(rel_path));
+
+    if matches_pattern {
+        debouncer.record(path, kind);
+    }
+}
+
 /// Convert a debounced file-system event into a [`StorageEvent`].
 ///
 /// Resolves the file path to a URL path and populates the event kind with
@@ -472,7 +720,7 @@ fn to_storage_event(event: &DebouncedEvent, resolver: &PathResolver) -> StorageE
             .classify_relpath(rel_path, &filename)
             .map_or_else(|| file_path_to_url(rel_path), Classification::into_url_path)
     } else {
-        // Outside source_dir (e.g., README.md) -> root
+        // Outside source_dir (a homepage candidate, e.g. README.md) -> root
         String::new()
     };
 
@@ -483,6 +731,8 @@ fn to_storage_event(event: &DebouncedEvent, resolver: &PathResolver) -> StorageE
             StorageEventKind::Modified {
                 title: meta.title,
                 pages: meta.pages,
+                order: meta.order,
+                redirects: meta.redirects,
             }
         }
         RawEventKind::Removed => StorageEventKind::Removed,
@@ -531,15 +781,27 @@ fn try_upgrade_recursive_watch(
 
 impl Storage for FsStorage {
     fn scan(&self) -> Result<Vec<Document>, StorageError> {
+        // A full scan re-reads every `.rwignore` through the walker; drop the
+        // parsed copies too, so lookups agree with it even without a watcher.
+        self.resolver.ignore().clear();
         let t0 = Instant::now();
-        let refs = self.scanner.scan();
+        let (refs, shadowed) = self.scanner.scan();
         let walk_elapsed = t0.elapsed();
 
         let t1 = Instant::now();
-        let mut documents: Vec<Document> = refs
+        let built = refs
             .par_iter()
-            .filter_map(|r| self.build_document(r).transpose())
+            .map(|r| self.build_document(r))
             .collect::<Result<Vec<_>, _>>()?;
+        let mut documents = Vec::with_capacity(built.len());
+        let mut unreadable = Vec::new();
+        for outcome in built {
+            match outcome {
+                Built::Document(doc) => documents.push(doc),
+                Built::Unreadable(warning) => unreadable.push(warning),
+                Built::Nothing => {}
+            }
+        }
         let build_elapsed = t1.elapsed();
 
         tracing::info!(
@@ -551,7 +813,15 @@ impl Storage for FsStorage {
             "Storage scan complete"
         );
 
-        // Inject README.md as homepage if no root document found
+        self.persist_meta_cache();
+        *self.scan_warnings.write() = shadowed
+            .iter()
+            .map(|file| self.shadowed_warning(file))
+            .chain(unreadable)
+            .collect();
+
+        // Inject the first existing homepage candidate (README.md by default)
+        // if no root document found
         if !documents.iter().any(|d| d.path.is_empty())
             && let Some(meta) = self.resolver.homepage_fallback_meta()
         {
@@ -561,28 +831,53 @@ impl Storage for FsStorage {
                 .file_name()
                 .and_then(|n| n.to_str())
                 .map(ToOwned::to_owned);
-            documents.push(Document {
-                path: String::new(),
-                title: meta.title,
-                has_content: true,
-                page_kind: None,
-                namespace: None,
-                description: None,
-                origin,
-                pages: None,
-                is_dir: true,
-            });
+            // Root sorts first; keep the scan's URL path order.
+            documents.insert(
+                0,
+                Document {
+                    path: String::new(),
+                    title: meta.title,
+                    has_content: true,
+                    page_kind: None,
+                    namespace: None,
+                    description: None,
+                    origin,
+                    pages: None,
+                    order: None,
+                    redirects: None,
+                    is_dir: true,
+                },
+            );
         }
 
         Ok(documents)
     }
 
+    /// Reads the one directory `path` maps to instead of walking the whole
+    /// source tree. The README homepage is the root itself, never a child, so
+    /// unlike `scan` nothing is injected.
+    fn list(&self, path: &str) -> Result<Vec<Document>, StorageError> {
+        Self::validate_path(path)?;
+        let mut documents: Vec<Document> = self
+            .scanner
+            .scan_children(path)
+            .iter()
+            .filter_map(|r| match self.build_document(r) {
+                Ok(Built::Document(doc)) => Some(Ok(doc)),
+                Ok(Built::Unreadable(_) | Built::Nothing) => None,
+                Err(e) => Some(Err(e)),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        documents.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(documents)
+    }
+
     fn read(&self, path: &str) -> Result<String, StorageError> {
         Self::validate_path(path)?;
         let full_path = self
             .resolve_content(path)
             .ok_or_else(|| StorageError::not_found(path).with_backend(BACKEND))?;
-        fs::read_to_string(&full_path)
+        read_source(&full_path)
             .map_err(|e| StorageError::io(e, Some(PathBuf::from(path))).with_backend(BACKEND))
     }
 
@@ -629,6 +924,7 @@ impl Storage for FsStorage {
 
         // Setup notify watcher
         let source_dir = self.resolver.source_dir().to_path_buf();
+        let ignore = std::sync::Arc::clone(self.resolver.ignore());
         let patterns = self.watch_patterns.clone();
         let debouncer_for_watcher = std::sync::Arc::clone(&debouncer);
 
@@ -640,28 +936,14 @@ impl Storage for FsStorage {
                     };
 
                     for path in event.paths {
-                        let Ok(rel_path) = path.strip_prefix(&source_dir) else {
-                            continue;
-                        };
-
-                        // Mirror the scanner's hidden-file filtering so a hidden
-                        // file (e.g. `.meta.yaml`) never produces an event the
-                        // scan would not.
-                        if is_hidden_rel_path(rel_path) {
-                            continue;
-                        }
-
-                        // Directory events (e.g., renames) signal structural
-                        // changes that must trigger a rescan.
-                        let matches_pattern = patterns.is_empty()
-                            || path.is_dir()
-                            || patterns
-                                .iter()
-                                .any(|pattern| pattern.matches_path(rel_path));
-
-                        if matches_pattern {
-                            debouncer_for_watcher.record(path, kind);
-                        }
+                        record_watched_path(
+                            &debouncer_for_watcher,
+                            &source_dir,
+                            &ignore,
+                            &patterns,
+                            path,
+                            kind,
+                        );
                     }
                 }
             })
@@ -692,14 +974,18 @@ impl Storage for FsStorage {
         // Keep watcher alive in Arc
         let watcher = std::sync::Arc::new(parking_lot::Mutex::new(watcher));
 
-        // Set up a second watcher for README.md. The README lives in the parent
-        // of source_dir, so it falls outside the recursive watch above and needs
-        // its own non-recursive watcher.
-        let readme_watcher = self
+        // Set up a watcher per existing homepage candidate outside source_dir
+        // (README.md by default, which lives in the project root). Those fall
+        // outside the recursive watch above and need their own non-recursive
+        // watcher. All of them, not just the served one: deleting the first
+        // must surface so the next takes over.
+        let homepage_watchers = self
             .resolver
-            .existing_readme()
-            .map(|p| Self::watch_readme(p, &debouncer))
-            .transpose()?;
+            .homepage_candidates()
+            .iter()
+            .filter(|p| p.is_file() && !p.starts_with(self.resolver.source_dir()))
+            .map(|p| Self::watch_homepage_file(p, &debouncer))
+            .collect::<Result<Vec<_>, _>>()?;
 
         // Spawn thread to drain debouncer and send to channel
         let resolver_for_drain = self.resolver.clone();
@@ -707,7 +993,7 @@ impl Storage for FsStorage {
             // Own the watcher in this thread; the drain loop also locks it to
             // upgrade to a recursive watch once source_dir appears.
             let watcher_guard = watcher;
-            let _readme_watcher_guard = readme_watcher;
+            let _homepage_watchers_guard = homepage_watchers;
             // Whether a persistent recursive-watch upgrade failure was logged
             // already, so the warning is emitted at most once (not every tick).
             let mut upgrade_warned = false;
@@ -774,7 +1060,7 @@ impl Storage for FsStorage {
             return Ok(None);
         };
 
-        let content = match fs::read_to_string(&meta_path) {
+        let content = match read_source(&meta_path) {
             Ok(c) => c,
             Err(e) => {
                 tracing::warn!(
@@ -807,6 +1093,10 @@ impl Storage for FsStorage {
             }
         }
     }
+
+    fn scan_warnings(&self) -> Vec<String> {
+        self.scan_warnings.read().clone()
+    }
 }
 
 #[cfg(test)]
@@ -872,6 +1162,39 @@ mod tests {
         assert_eq!(storage.read("guide").unwrap(), "# Original H1\n\nBody.");
     }
 
+    #[test]
+    fn test_scan_warns_when_index_shadows_standalone() {
+        let temp_dir = create_test_dir();
+        fs::create_dir(temp_dir.path().join("domain")).unwrap();
+        fs::write(temp_dir.path().join("domain/index.md"), "# Domain").unwrap();
+        fs::write(temp_dir.path().join("domain.md"), "# Ignored").unwrap();
+        fs::write(temp_dir.path().join("guide.md"), "# Guide").unwrap();
+
+        let storage = FsStorage::new(temp_dir.path().to_path_buf(), temp_dir.path().to_path_buf());
+        assert!(storage.scan_warnings().is_empty(), "no scan yet");
+        let docs = storage.scan().unwrap();
+
+        let domain = docs.iter().find(|d| d.path == "domain").unwrap();
+        assert_eq!(domain.title, "Domain");
+        let warnings = storage.scan_warnings();
+        assert_eq!(warnings.len(), 1, "warnings: {warnings:?}");
+        let expected_index = Path::new("domain").join("index.md");
+        assert!(
+            warnings[0].starts_with("domain.md is ignored"),
+            "{warnings:?}"
+        );
+        assert!(
+            warnings[0].contains(&expected_index.display().to_string()),
+            "{warnings:?}"
+        );
+        assert!(warnings[0].ends_with("/domain"), "{warnings:?}");
+
+        // Resolving the collision clears the warning on the next scan.
+        fs::remove_file(temp_dir.path().join("domain.md")).unwrap();
+        storage.scan().unwrap();
+        assert!(storage.scan_warnings().is_empty());
+    }
+
     #[test]
     fn test_meta_directory_wins_over_sibling_on_collision() {
         let temp_dir = create_test_dir();
@@ -1070,6 +1393,22 @@ mod tests {
         assert_eq!(docs[0].title, "My Custom Title");
     }
 
+    #[test]
+    fn test_scan_extracts_title_from_frontmatter() {
+        let temp_dir = create_test_dir();
+        fs::write(
+            temp_dir.path().join("guide.md"),
+            "---\r\ntitle: \"Guide: Getting Started\"\r\n---\r\n\r\nContent.\r\n",
+        )
+        .unwrap();
+
+        let storage = FsStorage::new(temp_dir.path().to_path_buf(), temp_dir.path().to_path_buf());
+        let docs = storage.scan().unwrap();
+
+        assert_eq!(docs.len(), 1);
+        assert_eq!(docs[0].title, "Guide: Getting Started");
+    }
+
     #[test]
     fn test_scan_falls_back_to_filename() {
         let temp_dir = create_test_dir();
@@ -1086,6 +1425,49 @@ mod tests {
         assert_eq!(docs[0].title, "Setup Guide");
     }
 
+    #[test]
+    fn test_scan_zero_byte_file_is_an_empty_page() {
+        let temp_dir = create_test_dir();
+        fs::write(temp_dir.path().join("release-notes.md"), "").unwrap();
+
+        let storage = FsStorage::new(temp_dir.path().to_path_buf(), temp_dir.path().to_path_buf());
+        let docs = storage.scan().unwrap();
+
+        assert_eq!(docs.len(), 1);
+        assert_eq!(docs[0].title, "Release Notes");
+        assert!(docs[0].has_content);
+        assert_eq!(storage.read("release-notes").unwrap(), "");
+        assert!(storage.scan_warnings().is_empty());
+    }
+
+    #[test]
+    fn test_scan_skips_binary_file_with_warning() {
+        let temp_dir = create_test_dir();
+        fs::write(
+            temp_dir.path().join("logo.md"),
+            b"\x89PNG\r\n\x1a\n\x00\xff\xfe",
+        )
+        .unwrap();
+        fs::write(temp_dir.path().join("guide.md"), "# Guide").unwrap();
+
+        let storage = FsStorage::new(temp_dir.path().to_path_buf(), temp_dir.path().to_path_buf());
+        let docs = storage.scan().unwrap();
+
+        assert_eq!(docs.len(), 1);
+        assert_eq!(docs[0].path, "guide");
+        let warnings = storage.scan_warnings();
+        assert_eq!(warnings.len(), 1, "warnings: {warnings:?}");
+        assert!(
+            warnings[0].starts_with("logo.md is skipped: not valid UTF-8"),
+            "{warnings:?}"
+        );
+        assert_eq!(storage.list("").unwrap().len(), 1);
+
+        // Still reported on the next scan: unreadable files are not cached.
+        storage.scan().unwrap();
+        assert_eq!(storage.scan_warnings().len(), 1);
+    }
+
     #[test]
     fn test_scan_skips_hidden_files() {
         let temp_dir = create_test_dir();
@@ -1099,6 +1481,71 @@ mod tests {
         assert_eq!(docs[0].path, "visible");
     }
 
+    #[test]
+    fn test_rwignored_page_is_not_served() {
+        let temp_dir = create_test_dir();
+        let drafts_dir = temp_dir.path().join("drafts");
+        fs::create_dir(&drafts_dir).unwrap();
+        fs::write(temp_dir.path().join(".rwignore"), "drafts/\n").unwrap();
+        fs::write(drafts_dir.join("index.md"), "# Drafts").unwrap();
+        fs::write(drafts_dir.join("meta.yaml"), "title: Drafts").unwrap();
+        fs::write(temp_dir.path().join("guide.md"), "# Guide").unwrap();
+
+        let storage = FsStorage::new(temp_dir.path().to_path_buf(), temp_dir.path().to_path_buf());
+        let docs = storage.scan().unwrap();
+
+        assert_eq!(docs.len(), 1);
+        assert_eq!(docs[0].path, "guide");
+        assert!(!storage.exists("drafts"));
+        assert_eq!(
+            storage.read("drafts").unwrap_err().kind,
+            StorageErrorKind::NotFound
+        );
+        assert!(storage.meta("drafts").unwrap().is_none());
+        assert!(
+            storage
+                .url_paths_for_source(Path::new("drafts/index.md"))
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_scan_extracts_order() {
+        let temp_dir = create_test_dir();
+        fs::write(temp_dir.path().join("guide.md"), "# Guide").unwrap();
+        fs::write(temp_dir.path().join("guide.meta.yaml"), "weight: 3").unwrap();
+        let domain_dir = temp_dir.path().join("domain");
+        fs::create_dir(&domain_dir).unwrap();
+        fs::write(domain_dir.join("meta.yaml"), "title: Domain\norder: -1").unwrap();
+
+        let storage = FsStorage::new(temp_dir.path().to_path_buf(), temp_dir.path().to_path_buf());
+        let docs = storage.scan().unwrap();
+
+        let order = |path: &str| docs.iter().find(|d| d.path == path).unwrap().order;
+        assert_eq!(order("guide"), Some(3));
+        assert_eq!(order("domain"), Some(-1));
+        assert_eq!(storage.meta("guide").unwrap().unwrap().order, Some(3));
+    }
+
+    #[test]
+    fn test_scan_extracts_redirects() {
+        let temp_dir = create_test_dir();
+        fs::write(
+            temp_dir.path().join("guide.md"),
+            "---\nredirects: [old/guide, legacy]\n---\n# Guide",
+        )
+        .unwrap();
+
+        let storage = FsStorage::new(temp_dir.path().to_path_buf(), temp_dir.path().to_path_buf());
+        let docs = storage.scan().unwrap();
+
+        let guide = docs.iter().find(|d| d.path == "guide").unwrap();
+        assert_eq!(
+            guide.redirects,
+            Some(vec!["old/guide".to_owned(), "legacy".to_owned()])
+        );
+    }
+
     #[test]
     fn test_scan_extracts_page_kind() {
         let temp_dir = create_test_dir();
@@ -1139,6 +1586,142 @@ mod tests {
         assert_eq!(doc.page_kind, Some("section".to_owned()));
     }
 
+    #[test]
+    fn test_markdown_extension_standalone_page() {
+        let temp_dir = create_test_dir();
+        fs::write(temp_dir.path().join("release-notes.markdown"), "Body.").unwrap();
+
+        let storage = FsStorage::new(temp_dir.path().to_path_buf(), temp_dir.path().to_path_buf())
+            .with_extensions(&["md".to_owned(), "markdown".to_owned()]);
+        let docs = storage.scan().unwrap();
+
+        assert_eq!(docs.len(), 1);
+        assert_eq!(docs[0].path, "release-notes");
+        // Titled from the stem, not "Release Notes.markdown".
+        assert_eq!(docs[0].title, "Release Notes");
+        assert!(!docs[0].is_dir);
+        assert_eq!(storage.read("release-notes").unwrap(), "Body.");
+    }
+
+    #[test]
+    fn test_markdown_extension_ignored_by_default() {
+        let temp_dir = create_test_dir();
+        fs::write(temp_dir.path().join("guide.markdown"), "# Guide").unwrap();
+
+        let storage = FsStorage::new(temp_dir.path().to_path_buf(), temp_dir.path().to_path_buf());
+
+        assert!(storage.scan().unwrap().is_empty());
+        assert!(storage.read("guide").is_err());
+    }
+
+    /// Overwrite `path` and set its mtime to `mtime`.
+    fn write_with_mtime(path: &Path, contents: &str, mtime: SystemTime) {
+        fs::write(path, contents).unwrap();
+        fs::File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(mtime)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_meta_cache_persists_across_instances() {
+        use rw_cache::{Cache, FileCache};
+
+        let temp_dir = create_test_dir();
+        let docs = temp_dir.path().join("docs");
+        fs::create_dir(&docs).unwrap();
+        fs::write(docs.join("guide.md"), "# Original").unwrap();
+        let cache = FileCache::new(temp_dir.path().join("cache"), "v1");
+        let storage = || FsStorage::new(temp_dir.path().to_path_buf(), docs.clone());
+
+        storage()
+            .with_meta_cache(cache.bucket("meta"))
+            .scan()
+            .unwrap();
+        // Same mtime: only a cache that was really consulted can still
+        // report the old content.
+        let path = docs.join("guide.md");
+        let mtime = fs::metadata(&path).unwrap().modified().unwrap();
+        write_with_mtime(&path, "# Changed", mtime);
+
+        // A fresh instance trusts the snapshot because the mtime still matches.
+        let docs_cached = storage()
+            .with_meta_cache(cache.bucket("meta"))
+            .scan()
+            .unwrap();
+        assert_eq!(docs_cached[0].title, "Original");
+
+        let docs_uncached = storage().scan().unwrap();
+        assert_eq!(docs_uncached[0].title, "Changed");
+    }
+
+    #[test]
+    fn test_meta_cache_snapshot_is_revalidated_by_mtime() {
+        use rw_cache::{Cache, FileCache};
+
+        let temp_dir = create_test_dir();
+        fs::write(temp_dir.path().join("guide.md"), "# Original").unwrap();
+        let cache = FileCache::new(temp_dir.path().join(".cache"), "v1");
+        let storage =
+            || FsStorage::new(temp_dir.path().to_path_buf(), temp_dir.path().to_path_buf());
+
+        storage()
+            .with_meta_cache(cache.bucket("meta"))
+            .scan()
+            .unwrap();
+        let path = temp_dir.path().join("guide.md");
+        let later = fs::metadata(&path).unwrap().modified().unwrap() + Duration::from_secs(5);
+        write_with_mtime(&path, "# Edited", later);
+
+        let docs = storage()
+            .with_meta_cache(cache.bucket("meta"))
+            .scan()
+            .unwrap();
+        assert_eq!(docs[0].title, "Edited");
+    }
+
+    #[test]
+    fn test_meta_cache_ignores_corrupt_or_outdated_snapshot() {
+        use rw_cache::{Cache, FileCache};
+
+        let temp_dir = create_test_dir();
+        fs::write(temp_dir.path().join("guide.md"), "# Guide").unwrap();
+        let cache = FileCache::new(temp_dir.path().join(".cache"), "v1");
+        let storage =
+            || FsStorage::new(temp_dir.path().to_path_buf(), temp_dir.path().to_path_buf());
+
+        cache
+            .bucket("meta")
+            .set(META_CACHE_KEY, META_CACHE_FORMAT, b"{not json");
+        let docs = storage()
+            .with_meta_cache(cache.bucket("meta"))
+            .scan()
+            .unwrap();
+        assert_eq!(docs[0].title, "Guide");
+
+        // A snapshot in another format version is never read, even if valid.
+        let mut stale = HashMap::new();
+        stale.insert(
+            temp_dir.path().join("guide.md"),
+            CachedMeta {
+                md_mtime: fs::metadata(temp_dir.path().join("guide.md"))
+                    .unwrap()
+                    .modified()
+                    .unwrap(),
+                meta_mtime: None,
+                meta: Meta::resolve(Some("# Stale"), None, "guide"),
+            },
+        );
+        cache.bucket("meta").set_json(META_CACHE_KEY, "0", &stale);
+        let docs = storage()
+            .with_meta_cache(cache.bucket("meta"))
+            .scan()
+            .unwrap();
+        assert_eq!(docs[0].title, "Guide");
+    }
+
     #[test]
     fn test_scan_no_page_kind_without_kind_field() {
         let temp_dir = create_test_dir();
@@ -1315,6 +1898,103 @@ mod tests {
         assert_eq!(meta.title, Some("Guide".to_owned()));
     }
 
+    #[test]
+    fn test_list_root_returns_direct_children() {
+        let temp_dir = create_test_dir();
+        let source_dir = temp_dir.path().join("docs");
+        fs::create_dir_all(source_dir.join("domain/billing")).unwrap();
+        fs::create_dir_all(source_dir.join("empty")).unwrap();
+        fs::create_dir_all(source_dir.join("virtual")).unwrap();
+        fs::write(source_dir.join("index.md"), "# Home").unwrap();
+        fs::write(source_dir.join("guide.md"), "# Guide").unwrap();
+        fs::write(source_dir.join("domain/index.md"), "# Domain").unwrap();
+        fs::write(source_dir.join("domain/billing/index.md"), "# Billing").unwrap();
+        fs::write(source_dir.join("virtual/meta.yaml"), "title: Virtual").unwrap();
+
+        let storage = FsStorage::new(temp_dir.path().to_path_buf(), source_dir);
+        let docs = storage.list("").unwrap();
+
+        let summary: Vec<(&str, &str, bool)> = docs
+            .iter()
+            .map(|d| (d.path.as_str(), d.title.as_str(), d.has_content))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("domain", "Domain", true),
+                ("guide", "Guide", true),
+                ("virtual", "Virtual", false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_list_nested_excludes_grandchildren() {
+        let temp_dir = create_test_dir();
+        let source_dir = temp_dir.path().join("docs");
+        fs::create_dir_all(source_dir.join("domain/billing/api")).unwrap();
+        fs::write(source_dir.join("domain/index.md"), "# Domain").unwrap();
+        fs::write(source_dir.join("domain/overview.md"), "# Overview").unwrap();
+        fs::write(source_dir.join("domain/billing/index.md"), "# Billing").unwrap();
+        fs::write(source_dir.join("domain/billing/rates.md"), "# Rates").unwrap();
+        fs::write(source_dir.join("domain/billing/api/index.md"), "# API").unwrap();
+
+        let storage = FsStorage::new(temp_dir.path().to_path_buf(), source_dir);
+
+        let paths: Vec<String> = storage
+            .list("domain")
+            .unwrap()
+            .into_iter()
+            .map(|d| d.path)
+            .collect();
+        assert_eq!(paths, ["domain/billing", "domain/overview"]);
+
+        let paths: Vec<String> = storage
+            .list("domain/billing")
+            .unwrap()
+            .into_iter()
+            .map(|d| d.path)
+            .collect();
+        assert_eq!(paths, ["domain/billing/api", "domain/billing/rates"]);
+
+        assert!(storage.list("domain/overview").unwrap().is_empty());
+        assert!(storage.list("missing").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_list_matches_scan() {
+        let temp_dir = create_test_dir();
+        let source_dir = temp_dir.path().join("docs");
+        fs::create_dir_all(source_dir.join("a/b")).unwrap();
+        fs::write(source_dir.join("a.md"), "# A standalone").unwrap();
+        fs::write(source_dir.join("a/index.md"), "# A index").unwrap();
+        fs::write(source_dir.join("a/b.md"), "# B").unwrap();
+        fs::write(source_dir.join("a/b/meta.yaml"), "title: B meta").unwrap();
+        fs::write(source_dir.join("a/.hidden.md"), "# Hidden").unwrap();
+
+        let storage = FsStorage::new(temp_dir.path().to_path_buf(), source_dir);
+        let scanned = storage.scan().unwrap();
+
+        for parent in ["", "a", "a/b"] {
+            let mut expected: Vec<Document> = scanned
+                .iter()
+                .filter(|d| rw_storage::is_direct_child(&d.path, parent))
+                .cloned()
+                .collect();
+            expected.sort_by(|x, y| x.path.cmp(&y.path));
+            assert_eq!(storage.list(parent).unwrap(), expected, "parent {parent:?}");
+        }
+    }
+
+    #[test]
+    fn test_list_rejects_path_traversal() {
+        let temp_dir = create_test_dir();
+        let storage = FsStorage::new(temp_dir.path().to_path_buf(), temp_dir.path().join("docs"));
+
+        let err = storage.list("../etc").unwrap_err();
+        assert_eq!(err.kind, StorageErrorKind::InvalidPath);
+    }
+
     #[test]
     fn test_read_existing_file() {
         let temp_dir = create_test_dir();
@@ -1344,6 +2024,28 @@ mod tests {
         assert_eq!(content, "# Domain Guide");
     }
 
+    #[test]
+    fn test_bom_is_stripped_from_read_and_title() {
+        let temp_dir = create_test_dir();
+        fs::write(temp_dir.path().join("guide.md"), "\u{FEFF}# Guide\n\nBody.").unwrap();
+
+        let storage = FsStorage::new(temp_dir.path().to_path_buf(), temp_dir.path().to_path_buf());
+
+        assert_eq!(storage.read("guide").unwrap(), "# Guide\n\nBody.");
+        assert_eq!(storage.scan().unwrap()[0].title, "Guide");
+    }
+
+    #[test]
+    fn test_read_non_utf8_file_is_a_clear_error() {
+        let temp_dir = create_test_dir();
+        fs::write(temp_dir.path().join("guide.md"), b"# Caf\xE9").unwrap();
+
+        let storage = FsStorage::new(temp_dir.path().to_path_buf(), temp_dir.path().to_path_buf());
+        let err = storage.read("guide").unwrap_err();
+
+        assert!(err.to_string().contains("not valid UTF-8"), "{err}");
+    }
+
     #[test]
     fn test_read_missing_file() {
         let temp_dir = create_test_dir();
@@ -1479,6 +2181,20 @@ mod tests {
         assert!(resolved.unwrap().ends_with("guide.md"));
     }
 
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_read_case_insensitive() {
+        let temp_dir = create_test_dir();
+        fs::write(temp_dir.path().join("guide.md"), "# Guide").unwrap();
+
+        let storage = FsStorage::new(temp_dir.path().to_path_buf(), temp_dir.path().to_path_buf());
+        assert!(!storage.exists("Guide"));
+
+        let storage = storage.with_case_insensitive(true);
+        assert!(storage.exists("Guide"));
+        assert_eq!(storage.read("Guide").unwrap(), "# Guide");
+    }
+
     #[test]
     fn test_resolve_content_not_found() {
         let temp_dir = create_test_dir();
@@ -2075,6 +2791,71 @@ mod tests {
         assert_eq!(homes[0].title, "Docs Home");
     }
 
+    /// Create `<tmp>/docs/guide.md` with the given project-root files and a
+    /// `HOME.md`, `overview.md`, `README.md` homepage chain.
+    fn create_homepage_chain_dir(files: &[(&str, &str)]) -> (tempfile::TempDir, FsStorage) {
+        let temp_dir = create_test_dir();
+        let project_root = temp_dir.path().to_path_buf();
+        let source_dir = project_root.join("docs");
+        fs::create_dir(&source_dir).unwrap();
+        fs::write(source_dir.join("guide.md"), "# Guide").unwrap();
+        for (name, content) in files {
+            fs::write(project_root.join(name), content).unwrap();
+        }
+
+        let candidates = ["HOME.md", "overview.md", "README.md"]
+            .iter()
+            .map(|name| project_root.join(name))
+            .collect();
+        let storage = FsStorage::new(project_root, source_dir).with_homepage(candidates);
+        (temp_dir, storage)
+    }
+
+    #[test]
+    fn test_homepage_chain_uses_first_existing_candidate() {
+        let (_dir, storage) =
+            create_homepage_chain_dir(&[("overview.md", "# Overview"), ("README.md", "# README")]);
+
+        assert_eq!(storage.read("").unwrap(), "# Overview");
+        let docs = storage.scan().unwrap();
+        let home = docs.iter().find(|d| d.path.is_empty()).unwrap();
+        assert_eq!(home.title, "Overview");
+        assert_eq!(
+            storage.url_paths_for_source(Path::new("overview.md")),
+            vec![String::new()]
+        );
+        assert!(
+            storage
+                .url_paths_for_source(Path::new("README.md"))
+                .is_empty(),
+            "a shadowed candidate is not a served page"
+        );
+    }
+
+    #[test]
+    fn test_homepage_chain_falls_through_to_last_candidate() {
+        let (_dir, storage) = create_homepage_chain_dir(&[("README.md", "# README")]);
+        assert_eq!(storage.read("").unwrap(), "# README");
+    }
+
+    #[test]
+    fn test_homepage_chain_yields_to_index() {
+        let (dir, storage) = create_homepage_chain_dir(&[("HOME.md", "# Home")]);
+        fs::write(dir.path().join("docs/index.md"), "# Docs Home").unwrap();
+        assert_eq!(storage.read("").unwrap(), "# Docs Home");
+    }
+
+    #[test]
+    fn test_empty_homepage_chain_disables_fallback() {
+        let (_dir, _, storage) = create_readme_test_dir("# README");
+        let storage = storage.with_homepage(Vec::new());
+
+        assert!(!storage.exists(""));
+        assert!(storage.read("").is_err());
+        let docs = storage.scan().unwrap();
+        assert!(docs.iter().all(|d| !d.path.is_empty()));
+    }
+
     #[test]
     fn test_exists_returns_true_for_readme_homepage() {
         let (_dir, _, storage) = create_readme_test_dir("# Home");
@@ -2635,7 +3416,7 @@ mod tests {
 
         let storage = FsStorage::new(root.to_path_buf(), root.to_path_buf());
         let resolver = PathResolver::new(root, root.to_path_buf(), "meta.yaml");
-        let refs = storage.scanner.scan();
+        let (refs, _) = storage.scanner.scan();
 
         let mut urls: Vec<_> = refs.iter().map(|r| r.url_path.clone()).collect();
         urls.sort();
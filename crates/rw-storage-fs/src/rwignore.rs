@@ -0,0 +1,204 @@
+//! `.rwignore` files: gitignore-style exclusions for a source tree.
+//!
+//! A `.rwignore` in `source_dir` or any directory below it lists glob patterns
+//! (gitignore syntax, including `!` re-includes and trailing `/` for
+//! directories) relative to the directory it sits in. A nested file extends
+//! its parents' patterns, and where two disagree the deeper one wins. As in
+//! git, nothing below an ignored directory can be re-included.
+//!
+//! The scanner honors these files through the `ignore` walker; [`IgnoreCache`]
+//! answers the same question for a single path, so `read`/`exists`/`meta` and
+//! the watch path exclude exactly what the scan does.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use ignore::gitignore::Gitignore;
+use parking_lot::RwLock;
+
+/// Name of the ignore file looked up in each source directory.
+pub(crate) const IGNORE_FILENAME: &str = ".rwignore";
+
+/// The parsed `.rwignore` files of one source tree, read once per directory.
+///
+/// Lookups run on every `read`, `exists` and watch event, so each directory's
+/// file is parsed on first use and kept until [`invalidate`](Self::invalidate)
+/// or [`clear`](Self::clear) drops it.
+#[derive(Debug)]
+pub(crate) struct IgnoreCache {
+    source_dir: PathBuf,
+    /// Matcher per directory; `None` when the directory has no `.rwignore`.
+    matchers: RwLock<HashMap<PathBuf, Option<Arc<Gitignore>>>>,
+}
+
+impl IgnoreCache {
+    pub(crate) fn new(source_dir: PathBuf) -> Self {
+        Self {
+            source_dir,
+            matchers: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Whether `path` (absolute, under `source_dir`) is excluded by a
+    /// `.rwignore`.
+    ///
+    /// Consults the `.rwignore` of every directory from `source_dir` down to
+    /// the path's parent. `is_dir` describes `path` itself; its ancestors are
+    /// directories by construction. Paths outside `source_dir` (e.g. a
+    /// homepage fallback in the project root) are never ignored.
+    pub(crate) fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        let Ok(rel_path) = path.strip_prefix(&self.source_dir) else {
+            return false;
+        };
+
+        let mut matchers: Vec<Arc<Gitignore>> = Vec::new();
+        let mut current = self.source_dir.clone();
+        let mut components = rel_path.components().peekable();
+        while let Some(component) = components.next() {
+            matchers.extend(self.matcher(&current));
+            current.push(component);
+
+            let entry_is_dir = components.peek().is_some() || is_dir;
+            // The deepest file with an opinion on this entry decides.
+            let decision = matchers
+                .iter()
+                .rev()
+                .map(|m| m.matched(&current, entry_is_dir))
+                .find(|m| !m.is_none());
+            if decision.is_some_and(|m| m.is_ignore()) {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Forget the parsed files of `dir` and every directory below it, so the
+    /// next lookup reads them again.
+    pub(crate) fn invalidate(&self, dir: &Path) {
+        self.matchers
+            .write()
+            .retain(|cached, _| !cached.starts_with(dir));
+    }
+
+    /// Forget every parsed file.
+    pub(crate) fn clear(&self) {
+        self.matchers.write().clear();
+    }
+
+    /// The matcher for `dir/.rwignore`, parsing it on first use.
+    fn matcher(&self, dir: &Path) -> Option<Arc<Gitignore>> {
+        if let Some(cached) = self.matchers.read().get(dir) {
+            return cached.clone();
+        }
+        let matcher = load(dir).map(Arc::new);
+        self.matchers
+            .write()
+            .insert(dir.to_path_buf(), matcher.clone());
+        matcher
+    }
+}
+
+/// Parse `dir/.rwignore`, if there is one.
+///
+/// A malformed pattern is skipped with a warning; the rest of the file still
+/// applies.
+fn load(dir: &Path) -> Option<Gitignore> {
+    let file = dir.join(IGNORE_FILENAME);
+    if !file.is_file() {
+        return None;
+    }
+    let (matcher, error) = Gitignore::new(&file);
+    if let Some(error) = error {
+        tracing::warn!(file = %file.display(), error = %error, "Invalid pattern in .rwignore");
+    }
+    Some(matcher)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    fn is_ignored(source_dir: &Path, path: &Path, is_dir: bool) -> bool {
+        IgnoreCache::new(source_dir.to_path_buf()).is_ignored(path, is_dir)
+    }
+
+    #[test]
+    fn no_ignore_file_ignores_nothing() {
+        let temp_dir = tempfile::tempdir().unwrap();
+
+        assert!(!is_ignored(
+            temp_dir.path(),
+            &temp_dir.path().join("guide.md"),
+            false
+        ));
+    }
+
+    #[test]
+    fn root_patterns_apply_recursively() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        fs::write(root.join(IGNORE_FILENAME), "drafts/\n*.wip.md\n").unwrap();
+
+        assert!(is_ignored(root, &root.join("drafts"), true));
+        assert!(is_ignored(root, &root.join("drafts/idea.md"), false));
+        assert!(is_ignored(root, &root.join("domain/drafts/idea.md"), false));
+        assert!(is_ignored(root, &root.join("domain/plan.wip.md"), false));
+        assert!(!is_ignored(root, &root.join("domain/plan.md"), false));
+    }
+
+    #[test]
+    fn nested_file_extends_and_overrides_parent() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        fs::write(root.join(IGNORE_FILENAME), "*.wip.md\n").unwrap();
+        fs::create_dir(root.join("domain")).unwrap();
+        fs::write(
+            root.join("domain").join(IGNORE_FILENAME),
+            "generated/\n!keep.wip.md\n",
+        )
+        .unwrap();
+
+        assert!(is_ignored(
+            root,
+            &root.join("domain/generated/api.md"),
+            false
+        ));
+        assert!(is_ignored(root, &root.join("domain/other.wip.md"), false));
+        assert!(!is_ignored(root, &root.join("domain/keep.wip.md"), false));
+        // The nested file's patterns stay in its own subtree.
+        assert!(!is_ignored(root, &root.join("generated/api.md"), false));
+    }
+
+    #[test]
+    fn paths_outside_source_dir_are_not_ignored() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let source_dir = temp_dir.path().join("docs");
+        fs::create_dir(&source_dir).unwrap();
+        fs::write(source_dir.join(IGNORE_FILENAME), "*.md\n").unwrap();
+
+        assert!(!is_ignored(
+            &source_dir,
+            &temp_dir.path().join("README.md"),
+            false
+        ));
+    }
+
+    #[test]
+    fn edited_file_applies_after_invalidate() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir(root.join("domain")).unwrap();
+        let cache = IgnoreCache::new(root.to_path_buf());
+        let page = root.join("domain/plan.md");
+        assert!(!cache.is_ignored(&page, false));
+
+        fs::write(root.join("domain").join(IGNORE_FILENAME), "plan.md\n").unwrap();
+        assert!(!cache.is_ignored(&page, false), "parsed files are cached");
+
+        cache.invalidate(&root.join("domain"));
+        assert!(cache.is_ignored(&page, false));
+    }
+}
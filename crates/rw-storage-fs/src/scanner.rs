@@ -8,9 +8,11 @@ use parking_lot::Mutex;
 use std::collections::HashMap;
 use std::num::NonZeroUsize;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 use ignore::WalkBuilder;
 
+use crate::rwignore::{IGNORE_FILENAME, IgnoreCache};
 use crate::source::{SourceFile, SourceKind};
 
 /// Reference to a document's source files.
@@ -22,12 +24,24 @@ use crate::source::{SourceFile, SourceKind};
 pub(crate) struct DocumentRef {
     /// URL path (e.g., "", "domain", "domain/guide")
     pub url_path: String,
-    /// Path to content file (.md), if present
+    /// Path to content file (`.md` or another configured extension), if present
     pub content_path: Option<PathBuf>,
     /// Path to metadata file (e.g., "meta.yaml"), if present
     pub meta_path: Option<PathBuf>,
 }
 
+/// A content file ignored because a higher-precedence file maps to the same
+/// URL path (e.g. `domain.md` next to `domain/index.md`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ShadowedFile {
+    /// URL path both files map to.
+    pub url_path: String,
+    /// The file that is served.
+    pub used: PathBuf,
+    /// The file that is ignored.
+    pub ignored: PathBuf,
+}
+
 /// Discovers document references by walking the filesystem.
 ///
 /// The Scanner performs Phase 1 of document loading:
@@ -39,6 +53,8 @@ pub(crate) struct DocumentRef {
 pub(crate) struct Scanner {
     source_dir: PathBuf,
     meta_filename: String,
+    extensions: Vec<String>,
+    ignore: Arc<IgnoreCache>,
 }
 
 impl Scanner {
@@ -52,25 +68,92 @@ impl Scanner {
         Self {
             source_dir: source_dir.to_path_buf(),
             meta_filename: meta_filename.to_owned(),
+            extensions: crate::source::DEFAULT_EXTENSIONS
+                .iter()
+                .map(|&e| e.to_owned())
+                .collect(),
+            ignore: Arc::new(IgnoreCache::new(source_dir.to_path_buf())),
         }
     }
 
-    /// Scan filesystem and return document references.
+    /// Share `.rwignore` matchers with the rest of the storage (default: a
+    /// cache of its own).
+    pub fn with_ignore(mut self, ignore: Arc<IgnoreCache>) -> Self {
+        self.ignore = ignore;
+        self
+    }
+
+    /// Replace the recognized content extensions (default `["md"]`).
+    pub fn with_extensions(mut self, extensions: Vec<String>) -> Self {
+        self.extensions = extensions;
+        self
+    }
+
+    /// Scan filesystem and return document references, plus the content
+    /// files that lost a URL path collision, both sorted by URL path.
     ///
-    /// Returns an empty Vec if the source directory doesn't exist.
-    pub fn scan(&self) -> Vec<DocumentRef> {
+    /// Returns empty Vecs if the source directory doesn't exist.
+    pub fn scan(&self) -> (Vec<DocumentRef>, Vec<ShadowedFile>) {
         if !self.source_dir.exists() {
-            return Vec::new();
+            return (Vec::new(), Vec::new());
         }
         let files = self.collect_source_files();
         Self::group_into_documents(files)
     }
 
+    /// Return references for the direct children of `url_path`.
+    ///
+    /// Reads only the two directory levels a child's files can live in
+    /// (`<dir>/x.<ext>` and `<dir>/x/index.<ext>` and their metadata), then
+    /// classifies and groups them exactly as [`scan`](Self::scan) does.
+    /// Returns an empty Vec if the directory doesn't exist.
+    pub fn scan_children(&self, url_path: &str) -> Vec<DocumentRef> {
+        let dir = if url_path.is_empty() {
+            self.source_dir.clone()
+        } else {
+            self.source_dir.join(url_path)
+        };
+        if !dir.is_dir() {
+            return Vec::new();
+        }
+
+        let files = WalkBuilder::new(&dir)
+            .hidden(true)
+            .git_ignore(false)
+            .git_global(false)
+            .git_exclude(false)
+            .follow_links(false)
+            .max_depth(Some(2))
+            .build()
+            .filter_map(Result::ok)
+            .filter(|entry| entry.file_type().is_some_and(|ft| ft.is_file()))
+            // `.rwignore` files above `dir` count too, which the walker rooted
+            // at `dir` would not read.
+            .filter(|entry| !self.ignore.is_ignored(entry.path(), false))
+            .filter_map(|entry| {
+                let filename = entry.file_name().to_os_string();
+                SourceFile::classify(
+                    entry.into_path(),
+                    &filename,
+                    &self.source_dir,
+                    &self.meta_filename,
+                    &self.extensions,
+                )
+            })
+            .collect();
+
+        let (mut refs, _) = Self::group_into_documents(files);
+        refs.retain(|r| rw_storage::is_direct_child(&r.url_path, url_path));
+        refs
+    }
+
     /// Walk directory tree in parallel and collect all source files.
     ///
     /// Uses the `ignore` crate's parallel walker which distributes directory
     /// traversal across multiple threads with work-stealing. Hidden files
-    /// and hidden directories are skipped automatically.
+    /// and hidden directories are skipped automatically, as is anything a
+    /// `.rwignore` in `source_dir` or below excludes. Ignore files above
+    /// `source_dir` are not read, matching [`IgnoreCache::is_ignored`].
     fn collect_source_files(&self) -> Vec<SourceFile> {
         let files: Mutex<Vec<SourceFile>> = Mutex::new(Vec::new());
 
@@ -79,6 +162,8 @@ impl Scanner {
             .git_ignore(false)
             .git_global(false)
             .git_exclude(false)
+            .parents(false)
+            .add_custom_ignore_filename(IGNORE_FILENAME)
             .follow_links(false)
             .threads(
                 std::thread::available_parallelism()
@@ -90,6 +175,7 @@ impl Scanner {
                 let files = &files;
                 let source_dir = &self.source_dir;
                 let meta_filename = &self.meta_filename;
+                let extensions = &self.extensions;
 
                 Box::new(move |result| {
                     let Ok(entry) = result else {
@@ -106,7 +192,7 @@ impl Scanner {
                     let path = entry.into_path();
 
                     if let Some(source) =
-                        SourceFile::classify(path, &filename, source_dir, meta_filename)
+                        SourceFile::classify(path, &filename, source_dir, meta_filename, extensions)
                     {
                         files.lock().push(source);
                     }
@@ -128,15 +214,23 @@ impl Scanner {
     /// bare form, then the `index.` variant, then a sibling), so the chosen
     /// `meta_path` matches `meta()`'s resolution.
     ///
-    /// Content collisions — a url path with both `X.md` and `X/index.md` —
-    /// prefer `index.md`, matching `PathResolver::resolve_content`, so the
-    /// scanned document and the file `read()` serves are the same one.
-    fn group_into_documents(files: Vec<SourceFile>) -> Vec<DocumentRef> {
-        use crate::source::MetaRank;
+    /// Content collisions — a url path with both `X.md` and `X/index.md`, or
+    /// the same name in two configured extensions — are resolved by
+    /// `ContentRank` (lower wins: every `index.<ext>` before any standalone
+    /// file, each in configured extension order), matching
+    /// `PathResolver::resolve_content`, so the scanned document and the file
+    /// `read()` serves are the same one. Every losing content file is returned
+    /// as a [`ShadowedFile`].
+    fn group_into_documents(files: Vec<SourceFile>) -> (Vec<DocumentRef>, Vec<ShadowedFile>) {
+        use crate::source::{ContentRank, MetaRank};
 
         let mut docs: HashMap<String, DocumentRef> = HashMap::new();
         // Rank of the metadata file currently stored in each DocumentRef.
         let mut meta_rank: HashMap<String, MetaRank> = HashMap::new();
+        // Rank of the content file currently stored in each DocumentRef.
+        let mut content_rank: HashMap<String, ContentRank> = HashMap::new();
+        // Content files that lost a collision, by url path.
+        let mut ignored: HashMap<String, Vec<PathBuf>> = HashMap::new();
 
         for file in files {
             let doc = docs
@@ -149,18 +243,29 @@ impl Scanner {
 
             match file.kind {
                 SourceKind::Content => {
-                    if doc.content_path.is_some() {
-                        tracing::warn!(
-                            url_path = %doc.url_path,
-                            "Multiple content files for same url_path, preferring index.md"
-                        );
-                    }
-                    // index.md wins over the standalone sibling, matching
-                    // PathResolver::resolve_content. Without this the winner
-                    // is whichever the parallel walk yielded last.
-                    let incoming_is_index = file.path.file_name().is_some_and(|n| n == "index.md");
-                    if doc.content_path.is_none() || incoming_is_index {
-                        doc.content_path = Some(file.path);
+                    // Metadata files have content_rank None; content always Some.
+                    let Some(incoming) = file.content_rank else {
+                        continue;
+                    };
+                    match content_rank.get(&doc.url_path).copied() {
+                        None => {
+                            doc.content_path = Some(file.path);
+                            content_rank.insert(doc.url_path.clone(), incoming);
+                        }
+                        Some(stored) => {
+                            // Without the rank the winner is whichever the
+                            // parallel walk yielded last.
+                            let loser = if incoming < stored {
+                                content_rank.insert(doc.url_path.clone(), incoming);
+                                doc.content_path.replace(file.path)
+                            } else {
+                                Some(file.path)
+                            };
+                            ignored
+                                .entry(doc.url_path.clone())
+                                .or_default()
+                                .extend(loser);
+                        }
                     }
                 }
                 SourceKind::Metadata => {
@@ -186,7 +291,29 @@ impl Scanner {
             }
         }
 
-        docs.into_values().collect()
+        let mut shadowed: Vec<ShadowedFile> = ignored
+            .into_iter()
+            .flat_map(|(url_path, paths)| {
+                let used = docs[&url_path].content_path.clone().unwrap_or_default();
+                paths.into_iter().map(move |ignored| ShadowedFile {
+                    url_path: url_path.clone(),
+                    used: used.clone(),
+                    ignored,
+                })
+            })
+            .collect();
+        shadowed.sort_by(|a, b| {
+            a.url_path
+                .cmp(&b.url_path)
+                .then_with(|| a.ignored.cmp(&b.ignored))
+        });
+
+        // The parallel walk yields files in no particular order; sorting makes
+        // every scan of the same tree return the same sequence.
+        let mut refs: Vec<DocumentRef> = docs.into_values().collect();
+        refs.sort_unstable_by(|a, b| a.url_path.cmp(&b.url_path));
+
+        (refs, shadowed)
     }
 }
 
@@ -195,6 +322,7 @@ mod tests {
     use std::fs;
 
     use super::*;
+    use crate::source::ContentRank;
 
     fn create_test_dir() -> tempfile::TempDir {
         tempfile::tempdir().unwrap()
@@ -210,7 +338,7 @@ mod tests {
         fs::write(domain_dir.join("index.md"), "# Domain").unwrap();
 
         let scanner = Scanner::new(temp_dir.path(), "meta.yaml");
-        let refs = scanner.scan();
+        let (refs, _) = scanner.scan();
 
         assert_eq!(refs.len(), 2);
 
@@ -235,6 +363,47 @@ mod tests {
         assert!(domain_ref.meta_path.is_none());
     }
 
+    #[test]
+    fn test_scan_finds_configured_extensions() {
+        let temp_dir = create_test_dir();
+        fs::write(temp_dir.path().join("guide.markdown"), "# Guide").unwrap();
+        fs::write(temp_dir.path().join("notes.txt"), "not a page").unwrap();
+
+        let scanner = Scanner::new(temp_dir.path(), "meta.yaml")
+            .with_extensions(vec!["md".to_owned(), "markdown".to_owned()]);
+        let (refs, _) = scanner.scan();
+
+        assert_eq!(refs.len(), 1);
+        assert_eq!(refs[0].url_path, "guide");
+        assert!(
+            refs[0]
+                .content_path
+                .as_ref()
+                .unwrap()
+                .ends_with("guide.markdown")
+        );
+    }
+
+    #[test]
+    fn test_scan_prefers_earlier_configured_extension() {
+        let temp_dir = create_test_dir();
+        fs::write(temp_dir.path().join("guide.md"), "# Md").unwrap();
+        fs::write(temp_dir.path().join("guide.markdown"), "# Markdown").unwrap();
+
+        let scanner = Scanner::new(temp_dir.path(), "meta.yaml")
+            .with_extensions(vec!["markdown".to_owned(), "md".to_owned()]);
+        let (refs, _) = scanner.scan();
+
+        assert_eq!(refs.len(), 1);
+        assert!(
+            refs[0]
+                .content_path
+                .as_ref()
+                .unwrap()
+                .ends_with("guide.markdown")
+        );
+    }
+
     #[test]
     fn test_scan_finds_virtual_pages() {
         let temp_dir = create_test_dir();
@@ -244,7 +413,7 @@ mod tests {
         fs::write(domain_dir.join("meta.yaml"), "title: Domain").unwrap();
 
         let scanner = Scanner::new(temp_dir.path(), "meta.yaml");
-        let refs = scanner.scan();
+        let (refs, _) = scanner.scan();
 
         assert_eq!(refs.len(), 1);
         assert_eq!(refs[0].url_path, "domain");
@@ -261,7 +430,7 @@ mod tests {
         fs::write(domain_dir.join("meta.yaml"), "type: section").unwrap();
 
         let scanner = Scanner::new(temp_dir.path(), "meta.yaml");
-        let refs = scanner.scan();
+        let (refs, _) = scanner.scan();
 
         assert_eq!(refs.len(), 1);
         assert_eq!(refs[0].url_path, "domain");
@@ -276,18 +445,61 @@ mod tests {
         fs::write(temp_dir.path().join("visible.md"), "# Visible").unwrap();
 
         let scanner = Scanner::new(temp_dir.path(), "meta.yaml");
-        let refs = scanner.scan();
+        let (refs, _) = scanner.scan();
 
         assert_eq!(refs.len(), 1);
         assert_eq!(refs[0].url_path, "visible");
     }
 
+    #[test]
+    fn test_scan_skips_rwignored_files() {
+        let temp_dir = create_test_dir();
+        let domain_dir = temp_dir.path().join("domain");
+        let generated_dir = domain_dir.join("generated");
+        fs::create_dir_all(&generated_dir).unwrap();
+        fs::write(temp_dir.path().join(".rwignore"), "*.wip.md\n").unwrap();
+        fs::write(domain_dir.join(".rwignore"), "generated/\n").unwrap();
+        fs::write(domain_dir.join("guide.md"), "# Guide").unwrap();
+        fs::write(domain_dir.join("plan.wip.md"), "# Plan").unwrap();
+        fs::write(generated_dir.join("index.md"), "# API").unwrap();
+
+        let scanner = Scanner::new(temp_dir.path(), "meta.yaml");
+        let (refs, _) = scanner.scan();
+        let children = scanner.scan_children("domain");
+
+        assert_eq!(refs.len(), 1);
+        assert_eq!(refs[0].url_path, "domain/guide");
+        assert_eq!(children.len(), 1);
+        assert_eq!(children[0].url_path, "domain/guide");
+    }
+
+    #[test]
+    fn test_scan_is_sorted_by_url_path() {
+        let temp_dir = create_test_dir();
+        for dir in ["b", "a/c", "a/b"] {
+            let dir = temp_dir.path().join(dir);
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(dir.join("index.md"), "# Page").unwrap();
+            fs::write(dir.join("z.md"), "# Z").unwrap();
+        }
+
+        let scanner = Scanner::new(temp_dir.path(), "meta.yaml");
+        let (refs, _) = scanner.scan();
+        let paths: Vec<_> = refs.iter().map(|r| r.url_path.as_str()).collect();
+
+        assert_eq!(
+            paths,
+            ["a/b", "a/b/z", "a/c", "a/c/z", "b", "b/z"],
+            "same order on every scan"
+        );
+    }
+
     #[test]
     fn test_scan_empty_dir() {
         let temp_dir = create_test_dir();
 
         let scanner = Scanner::new(temp_dir.path(), "meta.yaml");
-        let refs = scanner.scan();
+        let (refs, _) = scanner.scan();
 
         assert!(refs.is_empty());
     }
@@ -295,7 +507,7 @@ mod tests {
     #[test]
     fn test_scan_missing_dir() {
         let scanner = Scanner::new(Path::new("/nonexistent"), "meta.yaml");
-        let refs = scanner.scan();
+        let (refs, _) = scanner.scan();
 
         assert!(refs.is_empty());
     }
@@ -319,7 +531,7 @@ mod tests {
         fs::write(l2.join("index.md"), "# L2").unwrap();
 
         let scanner = Scanner::new(temp_dir.path(), "meta.yaml");
-        let refs = scanner.scan();
+        let (refs, _) = scanner.scan();
 
         assert_eq!(refs.len(), 4);
 
@@ -340,7 +552,7 @@ mod tests {
         fs::write(domain_dir.join("meta.yaml"), "ignored").unwrap();
 
         let scanner = Scanner::new(temp_dir.path(), "config.yml");
-        let refs = scanner.scan();
+        let (refs, _) = scanner.scan();
 
         assert_eq!(refs.len(), 1);
         // Should include config.yml, not meta.yaml
@@ -358,7 +570,7 @@ mod tests {
         fs::write(temp_dir.path().join("visible.md"), "# Visible").unwrap();
 
         let scanner = Scanner::new(temp_dir.path(), "meta.yaml");
-        let refs = scanner.scan();
+        let (refs, _) = scanner.scan();
 
         assert_eq!(refs.len(), 1);
         assert_eq!(refs[0].url_path, "visible");
@@ -388,7 +600,7 @@ mod tests {
         symlink(&subdir, temp_dir.path().join("link_dir")).unwrap();
 
         let scanner = Scanner::new(temp_dir.path(), "meta.yaml");
-        let refs = scanner.scan();
+        let (refs, _) = scanner.scan();
 
         // Should find real.md and subdir/doc.md, not link.md or link_dir/*
         assert_eq!(refs.len(), 2);
@@ -405,7 +617,7 @@ mod tests {
         fs::write(deep.join("deep.md"), "# Deep").unwrap();
 
         let scanner = Scanner::new(temp_dir.path(), "meta.yaml");
-        let refs = scanner.scan();
+        let (refs, _) = scanner.scan();
 
         assert_eq!(refs.len(), 1);
         assert_eq!(refs[0].url_path, "a/b/c/d/deep");
@@ -418,7 +630,7 @@ mod tests {
         fs::write(temp_dir.path().join("meta.yaml"), "title: Root").unwrap();
 
         let scanner = Scanner::new(temp_dir.path(), "meta.yaml");
-        let refs = scanner.scan();
+        let (refs, _) = scanner.scan();
 
         assert_eq!(refs.len(), 1);
         assert_eq!(refs[0].url_path, "");
@@ -437,7 +649,7 @@ mod tests {
         .unwrap();
 
         let scanner = Scanner::new(temp_dir.path(), "meta.yaml");
-        let refs = scanner.scan();
+        let (refs, _) = scanner.scan();
 
         assert_eq!(refs.len(), 1);
         assert_eq!(refs[0].url_path, "payments");
@@ -467,7 +679,7 @@ mod tests {
         .unwrap();
 
         let scanner = Scanner::new(temp_dir.path(), "meta.yaml");
-        let refs = scanner.scan();
+        let (refs, _) = scanner.scan();
 
         assert_eq!(refs.len(), 1);
         assert_eq!(refs[0].url_path, "payments");
@@ -490,7 +702,7 @@ mod tests {
         fs::write(dir.join("index.meta.yaml"), "kind: ignored").unwrap();
 
         let scanner = Scanner::new(temp_dir.path(), "meta.yaml");
-        let refs = scanner.scan();
+        let (refs, _) = scanner.scan();
 
         let dir_ref = refs.iter().find(|r| r.url_path == "dir").unwrap();
         assert!(
@@ -514,7 +726,7 @@ mod tests {
         fs::write(dir.join("index.meta.yaml"), "kind: domain").unwrap();
 
         let scanner = Scanner::new(temp_dir.path(), "meta.yaml");
-        let refs = scanner.scan();
+        let (refs, _) = scanner.scan();
 
         let dir_ref = refs.iter().find(|r| r.url_path == "dir").unwrap();
         assert!(dir_ref.content_path.is_none());
@@ -539,16 +751,24 @@ mod tests {
                 kind: SourceKind::Content,
                 path: PathBuf::from("/docs/both/index.md"),
                 meta_rank: None,
+                content_rank: Some(ContentRank {
+                    standalone: false,
+                    extension: 0,
+                }),
             },
             SourceFile {
                 url_path: "both".to_owned(),
                 kind: SourceKind::Content,
                 path: PathBuf::from("/docs/both.md"),
                 meta_rank: None,
+                content_rank: Some(ContentRank {
+                    standalone: true,
+                    extension: 0,
+                }),
             },
         ];
 
-        let refs = Scanner::group_into_documents(files);
+        let (refs, shadowed) = Scanner::group_into_documents(files);
 
         assert_eq!(refs.len(), 1);
         assert_eq!(
@@ -556,6 +776,32 @@ mod tests {
             Some(Path::new("/docs/both/index.md")),
             "index.md must win over the standalone sibling regardless of order"
         );
+        assert_eq!(
+            shadowed,
+            [ShadowedFile {
+                url_path: "both".to_owned(),
+                used: PathBuf::from("/docs/both/index.md"),
+                ignored: PathBuf::from("/docs/both.md"),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_scan_reports_standalone_shadowed_by_index() {
+        let temp_dir = create_test_dir();
+        fs::create_dir(temp_dir.path().join("domain")).unwrap();
+        fs::write(temp_dir.path().join("domain/index.md"), "# Domain").unwrap();
+        fs::write(temp_dir.path().join("domain.md"), "# Standalone").unwrap();
+        fs::write(temp_dir.path().join("guide.md"), "# Guide").unwrap();
+
+        let scanner = Scanner::new(temp_dir.path(), "meta.yaml");
+        let (refs, shadowed) = scanner.scan();
+
+        assert_eq!(refs.len(), 2);
+        assert_eq!(shadowed.len(), 1);
+        assert_eq!(shadowed[0].url_path, "domain");
+        assert!(shadowed[0].used.ends_with("domain/index.md"));
+        assert!(shadowed[0].ignored.ends_with("domain.md"));
     }
 
     #[test]
@@ -567,7 +813,7 @@ mod tests {
         fs::write(index_dir.join("index.md"), "# Index Dir").unwrap();
 
         let scanner = Scanner::new(temp_dir.path(), "meta.yaml");
-        let refs = scanner.scan();
+        let (refs, _) = scanner.scan();
 
         assert_eq!(refs.len(), 1);
         assert_eq!(refs[0].url_path, "index");
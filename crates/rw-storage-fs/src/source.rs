@@ -9,19 +9,26 @@
 use glob::Pattern;
 use rw_meta::Meta;
 use std::ffi::OsStr;
-use std::fs::read_to_string;
 use std::path::{Path, PathBuf, absolute};
+use std::sync::Arc;
 
-/// Fallback name the README homepage is titled from when it has no H1.
+use crate::encoding::read_source;
+use crate::rwignore::IgnoreCache;
+
+/// Fallback name the fallback homepage (README by default) is titled from when
+/// it has no H1.
 ///
-/// Read by both the scan path's README injection and the watch path, which
+/// Read by both the scan path's homepage injection and the watch path, which
 /// must agree or the same page gets two different titles.
 pub(crate) const HOMEPAGE_FALLBACK_NAME: &str = "home";
 
+/// Content file extensions recognized when none are configured.
+pub(crate) const DEFAULT_EXTENSIONS: &[&str] = &["md"];
+
 /// The role a source file plays in document construction.
 #[derive(Debug, PartialEq, Eq)]
 pub(crate) enum SourceKind {
-    /// Content file (.md) - provides page body
+    /// Content file (`.md` or another configured extension) - provides page body
     Content,
     /// Metadata file (e.g., meta.yaml) - provides page configuration
     Metadata,
@@ -43,14 +50,28 @@ pub(crate) enum MetaRank {
     Sibling,
 }
 
+/// Resolution precedence of a content file that maps to a url path.
+///
+/// Lower wins. Mirrors `PathResolver::resolve_content`, which probes every
+/// `index.<ext>` before any standalone `<name>.<ext>`, each in configured
+/// extension order — so field order matters: the derived `Ord` compares
+/// `standalone` first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct ContentRank {
+    /// `false` for `index.<ext>`, `true` for a standalone `<name>.<ext>`.
+    pub standalone: bool,
+    /// Position of the file's extension in the configured list.
+    pub extension: usize,
+}
+
 /// The classification decision for a single relative path.
 ///
 /// Shared by `SourceFile::classify` (scan) and `to_storage_event` (watch) so
 /// the two never drift apart.
 #[derive(Debug, PartialEq, Eq)]
 pub(crate) enum Classification {
-    /// Content file (`.md`).
-    Content { url_path: String },
+    /// Content file (a configured extension, `.md` by default).
+    Content { url_path: String, rank: ContentRank },
     /// Metadata file, with its resolution rank.
     Metadata { url_path: String, rank: MetaRank },
 }
@@ -59,7 +80,7 @@ impl Classification {
     /// Consume into the url path this file contributes to.
     pub(crate) fn into_url_path(self) -> String {
         match self {
-            Self::Content { url_path } | Self::Metadata { url_path, .. } => url_path,
+            Self::Content { url_path, .. } | Self::Metadata { url_path, .. } => url_path,
         }
     }
 }
@@ -67,24 +88,30 @@ impl Classification {
 /// Classify a relative path into content/metadata + url path.
 ///
 /// Precedence (must stay in sync — that's why it lives in one place):
-/// 1. `*.md` → content (url path via `file_path_to_url`)
+/// 1. `*.<ext>` for a configured content extension → content (url path via
+///    `file_path_to_url`)
 /// 2. exact `<meta_filename>` → canonical directory metadata (parent url path)
 /// 3. `index.<meta_filename>` → directory metadata (parent url path) + warning
 /// 4. `<name>.<meta_filename>` → sibling metadata, when `<name>` is a safe stem
 ///    (non-empty, not `index`, not `.`/`..`, contains no `..`); degenerate
 ///    stems that would yield a `validate_path`-rejected url path fall through
 /// 5. otherwise → `None`
-pub(crate) fn classify_relpath(
+pub(crate) fn classify_relpath<S: AsRef<str>>(
     rel_path: &Path,
     filename: &str,
     meta_filename: &str,
+    extensions: &[S],
 ) -> Option<Classification> {
-    if Path::new(filename)
-        .extension()
-        .is_some_and(|ext| ext == "md")
+    let filename_path = Path::new(filename);
+    if let Some(ext) = filename_path.extension()
+        && let Some(extension) = extensions.iter().position(|e| ext == e.as_ref())
     {
         return Some(Classification::Content {
             url_path: file_path_to_url(rel_path),
+            rank: ContentRank {
+                standalone: filename_path.file_stem().is_none_or(|stem| stem != "index"),
+                extension,
+            },
         });
     }
 
@@ -155,41 +182,50 @@ pub(crate) struct SourceFile {
     pub path: PathBuf,
     /// Resolution rank for metadata files; `None` for content.
     pub meta_rank: Option<MetaRank>,
+    /// Resolution rank for content files; `None` for metadata.
+    pub content_rank: Option<ContentRank>,
 }
 
 impl SourceFile {
     /// Classify a file path as a source file.
     ///
     /// Returns `Some` if the file is a recognized source type:
-    /// - `.md` files become `SourceKind::Content`
+    /// - files with one of `extensions` become `SourceKind::Content`
     /// - Files matching `meta_filename` become `SourceKind::Metadata`
     ///
     /// Returns `None` for unrecognized file types.
     ///
     /// Note: This method assumes the caller has already filtered out
     /// hidden files, symlinks, and directories.
-    pub fn classify(
+    pub fn classify<S: AsRef<str>>(
         path: PathBuf,
         filename: &OsStr,
         source_dir: &Path,
         meta_filename: &str,
+        extensions: &[S],
     ) -> Option<Self> {
         let rel_path = path.strip_prefix(source_dir).ok()?;
-        let classification =
-            classify_relpath(rel_path, &filename.to_string_lossy(), meta_filename)?;
+        let classification = classify_relpath(
+            rel_path,
+            &filename.to_string_lossy(),
+            meta_filename,
+            extensions,
+        )?;
 
         Some(match classification {
-            Classification::Content { url_path } => Self {
+            Classification::Content { url_path, rank } => Self {
                 url_path,
                 kind: SourceKind::Content,
                 path,
                 meta_rank: None,
+                content_rank: Some(rank),
             },
             Classification::Metadata { url_path, rank } => Self {
                 url_path,
                 kind: SourceKind::Metadata,
                 path,
                 meta_rank: Some(rank),
+                content_rank: None,
             },
         })
     }
@@ -197,8 +233,9 @@ impl SourceFile {
 
 /// Convert a relative file path to a URL path.
 ///
-/// Handles `.md` extension stripping, `index.md` special case,
-/// and Windows path separator normalization.
+/// Handles extension stripping, the `index.<ext>` special case, and Windows
+/// path separator normalization. The caller has already decided the file is
+/// content, so any extension is stripped.
 ///
 /// # Examples
 ///
@@ -212,8 +249,11 @@ pub(crate) fn file_path_to_url(rel_path: &Path) -> String {
         .map(|f| f.to_string_lossy())
         .unwrap_or_default();
 
-    if filename == "index.md" {
-        // index.md -> parent directory's url_path
+    if Path::new(&*filename)
+        .file_stem()
+        .is_some_and(|stem| stem == "index")
+    {
+        // index.<ext> -> parent directory's url_path
         parent_url_path(rel_path)
     } else {
         // standalone.md -> parent/stem
@@ -240,8 +280,50 @@ fn parent_url_path(rel_path: &Path) -> String {
         .unwrap_or_default()
 }
 
+/// How [`PathResolver`] matches a candidate path against the filesystem.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Lookup {
+    /// The path as written.
+    Exact,
+    /// Each segment compared case-insensitively; see [`find_ignoring_case`].
+    IgnoreCase,
+}
+
+/// Walk `rel` from `root`, matching each segment exactly when it exists and
+/// otherwise by a case-insensitive comparison against the directory entries.
+///
+/// Returns `None` when a segment has no match, or more than one: with both
+/// `Guide.md` and `guide.md` on disk, `GUIDE.md` names neither.
+fn find_ignoring_case(root: &Path, rel: &str) -> Option<PathBuf> {
+    let mut path = root.to_path_buf();
+    for segment in rel.split('/').filter(|s| !s.is_empty()) {
+        let exact = path.join(segment);
+        if exact.exists() {
+            path = exact;
+            continue;
+        }
+
+        let wanted = segment.to_lowercase();
+        let mut matches = std::fs::read_dir(&path)
+            .ok()?
+            .filter_map(Result::ok)
+            .filter(|entry| entry.file_name().to_string_lossy().to_lowercase() == wanted);
+        let found = matches.next()?;
+        if matches.next().is_some() {
+            tracing::warn!(
+                dir = %path.display(),
+                segment,
+                "Ambiguous case-insensitive match; several entries differ only by case"
+            );
+            return None;
+        }
+        path = found.path();
+    }
+    Some(path)
+}
+
 /// Owner of the url→file probe order for a source tree, plus the file-level
-/// config (`source_dir`, `meta_filename`, README fallback) that determines it.
+/// config (`source_dir`, `meta_filename`, homepage fallbacks) that determines it.
 ///
 /// `source.rs` already held the file→url direction (`classify_relpath`,
 /// `file_path_to_url`); this type adds the reverse, so the request path
@@ -262,85 +344,152 @@ pub(crate) struct PathResolver {
     source_dir: PathBuf,
     /// Metadata file name (e.g. "meta.yaml").
     meta_filename: String,
-    /// `README.md` in the project directory, used as the homepage fallback when
-    /// `source_dir/index.md` does not exist.
+    /// Content file extensions without the dot, in precedence order.
+    extensions: Vec<String>,
+    /// Whether a lookup that misses retries with a case-insensitive match.
+    case_insensitive: bool,
+    /// Project root, against which [`Self::url_paths_for_source`] resolves
+    /// project-relative input.
+    project_dir: PathBuf,
+    /// Homepage fallbacks, tried in order when `source_dir` has no
+    /// `index.<ext>`. `README.md` in the project directory by default.
     ///
-    /// This is only the candidate path; whether a file is actually there is
-    /// answered by [`PathResolver::existing_readme`].
-    readme_path: PathBuf,
+    /// These are only candidate paths; which one is actually there is
+    /// answered by [`PathResolver::existing_homepage`].
+    homepage_candidates: Vec<PathBuf>,
+    /// Parsed `.rwignore` files, shared with clones, the scanner and the
+    /// watcher so an invalidation reaches every lookup.
+    ignore: Arc<IgnoreCache>,
 }
 
 impl PathResolver {
     /// Build a resolver.
     ///
     /// `project_dir` is the project root — `rw_config::Config::project_dir`,
-    /// the directory containing `rw.toml`. The `README.md` homepage fallback
-    /// lives there.
+    /// the directory containing `rw.toml`. The default `README.md` homepage
+    /// fallback lives there.
     ///
     /// Do **not** derive it from `source_dir`: a nested (`docs/site`) or
     /// absolute `docs.source_dir` puts `parent()` somewhere that isn't the
     /// project root. See `rw_config::Config::project_dir`.
-    ///
-    /// The two directories differ in reference style because they differ in
-    /// use: `source_dir` is stored on the resolver, so it is taken owned, while
-    /// `project_dir` is only joined once to form `readme_path`.
     pub(crate) fn new(project_dir: &Path, source_dir: PathBuf, meta_filename: &str) -> Self {
-        let readme_path = project_dir.join("README.md");
         Self {
+            ignore: Arc::new(IgnoreCache::new(source_dir.clone())),
             source_dir,
             meta_filename: meta_filename.to_owned(),
-            readme_path,
+            extensions: DEFAULT_EXTENSIONS.iter().map(|&e| e.to_owned()).collect(),
+            case_insensitive: false,
+            homepage_candidates: vec![project_dir.join("README.md")],
+            project_dir: project_dir.to_path_buf(),
         }
     }
 
+    /// Replace the homepage fallback candidates (default
+    /// `[project_dir/README.md]`). An empty list disables the fallback.
+    pub(crate) fn with_homepage(mut self, candidates: Vec<PathBuf>) -> Self {
+        self.homepage_candidates = candidates;
+        self
+    }
+
+    /// Homepage fallback candidates, in order.
+    pub(crate) fn homepage_candidates(&self) -> &[PathBuf] {
+        &self.homepage_candidates
+    }
+
+    /// Retry missed lookups case-insensitively (default off).
+    ///
+    /// See [`Self::resolve_content`] for the retry rules.
+    pub(crate) fn with_case_insensitive(mut self, enabled: bool) -> Self {
+        self.case_insensitive = enabled;
+        self
+    }
+
+    /// Replace the recognized content extensions (default `["md"]`).
+    pub(crate) fn with_extensions(mut self, extensions: Vec<String>) -> Self {
+        self.extensions = extensions;
+        self
+    }
+
     /// Root directory for document storage.
     pub(crate) fn source_dir(&self) -> &Path {
         &self.source_dir
     }
 
-    /// Every file whose change can affect a document: markdown, plus both
-    /// metadata forms.
+    /// The `.rwignore` matchers for `source_dir`.
+    pub(crate) fn ignore(&self) -> &Arc<IgnoreCache> {
+        &self.ignore
+    }
+
+    /// Every file whose change can affect a document: content in each
+    /// configured extension, plus both metadata forms.
+    ///
+    /// Extensions and the metadata filename are escaped, so a name such as
+    /// `meta[1].yaml` is matched literally instead of failing to compile.
     pub(crate) fn watch_patterns(&self) -> Vec<Pattern> {
-        let meta_filename = &self.meta_filename;
-        vec![
-            Pattern::new("**/*.md").expect("invalid glob pattern"),
-            Pattern::new(&format!("**/{meta_filename}")).expect("invalid glob pattern"),
-            Pattern::new(&format!("**/*.{meta_filename}")).expect("invalid glob pattern"),
-        ]
+        let glob = |pattern: String| Pattern::new(&pattern).expect("escaped glob is valid");
+        let meta_filename = Pattern::escape(&self.meta_filename);
+        let mut patterns: Vec<Pattern> = self
+            .extensions
+            .iter()
+            .map(|ext| glob(format!("**/*.{}", Pattern::escape(ext))))
+            .collect();
+        patterns.push(glob(format!("**/{meta_filename}")));
+        patterns.push(glob(format!("**/*.{meta_filename}")));
+        patterns
     }
 
     /// Resolve URL path to content file path.
     ///
+    /// Each step tries every configured extension in order (`md` alone by
+    /// default).
+    ///
     /// For root path (`""`):
-    /// 1. `source_dir/index.md`
-    /// 2. `readme_path` (`README.md` in the project directory)
+    /// 1. `source_dir/index.<ext>`
+    /// 2. the first existing homepage candidate (`README.md` in the project
+    ///    directory by default)
     ///
     /// For other paths:
-    /// 1. `{path}/index.md` (directory structure preferred)
-    /// 2. `{path}.md` (standalone file fallback)
+    /// 1. `{path}/index.<ext>` (directory structure preferred)
+    /// 2. `{path}.<ext>` (standalone file fallback)
     ///
-    /// Returns `None` if no content file exists.
+    /// Returns `None` if no content file exists. [`ContentRank`] encodes the
+    /// same order for the scanner's tie-break.
+    ///
+    /// With [`with_case_insensitive`](Self::with_case_insensitive), a miss
+    /// reruns the whole order matching each path segment case-insensitively,
+    /// so an exact match anywhere in the order always beats a case-folded
+    /// one. A segment with several case-folded matches (`Guide.md` and
+    /// `guide.md`) is ambiguous and matches nothing. The `README.md` fallback
+    /// and the other homepage candidates are only ever matched exactly.
     pub(crate) fn resolve_content(&self, url_path: &str) -> Option<PathBuf> {
+        self.resolve_content_by(url_path, Lookup::Exact)
+            .or_else(|| {
+                self.retry_ignoring_case(url_path, |l| self.resolve_content_by(url_path, l))
+            })
+    }
+
+    fn resolve_content_by(&self, url_path: &str, lookup: Lookup) -> Option<PathBuf> {
+        let first_existing = |candidate: &dyn Fn(&str) -> String| {
+            self.extensions
+                .iter()
+                .find_map(|ext| self.probe(&candidate(ext), lookup))
+        };
+
         if url_path.is_empty() {
-            let index = self.source_dir.join("index.md");
-            if index.exists() {
+            if let Some(index) = first_existing(&|ext| format!("index.{ext}")) {
                 return Some(index);
             }
-            if self.readme_path.exists() {
-                return Some(self.readme_path.clone());
+            if lookup == Lookup::Exact
+                && let Some(homepage) = self.existing_homepage()
+            {
+                return Some(homepage.to_path_buf());
             }
             return None;
         }
 
-        // Prefer directory/index.md
-        let index_path = self.source_dir.join(format!("{url_path}/index.md"));
-        if index_path.exists() {
-            return Some(index_path);
-        }
-
-        // Fall back to standalone file
-        let file_path = self.source_dir.join(format!("{url_path}.md"));
-        file_path.exists().then_some(file_path)
+        // Prefer directory/index.<ext>, then fall back to a standalone file
+        first_existing(&|ext| format!("{url_path}/index.{ext}"))
+            .or_else(|| first_existing(&|ext| format!("{url_path}.{ext}")))
     }
 
     /// Resolve a directory's metadata file (directory form).
@@ -348,20 +497,15 @@ impl PathResolver {
     /// Two candidates in precedence order: the canonical
     /// `<dir>/<meta_filename>`, then the `<dir>/index.<meta_filename>` variant.
     /// Returns `None` if neither exists.
-    pub(crate) fn resolve_dir_meta(&self, url_path: &str) -> Option<PathBuf> {
+    fn resolve_dir_meta(&self, url_path: &str, lookup: Lookup) -> Option<PathBuf> {
         let dir = if url_path.is_empty() {
-            self.source_dir.clone()
+            String::new()
         } else {
-            self.source_dir.join(url_path)
+            format!("{url_path}/")
         };
 
-        let canonical = dir.join(&self.meta_filename);
-        if canonical.exists() {
-            return Some(canonical);
-        }
-
-        let index_variant = dir.join(format!("index.{}", self.meta_filename));
-        index_variant.exists().then_some(index_variant)
+        self.probe(&format!("{dir}{}", self.meta_filename), lookup)
+            .or_else(|| self.probe(&format!("{dir}index.{}", self.meta_filename), lookup))
     }
 
     /// Resolve a page's own metadata file (leaf query).
@@ -373,17 +517,53 @@ impl PathResolver {
     /// The candidate order here is the same rule [`MetaRank`] encodes as an
     /// ordinal for the scanner's tie-break;
     /// `scan_and_resolver_agree_across_the_precedence_matrix` pins them together.
+    ///
+    /// Retries case-insensitively like [`Self::resolve_content`] when enabled.
     pub(crate) fn resolve_meta(&self, url_path: &str) -> Option<PathBuf> {
-        if let Some(dir_meta) = self.resolve_dir_meta(url_path) {
+        self.resolve_meta_by(url_path, Lookup::Exact)
+            .or_else(|| self.retry_ignoring_case(url_path, |l| self.resolve_meta_by(url_path, l)))
+    }
+
+    fn resolve_meta_by(&self, url_path: &str, lookup: Lookup) -> Option<PathBuf> {
+        if let Some(dir_meta) = self.resolve_dir_meta(url_path, lookup) {
             return Some(dir_meta);
         }
         if url_path.is_empty() {
             return None;
         }
-        let sibling = self
-            .source_dir
-            .join(format!("{url_path}.{}", self.meta_filename));
-        sibling.exists().then_some(sibling)
+        self.probe(&format!("{url_path}.{}", self.meta_filename), lookup)
+    }
+
+    /// Run `resolve` again ignoring case, if enabled, and warn on a hit so the
+    /// author can fix the link.
+    fn retry_ignoring_case(
+        &self,
+        url_path: &str,
+        resolve: impl FnOnce(Lookup) -> Option<PathBuf>,
+    ) -> Option<PathBuf> {
+        if !self.case_insensitive {
+            return None;
+        }
+        let found = resolve(Lookup::IgnoreCase)?;
+        tracing::warn!(
+            url_path,
+            file = %found.display(),
+            "Resolved path by case-insensitive match; fix the link to match the file name"
+        );
+        Some(found)
+    }
+
+    /// The file at `rel` (a `/`-separated path under `source_dir`), if it
+    /// exists and no `.rwignore` excludes it.
+    fn probe(&self, rel: &str, lookup: Lookup) -> Option<PathBuf> {
+        let path = match lookup {
+            Lookup::Exact => {
+                let path = self.source_dir.join(rel);
+                path.exists().then_some(path)
+            }
+            Lookup::IgnoreCase => find_ignoring_case(&self.source_dir, rel),
+        }?;
+        (!self.ignore.is_ignored(&path, false)).then_some(path)
     }
 
     /// Classify a file path as a source file, using this resolver's config.
@@ -391,7 +571,13 @@ impl PathResolver {
     /// Thin wrapper over [`SourceFile::classify`] so callers holding a resolver
     /// do not re-thread `(source_dir, meta_filename)`.
     pub(crate) fn classify(&self, path: PathBuf, filename: &OsStr) -> Option<SourceFile> {
-        SourceFile::classify(path, filename, &self.source_dir, &self.meta_filename)
+        SourceFile::classify(
+            path,
+            filename,
+            &self.source_dir,
+            &self.meta_filename,
+            &self.extensions,
+        )
     }
 
     /// Classify a path relative to `source_dir`, using this resolver's config.
@@ -400,13 +586,14 @@ impl PathResolver {
         rel_path: &Path,
         filename: &str,
     ) -> Option<Classification> {
-        classify_relpath(rel_path, filename, &self.meta_filename)
+        classify_relpath(rel_path, filename, &self.meta_filename, &self.extensions)
     }
 
     /// The name `Meta::resolve` falls back to when titling `url_path`.
     ///
     /// Mirrors what the scan path passes: the resolved file's own name, except
-    /// for the README homepage, which `FsStorage::scan` injects with a fixed
+    /// for the fallback homepage (README by default), which `FsStorage::scan`
+    /// injects with a fixed
     /// `"home"`. Passing the README's real filename here instead would title an
     /// H1-less README "Readme" on live reload and "Home" on scan — trading one
     /// disagreement for another.
@@ -414,15 +601,15 @@ impl PathResolver {
     /// Falls back to the url's last segment, then `"home"`, when nothing resolves.
     pub(crate) fn content_fallback_name(&self, url_path: &str, resolved: Option<&Path>) -> String {
         if let Some(path) = resolved {
-            // Plain equality against the field is sound only because
-            // `resolve_content` returns a clone of it. Do NOT make that method
+            // Plain equality against the candidates is sound only because
+            // `resolve_content` returns a clone of one. Do NOT make that method
             // canonicalize or rebuild the path without making this a
             // canonicalized comparison too.
-            if self.readme_path == path {
+            if url_path.is_empty() && self.homepage_candidates.iter().any(|c| c == path) {
                 return HOMEPAGE_FALLBACK_NAME.to_owned();
             }
-            if let Some(name) = path.file_name() {
-                return name.to_string_lossy().to_lowercase();
+            if let Some(stem) = path.file_stem() {
+                return stem.to_string_lossy().to_lowercase();
             }
         }
 
@@ -446,15 +633,17 @@ impl PathResolver {
             }
         };
 
-        // README homepage maps to the root url — but only when it is actually
-        // the served homepage. `resolve_content("")` applies the same
-        // index.md-then-README precedence the scanner uses, so a project with a
-        // real `docs/index.md` (which shadows the README) does not map README.md
-        // to the root here.
-        let readme = &self.readme_path;
-        if (file_path == Path::new("README.md")
-            || absolute(file_path).ok() == absolute(readme).ok())
-            && self.resolve_content("").as_deref() == Some(readme.as_path())
+        // A homepage candidate maps to the root url — but only when it is
+        // actually the served homepage. `resolve_content("")` applies the same
+        // index-then-candidates precedence the scanner uses, so a project with
+        // a real `docs/index.md` (which shadows the README) does not map
+        // README.md to the root here. Joining onto `project_dir` accepts both
+        // project-relative input and absolute paths.
+        if let Some(homepage) = self.existing_homepage()
+            && self.resolve_content("").as_deref() == Some(homepage)
+            && [self.project_dir.join(file_path), file_path.to_path_buf()]
+                .iter()
+                .any(|p| absolute(p).ok() == absolute(homepage).ok())
         {
             push(String::new());
         }
@@ -475,7 +664,7 @@ impl PathResolver {
 
         for rel in rels {
             let file = self.source_dir.join(&rel);
-            if !file.is_file() {
+            if !file.is_file() || self.ignore.is_ignored(&file, false) {
                 continue;
             }
             let Some(name) = file.file_name().map(OsStr::to_os_string) else {
@@ -491,28 +680,31 @@ impl PathResolver {
         urls
     }
 
-    /// The README homepage *candidate*, when a README exists on disk.
+    /// The first homepage candidate that exists on disk.
     ///
     /// Existence alone — a project with `docs/index.md` has an existing README
     /// that is never served, and this still returns it. Callers that need the
     /// actually-served homepage go through [`Self::resolve_content`] with `""`.
-    pub(crate) fn existing_readme(&self) -> Option<&Path> {
-        self.readme_path.exists().then_some(&*self.readme_path)
+    pub(crate) fn existing_homepage(&self) -> Option<&Path> {
+        self.homepage_candidates
+            .iter()
+            .find(|p| p.is_file())
+            .map(PathBuf::as_path)
     }
 
-    /// Metadata for the injected README homepage document, when a README exists.
+    /// Metadata for the injected homepage document, when a candidate exists.
     ///
     /// `scan` calls this only after finding no root document of its own, so the
-    /// README's own H1 (or [`HOMEPAGE_FALLBACK_NAME`] without one) titles the
+    /// candidate's own H1 (or [`HOMEPAGE_FALLBACK_NAME`] without one) titles the
     /// root page. Resolving the title here rather than at the call site is what
     /// keeps scan and the watch path — which titles the same page through
     /// [`Self::content_fallback_name`] — from drifting onto two literals.
     ///
-    /// Gated on the README existing, not on it being readable: an unreadable one
-    /// still yields metadata, titled from the fallback.
+    /// Gated on a candidate existing, not on it being readable: an unreadable
+    /// one still yields metadata, titled from the fallback.
     pub(crate) fn homepage_fallback_meta(&self) -> Option<Meta> {
-        let readme = self.existing_readme()?;
-        let markdown = read_to_string(readme).ok();
+        let homepage = self.existing_homepage()?;
+        let markdown = read_source(homepage).ok();
         Some(Meta::resolve(
             markdown.as_deref(),
             None,
@@ -526,12 +718,14 @@ mod tests {
     use super::*;
     use std::ffi::OsString;
 
+    const MD: &[&str] = DEFAULT_EXTENSIONS;
+
     /// Helper to classify a file path with default meta filename.
     fn classify(source_dir: &str, file_path: &str) -> Option<SourceFile> {
         let source = Path::new(source_dir);
         let path = source.join(file_path);
         let filename = OsString::from(Path::new(file_path).file_name().unwrap_or_default());
-        SourceFile::classify(path, &filename, source, "meta.yaml")
+        SourceFile::classify(path, &filename, source, "meta.yaml", MD)
     }
 
     #[test]
@@ -595,11 +789,12 @@ mod tests {
         let filename = OsString::from("config.yml");
 
         // Should match custom meta filename
-        let result = SourceFile::classify(path.clone(), &filename, source, "config.yml").unwrap();
+        let result =
+            SourceFile::classify(path.clone(), &filename, source, "config.yml", MD).unwrap();
         assert_eq!(result.kind, SourceKind::Metadata);
 
         // Should not match default meta filename
-        let result = SourceFile::classify(path, &filename, source, "meta.yaml");
+        let result = SourceFile::classify(path, &filename, source, "meta.yaml", MD);
         assert!(result.is_none());
     }
 
@@ -654,29 +849,87 @@ mod tests {
 
     #[test]
     fn classify_relpath_content_md() {
-        let c = classify_relpath(Path::new("domain/guide.md"), "guide.md", "meta.yaml").unwrap();
+        let c =
+            classify_relpath(Path::new("domain/guide.md"), "guide.md", "meta.yaml", MD).unwrap();
         assert_eq!(
             c,
             Classification::Content {
-                url_path: "domain/guide".to_owned()
+                url_path: "domain/guide".to_owned(),
+                rank: ContentRank {
+                    standalone: true,
+                    extension: 0,
+                },
             }
         );
     }
 
     #[test]
     fn classify_relpath_index_md_collapses_to_parent() {
-        let c = classify_relpath(Path::new("domain/index.md"), "index.md", "meta.yaml").unwrap();
+        let c =
+            classify_relpath(Path::new("domain/index.md"), "index.md", "meta.yaml", MD).unwrap();
         assert_eq!(
             c,
             Classification::Content {
-                url_path: "domain".to_owned()
+                url_path: "domain".to_owned(),
+                rank: ContentRank {
+                    standalone: false,
+                    extension: 0,
+                },
             }
         );
     }
 
+    #[test]
+    fn classify_relpath_uses_configured_extensions() {
+        let extensions = ["md", "markdown"];
+
+        let c = classify_relpath(
+            Path::new("domain/guide.markdown"),
+            "guide.markdown",
+            "meta.yaml",
+            &extensions,
+        )
+        .unwrap();
+        assert_eq!(
+            c,
+            Classification::Content {
+                url_path: "domain/guide".to_owned(),
+                rank: ContentRank {
+                    standalone: true,
+                    extension: 1,
+                },
+            }
+        );
+
+        // Not configured: ignored, like any other non-source file.
+        assert_eq!(
+            classify_relpath(
+                Path::new("guide.markdown"),
+                "guide.markdown",
+                "meta.yaml",
+                MD
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn content_rank_orders_every_index_before_any_standalone() {
+        let index_second_ext = ContentRank {
+            standalone: false,
+            extension: 1,
+        };
+        let standalone_first_ext = ContentRank {
+            standalone: true,
+            extension: 0,
+        };
+        assert!(index_second_ext < standalone_first_ext);
+    }
+
     #[test]
     fn classify_relpath_bare_meta_is_canonical_dir() {
-        let c = classify_relpath(Path::new("domain/meta.yaml"), "meta.yaml", "meta.yaml").unwrap();
+        let c =
+            classify_relpath(Path::new("domain/meta.yaml"), "meta.yaml", "meta.yaml", MD).unwrap();
         assert_eq!(
             c,
             Classification::Metadata {
@@ -688,7 +941,7 @@ mod tests {
 
     #[test]
     fn classify_relpath_root_bare_meta() {
-        let c = classify_relpath(Path::new("meta.yaml"), "meta.yaml", "meta.yaml").unwrap();
+        let c = classify_relpath(Path::new("meta.yaml"), "meta.yaml", "meta.yaml", MD).unwrap();
         assert_eq!(
             c,
             Classification::Metadata {
@@ -704,6 +957,7 @@ mod tests {
             Path::new("dir/index.meta.yaml"),
             "index.meta.yaml",
             "meta.yaml",
+            MD,
         )
         .unwrap();
         assert_eq!(
@@ -717,8 +971,13 @@ mod tests {
 
     #[test]
     fn classify_relpath_root_index_meta_maps_to_empty() {
-        let c =
-            classify_relpath(Path::new("index.meta.yaml"), "index.meta.yaml", "meta.yaml").unwrap();
+        let c = classify_relpath(
+            Path::new("index.meta.yaml"),
+            "index.meta.yaml",
+            "meta.yaml",
+            MD,
+        )
+        .unwrap();
         assert_eq!(
             c,
             Classification::Metadata {
@@ -734,6 +993,7 @@ mod tests {
             Path::new("systems/payments.meta.yaml"),
             "payments.meta.yaml",
             "meta.yaml",
+            MD,
         )
         .unwrap();
         assert_eq!(
@@ -751,6 +1011,7 @@ mod tests {
             Path::new("payments.meta.yaml"),
             "payments.meta.yaml",
             "meta.yaml",
+            MD,
         )
         .unwrap();
         assert_eq!(
@@ -765,7 +1026,7 @@ mod tests {
     #[test]
     fn classify_relpath_hidden_meta_is_none() {
         // ".meta.yaml" has an empty prefix -> not a named file, not bare -> None
-        assert!(classify_relpath(Path::new(".meta.yaml"), ".meta.yaml", "meta.yaml").is_none());
+        assert!(classify_relpath(Path::new(".meta.yaml"), ".meta.yaml", "meta.yaml", MD).is_none());
     }
 
     #[test]
@@ -774,6 +1035,7 @@ mod tests {
             Path::new("dir/app.config.yml"),
             "app.config.yml",
             "config.yml",
+            MD,
         )
         .unwrap();
         assert_eq!(
@@ -784,7 +1046,7 @@ mod tests {
             }
         );
         let bare =
-            classify_relpath(Path::new("dir/config.yml"), "config.yml", "config.yml").unwrap();
+            classify_relpath(Path::new("dir/config.yml"), "config.yml", "config.yml", MD).unwrap();
         assert_eq!(
             bare,
             Classification::Metadata {
@@ -796,19 +1058,23 @@ mod tests {
 
     #[test]
     fn classify_relpath_unrecognized_is_none() {
-        assert!(classify_relpath(Path::new("notes.txt"), "notes.txt", "meta.yaml").is_none());
+        assert!(classify_relpath(Path::new("notes.txt"), "notes.txt", "meta.yaml", MD).is_none());
     }
 
     #[test]
     fn classify_relpath_dotdot_prefix_is_none() {
         // `...meta.yaml` strips to prefix ".." — must not become a sibling page.
-        assert!(classify_relpath(Path::new("...meta.yaml"), "...meta.yaml", "meta.yaml").is_none());
+        assert!(
+            classify_relpath(Path::new("...meta.yaml"), "...meta.yaml", "meta.yaml", MD).is_none()
+        );
     }
 
     #[test]
     fn classify_relpath_dot_prefix_is_none() {
         // `..meta.yaml` strips to prefix "." — must not become a sibling page.
-        assert!(classify_relpath(Path::new("..meta.yaml"), "..meta.yaml", "meta.yaml").is_none());
+        assert!(
+            classify_relpath(Path::new("..meta.yaml"), "..meta.yaml", "meta.yaml", MD).is_none()
+        );
     }
 
     #[test]
@@ -816,7 +1082,13 @@ mod tests {
         // `a..b.meta.yaml` strips to prefix "a..b"; url path "a..b" would be
         // rejected by validate_path, so it must not classify as a sibling page.
         assert!(
-            classify_relpath(Path::new("a..b.meta.yaml"), "a..b.meta.yaml", "meta.yaml").is_none()
+            classify_relpath(
+                Path::new("a..b.meta.yaml"),
+                "a..b.meta.yaml",
+                "meta.yaml",
+                MD
+            )
+            .is_none()
         );
     }
 
@@ -826,6 +1098,7 @@ mod tests {
             Path::new("dir/index.meta.yaml"),
             "index.meta.yaml",
             "meta.yaml",
+            MD,
         )
         .unwrap();
         assert_eq!(c.into_url_path(), "dir");
@@ -879,6 +1152,144 @@ mod tests {
         );
     }
 
+    #[test]
+    fn resolver_resolve_content_tries_extensions_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::write(root.join("guide.markdown"), "# Markdown").unwrap();
+        std::fs::write(root.join("guide.md"), "# Md").unwrap();
+        std::fs::write(root.join("notes.markdown"), "# Notes").unwrap();
+
+        let resolver = PathResolver::new(root, root.to_path_buf(), "meta.yaml")
+            .with_extensions(vec!["md".to_owned(), "markdown".to_owned()]);
+
+        assert_eq!(
+            resolver.resolve_content("guide"),
+            Some(root.join("guide.md"))
+        );
+        assert_eq!(
+            resolver.resolve_content("notes"),
+            Some(root.join("notes.markdown"))
+        );
+    }
+
+    #[test]
+    fn resolver_resolve_content_prefers_any_index_over_standalone() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("domain")).unwrap();
+        std::fs::write(root.join("domain/index.markdown"), "# Domain").unwrap();
+        std::fs::write(root.join("domain.md"), "# Standalone").unwrap();
+
+        let resolver = PathResolver::new(root, root.to_path_buf(), "meta.yaml")
+            .with_extensions(vec!["md".to_owned(), "markdown".to_owned()]);
+
+        assert_eq!(
+            resolver.resolve_content("domain"),
+            Some(root.join("domain/index.markdown"))
+        );
+    }
+
+    #[test]
+    fn resolver_watch_patterns_cover_each_extension() {
+        let resolver = PathResolver::new(Path::new("/"), PathBuf::from("/docs"), "meta.yaml")
+            .with_extensions(vec!["md".to_owned(), "markdown".to_owned()]);
+        let patterns = resolver.watch_patterns();
+        let watched = |path: &str| patterns.iter().any(|p| p.matches(path));
+
+        assert!(watched("guide/setup.md"));
+        assert!(watched("guide/setup.markdown"));
+        assert!(watched("guide/meta.yaml"));
+        assert!(!watched("guide/setup.txt"));
+    }
+
+    #[test]
+    fn resolver_watch_patterns_match_glob_characters_literally() {
+        let resolver = PathResolver::new(Path::new("/"), PathBuf::from("/docs"), "[unclosed");
+        let patterns = resolver.watch_patterns();
+        let watched = |path: &str| patterns.iter().any(|p| p.matches(path));
+
+        assert!(watched("guide/[unclosed"));
+        assert!(watched("guide/setup.[unclosed"));
+        assert!(!watched("guide/u"));
+    }
+
+    // Case-insensitive fallback. The misses need a case-sensitive filesystem
+    // (Windows CI matches `Guide` to `guide.md` natively), hence Linux-only.
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn resolver_exact_case_only_by_default() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::write(root.join("guide.md"), "# Guide").unwrap();
+
+        let resolver = PathResolver::new(root, root.to_path_buf(), "meta.yaml");
+
+        assert_eq!(resolver.resolve_content("Guide"), None);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn resolver_case_insensitive_matches_each_segment() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("Domain")).unwrap();
+        std::fs::write(root.join("Domain/setup-guide.md"), "# Setup").unwrap();
+        std::fs::write(root.join("Domain/setup-guide.meta.yaml"), "kind: guide").unwrap();
+
+        let resolver =
+            PathResolver::new(root, root.to_path_buf(), "meta.yaml").with_case_insensitive(true);
+
+        assert_eq!(
+            resolver.resolve_content("domain/Setup-Guide"),
+            Some(root.join("Domain/setup-guide.md"))
+        );
+        assert_eq!(
+            resolver.resolve_meta("domain/Setup-Guide"),
+            Some(root.join("Domain/setup-guide.meta.yaml"))
+        );
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn resolver_case_insensitive_prefers_exact_match_anywhere_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        // Case-folded, `guide/index.md` would outrank the standalone file; the
+        // exact standalone match must still win.
+        std::fs::create_dir_all(root.join("guide")).unwrap();
+        std::fs::write(root.join("guide/index.md"), "# Folded").unwrap();
+        std::fs::write(root.join("Guide.md"), "# Exact").unwrap();
+
+        let resolver =
+            PathResolver::new(root, root.to_path_buf(), "meta.yaml").with_case_insensitive(true);
+
+        assert_eq!(
+            resolver.resolve_content("Guide"),
+            Some(root.join("Guide.md"))
+        );
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn resolver_case_insensitive_refuses_ambiguous_match() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::write(root.join("Guide.md"), "# Upper").unwrap();
+        std::fs::write(root.join("guide.md"), "# Lower").unwrap();
+
+        let resolver =
+            PathResolver::new(root, root.to_path_buf(), "meta.yaml").with_case_insensitive(true);
+
+        assert_eq!(resolver.resolve_content("GUIDE"), None);
+        // Exact spellings are unaffected by the ambiguity.
+        assert_eq!(
+            resolver.resolve_content("guide"),
+            Some(root.join("guide.md"))
+        );
+    }
+
     #[test]
     fn fallback_name_for_readme_homepage_is_the_shared_const() {
         let dir = tempfile::tempdir().unwrap();
@@ -898,13 +1309,18 @@ mod tests {
     }
 
     #[test]
-    fn fallback_name_for_resolved_file_is_its_lowercased_filename() {
+    fn fallback_name_for_resolved_file_is_its_lowercased_stem() {
         // No file is created: the name comes from the path, not from disk.
         let resolver = PathResolver::new(Path::new("/"), PathBuf::from("/docs"), "meta.yaml");
 
         assert_eq!(
             resolver.content_fallback_name("guide", Some(Path::new("/docs/Guide.md"))),
-            "guide.md"
+            "guide"
+        );
+        // Any content extension is dropped, not just `.md`.
+        assert_eq!(
+            resolver.content_fallback_name("guide", Some(Path::new("/docs/Guide.markdown"))),
+            "guide"
         );
     }
 
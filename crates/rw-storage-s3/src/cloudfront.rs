@@ -0,0 +1,97 @@
+//! `CloudFront` invalidation after a publish.
+//!
+//! A distribution in front of the bucket keeps serving cached bundles until
+//! their TTL runs out. Invalidating the entity's prefix right after the
+//! manifest upload makes a publish visible at once. Only available with the
+//! `publish` feature.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use aws_sdk_cloudfront::types::{InvalidationBatch, Paths};
+
+use crate::s3::{self, S3Config, error_chain};
+
+/// Invalidate every object under the bundle's prefix in the distribution
+/// `distribution_id`, returning the invalidation id.
+///
+/// The distribution is assumed to serve the bucket from its root, so object
+/// keys and request paths match. Returns `Err(String)` with the formatted
+/// error chain on failure.
+pub(crate) async fn invalidate_prefix(
+    config: &S3Config,
+    distribution_id: &str,
+) -> Result<String, String> {
+    let sdk_config = s3::sdk_config_loader(config).load().await;
+    let client = aws_sdk_cloudfront::Client::new(&sdk_config);
+
+    let paths = Paths::builder()
+        .quantity(1)
+        .items(invalidation_path(config))
+        .build()
+        .map_err(|e| error_chain(&e))?;
+    let batch = InvalidationBatch::builder()
+        .paths(paths)
+        .caller_reference(caller_reference())
+        .build()
+        .map_err(|e| error_chain(&e))?;
+
+    let output = client
+        .create_invalidation()
+        .distribution_id(distribution_id)
+        .invalidation_batch(batch)
+        .send()
+        .await
+        .map_err(|e| error_chain(&e))?;
+    let id = output
+        .invalidation()
+        .map(|invalidation| invalidation.id().to_owned())
+        .ok_or_else(|| "CloudFront returned no invalidation".to_owned())?;
+    tracing::debug!(distribution_id, invalidation_id = %id, "Invalidated");
+    Ok(id)
+}
+
+/// Wildcard path covering every object [`s3::build_key`] puts under the
+/// bundle's prefix. One wildcard path is billed as one path, however many
+/// objects it matches.
+fn invalidation_path(config: &S3Config) -> String {
+    format!("/{}/*", config.base_prefix())
+}
+
+/// A reference unique to this request, so a retried publish creates a new
+/// invalidation instead of CloudFront treating it as a duplicate.
+fn caller_reference() -> String {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos();
+    format!("rw-{nanos}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(bucket_root_path: Option<&str>) -> S3Config {
+        S3Config {
+            bucket: "docs".to_owned(),
+            prefix: "default/Component/arch".to_owned(),
+            region: "us-east-1".to_owned(),
+            endpoint: None,
+            bucket_root_path: bucket_root_path.map(str::to_owned),
+            access_key_id: None,
+            secret_access_key: None,
+        }
+    }
+
+    #[test]
+    fn invalidation_path_covers_the_entity_prefix() {
+        assert_eq!(
+            invalidation_path(&config(None)),
+            "/default/Component/arch/*"
+        );
+        assert_eq!(
+            invalidation_path(&config(Some("techdocs"))),
+            "/techdocs/default/Component/arch/*"
+        );
+    }
+}
@@ -87,6 +87,8 @@ mod tests {
                 description: None,
                 origin: None,
                 pages: None,
+                order: None,
+                redirects: None,
                 is_dir: true,
             },
             Document {
@@ -98,6 +100,8 @@ mod tests {
                 description: Some("Getting started".to_owned()),
                 origin: None,
                 pages: None,
+                order: None,
+                redirects: None,
                 is_dir: true,
             },
         ]);
@@ -119,6 +123,8 @@ mod tests {
                 description: None,
                 page_kind: None,
                 pages: None,
+                order: None,
+                redirects: None,
             }),
         };
 
@@ -153,6 +159,8 @@ mod tests {
             description: None,
             origin: None,
             pages: None,
+            order: None,
+            redirects: None,
             is_dir: true,
         };
 
@@ -199,6 +207,8 @@ mod tests {
             description: None,
             origin: None,
             pages: None,
+            order: None,
+            redirects: None,
             is_dir: true,
         }]);
         manifest.mtimes.insert("guide".to_owned(), 1_713_000_000.0);
@@ -254,6 +264,8 @@ mod tests {
                 "getting-started".to_owned(),
                 "configuration".to_owned(),
             ]),
+            order: None,
+            redirects: None,
             is_dir: true,
         }]);
 
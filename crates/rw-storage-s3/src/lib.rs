@@ -6,7 +6,8 @@
 //! # Features
 //!
 //! - Default: `S3Storage` reader and format types
-//! - `publish`: Bundle publisher for uploading docs to S3
+//! - `publish`: Bundle publisher for uploading docs to S3, with optional
+//!   `CloudFront` invalidation
 
 pub(crate) mod format;
 pub mod s3;
@@ -15,6 +16,8 @@ mod storage;
 pub use s3::S3Config;
 pub use storage::S3Storage;
 
+#[cfg(feature = "publish")]
+mod cloudfront;
 #[cfg(feature = "publish")]
 mod publisher;
 
@@ -11,6 +11,7 @@ use rw_kroki::DiagramProcessor;
 use rw_renderer::{CodeBlockProcessor, bundle_markdown};
 use rw_storage::Storage;
 
+use crate::cloudfront;
 use crate::format::{self, MANIFEST_KEY, Manifest, PageBundle};
 use crate::s3::{self, S3Config};
 
@@ -23,6 +24,8 @@ pub enum BundlePublishError {
     Json(#[from] serde_json::Error),
     #[error("S3 error: {0}")]
     S3(String),
+    #[error("CloudFront invalidation failed: {0}")]
+    CloudFront(String),
     #[error("I/O error: {0}")]
     Io(#[from] std::io::Error),
 }
@@ -30,6 +33,8 @@ pub enum BundlePublishError {
 /// Publisher that builds and uploads documentation bundles to S3.
 pub struct BundlePublisher {
     config: S3Config,
+    /// `CloudFront` distribution to invalidate after a successful upload.
+    cloudfront_distribution_id: Option<String>,
 }
 
 /// Outcome of a publish run.
@@ -49,27 +54,55 @@ pub struct BundlePublisher {
 pub struct PublishReport {
     /// Number of objects uploaded (page bundles + manifest).
     pub uploaded: usize,
+    /// Number of objects left alone because S3 already held the same
+    /// content.
+    pub skipped: usize,
     /// Deduplicated diagram processing warnings accumulated across all pages.
     pub warnings: Vec<String>,
+    /// Id of the `CloudFront` invalidation created after the upload, or
+    /// `None` when no distribution is configured or nothing was uploaded.
+    pub invalidation_id: Option<String>,
 }
 
 impl BundlePublisher {
     #[must_use]
     pub fn new(config: S3Config) -> Self {
-        Self { config }
+        Self {
+            config,
+            cloudfront_distribution_id: None,
+        }
+    }
+
+    /// Invalidate the entity's prefix in this `CloudFront` distribution once
+    /// the manifest is uploaded, so a CDN in front of the bucket serves the
+    /// new bundles without waiting for its TTL. The distribution must serve
+    /// the bucket from its root.
+    #[must_use]
+    pub fn with_cloudfront_distribution(mut self, distribution_id: impl Into<String>) -> Self {
+        self.cloudfront_distribution_id = Some(distribution_id.into());
+        self
     }
 
     /// Publish documentation from a storage backend to S3.
     ///
     /// Scans the storage, builds bundles with pre-resolved `PlantUML`
     /// includes, streams them to S3 (uploads start as soon as each bundle
-    /// is ready), and returns a [`PublishReport`] with the upload count and
+    /// is ready), and returns a [`PublishReport`] with the upload counts and
     /// any `!include` resolution warnings (see [`PublishReport`] for what
     /// is and isn't captured).
     ///
+    /// Publishing is incremental: an object whose content hash matches the
+    /// one recorded on the existing S3 object is skipped (see
+    /// [`s3::upload_if_changed`]). An object that is missing, or was
+    /// uploaded without a hash, is always uploaded.
+    ///
     /// Uses a single shared `DiagramProcessor` so warnings from every page
     /// accumulate in one place; identical warnings are deduplicated before
     /// the report is returned.
+    ///
+    /// With a [`CloudFront` distribution](Self::with_cloudfront_distribution),
+    /// a failed invalidation fails the publish, although every object has
+    /// been uploaded by then.
     pub async fn publish(
         &self,
         storage: &dyn Storage,
@@ -84,10 +117,18 @@ impl BundlePublisher {
         // stays bounded by MAX_CONCURRENT_UPLOADS rather than total site
         // size. Bundle construction is sequential because `DiagramProcessor`
         // is stateful.
-        let mut tasks: tokio::task::JoinSet<Result<(), String>> = tokio::task::JoinSet::new();
+        let mut tasks: tokio::task::JoinSet<Result<bool, String>> = tokio::task::JoinSet::new();
         let config = Arc::new(self.config.clone());
         let mut processor = DiagramProcessor::new("").include_dirs(include_dirs);
-        let mut num_bundles = 0;
+        let mut uploaded = 0;
+        let mut skipped = 0;
+        let mut count = |was_uploaded: bool| {
+            if was_uploaded {
+                uploaded += 1;
+            } else {
+                skipped += 1;
+            }
+        };
 
         for doc in &documents {
             if !doc.has_content {
@@ -105,28 +146,31 @@ impl BundlePublisher {
 
             let bundle_json = serde_json::to_vec(&bundle)?;
             let key = format::page_bundle_key(&doc.path);
-            num_bundles += 1;
 
             if tasks.len() >= MAX_CONCURRENT_UPLOADS {
-                tasks
-                    .join_next()
-                    .await
-                    .expect("task set is non-empty")
-                    .expect("upload task panicked")
-                    .map_err(BundlePublishError::S3)?;
+                count(
+                    tasks
+                        .join_next()
+                        .await
+                        .expect("task set is non-empty")
+                        .expect("upload task panicked")
+                        .map_err(BundlePublishError::S3)?,
+                );
             }
 
             let client = client.clone();
             let config = Arc::clone(&config);
             tasks.spawn(async move {
-                s3::upload(&client, &config, &key, bundle_json, "application/json").await
+                s3::upload_if_changed(&client, &config, &key, bundle_json, "application/json").await
             });
         }
 
         while let Some(result) = tasks.join_next().await {
-            result
-                .expect("upload task panicked")
-                .map_err(BundlePublishError::S3)?;
+            count(
+                result
+                    .expect("upload task panicked")
+                    .map_err(BundlePublishError::S3)?,
+            );
         }
 
         // Resolve modification times for each document.
@@ -142,19 +186,33 @@ impl BundlePublisher {
         let mut manifest = Manifest::from(documents);
         manifest.mtimes = mtimes;
         let manifest_json = serde_json::to_vec(&manifest)?;
-        s3::upload(
-            &client,
-            &self.config,
-            MANIFEST_KEY,
-            manifest_json,
-            "application/json",
-        )
-        .await
-        .map_err(BundlePublishError::S3)?;
+        count(
+            s3::upload_if_changed(
+                &client,
+                &self.config,
+                MANIFEST_KEY,
+                manifest_json,
+                "application/json",
+            )
+            .await
+            .map_err(BundlePublishError::S3)?,
+        );
+
+        // Nothing uploaded means nothing cached is stale.
+        let invalidation_id = match &self.cloudfront_distribution_id {
+            Some(distribution_id) if uploaded > 0 => Some(
+                cloudfront::invalidate_prefix(&self.config, distribution_id)
+                    .await
+                    .map_err(BundlePublishError::CloudFront)?,
+            ),
+            _ => None,
+        };
 
         Ok(PublishReport {
-            uploaded: num_bundles + 1,
+            uploaded,
+            skipped,
             warnings: dedup_preserving_order(processor.warnings()),
+            invalidation_id,
         })
     }
 }
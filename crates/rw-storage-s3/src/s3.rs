@@ -3,6 +3,11 @@
 use std::fmt;
 
 use aws_sdk_s3::Client;
+use sha2::{Digest, Sha256};
+
+/// User metadata key holding the SHA-256 of an uploaded body, which
+/// [`upload_if_changed`] compares against to skip unchanged objects.
+const CONTENT_HASH_METADATA: &str = "content-sha256";
 
 /// S3 bucket configuration shared by storage and publisher.
 #[derive(Clone)]
@@ -42,13 +47,30 @@ impl fmt::Debug for S3Config {
 
 /// Build an S3 client from connection configuration.
 pub async fn build_client(config: &S3Config) -> Client {
-    let mut loader = aws_config::defaults(aws_config::BehaviorVersion::latest())
-        .region(aws_config::Region::new(config.region.clone()));
+    let mut loader = sdk_config_loader(config);
 
     if let Some(endpoint) = &config.endpoint {
         loader = loader.endpoint_url(endpoint);
     }
 
+    let sdk_config = loader.load().await;
+    let mut s3_builder = aws_sdk_s3::config::Builder::from(&sdk_config);
+
+    if config.endpoint.is_some() {
+        s3_builder = s3_builder.force_path_style(true);
+    }
+
+    Client::from_conf(s3_builder.build())
+}
+
+/// AWS SDK configuration with the region and credentials of `config`.
+///
+/// The S3 `endpoint` is left out: it names an S3-compatible service, so
+/// clients for other AWS services must not inherit it.
+pub(crate) fn sdk_config_loader(config: &S3Config) -> aws_config::ConfigLoader {
+    let mut loader = aws_config::defaults(aws_config::BehaviorVersion::latest())
+        .region(aws_config::Region::new(config.region.clone()));
+
     if let (Some(access_key_id), Some(secret_access_key)) =
         (&config.access_key_id, &config.secret_access_key)
     {
@@ -62,14 +84,7 @@ pub async fn build_client(config: &S3Config) -> Client {
         loader = loader.credentials_provider(credentials);
     }
 
-    let sdk_config = loader.load().await;
-    let mut s3_builder = aws_sdk_s3::config::Builder::from(&sdk_config);
-
-    if config.endpoint.is_some() {
-        s3_builder = s3_builder.force_path_style(true);
-    }
-
-    Client::from_conf(s3_builder.build())
+    loader
 }
 
 impl S3Config {
@@ -91,9 +106,9 @@ pub fn build_key(config: &S3Config, relative_path: &str) -> String {
 
 /// Upload a single object to S3.
 ///
-/// Builds the full key from the config and relative path, uploads the body,
-/// and logs the result. Returns `Err(String)` with the formatted error chain
-/// on failure.
+/// Builds the full key from the config and relative path, uploads the body
+/// with its content hash as user metadata, and logs the result. Returns
+/// `Err(String)` with the formatted error chain on failure.
 pub async fn upload(
     client: &Client,
     config: &S3Config,
@@ -102,12 +117,49 @@ pub async fn upload(
     content_type: &str,
 ) -> Result<(), String> {
     let key = build_key(config, relative_key);
+    let hash = content_hash(&body);
+    put(client, config, &key, body, content_type, &hash).await
+}
+
+/// Upload a single object unless S3 already holds the same content.
+///
+/// The existing object's content hash, recorded by a previous [`upload`] or
+/// [`upload_if_changed`], is read with a `HEAD` request. The body is
+/// uploaded whenever that hash is absent or differs, including when the
+/// object is missing or the `HEAD` fails for any other reason. Returns
+/// whether the object was uploaded.
+pub async fn upload_if_changed(
+    client: &Client,
+    config: &S3Config,
+    relative_key: &str,
+    body: Vec<u8>,
+    content_type: &str,
+) -> Result<bool, String> {
+    let key = build_key(config, relative_key);
+    let hash = content_hash(&body);
+    if remote_content_hash(client, config, &key).await.as_deref() == Some(hash.as_str()) {
+        tracing::debug!(key = %key, "Unchanged, skipped");
+        return Ok(false);
+    }
+    put(client, config, &key, body, content_type, &hash).await?;
+    Ok(true)
+}
+
+async fn put(
+    client: &Client,
+    config: &S3Config,
+    key: &str,
+    body: Vec<u8>,
+    content_type: &str,
+    hash: &str,
+) -> Result<(), String> {
     client
         .put_object()
         .bucket(&config.bucket)
-        .key(&key)
+        .key(key)
         .body(body.into())
         .content_type(content_type)
+        .metadata(CONTENT_HASH_METADATA, hash)
         .send()
         .await
         .map_err(|e| error_chain(&e))?;
@@ -115,6 +167,24 @@ pub async fn upload(
     Ok(())
 }
 
+/// Content hash recorded on `key`, or `None` if it has none or cannot be
+/// read.
+async fn remote_content_hash(client: &Client, config: &S3Config, key: &str) -> Option<String> {
+    let head = client
+        .head_object()
+        .bucket(&config.bucket)
+        .key(key)
+        .send()
+        .await
+        .ok()?;
+    head.metadata()?.get(CONTENT_HASH_METADATA).cloned()
+}
+
+/// Hex-encoded SHA-256 of `body`.
+fn content_hash(body: &[u8]) -> String {
+    hex::encode(Sha256::digest(body))
+}
+
 /// Format an error and its full source chain into a single string.
 pub(crate) fn error_chain(err: &dyn std::error::Error) -> String {
     let mut msgs = vec![err.to_string()];
@@ -125,3 +195,17 @@ pub(crate) fn error_chain(err: &dyn std::error::Error) -> String {
     }
     msgs.join(": ")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn content_hash_is_hex_sha256() {
+        assert_eq!(
+            content_hash(b""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert_ne!(content_hash(b"a"), content_hash(b"b"));
+    }
+}
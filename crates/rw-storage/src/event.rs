@@ -22,6 +22,10 @@ pub enum StorageEventKind {
         title: String,
         /// Ordered list of child page slugs (from `pages` metadata).
         pages: Option<Vec<String>>,
+        /// Position among siblings (from `order` metadata).
+        order: Option<i64>,
+        /// Former URL paths of the page (from `redirects` metadata).
+        redirects: Option<Vec<String>>,
     },
     /// Document was removed.
     Removed,
@@ -137,12 +141,16 @@ mod tests {
             StorageEventKind::Modified {
                 title: "test".to_owned(),
                 pages: None,
+                order: None,
+                redirects: None,
             }
         );
         assert_ne!(
             StorageEventKind::Modified {
                 title: "test".to_owned(),
                 pages: None,
+                order: None,
+                redirects: None,
             },
             StorageEventKind::Removed
         );
@@ -156,6 +164,8 @@ mod tests {
             kind: StorageEventKind::Modified {
                 title: "Guide".to_owned(),
                 pages: None,
+                order: None,
+                redirects: None,
             },
         };
 
@@ -165,6 +175,8 @@ mod tests {
             StorageEventKind::Modified {
                 title: "Guide".to_owned(),
                 pages: None,
+                order: None,
+                redirects: None,
             }
         );
     }
@@ -215,6 +227,8 @@ mod tests {
             kind: StorageEventKind::Modified {
                 title: "Test".to_owned(),
                 pages: None,
+                order: None,
+                redirects: None,
             },
         })
         .unwrap();
@@ -226,6 +240,8 @@ mod tests {
             StorageEventKind::Modified {
                 title: "Test".to_owned(),
                 pages: None,
+                order: None,
+                redirects: None,
             }
         );
     }
@@ -245,6 +261,8 @@ mod tests {
             kind: StorageEventKind::Modified {
                 title: "B".to_owned(),
                 pages: None,
+                order: None,
+                redirects: None,
             },
         })
         .unwrap();
@@ -260,6 +278,8 @@ mod tests {
             StorageEventKind::Modified {
                 title: "B".to_owned(),
                 pages: None,
+                order: None,
+                redirects: None,
             }
         );
     }
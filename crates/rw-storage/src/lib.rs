@@ -10,7 +10,8 @@
 //! # Architecture
 //!
 //! The crate provides:
-//! - [`Storage`] trait with `scan()`, `read()`, `exists()`, `mtime()`, `watch()`, and `meta()` methods
+//! - [`Storage`] trait with `scan()`, `list()`, `read()`, `exists()`, `mtime()`, `watch()`, and `meta()` methods
+//! - [`MergedStorage`] for serving several backends as one site
 //! - [`MockStorage`] for testing (behind `mock` feature flag)
 //!
 //! For filesystem storage, use the `rw-storage-fs` crate which provides [`FsStorage`](https://docs.rs/rw-storage-fs).
@@ -29,13 +30,17 @@
 //! ```
 
 mod event;
+mod merged;
 mod metadata;
 #[cfg(feature = "mock")]
 mod mock;
 mod storage;
 
 pub use event::{StorageEvent, StorageEventKind, StorageEventReceiver, WatchHandle};
+pub use merged::MergedStorage;
 pub use metadata::{Metadata, MetadataError};
 #[cfg(feature = "mock")]
 pub use mock::MockStorage;
-pub use storage::{Document, Storage, StorageError, StorageErrorKind, mtime_to_datetime};
+pub use storage::{
+    Document, Storage, StorageError, StorageErrorKind, is_direct_child, mtime_to_datetime,
+};
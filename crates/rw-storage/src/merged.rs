@@ -0,0 +1,354 @@
+//! Storage that overlays several backends into one site.
+//!
+//! Provides [`MergedStorage`], which serves the union of its layers' pages.
+//! Layers are consulted in the order they were added; when more than one
+//! provides the same URL path, the first real page (one with content) wins.
+
+use std::collections::HashMap;
+use std::sync::{Arc, mpsc};
+
+use parking_lot::Mutex;
+
+use crate::event::{StorageEvent, StorageEventKind, StorageEventReceiver, WatchHandle};
+use crate::metadata::Metadata;
+use crate::storage::{Document, Storage, StorageError, StorageErrorKind};
+
+/// One backend in a [`MergedStorage`].
+struct Layer {
+    /// Shown in warnings (e.g., the source directory).
+    name: String,
+    storage: Arc<dyn Storage>,
+}
+
+/// Storage that merges several backends into one URL space.
+///
+/// `scan()` returns every layer's documents. A path provided by more than one
+/// layer is served from the first layer that has content for it, falling back
+/// to the first layer's virtual page when none does. Two layers with content
+/// for the same path are reported through
+/// [`scan_warnings`](Storage::scan_warnings).
+///
+/// `read()`, `mtime()` and `meta()` follow the same order, and `watch()`
+/// forwards every layer's events, dropping those for paths an earlier layer
+/// shadows.
+///
+/// # Example
+///
+/// ```
+/// use std::sync::Arc;
+/// use rw_storage::{MergedStorage, Storage};
+///
+/// fn docs_with_extras(docs: Arc<dyn Storage>, extras: Arc<dyn Storage>) -> MergedStorage {
+///     MergedStorage::new()
+///         .with_layer("docs", docs)
+///         .with_layer("extras", extras)
+/// }
+/// ```
+#[derive(Default)]
+pub struct MergedStorage {
+    layers: Vec<Layer>,
+    /// Collisions found by the most recent `scan()`.
+    scan_warnings: Mutex<Vec<String>>,
+}
+
+impl MergedStorage {
+    /// Create a merged storage with no layers.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a layer after the existing ones. `name` identifies it in warnings.
+    #[must_use]
+    pub fn with_layer(mut self, name: impl Into<String>, storage: Arc<dyn Storage>) -> Self {
+        self.layers.push(Layer {
+            name: name.into(),
+            storage,
+        });
+        self
+    }
+
+    /// Return the first successful result of `op` across the layers, in order.
+    ///
+    /// A layer reporting `NotFound` is skipped; any other error is returned.
+    fn first_found<T>(
+        &self,
+        path: &str,
+        op: impl Fn(&dyn Storage) -> Result<T, StorageError>,
+    ) -> Result<T, StorageError> {
+        for layer in &self.layers {
+            match op(layer.storage.as_ref()) {
+                Err(e) if e.kind == StorageErrorKind::NotFound => {}
+                result => return result,
+            }
+        }
+        Err(StorageError::not_found(path).with_backend("Merged"))
+    }
+}
+
+impl Storage for MergedStorage {
+    fn scan(&self) -> Result<Vec<Document>, StorageError> {
+        // Index of each path's document in `documents`, and the layer it came from.
+        let mut index: HashMap<String, (usize, usize)> = HashMap::new();
+        let mut documents: Vec<Document> = Vec::new();
+        let mut warnings = Vec::new();
+
+        for (layer_idx, layer) in self.layers.iter().enumerate() {
+            for doc in layer.storage.scan()? {
+                let Some(&(slot, owner)) = index.get(&doc.path) else {
+                    index.insert(doc.path.clone(), (documents.len(), layer_idx));
+                    documents.push(doc);
+                    continue;
+                };
+                let current = &documents[slot];
+                if !doc.has_content {
+                    continue;
+                }
+                if current.has_content {
+                    warnings.push(format!(
+                        "/{} is provided by both {} and {}; using {}",
+                        doc.path, self.layers[owner].name, layer.name, self.layers[owner].name
+                    ));
+                    continue;
+                }
+                // A real page replaces an earlier layer's virtual page.
+                index.insert(doc.path.clone(), (slot, layer_idx));
+                documents[slot] = doc;
+            }
+        }
+
+        warnings.extend(
+            self.layers
+                .iter()
+                .flat_map(|layer| layer.storage.scan_warnings()),
+        );
+        *self.scan_warnings.lock() = warnings;
+        Ok(documents)
+    }
+
+    fn read(&self, path: &str) -> Result<String, StorageError> {
+        self.first_found(path, |storage| storage.read(path))
+    }
+
+    fn exists(&self, path: &str) -> bool {
+        self.layers.iter().any(|layer| layer.storage.exists(path))
+    }
+
+    fn mtime(&self, path: &str) -> Result<f64, StorageError> {
+        self.first_found(path, |storage| storage.mtime(path))
+    }
+
+    fn watch(&self) -> Result<(StorageEventReceiver, WatchHandle), StorageError> {
+        let (tx, rx) = mpsc::channel();
+        let mut handles = Vec::with_capacity(self.layers.len());
+        let layers: Vec<Arc<dyn Storage>> = self
+            .layers
+            .iter()
+            .map(|layer| Arc::clone(&layer.storage))
+            .collect();
+
+        for (layer_idx, layer) in self.layers.iter().enumerate() {
+            let (layer_rx, handle) = layer.storage.watch()?;
+            handles.push(handle);
+            let tx = tx.clone();
+            let layers = layers.clone();
+            std::thread::spawn(move || {
+                for event in layer_rx.iter() {
+                    let Some(event) = route_event(event, layer_idx, &layers) else {
+                        continue;
+                    };
+                    if tx.send(event).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+
+        // Dropping the returned handle drops every layer's handle, which
+        // closes their channels and ends the forwarding threads above.
+        let (shutdown_tx, shutdown_rx) = mpsc::channel::<()>();
+        std::thread::spawn(move || {
+            let _ = shutdown_rx.recv();
+            drop(handles);
+        });
+
+        Ok((StorageEventReceiver::new(rx), WatchHandle::new(shutdown_tx)))
+    }
+
+    fn meta(&self, path: &str) -> Result<Option<Metadata>, StorageError> {
+        for layer in &self.layers {
+            if let Some(meta) = layer.storage.meta(path)? {
+                return Ok(Some(meta));
+            }
+        }
+        Ok(None)
+    }
+
+    fn has_changed(&self) -> Result<bool, StorageError> {
+        for layer in &self.layers {
+            if layer.storage.has_changed()? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    fn scan_warnings(&self) -> Vec<String> {
+        self.scan_warnings.lock().clone()
+    }
+}
+
+/// Decide what layer `layer_idx`'s event means for the merged site.
+///
+/// Events for a path an earlier layer provides are dropped: that layer's page
+/// is the one served. A removal that uncovers a later layer's page becomes
+/// `Created`, so consumers rescan instead of dropping the page.
+fn route_event(
+    event: StorageEvent,
+    layer_idx: usize,
+    layers: &[Arc<dyn Storage>],
+) -> Option<StorageEvent> {
+    if layers[..layer_idx]
+        .iter()
+        .any(|earlier| earlier.exists(&event.path))
+    {
+        return None;
+    }
+    if event.kind == StorageEventKind::Removed
+        && layers[layer_idx + 1..]
+            .iter()
+            .any(|later| later.exists(&event.path))
+    {
+        return Some(StorageEvent {
+            path: event.path,
+            kind: StorageEventKind::Created,
+        });
+    }
+    Some(event)
+}
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use super::*;
+    use crate::MockStorage;
+
+    fn merged(first: MockStorage, second: MockStorage) -> MergedStorage {
+        MergedStorage::new()
+            .with_layer("first", Arc::new(first))
+            .with_layer("second", Arc::new(second))
+    }
+
+    #[test]
+    fn test_scan_returns_union_of_layers() {
+        let storage = merged(
+            MockStorage::new().with_file("guide", "Guide", "# Guide"),
+            MockStorage::new().with_file("faq", "FAQ", "# FAQ"),
+        );
+
+        let mut paths: Vec<_> = storage
+            .scan()
+            .unwrap()
+            .into_iter()
+            .map(|d| d.path)
+            .collect();
+        paths.sort();
+
+        assert_eq!(paths, ["faq", "guide"]);
+        assert!(storage.scan_warnings().is_empty());
+    }
+
+    #[test]
+    fn test_first_layer_wins_and_collision_is_reported() {
+        let storage = merged(
+            MockStorage::new().with_file("guide", "First", "# First"),
+            MockStorage::new().with_file("guide", "Second", "# Second"),
+        );
+
+        let docs = storage.scan().unwrap();
+
+        assert_eq!(docs.len(), 1);
+        assert_eq!(docs[0].title, "First");
+        assert_eq!(storage.read("guide").unwrap(), "# First");
+        assert_eq!(
+            storage.scan_warnings(),
+            ["/guide is provided by both first and second; using first"]
+        );
+    }
+
+    #[test]
+    fn test_real_page_replaces_earlier_virtual_page() {
+        let storage = merged(
+            MockStorage::new().with_virtual_page("api", "Api"),
+            MockStorage::new().with_file("api", "API Reference", "# API Reference"),
+        );
+
+        let docs = storage.scan().unwrap();
+
+        assert_eq!(docs.len(), 1);
+        assert_eq!(docs[0].title, "API Reference");
+        assert!(docs[0].has_content);
+        assert_eq!(storage.read("api").unwrap(), "# API Reference");
+        assert!(storage.scan_warnings().is_empty());
+    }
+
+    #[test]
+    fn test_read_missing_everywhere_is_not_found() {
+        let storage = merged(MockStorage::new(), MockStorage::new());
+
+        let err = storage.read("missing").unwrap_err();
+
+        assert_eq!(err.kind, StorageErrorKind::NotFound);
+    }
+
+    #[test]
+    fn test_layer_scan_warnings_are_included() {
+        let storage = merged(
+            MockStorage::new().with_scan_warning("a.md is ignored"),
+            MockStorage::new(),
+        );
+
+        storage.scan().unwrap();
+
+        assert_eq!(storage.scan_warnings(), ["a.md is ignored"]);
+    }
+
+    #[test]
+    fn test_route_event_drops_events_for_shadowed_paths() {
+        let first: Arc<dyn Storage> =
+            Arc::new(MockStorage::new().with_file("guide", "First", "# First"));
+        let second: Arc<dyn Storage> =
+            Arc::new(MockStorage::new().with_file("guide", "Second", "# Second"));
+        let layers = [first, second];
+        let event = |kind| StorageEvent {
+            path: "guide".to_owned(),
+            kind,
+        };
+
+        assert_eq!(
+            route_event(event(StorageEventKind::Created), 1, &layers),
+            None
+        );
+        assert_eq!(
+            route_event(event(StorageEventKind::Removed), 0, &layers),
+            Some(event(StorageEventKind::Created)),
+            "removing the first copy uncovers the second"
+        );
+    }
+
+    #[test]
+    fn test_watch_forwards_events_from_every_layer() {
+        let first = Arc::new(MockStorage::new());
+        let second = Arc::new(MockStorage::new());
+        let storage = MergedStorage::new()
+            .with_layer("first", Arc::clone(&first) as Arc<dyn Storage>)
+            .with_layer("second", Arc::clone(&second) as Arc<dyn Storage>);
+
+        let (rx, _handle) = storage.watch().unwrap();
+        first.emit_created("guide");
+        second.emit_created("faq");
+
+        let mut paths = vec![rx.recv().unwrap().path, rx.recv().unwrap().path];
+        paths.sort();
+        assert_eq!(paths, ["faq", "guide"]);
+    }
+}
@@ -9,6 +9,7 @@
 //! - `title`: Custom page title (overrides H1 extraction)
 //! - `description`: Page description for display
 //! - `page_kind`: Page kind (e.g., "domain", "guide")
+//! - `redirects`: Former URL paths that redirect to the page
 
 use serde::{Deserialize, Serialize};
 
@@ -42,6 +43,16 @@ pub struct Metadata {
     /// Ordered list of child page slugs for navigation ordering.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub pages: Option<Vec<String>>,
+
+    /// Position among siblings in navigation; lower sorts first.
+    /// Read from `order` or its alias `weight`.
+    #[serde(default, alias = "weight", skip_serializing_if = "Option::is_none")]
+    pub order: Option<i64>,
+
+    /// Former URL paths of this page (e.g. `"old/path"`) that should
+    /// redirect to it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub redirects: Option<Vec<String>>,
 }
 
 impl Metadata {
@@ -52,6 +63,8 @@ impl Metadata {
             && self.description.is_none()
             && self.page_kind.is_none()
             && self.pages.is_none()
+            && self.order.is_none()
+            && self.redirects.is_none()
     }
 }
 
@@ -117,6 +130,15 @@ mod tests {
         assert!(!meta.is_empty());
     }
 
+    #[test]
+    fn test_is_empty_with_redirects() {
+        let meta = Metadata {
+            redirects: Some(vec!["legacy".to_owned()]),
+            ..Default::default()
+        };
+        assert!(!meta.is_empty());
+    }
+
     #[test]
     fn test_metadata_equality() {
         let meta1 = Metadata {
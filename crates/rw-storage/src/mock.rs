@@ -11,7 +11,7 @@ use parking_lot::RwLock;
 
 use crate::event::{StorageEvent, StorageEventKind, StorageEventReceiver, WatchHandle};
 use crate::metadata::Metadata;
-use crate::storage::{Document, Storage, StorageError, StorageErrorKind};
+use crate::storage::{Document, Storage, StorageError, StorageErrorKind, is_direct_child};
 
 /// A one-shot/repeatable hook invoked inside a *successful* `scan()`.
 ///
@@ -69,6 +69,8 @@ pub struct MockStorage {
     scan_count: AtomicUsize,
     /// Optional hook run inside a successful `scan()` (test injection point).
     scan_hook: RwLock<ScanHook>,
+    /// Returned by `scan_warnings()`.
+    scan_warnings: RwLock<Vec<String>>,
 }
 
 impl Default for MockStorage {
@@ -84,6 +86,7 @@ impl Default for MockStorage {
             event_sender: RwLock::new(None),
             scan_count: AtomicUsize::new(0),
             scan_hook: RwLock::new(ScanHook::default()),
+            scan_warnings: RwLock::new(Vec::new()),
         }
     }
 }
@@ -109,6 +112,8 @@ impl MockStorage {
             description: None,
             origin: None,
             pages: None,
+            order: None,
+            redirects: None,
             is_dir: true,
         });
         self
@@ -133,6 +138,8 @@ impl MockStorage {
             description: None,
             origin: None,
             pages: Some(pages),
+            order: None,
+            redirects: None,
             is_dir: true,
         });
         self
@@ -157,6 +164,8 @@ impl MockStorage {
             description: None,
             origin: None,
             pages: None,
+            order: None,
+            redirects: None,
             is_dir: true,
         });
         self
@@ -182,6 +191,8 @@ impl MockStorage {
             description: None,
             origin: None,
             pages: None,
+            order: None,
+            redirects: None,
             is_dir: true,
         });
         self
@@ -201,6 +212,8 @@ impl MockStorage {
             description: None,
             origin: None,
             pages: None,
+            order: None,
+            redirects: None,
             is_dir: true,
         });
         self
@@ -223,11 +236,35 @@ impl MockStorage {
             description: None,
             origin: None,
             pages: None,
+            order: None,
+            redirects: None,
             is_dir: true,
         });
         self
     }
 
+    /// Set the navigation `order` of every document added so far at `path`.
+    #[must_use]
+    pub fn with_order(self, path: &str, order: i64) -> Self {
+        for doc in self.documents.write().iter_mut() {
+            if doc.path == path {
+                doc.order = Some(order);
+            }
+        }
+        self
+    }
+
+    /// Set the `redirects` of every document added so far at `path`.
+    #[must_use]
+    pub fn with_redirects(self, path: &str, redirects: &[&str]) -> Self {
+        for doc in self.documents.write().iter_mut() {
+            if doc.path == path {
+                doc.redirects = Some(redirects.iter().map(|&r| r.to_owned()).collect());
+            }
+        }
+        self
+    }
+
     /// Add content for a URL path.
     #[must_use]
     pub fn with_content(self, path: impl Into<String>, content: impl Into<String>) -> Self {
@@ -255,6 +292,8 @@ impl MockStorage {
             description: None,
             origin: None,
             pages: None,
+            order: None,
+            redirects: None,
             is_dir: true,
         });
         self.contents.write().insert(path, content.into());
@@ -289,6 +328,13 @@ impl MockStorage {
         self
     }
 
+    /// Add a message for `scan_warnings()` to return.
+    #[must_use]
+    pub fn with_scan_warning(self, message: impl Into<String>) -> Self {
+        self.scan_warnings.write().push(message.into());
+        self
+    }
+
     /// Set or clear the scan error at runtime (for testing reload-with-error scenarios).
     pub fn set_scan_error(&self, kind: Option<StorageErrorKind>) {
         *self.scan_error.write() = kind;
@@ -360,6 +406,8 @@ impl MockStorage {
             kind: StorageEventKind::Modified {
                 title: title.into(),
                 pages: None,
+                order: None,
+                redirects: None,
             },
         });
     }
@@ -398,11 +446,28 @@ impl Storage for MockStorage {
                 description: d.description.clone(),
                 origin: d.origin.clone(),
                 pages: d.pages.clone(),
+                order: d.order,
+                redirects: d.redirects.clone(),
                 is_dir: d.is_dir,
             })
             .collect())
     }
 
+    /// Filters the configured documents directly. Unlike the trait default,
+    /// this does not go through `scan()`, so it leaves `scan_count` and the
+    /// induced scan errors/hooks alone.
+    fn list(&self, path: &str) -> Result<Vec<Document>, StorageError> {
+        let mut children: Vec<Document> = self
+            .documents
+            .read()
+            .iter()
+            .filter(|d| is_direct_child(&d.path, path))
+            .cloned()
+            .collect();
+        children.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(children)
+    }
+
     fn read(&self, path: &str) -> Result<String, StorageError> {
         self.contents.read().get(path).cloned().ok_or_else(|| {
             StorageError::new(StorageErrorKind::NotFound)
@@ -441,6 +506,8 @@ impl Storage for MockStorage {
             description: m.description.clone(),
             page_kind: m.page_kind.clone(),
             pages: m.pages.clone(),
+            order: m.order,
+            redirects: m.redirects.clone(),
         }))
     }
 
@@ -451,6 +518,10 @@ impl Storage for MockStorage {
             None => Ok(true),
         }
     }
+
+    fn scan_warnings(&self) -> Vec<String> {
+        self.scan_warnings.read().clone()
+    }
 }
 
 #[cfg(test)]
@@ -762,6 +833,41 @@ mod tests {
         assert_eq!(calls.load(Ordering::Relaxed), 0);
     }
 
+    #[test]
+    fn test_list_root_returns_top_level_documents_only() {
+        let storage = MockStorage::new()
+            .with_document("", "Home")
+            .with_document("guide", "Guide")
+            .with_virtual_page("domain", "Domain")
+            .with_document("domain/billing", "Billing");
+
+        let paths: Vec<String> = storage
+            .list("")
+            .unwrap()
+            .into_iter()
+            .map(|d| d.path)
+            .collect();
+
+        assert_eq!(paths, ["domain", "guide"]);
+        assert_eq!(storage.scan_count(), 0);
+    }
+
+    #[test]
+    fn test_list_nested_excludes_grandchildren() {
+        let storage = MockStorage::new()
+            .with_document("domain", "Domain")
+            .with_document("domain/billing", "Billing")
+            .with_document("domain/billing/api", "API")
+            .with_document("domains", "Other");
+
+        let docs = storage.list("domain").unwrap();
+
+        assert_eq!(docs.len(), 1);
+        assert_eq!(docs[0].path, "domain/billing");
+        assert!(storage.list("domain/billing/api").unwrap().is_empty());
+        assert!(storage.list("missing").unwrap().is_empty());
+    }
+
     #[test]
     fn test_has_changed_default_returns_true() {
         let storage = MockStorage::new();
@@ -67,6 +67,14 @@ pub struct Document {
     /// Ordered list of child page slugs for navigation ordering.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub pages: Option<Vec<String>>,
+    /// Position among siblings in navigation, from metadata `order` (or
+    /// `weight`). Lower sorts first; pages without one follow.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub order: Option<i64>,
+    /// Former URL paths of this page, from metadata `redirects`. `rw-site`
+    /// maps each one to [`path`](Self::path).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub redirects: Option<Vec<String>>,
     /// True when this page's URL denotes a directory — its content comes from a
     /// directory index file (`index.md`, or the README homepage) — rather than a
     /// single file (a leaf `name.md`).
@@ -249,6 +257,33 @@ impl std::error::Error for StorageError {
     }
 }
 
+/// Whether URL path `child` sits exactly one level below `parent`.
+///
+/// ```
+/// use rw_storage::is_direct_child;
+///
+/// assert!(is_direct_child("guide", ""));
+/// assert!(is_direct_child("domain/billing", "domain"));
+/// assert!(!is_direct_child("domain/billing/api", "domain"));
+/// assert!(!is_direct_child("", ""));
+/// assert!(!is_direct_child("domains/x", "domain"));
+/// ```
+#[must_use]
+pub fn is_direct_child(child: &str, parent: &str) -> bool {
+    let rest = if parent.is_empty() {
+        child
+    } else {
+        match child
+            .strip_prefix(parent)
+            .and_then(|rest| rest.strip_prefix('/'))
+        {
+            Some(rest) => rest,
+            None => return false,
+        }
+    };
+    !rest.is_empty() && !rest.contains('/')
+}
+
 /// Storage abstraction for document scanning and retrieval.
 ///
 /// Provides a unified interface for accessing documents regardless of backend.
@@ -276,6 +311,34 @@ pub trait Storage: Send + Sync {
     /// backend unavailable).
     fn scan(&self) -> Result<Vec<Document>, StorageError>;
 
+    /// List the direct children of a URL path.
+    ///
+    /// Returns the documents one level below `path` — pages and
+    /// [virtual pages](Document) alike — sorted by path. Deeper descendants
+    /// are not included; list a child's path to descend. `path` itself is not
+    /// part of the result (`list("")` never returns the home page).
+    ///
+    /// A path with no children, including one that does not exist, yields an
+    /// empty list. The default implementation filters [`scan`](Self::scan);
+    /// backends that can enumerate a single directory cheaply override it.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - URL path (e.g., "domain", "" for root)
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError`] if the path is invalid or listing fails.
+    fn list(&self, path: &str) -> Result<Vec<Document>, StorageError> {
+        let mut children: Vec<Document> = self
+            .scan()?
+            .into_iter()
+            .filter(|doc| is_direct_child(&doc.path, path))
+            .collect();
+        children.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(children)
+    }
+
     /// Read full content for rendering.
     ///
     /// # Arguments
@@ -356,6 +419,15 @@ pub trait Storage: Send + Sync {
     fn has_changed(&self) -> Result<bool, StorageError> {
         Ok(true)
     }
+
+    /// Problems found by the most recent [`scan`](Self::scan) that did not
+    /// stop it, as human-readable messages naming the files involved (e.g. a
+    /// source file ignored because another maps to the same URL path).
+    ///
+    /// Empty before the first scan. Default returns no warnings.
+    fn scan_warnings(&self) -> Vec<String> {
+        Vec::new()
+    }
 }
 
 /// Convert an mtime in seconds since the Unix epoch into a [`DateTime<Utc>`].
@@ -479,6 +551,8 @@ mod tests {
             description: None,
             origin: None,
             pages: None,
+            order: None,
+            redirects: None,
             is_dir: true,
         };
 
@@ -499,6 +573,8 @@ mod tests {
             description: None,
             origin: None,
             pages: None,
+            order: None,
+            redirects: None,
             is_dir: true,
         };
 
@@ -519,6 +595,8 @@ mod tests {
             description: None,
             origin: None,
             pages: None,
+            order: None,
+            redirects: None,
             is_dir: true,
         };
 
@@ -538,6 +616,8 @@ mod tests {
             description: None,
             origin: None,
             pages: None,
+            order: None,
+            redirects: None,
             is_dir: true,
         };
 
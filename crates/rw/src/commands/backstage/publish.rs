@@ -1,15 +1,13 @@
 //! `rw backstage publish` command implementation.
 
 use std::path::PathBuf;
-use std::sync::Arc;
 
 use clap::Args;
 use rw_config::Config;
-use rw_storage::Storage;
-use rw_storage_fs::{FsStorage, MtimeSource};
+use rw_storage_fs::MtimeSource;
 use rw_storage_s3::{BundlePublisher, PublishReport};
 
-use crate::commands::S3Args;
+use crate::commands::{S3Args, docs_storage, source_dirs_display};
 use crate::error::CliError;
 use crate::output::Output;
 
@@ -36,6 +34,11 @@ pub(crate) struct PublishArgs {
     #[arg(long, conflicts_with = "config")]
     project_dir: Option<PathBuf>,
 
+    /// `CloudFront` distribution to invalidate after the upload, for a CDN
+    /// serving the bucket. The entity's whole prefix is invalidated.
+    #[arg(long, env = "RW_CLOUDFRONT_DISTRIBUTION_ID")]
+    cloudfront_distribution_id: Option<String>,
+
     /// Exit with a non-zero status when diagram warnings are emitted.
     ///
     /// Bundles are still uploaded — strict mode only affects the exit code.
@@ -54,10 +57,7 @@ impl PublishArgs {
             None => Config::load(self.config.as_deref(), None)?,
         };
 
-        output.info(&format!(
-            "Source: {}",
-            config.docs_resolved.source_dir.display()
-        ));
+        output.info(&format!("Source: {}", source_dirs_display(&config)));
         output.info(&format!(
             "Publishing to s3://{}/{}",
             self.s3.bucket, self.s3.entity
@@ -67,17 +67,13 @@ impl PublishArgs {
         // stable across CI checkouts (fs mtime would be the meaningless
         // checkout time). FsStorage defaults to filesystem mtime, so git is
         // selected explicitly here.
-        let storage: Arc<dyn Storage> = Arc::new(
-            FsStorage::with_meta_filename(
-                config.project_dir.clone(),
-                config.docs_resolved.source_dir.clone(),
-                &config.metadata.name,
-            )
-            .with_mtime_source(MtimeSource::Git),
-        );
+        let storage = docs_storage(&config, MtimeSource::Git);
 
         let include_dirs = config.diagrams_resolved.include_dirs;
-        let publisher = BundlePublisher::new(self.s3.into_config());
+        let mut publisher = BundlePublisher::new(self.s3.into_config());
+        if let Some(distribution_id) = self.cloudfront_distribution_id {
+            publisher = publisher.with_cloudfront_distribution(distribution_id);
+        }
 
         let rt = tokio::runtime::Runtime::new()?;
         let report = rt.block_on(publisher.publish(storage.as_ref(), &include_dirs))?;
@@ -91,7 +87,13 @@ impl PublishArgs {
 ///
 /// Extracted as a free function so it can be unit-tested without S3 access.
 fn finish_publish(report: &PublishReport, strict: bool, output: &Output) -> Result<(), CliError> {
-    output.success(&format!("Published {} files", report.uploaded));
+    output.success(&format!(
+        "Published {} files ({} unchanged)",
+        report.uploaded, report.skipped
+    ));
+    if let Some(id) = &report.invalidation_id {
+        output.info(&format!("Created CloudFront invalidation {id}"));
+    }
 
     if !report.warnings.is_empty() {
         output.warning(&format!("Diagram warnings ({}):", report.warnings.len()));
@@ -116,7 +118,9 @@ mod tests {
     fn report(uploaded: usize, warnings: &[&str]) -> PublishReport {
         PublishReport {
             uploaded,
+            skipped: 0,
             warnings: warnings.iter().map(|s| (*s).to_owned()).collect(),
+            invalidation_id: None,
         }
     }
 
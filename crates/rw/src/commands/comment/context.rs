@@ -4,9 +4,10 @@ use std::sync::Arc;
 use rw_cache::NullCache;
 use rw_comments::SqliteCommentStore;
 use rw_config::Config;
-use rw_site::{PageRendererConfig, Site};
-use rw_storage_fs::FsStorage;
+use rw_site::{DiagramFormat, PageRendererConfig, Site};
+use rw_storage_fs::MtimeSource;
 
+use crate::commands::{docs_layer, docs_storage, source_dirs_display};
 use crate::error::CliError;
 
 /// Per-invocation context shared across subcommands.
@@ -24,22 +25,25 @@ impl Context {
     }
 }
 
-/// Build an [`FsStorage`] over the project's docs directory.
-fn build_storage(config: &Config) -> FsStorage {
-    FsStorage::with_meta_filename(
-        config.project_dir.clone(),
-        config.docs_resolved.source_dir.clone(),
-        &config.metadata.name,
-    )
-}
-
 /// Build a read-only [`Site`] over the project's docs for the comment CLI.
 pub(super) fn build_site(config: &Config) -> Site {
-    let storage: Arc<dyn rw_storage::Storage> = Arc::new(build_storage(config));
+    let storage = docs_storage(config, MtimeSource::Filesystem);
     let cache: Arc<dyn rw_cache::Cache> = Arc::new(NullCache);
     let renderer_config = PageRendererConfig {
         kroki_url: config.diagrams_resolved.kroki_url.clone(),
         include_dirs: config.diagrams_resolved.include_dirs.clone(),
+        diagram_format: config
+            .diagrams_resolved
+            .default_format
+            .as_deref()
+            .and_then(DiagramFormat::parse)
+            .unwrap_or_default(),
+        kroki_headers: config
+            .diagrams_resolved
+            .kroki_headers
+            .clone()
+            .into_iter()
+            .collect(),
         ..PageRendererConfig::default()
     };
     Site::new(storage, cache, renderer_config)
@@ -65,12 +69,19 @@ pub(super) fn document_url_path(config: &Config, document: &str) -> Result<Strin
         return Ok(document.trim_start_matches('/').to_owned());
     }
 
-    let mut urls = build_storage(config).url_paths_for_source(Path::new(document));
+    // Ask every source directory, so a file from any of them resolves.
+    let mut urls: Vec<String> = config
+        .docs_resolved
+        .source_dirs
+        .iter()
+        .flat_map(|dir| docs_layer(config, dir).url_paths_for_source(Path::new(document)))
+        .collect();
     urls.sort();
+    urls.dedup();
     match urls.len() {
         0 => Err(CliError::Validation(format!(
             "'{document}' is not a markdown page under {}",
-            config.docs_resolved.source_dir.display()
+            source_dirs_display(config)
         ))),
         1 => Ok(urls.pop().unwrap()),
         _ => Err(CliError::Validation(format!(
@@ -4,13 +4,15 @@ use std::io::{IsTerminal, Read, Write};
 use std::path::PathBuf;
 
 use clap::Args;
+use console::Style;
 use rw_config::{CliSettings, Config};
-use rw_confluence::{RenderOptions, RenderOutput, render};
+use rw_confluence::{CodeMacroOptions, RenderOptions, RenderOutput, StorageDiff, render};
 
 use crate::error::CliError;
 
 /// Arguments for `rw confluence render`.
 #[derive(Args)]
+#[allow(clippy::struct_excessive_bools)]
 pub(crate) struct RenderArgs {
     /// Path to the markdown file to render.
     markdown_file: PathBuf,
@@ -36,10 +38,50 @@ pub(crate) struct RenderArgs {
     #[arg(long)]
     no_toc: bool,
 
+    /// Shallowest heading level the TOC macro lists (1-6).
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=6))]
+    toc_min_level: Option<u8>,
+
+    /// Deepest heading level the TOC macro lists (1-6).
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=6))]
+    toc_max_level: Option<u8>,
+
+    /// Put a Confluence anchor macro before every heading, named after the
+    /// heading's slug id, so `#section` links resolve in Confluence.
+    #[arg(long)]
+    heading_anchors: bool,
+
+    /// Leave line numbers off code blocks (they are numbered by default).
+    #[arg(long)]
+    no_line_numbers: bool,
+
+    /// Confluence theme for code blocks (e.g. `Midnight`, `Eclipse`).
+    #[arg(long, value_name = "THEME")]
+    code_theme: Option<String>,
+
+    /// Render code blocks collapsed.
+    #[arg(long)]
+    collapse_code: bool,
+
+    /// Render blockquotes as plain quotations instead of `info` panels.
+    /// GitHub alerts (`> [!NOTE]`) stay panels.
+    #[arg(long)]
+    plain_blockquotes: bool,
+
     /// Exit non-zero if any warning was emitted.
     #[arg(long)]
     strict: bool,
 
+    /// Print a unified diff of the current page (XHTML on stdin) against the
+    /// rendered body to stderr. Comment markers are ignored.
+    #[arg(long)]
+    diff: bool,
+
+    /// With `--diff`, exit with status 4 when the rendered body differs from
+    /// the current page, so a script can skip publishing unchanged pages.
+    #[arg(long, requires = "diff")]
+    exit_code: bool,
+
     /// Path to `rw.toml` (default: auto-discover).
     #[arg(short, long)]
     config: Option<PathBuf>,
@@ -47,6 +89,14 @@ pub(crate) struct RenderArgs {
 
 impl RenderArgs {
     pub(crate) fn execute(self) -> Result<(), CliError> {
+        if let (Some(min), Some(max)) = (self.toc_min_level, self.toc_max_level)
+            && min > max
+        {
+            return Err(CliError::Validation(format!(
+                "--toc-min-level ({min}) must not exceed --toc-max-level ({max})"
+            )));
+        }
+
         // Load `rw.toml` for [diagrams] defaults.
         let cli_settings = CliSettings {
             kroki_url: self.kroki_url,
@@ -57,9 +107,12 @@ impl RenderArgs {
         let markdown = std::fs::read_to_string(&self.markdown_file)?;
 
         let current_xhtml = read_current_xhtml_from_stdin()?;
+        // Without a current page on stdin, everything rendered is new.
+        let diff_base = self.diff.then(|| current_xhtml.clone().unwrap_or_default());
 
         let opts = RenderOptions {
             kroki_url: config.diagrams_resolved.kroki_url,
+            kroki_headers: config.diagrams_resolved.kroki_headers.into_iter().collect(),
             include_dirs: {
                 let mut dirs = config.diagrams_resolved.include_dirs;
                 dirs.extend(self.include_dirs);
@@ -67,14 +120,36 @@ impl RenderArgs {
             },
             extract_title: !self.no_extract_title,
             prepend_toc: !self.no_toc,
+            toc_min_level: self.toc_min_level,
+            toc_max_level: self.toc_max_level,
+            heading_anchors: self.heading_anchors,
+            code_macro: CodeMacroOptions {
+                line_numbers: !self.no_line_numbers,
+                theme: self.code_theme,
+                collapse: self.collapse_code,
+            },
+            plain_blockquotes: self.plain_blockquotes,
             current_xhtml,
         };
 
         if self.out == "-" {
-            run_stdout_mode(&markdown, opts, self.strict)
+            run_stdout_mode(
+                &markdown,
+                opts,
+                diff_base.as_deref(),
+                self.strict,
+                self.exit_code,
+            )
         } else {
             let dir = PathBuf::from(&self.out);
-            run_dir_mode(&markdown, &dir, opts, self.strict)
+            run_dir_mode(
+                &markdown,
+                &dir,
+                opts,
+                diff_base.as_deref(),
+                self.strict,
+                self.exit_code,
+            )
         }
     }
 }
@@ -83,21 +158,33 @@ fn run_dir_mode(
     markdown: &str,
     out_dir: &std::path::Path,
     opts: RenderOptions,
+    diff_base: Option<&str>,
     strict: bool,
+    exit_code: bool,
 ) -> Result<(), CliError> {
     let result = render(markdown, out_dir, opts)?;
 
     print_diagnostics(&result);
+    let changed = diff_base.is_some_and(|current| print_diff(current, &result.xhtml));
 
     if strict && (!result.warnings.is_empty() || !result.unmatched_comments.is_empty()) {
         return Err(CliError::DiagramWarningsInStrictMode {
             count: result.warnings.len() + result.unmatched_comments.len(),
         });
     }
+    if exit_code && changed {
+        return Err(CliError::DiffHasChanges);
+    }
     Ok(())
 }
 
-fn run_stdout_mode(markdown: &str, opts: RenderOptions, strict: bool) -> Result<(), CliError> {
+fn run_stdout_mode(
+    markdown: &str,
+    opts: RenderOptions,
+    diff_base: Option<&str>,
+    strict: bool,
+    exit_code: bool,
+) -> Result<(), CliError> {
     let tmp = tempfile::tempdir()?;
     let result = render(markdown, tmp.path(), opts)?;
 
@@ -115,12 +202,16 @@ fn run_stdout_mode(markdown: &str, opts: RenderOptions, strict: bool) -> Result<
     stdout.write_all(result.xhtml.as_bytes())?;
 
     print_diagnostics(&result);
+    let changed = diff_base.is_some_and(|current| print_diff(current, &result.xhtml));
 
     if strict && (!result.warnings.is_empty() || !result.unmatched_comments.is_empty()) {
         return Err(CliError::DiagramWarningsInStrictMode {
             count: result.warnings.len() + result.unmatched_comments.len(),
         });
     }
+    if exit_code && changed {
+        return Err(CliError::DiffHasChanges);
+    }
     Ok(())
 }
 
@@ -148,6 +239,37 @@ fn print_diagnostics(result: &RenderOutput) {
     }
 }
 
+/// Print the storage diff to stderr, colored when stderr is a terminal, and
+/// return whether there were changes.
+///
+/// Stderr like the other diagnostics, so `--out -` keeps stdout to the body.
+fn print_diff(current: &str, rendered: &str) -> bool {
+    let diff = StorageDiff::new(current, rendered);
+    let mut stderr = std::io::stderr().lock();
+    if !diff.has_changes() {
+        let _ = writeln!(stderr, "diff: no changes");
+        return false;
+    }
+    let removed = Style::new().red().for_stderr();
+    let added = Style::new().green().for_stderr();
+    let hunk = Style::new().cyan().for_stderr();
+    for line in diff.unified().lines() {
+        let styled = if line.starts_with("---") || line.starts_with("+++") {
+            Style::new().bold().for_stderr().apply_to(line)
+        } else if line.starts_with('-') {
+            removed.apply_to(line)
+        } else if line.starts_with('+') {
+            added.apply_to(line)
+        } else if line.starts_with("@@") {
+            hunk.apply_to(line)
+        } else {
+            Style::new().for_stderr().apply_to(line)
+        };
+        let _ = writeln!(stderr, "{styled}");
+    }
+    true
+}
+
 fn read_current_xhtml_from_stdin() -> Result<Option<String>, CliError> {
     if std::io::stdin().is_terminal() {
         return Ok(None);
@@ -0,0 +1,29 @@
+//! Export subcommand group.
+
+mod pdf;
+
+use clap::Subcommand;
+
+use pdf::PdfArgs;
+
+use crate::error::CliError;
+
+/// Offline export commands.
+#[derive(Subcommand)]
+pub(crate) enum ExportCommand {
+    /// Print a page, or the whole site, to PDF through headless Chromium.
+    Pdf(PdfArgs),
+}
+
+impl ExportCommand {
+    /// Execute the export subcommand.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the subcommand fails.
+    pub(crate) fn execute(self) -> Result<(), CliError> {
+        match self {
+            Self::Pdf(args) => args.execute(),
+        }
+    }
+}
@@ -0,0 +1,472 @@
+//! `rw export pdf` command.
+//!
+//! Pages are rendered by the same pipeline as `rw serve`, joined into one HTML
+//! document with a print stylesheet, and printed by headless Chromium. No PDF
+//! library is involved, so the output looks like the browser view.
+
+use std::collections::{HashMap, HashSet};
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Arc;
+
+use clap::Args;
+use rayon::prelude::*;
+use rw_config::Config;
+use rw_renderer::escape_html;
+use rw_site::{DiagramFormat, NavItem, PageRendererConfig, Site};
+use rw_storage_fs::MtimeSource;
+
+use crate::commands::{docs_storage, source_dirs_display};
+use crate::error::CliError;
+use crate::output::Output;
+
+/// Stylesheet embedded in every exported document.
+const PRINT_CSS: &str = include_str!("print.css");
+
+/// Executables looked up on `PATH`, in order, when no browser is given.
+const CHROME_CANDIDATES: &[&str] = &[
+    "chromium",
+    "chromium-browser",
+    "google-chrome",
+    "google-chrome-stable",
+    "chrome",
+];
+
+/// macOS app bundles, which are not on `PATH`.
+const CHROME_APP_PATHS: &[&str] = &[
+    "/Applications/Google Chrome.app/Contents/MacOS/Google Chrome",
+    "/Applications/Chromium.app/Contents/MacOS/Chromium",
+];
+
+/// Arguments for `rw export pdf`.
+#[derive(Args)]
+pub(crate) struct PdfArgs {
+    /// URL path of the page to export (e.g. `guide/install`). Omit it to
+    /// export the whole site in navigation order.
+    page: Option<String>,
+
+    /// Output file (default: `<page>.pdf`, or `site.pdf` for the whole site).
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Cover page title (default: the page title, or the homepage title for
+    /// the whole site).
+    #[arg(long)]
+    title: Option<String>,
+
+    /// Leave out the cover page.
+    #[arg(long)]
+    no_cover: bool,
+
+    /// Write the HTML document that would be printed instead of a PDF. Needs
+    /// no browser.
+    #[arg(long)]
+    html: bool,
+
+    /// Pages rendered at once in a whole-site export (default: one per CPU).
+    #[arg(short, long)]
+    jobs: Option<NonZeroUsize>,
+
+    /// In a whole-site export, fail on the first page that cannot be
+    /// rendered instead of leaving it out with a warning.
+    #[arg(long)]
+    strict: bool,
+
+    /// Chromium or Chrome executable (default: search `PATH`).
+    #[arg(long, env = "RW_CHROME")]
+    chrome: Option<PathBuf>,
+
+    /// Path to configuration file (default: auto-discover rw.toml).
+    #[arg(short, long)]
+    config: Option<PathBuf>,
+
+    /// Root the project at this directory instead of discovering `rw.toml`
+    /// upward from the current directory.
+    #[arg(long, conflicts_with = "config")]
+    project_dir: Option<PathBuf>,
+}
+
+impl PdfArgs {
+    pub(crate) fn execute(self) -> Result<(), CliError> {
+        let output = Output::new();
+        let config = match self.project_dir.as_deref() {
+            Some(dir) => Config::load_from_dir(dir, None)?,
+            None => Config::load(self.config.as_deref(), None)?,
+        };
+        let site = build_site(&config);
+
+        let single = self.page.as_deref().map(|p| p.trim_matches('/').to_owned());
+        let paths = match &single {
+            Some(path) => vec![path.clone()],
+            None => reading_order(&site)?,
+        };
+
+        // Diagrams make rendering the slow part of a whole-site export, so
+        // pages render in parallel. `collect` keeps navigation order.
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(self.jobs.map_or(0, NonZeroUsize::get))
+            .build()
+            .map_err(|e| CliError::Validation(format!("cannot start render threads: {e}")))?;
+        let rendered: Vec<_> = pool.install(|| {
+            paths
+                .into_par_iter()
+                .map(|path| {
+                    let result = site.render(&path);
+                    (path, result)
+                })
+                .collect()
+        });
+
+        let mut pages = Vec::with_capacity(rendered.len());
+        for (path, result) in rendered {
+            let result = match result {
+                Ok(result) => result,
+                // A single page has nothing else to export.
+                Err(e) if single.is_some() => return Err(e.into()),
+                Err(e) if self.strict => {
+                    return Err(CliError::Validation(format!("/{path}: {e}")));
+                }
+                Err(e) => {
+                    output.warning(&format!("/{path}: skipped: {e}"));
+                    continue;
+                }
+            };
+            for warning in &result.warnings {
+                output.warning(&format!("/{path}: {warning}"));
+            }
+            // Directories without an index page only add an empty sheet.
+            if !result.has_content && single.is_none() {
+                continue;
+            }
+            pages.push(ExportedPage {
+                has_heading: result.title.is_some(),
+                title: site
+                    .page_title(&path)
+                    .or(result.title)
+                    .unwrap_or_else(|| path.clone()),
+                description: result.metadata.and_then(|m| m.description),
+                html: result.html,
+                path,
+            });
+        }
+        if pages.is_empty() {
+            return Err(CliError::Validation(format!(
+                "no pages to export under {}",
+                source_dirs_display(&config)
+            )));
+        }
+
+        let cover = (!self.no_cover).then(|| Cover {
+            title: self.title.clone().unwrap_or_else(|| pages[0].title.clone()),
+            description: pages[0].description.clone(),
+        });
+        let document = build_document(cover.as_ref(), &pages);
+
+        let extension = if self.html { "html" } else { "pdf" };
+        let out = self
+            .output
+            .unwrap_or_else(|| default_output(single.as_deref(), extension));
+
+        if self.html {
+            std::fs::write(&out, document)?;
+        } else {
+            let chrome = find_chrome(self.chrome)?;
+            print_to_pdf(&chrome, &document, &out)?;
+        }
+        output.success(&format!(
+            "Exported {} page(s) to {}",
+            pages.len(),
+            out.display()
+        ));
+        Ok(())
+    }
+}
+
+/// Build a read-only [`Site`] over the project's docs, caching diagrams the
+/// way `rw serve` does.
+fn build_site(config: &Config) -> Site {
+    let cache: Arc<dyn rw_cache::Cache> = if config.docs_resolved.cache_enabled {
+        Arc::new(rw_cache::FileCache::new(
+            config.docs_resolved.cache_dir(),
+            env!("CARGO_PKG_VERSION"),
+        ))
+    } else {
+        Arc::new(rw_cache::NullCache)
+    };
+    let storage = docs_storage(config, MtimeSource::Filesystem);
+    let renderer_config = PageRendererConfig {
+        extract_title: true,
+        kroki_url: config.diagrams_resolved.kroki_url.clone(),
+        include_dirs: config.diagrams_resolved.include_dirs.clone(),
+        diagram_format: config
+            .diagrams_resolved
+            .default_format
+            .as_deref()
+            .and_then(DiagramFormat::parse)
+            .unwrap_or_default(),
+        kroki_headers: config
+            .diagrams_resolved
+            .kroki_headers
+            .clone()
+            .into_iter()
+            .collect(),
+        toc_max_depth: config.docs_resolved.toc_max_depth,
+        math: config.docs_resolved.math,
+        // The exported document loads no mermaid.js, so a client-side
+        // diagram would print as its source.
+        client_side_mermaid: false,
+    };
+    Site::new(storage, cache, renderer_config)
+}
+
+/// Every page path in navigation order: the homepage, then each item before
+/// its children. Sections, which navigation shows as leaves, are entered.
+fn reading_order(site: &Site) -> Result<Vec<String>, CliError> {
+    let section_refs: HashMap<String, String> = site
+        .list_sections()?
+        .into_iter()
+        .map(|section| (section.path, section.section_ref))
+        .collect();
+    let mut order = Vec::new();
+    let mut seen = HashSet::new();
+    if site.has_page("")? {
+        seen.insert(String::new());
+        order.push(String::new());
+    }
+    let root = site.navigation(None)?;
+    walk(site, &root.items, &section_refs, &mut seen, &mut order)?;
+    Ok(order)
+}
+
+fn walk(
+    site: &Site,
+    items: &[NavItem],
+    section_refs: &HashMap<String, String>,
+    seen: &mut HashSet<String>,
+    order: &mut Vec<String>,
+) -> Result<(), CliError> {
+    for item in items {
+        if !seen.insert(item.path.clone()) {
+            continue;
+        }
+        order.push(item.path.clone());
+        if let Some(section_ref) = section_refs.get(&item.path)
+            && item.children.is_empty()
+        {
+            let scoped = site.navigation(Some(section_ref))?;
+            walk(site, &scoped.items, section_refs, seen, order)?;
+        } else {
+            walk(site, &item.children, section_refs, seen, order)?;
+        }
+    }
+    Ok(())
+}
+
+/// A rendered page ready to be placed in the document.
+struct ExportedPage {
+    path: String,
+    title: String,
+    /// Whether `html` starts with the page's own H1. Pages titled by metadata
+    /// or file name get one added.
+    has_heading: bool,
+    description: Option<String>,
+    html: String,
+}
+
+/// Content of the cover page.
+struct Cover {
+    title: String,
+    description: Option<String>,
+}
+
+/// Join the pages into one printable HTML document.
+///
+/// Each page starts on a new sheet. Links between exported pages are pointed
+/// at the page in the document, since `/guide` means nothing in a PDF.
+fn build_document(cover: Option<&Cover>, pages: &[ExportedPage]) -> String {
+    let doc_title = cover.map_or(pages[0].title.as_str(), |c| c.title.as_str());
+    let mut html = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>\n{PRINT_CSS}</style>\n</head>\n<body>\n",
+        escape_html(doc_title)
+    );
+    if let Some(cover) = cover {
+        html.push_str("<section class=\"cover\">\n<h1>");
+        html.push_str(&escape_html(&cover.title));
+        html.push_str("</h1>\n");
+        if let Some(description) = &cover.description {
+            html.push_str("<p>");
+            html.push_str(&escape_html(description));
+            html.push_str("</p>\n");
+        }
+        html.push_str("</section>\n");
+    }
+    for page in pages {
+        html.push_str("<section class=\"page\" id=\"");
+        html.push_str(&page_anchor(&page.path));
+        html.push_str("\">\n");
+        if !page.has_heading {
+            html.push_str("<h1>");
+            html.push_str(&escape_html(&page.title));
+            html.push_str("</h1>\n");
+        }
+        html.push_str(&link_internally(&page.html, pages));
+        html.push_str("\n</section>\n");
+    }
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+/// Element id of an exported page.
+fn page_anchor(path: &str) -> String {
+    format!("page-{path}")
+}
+
+/// Rewrite `href="/<path>"` links to exported pages into in-document anchors.
+fn link_internally(html: &str, pages: &[ExportedPage]) -> String {
+    let mut html = html.to_owned();
+    for page in pages {
+        html = html.replace(
+            &format!("href=\"/{}\"", page.path),
+            &format!("href=\"#{}\"", page_anchor(&page.path)),
+        );
+    }
+    html
+}
+
+/// `guide/install` exports to `install.pdf`, the homepage to `index.pdf`, and
+/// the whole site to `site.pdf`.
+fn default_output(page: Option<&str>, extension: &str) -> PathBuf {
+    let stem = match page {
+        None => "site",
+        Some("") => "index",
+        Some(path) => path.rsplit('/').next().unwrap_or(path),
+    };
+    PathBuf::from(format!("{stem}.{extension}"))
+}
+
+/// The browser to print with: `explicit` if given, else the first
+/// [`CHROME_CANDIDATES`] entry on `PATH`, else a [`CHROME_APP_PATHS`] bundle.
+fn find_chrome(explicit: Option<PathBuf>) -> Result<PathBuf, CliError> {
+    if let Some(path) = explicit {
+        return Ok(path);
+    }
+    let dirs: Vec<PathBuf> = std::env::var_os("PATH")
+        .map(|path| std::env::split_paths(&path).collect())
+        .unwrap_or_default();
+    CHROME_CANDIDATES
+        .iter()
+        .flat_map(|name| dirs.iter().map(move |dir| dir.join(name)))
+        .chain(CHROME_APP_PATHS.iter().map(PathBuf::from))
+        .find(|candidate| candidate.is_file())
+        .ok_or(CliError::ChromeNotFound)
+}
+
+/// Print `document` to `out` with headless Chromium.
+fn print_to_pdf(chrome: &Path, document: &str, out: &Path) -> Result<(), CliError> {
+    let tmp = tempfile::tempdir()?;
+    let html_path = tmp.path().join("export.html");
+    std::fs::write(&html_path, document)?;
+    // Chromium resolves a relative `--print-to-pdf` against its own working
+    // directory, which is not guaranteed to be ours.
+    let out = std::path::absolute(out)?;
+
+    let result = Command::new(chrome)
+        .arg("--headless")
+        .arg("--disable-gpu")
+        .arg("--no-pdf-header-footer")
+        .arg(format!("--print-to-pdf={}", out.display()))
+        .arg(&html_path)
+        .output()
+        .map_err(|e| CliError::PdfPrint(format!("cannot run {}: {e}", chrome.display())))?;
+    if !result.status.success() {
+        return Err(CliError::PdfPrint(format!(
+            "{} exited with {}: {}",
+            chrome.display(),
+            result.status,
+            String::from_utf8_lossy(&result.stderr).trim()
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(path: &str, title: &str, html: &str) -> ExportedPage {
+        ExportedPage {
+            path: path.to_owned(),
+            title: title.to_owned(),
+            has_heading: false,
+            description: None,
+            html: html.to_owned(),
+        }
+    }
+
+    #[test]
+    fn build_document_puts_cover_before_pages() {
+        let cover = Cover {
+            title: "Handbook <v2>".to_owned(),
+            description: Some("For reviewers".to_owned()),
+        };
+        let pages = [page("", "Home", "<p>Hi</p>"), page("guide", "Guide", "")];
+
+        let html = build_document(Some(&cover), &pages);
+
+        let cover_at = html.find("<section class=\"cover\">").unwrap();
+        let home_at = html.find("id=\"page-\"").unwrap();
+        let guide_at = html.find("id=\"page-guide\"").unwrap();
+        assert!(cover_at < home_at && home_at < guide_at, "{html}");
+        assert!(html.contains("<h1>Handbook &lt;v2&gt;</h1>"), "{html}");
+        assert!(
+            html.contains("<h1>Guide</h1>"),
+            "untitled page gets a heading"
+        );
+        assert!(html.contains("<p>For reviewers</p>"), "{html}");
+        assert!(html.contains("@page"), "print stylesheet is embedded");
+    }
+
+    #[test]
+    fn build_document_without_cover() {
+        let html = build_document(None, &[page("guide", "Guide", "<p>Body</p>")]);
+
+        assert!(!html.contains("class=\"cover\""));
+        assert!(html.contains("<title>Guide</title>"));
+    }
+
+    #[test]
+    fn links_to_exported_pages_become_anchors() {
+        let pages = [
+            page("guide", "Guide", ""),
+            page("guide/install", "Install", ""),
+        ];
+
+        let html = link_internally(
+            r#"<a href="/guide">G</a> <a href="/guide/install">I</a> <a href="/other">O</a>"#,
+            &pages,
+        );
+
+        assert_eq!(
+            html,
+            r##"<a href="#page-guide">G</a> <a href="#page-guide/install">I</a> <a href="/other">O</a>"##
+        );
+    }
+
+    #[test]
+    fn default_output_is_named_after_the_page() {
+        assert_eq!(default_output(None, "pdf"), PathBuf::from("site.pdf"));
+        assert_eq!(default_output(Some(""), "pdf"), PathBuf::from("index.pdf"));
+        assert_eq!(
+            default_output(Some("guide/install"), "html"),
+            PathBuf::from("install.html")
+        );
+    }
+
+    #[test]
+    fn find_chrome_prefers_explicit_path() {
+        let path = PathBuf::from("/opt/chrome/chrome");
+
+        assert_eq!(find_chrome(Some(path.clone())).unwrap(), path);
+    }
+}
@@ -2,18 +2,59 @@
 
 pub(crate) mod backstage;
 pub(crate) mod comment;
+pub(crate) mod config;
 pub(crate) mod confluence;
+pub(crate) mod export;
 pub(crate) mod serve;
 pub(crate) mod update;
 pub(crate) use backstage::BackstageCommand;
 pub(crate) use comment::CommentCommand;
+pub(crate) use config::ConfigCommand;
 pub(crate) use confluence::ConfluenceCommand;
+pub(crate) use export::ExportCommand;
 pub(crate) use serve::ServeArgs;
 pub(crate) use update::UpdateArgs;
 
+use std::path::Path;
+use std::sync::Arc;
+
 use clap::Args;
+use rw_config::Config;
+use rw_storage::Storage;
+use rw_storage_fs::{FsStorage, MtimeSource};
 use rw_storage_s3::S3Config;
 
+/// Build the [`FsStorage`] for one of the project's source directories.
+pub(crate) fn docs_layer(config: &Config, source_dir: &Path) -> FsStorage {
+    FsStorage::with_meta_filename(
+        config.project_dir.clone(),
+        source_dir.to_path_buf(),
+        &config.metadata.name,
+    )
+    .with_extensions(&config.docs_resolved.extensions)
+    .with_case_insensitive(config.docs_resolved.case_insensitive)
+    .with_homepage(config.docs_resolved.homepage.clone())
+}
+
+/// The project's source directories as shown to the user, comma-separated.
+pub(crate) fn source_dirs_display(config: &Config) -> String {
+    config
+        .docs_resolved
+        .source_dirs
+        .iter()
+        .map(|dir| dir.display().to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Build the storage over every source directory, merged as `rw serve`
+/// merges them.
+pub(crate) fn docs_storage(config: &Config, mtime_source: MtimeSource) -> Arc<dyn Storage> {
+    rw_storage_fs::source_dirs_storage(&config.docs_resolved.source_dirs, |_, source_dir| {
+        docs_layer(config, source_dir).with_mtime_source(mtime_source)
+    })
+}
+
 /// Shared S3 CLI arguments used by backstage publish commands.
 #[derive(Args)]
 pub(crate) struct S3Args {
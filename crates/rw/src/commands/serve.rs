@@ -7,6 +7,7 @@ use clap::Args;
 use rw_config::{CliSettings, Config};
 use rw_server::{bind_listener, run_server, server_config_from_rw_config};
 
+use crate::commands::source_dirs_display;
 use crate::error::CliError;
 use crate::output::Output;
 
@@ -39,6 +40,11 @@ pub(crate) struct ServeArgs {
     #[arg(short, long)]
     port: Option<u16>,
 
+    /// Try the next free port when the chosen one is in use, even if it was
+    /// set explicitly.
+    #[arg(long)]
+    port_auto: bool,
+
     /// Kroki server URL for diagram rendering (overrides config).
     #[arg(long)]
     kroki_url: Option<String>,
@@ -90,6 +96,7 @@ impl ServeArgs {
         let cli_settings = CliSettings {
             host: self.host,
             port: self.port,
+            port_auto: self.port_auto.then_some(true),
             cache_enabled,
             kroki_url: self.kroki_url,
             live_reload_enabled,
@@ -105,10 +112,11 @@ impl ServeArgs {
         ensure_data_dir(&config.docs_resolved.data_dir)?;
 
         // Bind up front so we report the port the server actually listens on.
-        // An explicit port (`-p` or `[server].port`) is a hard requirement; the
-        // default port falls back to the next free one when it's busy.
+        // An explicit port (`-p` or `[server].port`) is a hard requirement
+        // unless `port_auto` is on; the default port falls back to the next
+        // free one when it's busy.
         let requested_port = config.server.port;
-        let allow_fallback = !config.server.port_explicit;
+        let allow_fallback = !config.server.port_explicit || config.server.port_auto;
         let listener = bind_listener(&config.server.host, requested_port, allow_fallback).await?;
         let bound = listener.local_addr()?;
         if bound.port() != requested_port {
@@ -122,7 +130,7 @@ impl ServeArgs {
         output.info(&format!("Starting server on http://{bound}"));
         output.info(&format!(
             "Source directory: {}",
-            config.docs_resolved.source_dir.display()
+            source_dirs_display(&config)
         ));
 
         if config.docs_resolved.cache_enabled {
@@ -154,6 +162,8 @@ impl ServeArgs {
         let mut server_config =
             server_config_from_rw_config(&config, version.to_owned(), self.verbose);
         server_config.embedded_preview = self.embedded_preview;
+        // Kept so a config reload on SIGHUP honors the same flags
+        server_config.cli_settings = cli_settings;
 
         // Open the browser at the bound URL, once, before serving. The listener
         // is already bound, so the browser's connection is accepted into the
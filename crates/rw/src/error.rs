@@ -33,12 +33,26 @@ pub(crate) enum CliError {
     #[error(transparent)]
     Storage(#[from] rw_storage::StorageError),
 
+    #[error(transparent)]
+    Render(#[from] rw_site::RenderError),
+
+    #[error(
+        "no Chromium or Chrome found to print with; install one, or pass --chrome <path> or set RW_CHROME"
+    )]
+    ChromeNotFound,
+
+    #[error("printing the PDF failed: {0}")]
+    PdfPrint(String),
+
     #[error(transparent)]
     QuoteResolution(#[from] QuoteResolutionError),
 
     #[error("completed with {count} warning(s); --strict was set")]
     DiagramWarningsInStrictMode { count: usize },
 
+    #[error("the rendered page differs from the current page; --exit-code was set")]
+    DiffHasChanges,
+
     #[error("--out - cannot stream {count} attachment(s); pass --out <dir> instead")]
     OutStdoutHasAttachments { count: usize },
 
@@ -71,6 +85,7 @@ impl From<CreateError> for CliError {
 
 impl CliError {
     /// Exit code category:
+    /// - `4` — `--exit-code` and the rendered page has changes
     /// - `3` — validation / caller error (bad flags, ambiguous quote, etc.)
     /// - `2` — referenced entity does not exist
     /// - `1` — anything else
@@ -83,7 +98,9 @@ impl CliError {
                 QuoteResolutionError::NotFound { .. } | QuoteResolutionError::Ambiguous { .. },
             ) => 3,
             CliError::Store(StoreError::NotFound(_))
+            | CliError::Render(rw_site::RenderError::PageNotFound(_))
             | CliError::QuoteResolution(QuoteResolutionError::DocumentNotFound { .. }) => 2,
+            CliError::DiffHasChanges => 4,
             _ => 1,
         }
     }
@@ -99,6 +116,11 @@ mod tests {
         assert_eq!(err.exit_code(), 1);
     }
 
+    #[test]
+    fn diff_has_changes_exits_4() {
+        assert_eq!(CliError::DiffHasChanges.exit_code(), 4);
+    }
+
     #[test]
     fn out_stdout_has_attachments_exits_3() {
         let err = CliError::OutStdoutHasAttachments { count: 2 };
@@ -107,6 +129,13 @@ mod tests {
         assert!(err.to_string().contains("--out -"));
     }
 
+    #[test]
+    fn render_page_not_found_exits_2() {
+        let err = CliError::Render(rw_site::RenderError::PageNotFound("nope".to_owned()));
+        assert_eq!(err.exit_code(), 2);
+        assert_eq!(CliError::ChromeNotFound.exit_code(), 1);
+    }
+
     #[test]
     fn cant_self_update_exits_1() {
         // Falls through to the `_ => 1` arm (not a validation/not-found bucket);
@@ -6,6 +6,8 @@
 //! - `confluence update`: Update Confluence pages from markdown
 //! - `confluence generate-tokens`: Generate OAuth access tokens
 //! - `comment`: Read and write comments directly against the local `SQLite` store
+//! - `config show` / `config validate`: Inspect the resolved configuration
+//! - `export pdf`: Print a page or the whole site to PDF
 //! - `update`: Update rw to the latest release
 
 mod commands;
@@ -15,7 +17,10 @@ mod output;
 use clap::{Parser, Subcommand};
 use tracing_subscriber::EnvFilter;
 
-use commands::{BackstageCommand, CommentCommand, ConfluenceCommand, ServeArgs, UpdateArgs};
+use commands::{
+    BackstageCommand, CommentCommand, ConfigCommand, ConfluenceCommand, ExportCommand, ServeArgs,
+    UpdateArgs,
+};
 use output::Output;
 
 /// Application version from Cargo.toml.
@@ -42,6 +47,12 @@ enum Commands {
     /// Read and write inline comments on project docs (for scripts and LLM agents).
     #[command(subcommand)]
     Comment(CommentCommand),
+    /// Show or validate the resolved configuration.
+    #[command(subcommand)]
+    Config(ConfigCommand),
+    /// Export documentation for offline use.
+    #[command(subcommand)]
+    Export(ExportCommand),
     /// Update rw to the latest release.
     Update(UpdateArgs),
 }
@@ -70,6 +81,8 @@ fn main() {
         Commands::Backstage(cmd) => cmd.execute(),
         Commands::Confluence(cmd) => cmd.execute(),
         Commands::Comment(cmd) => cmd.execute(),
+        Commands::Config(cmd) => cmd.execute(),
+        Commands::Export(cmd) => cmd.execute(),
         Commands::Update(args) => args.execute(VERSION),
     };
 
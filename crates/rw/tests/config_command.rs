@@ -0,0 +1,74 @@
+//! Integration tests for `rw config show` and `rw config validate`.
+
+use std::process::Command;
+
+/// Path to the `rw` binary built by Cargo.
+fn rw_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_rw")
+}
+
+fn project_with_toml(contents: &str) -> tempfile::TempDir {
+    let dir = tempfile::tempdir().expect("project tempdir");
+    std::fs::write(dir.path().join("rw.toml"), contents).expect("write rw.toml");
+    dir
+}
+
+fn rw_config(args: &[&str], project: &tempfile::TempDir) -> std::process::Output {
+    Command::new(rw_bin())
+        .arg("config")
+        .args(args)
+        .arg("--project-dir")
+        .arg(project.path())
+        .env_remove("RW_DIAGRAMS_KROKI_URL")
+        .output()
+        .expect("spawn rw config")
+}
+
+#[test]
+fn show_prints_resolved_toml() {
+    let project = project_with_toml("[docs]\nsource_dir = \"content\"\n");
+
+    let output = rw_config(&["show"], &project);
+
+    assert!(output.status.success(), "exit: {:?}", output.status);
+    let stdout = String::from_utf8(output.stdout).expect("utf8 stdout");
+    let source_dir = project.path().join("content");
+    assert!(
+        stdout.contains(&format!("source_dir = \"{}\"", source_dir.display())),
+        "stdout: {stdout}"
+    );
+}
+
+#[test]
+fn validate_accepts_a_valid_config() {
+    let project = project_with_toml("[server]\nport = 8080\n");
+
+    let output = rw_config(&["validate"], &project);
+
+    assert!(output.status.success(), "exit: {:?}", output.status);
+    let stderr = String::from_utf8(output.stderr).expect("utf8 stderr");
+    assert!(
+        stderr.contains("Configuration is valid"),
+        "stderr: {stderr}"
+    );
+}
+
+#[test]
+fn validate_exits_3_on_an_invalid_value() {
+    let project = project_with_toml("[diagrams]\nkroki_url = \"kroki.io\"\n");
+
+    let output = rw_config(&["validate"], &project);
+
+    assert_eq!(output.status.code(), Some(3));
+    let stderr = String::from_utf8(output.stderr).expect("utf8 stderr");
+    assert!(stderr.contains("diagrams.kroki_url"), "stderr: {stderr}");
+}
+
+#[test]
+fn validate_exits_1_on_unparsable_toml() {
+    let project = project_with_toml("[docs\n");
+
+    let output = rw_config(&["validate"], &project);
+
+    assert_eq!(output.status.code(), Some(1));
+}
@@ -449,6 +449,61 @@ fn render_no_toc_omits_confluence_toc_macro() {
     );
 }
 
+#[test]
+fn render_toc_levels_become_toc_macro_parameters() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let md = write_markdown(tmp.path(), "in.md", "# Title\n\n## Sub\n\nBody.\n");
+    let out_dir = tmp.path().join("dist");
+
+    let status = Command::new(rw_bin())
+        .arg("confluence")
+        .arg("render")
+        .arg(&md)
+        .arg("--out")
+        .arg(&out_dir)
+        .arg("--toc-min-level")
+        .arg("1")
+        .arg("--toc-max-level")
+        .arg("2")
+        .stdin(Stdio::null())
+        .status()
+        .expect("spawn rw");
+    assert!(status.success());
+
+    let xhtml = std::fs::read_to_string(out_dir.join("page.xhtml")).expect("page.xhtml");
+    assert!(
+        xhtml.contains(r#"<ac:parameter ac:name="minLevel">1</ac:parameter>"#),
+        "page.xhtml should carry minLevel: {xhtml}"
+    );
+    assert!(
+        xhtml.contains(r#"<ac:parameter ac:name="maxLevel">2</ac:parameter>"#),
+        "page.xhtml should carry maxLevel: {xhtml}"
+    );
+}
+
+#[test]
+fn render_toc_min_level_above_max_level_exits_3() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let md = write_markdown(tmp.path(), "in.md", "# Title\n\n## Sub\n");
+    let out_dir = tmp.path().join("dist");
+
+    let output = Command::new(rw_bin())
+        .arg("confluence")
+        .arg("render")
+        .arg(&md)
+        .arg("--out")
+        .arg(&out_dir)
+        .arg("--toc-min-level")
+        .arg("3")
+        .arg("--toc-max-level")
+        .arg("2")
+        .stdin(Stdio::null())
+        .output()
+        .expect("spawn rw");
+    assert_eq!(output.status.code(), Some(3), "exit: {:?}", output.status);
+    assert!(!out_dir.join("page.xhtml").exists());
+}
+
 #[test]
 fn render_stdout_mode_with_stdin_preserves_comment_marker() {
     let tmp = tempfile::tempdir().expect("tempdir");
@@ -489,3 +544,64 @@ fn render_stdout_mode_with_stdin_preserves_comment_marker() {
         "stdout missing ref: {stdout}"
     );
 }
+
+fn render_diff_against(current: &[u8], markdown: &str, args: &[&str]) -> std::process::Output {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let md = write_markdown(tmp.path(), "in.md", markdown);
+    let out_dir = tmp.path().join("dist");
+
+    let mut child = Command::new(rw_bin())
+        .arg("confluence")
+        .arg("render")
+        .arg(&md)
+        .arg("--out")
+        .arg(&out_dir)
+        .arg("--no-toc")
+        .arg("--diff")
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("spawn rw");
+    child
+        .stdin
+        .as_mut()
+        .expect("stdin")
+        .write_all(current)
+        .expect("write stdin");
+    child.wait_with_output().expect("wait")
+}
+
+#[test]
+fn render_diff_prints_changed_lines_to_stderr() {
+    let output = render_diff_against(b"<p>Old text.</p><p>Same.</p>", "New text.\n\nSame.\n", &[]);
+    assert!(output.status.success(), "exit: {:?}", output.status);
+
+    let stderr = String::from_utf8(output.stderr).expect("utf8 stderr");
+    assert!(stderr.contains("-<p>Old text.</p>"), "stderr: {stderr}");
+    assert!(stderr.contains("+<p>New text.</p>"), "stderr: {stderr}");
+    assert!(stderr.contains(" <p>Same.</p>"), "stderr: {stderr}");
+}
+
+#[test]
+fn render_diff_ignores_comment_markers() {
+    let output = render_diff_against(
+        b"<p>Hello <ac:inline-comment-marker ac:ref=\"abc\">marked</ac:inline-comment-marker> text.</p>",
+        "Hello marked text.\n",
+        &[],
+    );
+    assert!(output.status.success(), "exit: {:?}", output.status);
+
+    let stderr = String::from_utf8(output.stderr).expect("utf8 stderr");
+    assert!(stderr.contains("diff: no changes"), "stderr: {stderr}");
+}
+
+#[test]
+fn render_diff_exit_code_reports_changes_with_status_4() {
+    let changed = render_diff_against(b"<p>Old text.</p>", "New text.\n", &["--exit-code"]);
+    let unchanged = render_diff_against(b"<p>Same.</p>", "Same.\n", &["--exit-code"]);
+
+    assert_eq!(changed.status.code(), Some(4), "exit: {:?}", changed.status);
+    assert!(unchanged.status.success(), "exit: {:?}", unchanged.status);
+}
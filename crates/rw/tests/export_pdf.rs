@@ -0,0 +1,77 @@
+//! Integration tests for `rw export pdf`, through `--html` so no browser is
+//! needed.
+
+use std::process::Command;
+
+fn project() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().expect("project tempdir");
+    let docs = dir.path().join("docs");
+    std::fs::create_dir_all(docs.join("guide")).expect("create docs");
+    std::fs::write(
+        docs.join("index.md"),
+        "# Home\n\nSee [the guide](guide/index.md).\n",
+    )
+    .expect("write index.md");
+    std::fs::write(docs.join("guide/index.md"), "# Guide\n\nRead on.\n").expect("write guide");
+    std::fs::write(docs.join("guide/install.md"), "# Install\n\nSteps.\n").expect("write install");
+    dir
+}
+
+fn rw_export(args: &[&str], project: &tempfile::TempDir) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_rw"))
+        .args(["export", "pdf", "--html", "--project-dir"])
+        .arg(project.path())
+        .args(args)
+        .current_dir(project.path())
+        .env_remove("RW_DIAGRAMS_KROKI_URL")
+        .output()
+        .expect("spawn rw export pdf")
+}
+
+#[test]
+fn whole_site_is_exported_in_navigation_order() {
+    let project = project();
+
+    let output = rw_export(&[], &project);
+
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let html = std::fs::read_to_string(project.path().join("site.html")).expect("site.html");
+    let home = html.find("id=\"page-\"").expect("home page");
+    let guide = html.find("id=\"page-guide\"").expect("guide page");
+    let install = html
+        .find("id=\"page-guide/install\"")
+        .expect("install page");
+    assert!(home < guide && guide < install, "{html}");
+    assert!(html.contains("href=\"#page-guide\""), "{html}");
+    assert!(html.contains("class=\"cover\""), "{html}");
+}
+
+#[test]
+fn single_page_without_cover() {
+    let project = project();
+
+    let output = rw_export(&["guide/install", "--no-cover"], &project);
+
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let html = std::fs::read_to_string(project.path().join("install.html")).expect("install.html");
+    assert!(html.contains("Steps."), "{html}");
+    assert!(!html.contains("Read on."), "{html}");
+    assert!(!html.contains("class=\"cover\""), "{html}");
+}
+
+#[test]
+fn missing_page_exits_2() {
+    let project = project();
+
+    let output = rw_export(&["nope"], &project);
+
+    assert_eq!(output.status.code(), Some(2));
+}
@@ -70,6 +70,14 @@ export declare function createSite(config: SiteConfig): RwSite
 
 export interface DiagramsConfig {
   krokiUrl?: string
+  /** Extra HTTP headers sent with every Kroki request, by header name. */
+  krokiHeaders?: Record<string, string>
+  /**
+   * Render ```` ```mermaid ```` fences as `<pre class="mermaid">` for
+   * mermaid.js in the page, instead of through Kroki. The host must load
+   * mermaid.js itself; `rw serve` has no such mode.
+   */
+  clientSideMermaid?: boolean
 }
 
 export interface NavigationResponse {
@@ -254,6 +262,18 @@ export interface SiteConfig {
    * rejected.
    */
   mtimeSource?: string
+  /**
+   * Deepest heading level, 1 to 6, listed in a page's `toc`; deeper
+   * headings keep their anchors. Overrides `[docs] toc_max_depth` of a
+   * `projectDir` site. Every level when neither is set.
+   */
+  tocMaxDepth?: number
+  /**
+   * Render `$tex$` and `$$tex$$` as math, for the host's `KaTeX` or
+   * `MathJax` to typeset. Overrides `[docs] math` of a `projectDir` site.
+   * Off when neither is set.
+   */
+  math?: boolean
 }
 
 export interface TocEntryResponse {
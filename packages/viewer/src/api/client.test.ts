@@ -1,5 +1,5 @@
 import { describe, it, expect, vi, beforeEach, afterEach } from "vitest";
-import { createApiClient, NotFoundError } from "./client";
+import { createApiClient, NotFoundError, PageMovedError } from "./client";
 import type { ConfigResponse, NavigationTree, PageResponse } from "../types";
 
 const mockNavTree: NavigationTree = {
@@ -52,6 +52,20 @@ describe("fetchNavigation", () => {
     expect(fetch).toHaveBeenCalledWith("/_api/navigation", { cache: "no-store" });
   });
 
+  it("passes depth and expand as query parameters", async () => {
+    const client = createApiClient();
+    await client.fetchNavigation({ depth: 1, expand: "/guide" });
+
+    expect(fetch).toHaveBeenCalledWith("/_api/navigation?depth=1&expand=%2Fguide", {});
+  });
+
+  it("passes current as a query parameter", async () => {
+    const client = createApiClient();
+    await client.fetchNavigation({ current: "/guide/setup" });
+
+    expect(fetch).toHaveBeenCalledWith("/_api/navigation?current=%2Fguide%2Fsetup", {});
+  });
+
   it("throws error on non-ok response", async () => {
     vi.stubGlobal(
       "fetch",
@@ -139,6 +153,65 @@ describe("fetchPage", () => {
     await expect(client.fetchPage("missing")).rejects.toThrow("Page not found: missing");
   });
 
+  it("attaches the site's not-found page to NotFoundError", async () => {
+    vi.stubGlobal(
+      "fetch",
+      vi.fn(() =>
+        Promise.resolve({
+          ok: false,
+          status: 404,
+          statusText: "Not Found",
+          json: () => Promise.resolve(mockPage),
+        }),
+      ),
+    );
+
+    const client = createApiClient();
+    const error = await client.fetchPage("missing").catch((e: unknown) => e);
+
+    expect(error).toBeInstanceOf(NotFoundError);
+    expect((error as NotFoundError).page).toEqual(mockPage);
+  });
+
+  it("leaves page unset for a plain 404 error body", async () => {
+    vi.stubGlobal(
+      "fetch",
+      vi.fn(() =>
+        Promise.resolve({
+          ok: false,
+          status: 404,
+          statusText: "Not Found",
+          json: () => Promise.resolve({ error: "Page not found", path: "missing" }),
+        }),
+      ),
+    );
+
+    const client = createApiClient();
+    const error = await client.fetchPage("missing").catch((e: unknown) => e);
+
+    expect((error as NotFoundError).page).toBeUndefined();
+  });
+
+  it("throws PageMovedError when redirected from a former URL", async () => {
+    vi.stubGlobal(
+      "fetch",
+      vi.fn(() =>
+        Promise.resolve({
+          ok: true,
+          redirected: true,
+          json: () => Promise.resolve(mockPage),
+        }),
+      ),
+    );
+
+    const client = createApiClient();
+    const error = await client.fetchPage("old/test").catch((e: unknown) => e);
+
+    expect(error).toBeInstanceOf(PageMovedError);
+    expect((error as PageMovedError).path).toBe("old/test");
+    expect((error as PageMovedError).page).toEqual(mockPage);
+  });
+
   it("throws generic error on other non-ok responses", async () => {
     vi.stubGlobal(
       "fetch",
@@ -20,16 +20,56 @@ function buildRequestInit(options?: FetchOptions): RequestInit {
 
 /** Error thrown when a page is not found */
 export class NotFoundError extends Error {
-  constructor(public path: string) {
+  /**
+   * @param path - The requested page path
+   * @param page - The site's custom not-found page, when the 404 response carries one
+   */
+  constructor(
+    public path: string,
+    public page?: PageResponse,
+  ) {
     super(`Page not found: ${path}`);
     this.name = "NotFoundError";
   }
 }
 
+/** Error thrown when a page was requested by a former URL listed in its `redirects` */
+export class PageMovedError extends Error {
+  /**
+   * @param path - The requested (former) page path
+   * @param page - The page it moved to; `page.meta.path` is its current URL
+   */
+  constructor(
+    public path: string,
+    public page: PageResponse,
+  ) {
+    super(`Page moved: ${path} -> ${page.meta.path}`);
+    this.name = "PageMovedError";
+  }
+}
+
+/** The custom not-found page a 404 response carries, if any. */
+async function notFoundPage(response: Response): Promise<PageResponse | undefined> {
+  try {
+    const body: unknown = await response.json();
+    return body && typeof body === "object" && "meta" in body
+      ? (body as PageResponse)
+      : undefined;
+  } catch {
+    return undefined;
+  }
+}
+
 /** Options for fetching navigation */
 export interface FetchNavigationOptions extends FetchOptions {
   /** Section ref string (e.g., "domain:default/billing") to load navigation for a specific section. */
   sectionRef?: string;
+  /** Levels of items to return (1 = top level only). Omitted returns the whole tree. */
+  depth?: number;
+  /** URL path of an item whose children to return instead of the top level. */
+  expand?: string;
+  /** URL path of the page being viewed; marks its item `isCurrent` and its trail `active`. */
+  current?: string;
 }
 
 export interface ApiClient {
@@ -49,6 +89,15 @@ export function createApiClient(apiBase: string = "/_api", fetchFn?: typeof fetc
       if (options?.sectionRef) {
         params.set("sectionRef", options.sectionRef);
       }
+      if (options?.depth !== undefined) {
+        params.set("depth", String(options.depth));
+      }
+      if (options?.expand) {
+        params.set("expand", options.expand);
+      }
+      if (options?.current) {
+        params.set("current", options.current);
+      }
       const url = params.toString() ? `${base}/navigation?${params}` : `${base}/navigation`;
 
       const response = await doFetch(url, buildRequestInit(options));
@@ -62,11 +111,16 @@ export function createApiClient(apiBase: string = "/_api", fetchFn?: typeof fetc
       const response = await doFetch(`${base}/pages/${path}`, buildRequestInit(options));
       if (!response.ok) {
         if (response.status === 404) {
-          throw new NotFoundError(path);
+          throw new NotFoundError(path, await notFoundPage(response));
         }
         throw new Error(`Failed to fetch page: ${response.status} ${response.statusText}`);
       }
-      return response.json();
+      const page: PageResponse = await response.json();
+      // The server redirects a former page URL to the page it moved to.
+      if (response.redirected) {
+        throw new PageMovedError(path, page);
+      }
+      return page;
     },
 
     async fetchConfig(): Promise<ConfigResponse> {
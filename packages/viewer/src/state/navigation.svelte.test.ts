@@ -1,6 +1,6 @@
 import { describe, it, expect, vi, beforeEach } from "vitest";
 import type { NavigationTree } from "../types";
-import { Navigation, collectParentPaths, getParentPaths } from "./navigation.svelte";
+import { NAV_DEPTH, Navigation, collectParentPaths, getParentPaths } from "./navigation.svelte";
 import type { ApiClient } from "../api/client";
 
 const mockTree: NavigationTree = {
@@ -58,6 +58,11 @@ describe("collectParentPaths", () => {
     expect(paths).toContain("/guide/advanced");
     expect(paths).toHaveLength(2);
   });
+
+  it("collects items whose children are not loaded yet", () => {
+    const items = [{ title: "Guide", path: "/guide", hasChildren: true }];
+    expect(collectParentPaths(items)).toEqual(["/guide"]);
+  });
 });
 
 describe("getParentPaths", () => {
@@ -145,6 +150,17 @@ describe("navigation store", () => {
       );
     });
 
+    it("requests a depth-limited tree with the trail to the current page", async () => {
+      mockFetchNavigation.mockResolvedValue(mockTree);
+      const navigation = new Navigation(mockApiClient);
+
+      await navigation.load({ current: "/guide/advanced/plugins" });
+
+      expect(mockFetchNavigation).toHaveBeenCalledWith(
+        expect.objectContaining({ depth: NAV_DEPTH, current: "/guide/advanced/plugins" }),
+      );
+    });
+
     it("forwards sectionRef to fetchNavigation", async () => {
       mockFetchNavigation.mockResolvedValue(mockTree);
       const navigation = new Navigation(mockApiClient);
@@ -312,6 +328,90 @@ describe("navigation store", () => {
     });
   });
 
+  describe("lazy loading", () => {
+    const cutTree: NavigationTree = {
+      items: [
+        {
+          title: "Guide",
+          path: "/guide",
+          children: [{ title: "Advanced", path: "/guide/advanced", hasChildren: true }],
+        },
+      ],
+    };
+    const advancedChildren: NavigationTree = {
+      items: [{ title: "Plugins", path: "/guide/advanced/plugins", hasChildren: true }],
+    };
+
+    it("fetches the children of an unloaded item when it is expanded", async () => {
+      mockFetchNavigation.mockResolvedValueOnce(cutTree).mockResolvedValueOnce(advancedChildren);
+      const navigation = new Navigation(mockApiClient);
+      await navigation.load();
+
+      navigation.toggle("/guide/advanced");
+
+      await vi.waitFor(() => {
+        expect(navigation.tree!.items[0]!.children![0]!.children).toEqual(advancedChildren.items);
+      });
+      expect(mockFetchNavigation).toHaveBeenLastCalledWith(
+        expect.objectContaining({ expand: "/guide/advanced", depth: NAV_DEPTH }),
+      );
+      expect(navigation.tree!.items[0]!.children![0]!.hasChildren).toBeUndefined();
+      expect(navigation.collapsed.has("/guide/advanced")).toBe(false);
+      // The new level's own unloaded items start collapsed.
+      expect(navigation.collapsed.has("/guide/advanced/plugins")).toBe(true);
+    });
+
+    it("does not refetch loaded children", async () => {
+      mockFetchNavigation.mockResolvedValue(mockTree);
+      const navigation = new Navigation(mockApiClient);
+      await navigation.load();
+
+      navigation.toggle("/guide");
+
+      expect(mockFetchNavigation).toHaveBeenCalledTimes(1);
+    });
+
+    it("loads the unloaded levels above the page expandOnlyTo targets", async () => {
+      mockFetchNavigation
+        .mockResolvedValueOnce(cutTree)
+        .mockResolvedValueOnce(advancedChildren)
+        // The page's own children, which expandOnlyTo expands as well.
+        .mockResolvedValueOnce({ items: [] });
+      const navigation = new Navigation(mockApiClient);
+      await navigation.load();
+
+      navigation.expandOnlyTo("/guide/advanced/plugins");
+
+      await vi.waitFor(() => {
+        expect(navigation.tree!.items[0]!.children![0]!.children).toEqual(advancedChildren.items);
+      });
+      expect(navigation.collapsed.has("/guide")).toBe(false);
+      expect(navigation.collapsed.has("/guide/advanced")).toBe(false);
+    });
+
+    it("drops children fetched for a tree that was since replaced", async () => {
+      let resolveChildren: (value: NavigationTree) => void;
+      mockFetchNavigation
+        .mockResolvedValueOnce(cutTree)
+        .mockImplementationOnce(
+          () =>
+            new Promise<NavigationTree>((resolve) => {
+              resolveChildren = resolve;
+            }),
+        )
+        .mockResolvedValueOnce(cutTree);
+      const navigation = new Navigation(mockApiClient);
+      await navigation.load();
+
+      navigation.toggle("/guide/advanced");
+      await navigation.load();
+      resolveChildren!(advancedChildren);
+      await Promise.resolve();
+
+      expect(navigation.tree!.items[0]!.children![0]!.children).toBeUndefined();
+    });
+  });
+
   describe("expandOnlyTo", () => {
     it("expands path to target and collapses others", async () => {
       mockFetchNavigation.mockResolvedValue(mockTree);
@@ -4,18 +4,47 @@ import type { ApiClient } from "../api/client";
 import type { SectionRefResolver } from "$lib/sectionRefs";
 import { resolveNavTree } from "$lib/sectionRefs";
 
-/** Collect all paths with children from the navigation tree */
+/** Levels of the tree fetched at once; deeper levels load when expanded. */
+export const NAV_DEPTH = 2;
+
+/** Collect all paths with children (loaded or not) from the navigation tree */
 export function collectParentPaths(items: NavItem[]): string[] {
   const paths: string[] = [];
   for (const item of items) {
     if (item.children && item.children.length > 0) {
       paths.push(item.path);
       paths.push(...collectParentPaths(item.children));
+    } else if (item.hasChildren) {
+      paths.push(item.path);
     }
   }
   return paths;
 }
 
+/** Whether the item's children exist but have not been fetched yet. */
+function isUnloaded(item: NavItem): boolean {
+  return !!item.hasChildren && !item.children?.length;
+}
+
+/** Find the item at `path` anywhere in the tree. */
+function findItem(items: NavItem[], path: string): NavItem | undefined {
+  for (const item of items) {
+    if (item.path === path) return item;
+    const found = item.children && findItem(item.children, path);
+    if (found) return found;
+  }
+  return undefined;
+}
+
+/** Return a copy of the tree with `children` attached to the item at `path`. */
+function withChildren(items: NavItem[], path: string, children: NavItem[]): NavItem[] {
+  return items.map((item) => {
+    if (item.path === path) return { ...item, children, hasChildren: undefined };
+    if (!item.children) return item;
+    return { ...item, children: withChildren(item.children, path, children) };
+  });
+}
+
 /** Get parent paths for a given path */
 export function getParentPaths(path: string): string[] {
   const parts = path.split("/").filter(Boolean);
@@ -40,6 +69,9 @@ export class Navigation {
   private currentController: AbortController | null = null;
   private activePath: string | null = null;
   private sectionRefResolver?: SectionRefResolver;
+  // Bumped by every loadSection() so a lazy expansion of a replaced tree is dropped.
+  private generation = 0;
+  private expanding = new Set<string>();
 
   constructor(apiClient: ApiClient) {
     this.apiClient = apiClient;
@@ -57,17 +89,28 @@ export class Navigation {
     this.sectionRefResolver = resolver;
   }
 
-  load = async (options?: { bypassCache?: boolean; sectionRef?: string }): Promise<void> => {
+  load = async (options?: {
+    bypassCache?: boolean;
+    sectionRef?: string;
+    current?: string;
+  }): Promise<void> => {
     return this.loadSection(options?.sectionRef, options);
   };
 
+  /**
+   * Load the top `NAV_DEPTH` levels of a section's tree. The items above the
+   * current page (`options.current`, else the last `expandOnlyTo` path) come
+   * with their children, so the page is in the tree from the first response.
+   */
   loadSection = async (
     sectionRef: string | undefined,
-    options?: { bypassCache?: boolean },
+    options?: { bypassCache?: boolean; current?: string },
   ): Promise<void> => {
     this.currentController?.abort();
     const controller = new AbortController();
     this.currentController = controller;
+    this.generation++;
+    this.expanding = new Set();
 
     // Only show loading state on initial load — during live reload, keep
     // displaying the existing tree while fetching updated data in the background.
@@ -82,26 +125,14 @@ export class Navigation {
       const tree = await this.apiClient.fetchNavigation({
         ...options,
         sectionRef,
+        depth: NAV_DEPTH,
+        current: options?.current ?? this.activePath ?? undefined,
         signal: controller.signal,
       });
       if (controller.signal.aborted) return;
 
-      let resolvedTree = tree;
-      if (this.sectionRefResolver) {
-        try {
-          resolvedTree = await resolveNavTree(tree, this.sectionRefResolver);
-          if (controller.signal.aborted) return;
-        } catch (e) {
-          if (controller.signal.aborted) return; // superseded load: don't commit
-          if (import.meta.env.DEV) {
-            console.warn(
-              "[rw] nav section-ref resolution failed; using unresolved navigation tree:",
-              e,
-            );
-          }
-          // keep the fetched (unresolved) tree
-        }
-      }
+      const resolvedTree = await this.resolve(tree, () => controller.signal.aborted);
+      if (controller.signal.aborted) return;
 
       const allParentPaths = collectParentPaths(resolvedTree.items);
       this.tree = resolvedTree;
@@ -128,6 +159,7 @@ export class Navigation {
     const collapsed = new Set(this.collapsed);
     if (collapsed.has(path)) {
       collapsed.delete(path);
+      this.loadChildrenOf([path]);
     } else {
       collapsed.add(path);
     }
@@ -146,12 +178,77 @@ export class Navigation {
     this.error = null;
     this.collapsed = new Set();
     this.currentSectionRef = undefined;
+    this.generation++;
+    this.expanding = new Set();
+  };
+
+  /** Resolve section refs in `tree`, keeping it unresolved if the resolver fails. */
+  private resolve = async (
+    tree: NavigationTree,
+    superseded: () => boolean,
+  ): Promise<NavigationTree> => {
+    if (!this.sectionRefResolver) return tree;
+    try {
+      return await resolveNavTree(tree, this.sectionRefResolver);
+    } catch (e) {
+      if (!superseded() && import.meta.env.DEV) {
+        console.warn(
+          "[rw] nav section-ref resolution failed; using unresolved navigation tree:",
+          e,
+        );
+      }
+      return tree;
+    }
+  };
+
+  /** Fetch the children of each item at `paths` that has them but has not loaded them. */
+  private loadChildrenOf = (paths: string[]): void => {
+    const items = untrack(() => this.tree)?.items;
+    if (!items) return;
+    for (const path of paths) {
+      const item = findItem(items, path);
+      if (item && isUnloaded(item) && !this.expanding.has(path)) {
+        void this.loadChildren(path);
+      }
+    }
+  };
+
+  private loadChildren = async (path: string): Promise<void> => {
+    const generation = this.generation;
+    const expanding = this.expanding;
+    const superseded = () => generation !== this.generation;
+    expanding.add(path);
+    try {
+      const tree = await this.apiClient.fetchNavigation({
+        // Untracked: this runs inside the router's path $effect via expandOnlyTo.
+        sectionRef: untrack(() => this.currentSectionRef),
+        expand: path,
+        depth: NAV_DEPTH,
+      });
+      if (superseded()) return;
+      const { items } = await this.resolve(tree, superseded);
+      if (superseded() || !this.tree) return;
+
+      this.tree = { ...this.tree, items: withChildren(this.tree.items, path, items) };
+      this.collapsed = new Set([...this.collapsed, ...collectParentPaths(items)]);
+      if (this.activePath) {
+        this.doExpandTo(this.activePath);
+      }
+    } catch (e) {
+      // The item stays expandable; toggling it again retries.
+      if (!superseded() && import.meta.env.DEV) {
+        console.warn(`[rw] failed to load navigation children of ${path}:`, e);
+      }
+    } finally {
+      expanding.delete(path);
+    }
   };
 
   private doExpandTo = (path: string): void => {
     if (!this.tree) return;
 
     const pathsToExpand = getParentPaths(path);
+    this.loadChildrenOf(pathsToExpand);
     const alreadyCorrect = pathsToExpand.every((p) => !this.collapsed.has(p));
     if (alreadyCorrect) return;
 
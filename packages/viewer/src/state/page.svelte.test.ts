@@ -2,7 +2,7 @@ import { describe, it, expect, vi, beforeEach } from "vitest";
 import type { PageResponse } from "../types";
 import { Page } from "./page.svelte";
 import type { ApiClient } from "../api/client";
-import { NotFoundError } from "../api/client";
+import { NotFoundError, PageMovedError } from "../api/client";
 
 const mockPageResponse: PageResponse = {
   meta: {
@@ -102,6 +102,29 @@ describe("page store", () => {
       expect(page.notFound).toBe(true);
     });
 
+    it("keeps the not-found page a 404 carries", async () => {
+      mockFetchPage.mockRejectedValue(new NotFoundError("missing", mockPageResponse));
+      const page = new Page(mockApiClient);
+
+      await page.load("missing");
+
+      expect(page.data).toEqual(mockPageResponse);
+      expect(page.error).toBeNull();
+      expect(page.notFound).toBe(true);
+    });
+
+    it("shows a moved page and records its current path", async () => {
+      mockFetchPage.mockRejectedValue(new PageMovedError("old/test", mockPageResponse));
+      const page = new Page(mockApiClient);
+
+      await page.load("old/test");
+
+      expect(page.data).toEqual(mockPageResponse);
+      expect(page.error).toBeNull();
+      expect(page.notFound).toBe(false);
+      expect(page.movedTo).toBe("/test");
+    });
+
     it("sets error on other failures", async () => {
       mockFetchPage.mockRejectedValue(new Error("Server error"));
       const page = new Page(mockApiClient);
@@ -1,6 +1,6 @@
 import type { PageResponse } from "../types";
 import type { ApiClient } from "../api/client";
-import { NotFoundError } from "../api/client";
+import { NotFoundError, PageMovedError } from "../api/client";
 import type { SectionRefResolver } from "$lib/sectionRefs";
 import { resolveBreadcrumbs } from "$lib/sectionRefs";
 
@@ -9,6 +9,8 @@ export class Page {
   loading = $state(false);
   error = $state<string | null>(null);
   notFound = $state(false);
+  /** Current URL of the page when it was loaded by a former one, for the router to switch to. */
+  movedTo = $state<string | null>(null);
 
   private apiClient: ApiClient;
   private embedded: boolean;
@@ -31,6 +33,7 @@ export class Page {
     }
     this.abortController = new AbortController();
     const signal = this.abortController.signal;
+    this.movedTo = null;
 
     if (!options?.silent) {
       this.loading = true;
@@ -79,6 +82,15 @@ export class Page {
       // state, even when its fetch/resolver rejects with a plain Error rather
       // than an AbortError. Mirrors the success-path guards above.
       if (signal.aborted) return;
+      if (e instanceof PageMovedError) {
+        // Show the page now; the router switches to its current URL.
+        this.data = e.page;
+        this.loading = false;
+        this.error = null;
+        this.notFound = false;
+        this.movedTo = e.page.meta.path;
+        return;
+      }
       if (options?.silent) {
         // Silent (background/live-reload) refresh failed: keep the
         // last-known-good page on screen instead of blanking it — data,
@@ -94,10 +106,15 @@ export class Page {
         return;
       }
       if (e instanceof NotFoundError) {
-        this.data = null;
+        // The site's own 404 page, if it has one, is shown in place of the
+        // built-in message.
+        this.data = e.page ?? null;
         this.loading = false;
         this.error = null;
         this.notFound = true;
+        if (e.page?.meta.title && !this.embedded) {
+          document.title = `${e.page.meta.title} - RW`;
+        }
       } else {
         const message = e instanceof Error ? e.message : "Unknown error";
         this.data = null;
@@ -74,6 +74,32 @@ describe("goto", () => {
   // which scrolls the actual content container element
 });
 
+describe("replace", () => {
+  beforeEach(() => {
+    Object.defineProperty(window, "location", {
+      value: { origin: "http://localhost:8001", pathname: "/old", hash: "#setup" },
+      writable: true,
+      configurable: true,
+    });
+    vi.spyOn(window.history, "replaceState").mockImplementation(() => {});
+    vi.spyOn(window.history, "pushState").mockImplementation(() => {});
+  });
+
+  afterEach(() => {
+    vi.restoreAllMocks();
+  });
+
+  it("replaces the history entry and keeps the hash", () => {
+    const router = new Router();
+    router.replace("/guides/setup");
+
+    expect(window.history.replaceState).toHaveBeenCalledWith({}, "", "/guides/setup#setup");
+    expect(window.history.pushState).not.toHaveBeenCalled();
+    expect(router.path).toBe("/guides/setup");
+    expect(router.hash).toBe("setup");
+  });
+});
+
 describe("initRouter", () => {
   let popstateHandler: ((e: PopStateEvent) => void) | null = null;
   let clickHandler: ((e: MouseEvent) => void) | null = null;
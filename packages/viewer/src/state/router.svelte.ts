@@ -129,6 +129,21 @@ export class Router {
     this.hash = decodeHash(url.hash.slice(1));
   };
 
+  /** Switch to `newPath` in place of the current path, without a new history entry
+   * (a moved page shown under its current URL). The hash is kept. */
+  replace = (newPath: string) => {
+    const origin = typeof window !== "undefined" ? window.location.origin : "http://localhost";
+    const url = new URL(newPath, origin);
+
+    if (!this.embedded) {
+      window.history.replaceState({}, "", url.pathname + window.location.hash);
+    } else if (this.onNavigate) {
+      this.onNavigate(this.basePath + this.stripScope(url.pathname));
+    }
+
+    this.path = url.pathname;
+  };
+
   /** Initialize router - call once on app mount. Returns cleanup function.
    * In embedded mode, pass the app's root element to scope click handling
    * to links within the RW app instead of the entire document. */
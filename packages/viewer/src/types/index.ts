@@ -17,6 +17,12 @@ export interface NavItem {
   /** Section identity if this item is a section root. */
   section?: SectionInfo;
   children?: NavItem[];
+  /** Children exist but were left out by a `depth` limit; fetch them with `expand`. */
+  hasChildren?: boolean;
+  /** This item is the `current` page passed to `fetchNavigation`. */
+  isCurrent?: boolean;
+  /** This item is on the trail to the `current` page (the page or an item above it). */
+  active?: boolean;
 }
 
 /** Group of navigation items with optional label. */